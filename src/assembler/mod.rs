@@ -1,3 +1,4 @@
+#![allow(dead_code, unused_imports)]
 mod arm64_assembler;
 mod cpu;
 mod x86_assembler;
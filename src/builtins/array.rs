@@ -0,0 +1,1082 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+23.1 Array Objects
+Arrays are exotic objects that give special treatment to a certain class of property names.
+The Array prototype object is itself an Array exotic object. Most of its methods are intentionally generic, they
+  do not require that their this value be an Array, only an object with a "length" property.
+
+@links:
+- https://tc39.es/ecma262/#sec-array-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-array-prototype-object
+*/
+use super::{create_constructor, define_method, define_symbol_property, get_argument, get_relative_index};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::function::NativeFn;
+use crate::values::iterator::IterationKind;
+use crate::values::property::PropertyKey;
+use crate::values::{ObjectValue, StringValue, Value};
+use crate::vm::coercion::{to_integer_or_infinity, to_number, to_string, to_uint32};
+use crate::vm::comparison::{is_strictly_equal, same_value_zero};
+use crate::vm::core::Engine;
+use std::cmp::Ordering;
+
+// 2^53 - 1, the largest length of an array-like object.
+const MAX_SAFE_LENGTH: u64 = 9007199254740991;
+
+const PROTOTYPE_METHODS: [(&str, usize, NativeFn); 37] = [
+  ("at", 1, at),
+  ("concat", 1, concat),
+  ("copyWithin", 2, copy_within),
+  ("entries", 0, entries),
+  ("every", 1, every),
+  ("fill", 1, fill),
+  ("filter", 1, filter),
+  ("find", 1, find),
+  ("findIndex", 1, find_index),
+  ("findLast", 1, find_last),
+  ("findLastIndex", 1, find_last_index),
+  ("flat", 0, flat),
+  ("flatMap", 1, flat_map),
+  ("forEach", 1, for_each),
+  ("includes", 1, includes),
+  ("indexOf", 1, index_of),
+  ("join", 1, join),
+  ("keys", 0, keys),
+  ("lastIndexOf", 1, last_index_of),
+  ("map", 1, map),
+  ("pop", 0, pop),
+  ("push", 1, push),
+  ("reduce", 1, reduce),
+  ("reduceRight", 1, reduce_right),
+  ("reverse", 0, reverse),
+  ("shift", 0, shift),
+  ("slice", 2, slice),
+  ("some", 1, some),
+  ("sort", 1, sort),
+  ("splice", 2, splice),
+  ("toLocaleString", 0, to_locale_string),
+  ("toReversed", 0, to_reversed),
+  ("toSorted", 1, to_sorted),
+  ("toSpliced", 2, to_spliced),
+  ("toString", 0, array_to_string),
+  ("unshift", 1, unshift),
+  ("values", 0, values),
+];
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_array(object_prototype);
+  let array = create_constructor("Array", 1, array_constructor, &prototype);
+  for (name, arity, function) in PROTOTYPE_METHODS {
+    define_method(&prototype, name, arity, function);
+  }
+  define_method(&prototype, "with", 2, with);
+  // 23.1.3.40 Array.prototype [ @@iterator ] ( ), the initial value is %Array.prototype.values%
+  let values = prototype.get_property("values").expect("values method to be defined");
+  define_symbol_property(ctx, &prototype, "Symbol.iterator", values);
+  define_method(&array, "from", 1, from);
+  define_method(&array, "isArray", 1, is_array);
+  define_method(&array, "of", 0, of);
+  ctx.define_builtin("Array", Value::Object(array));
+  ctx.set_intrinsic("%Array.prototype%", prototype);
+}
+
+fn index_key(index: u64) -> PropertyKey {
+  PropertyKey::from(index.to_string())
+}
+
+fn index_value(index: u64) -> Value {
+  Value::create_number_value(index as i64)
+}
+
+// the object the generic methods work on, and its length.
+fn to_array_like(engine: &mut Engine, this: &Value) -> Result<(ObjectValue, u64)> {
+  let object = engine.to_object(this)?;
+  let length = engine.length_of_array_like(&object)?;
+  Ok((object, length))
+}
+
+fn get_callback(value: &Value) -> Result<Value> {
+  if !value.is_function() {
+    return Err(EngineError::TypeError(format!("{} is not a function", value)));
+  }
+  Ok(value.clone())
+}
+
+fn get_element(engine: &mut Engine, object: &ObjectValue, index: u64) -> Result<Value> {
+  engine.get(object, &index_key(index), &Value::Object(object.clone()))
+}
+
+fn set_element(engine: &mut Engine, object: &ObjectValue, index: u64, value: Value) -> Result<()> {
+  engine.set_or_throw(object, index_key(index), value)
+}
+
+fn set_length(engine: &mut Engine, object: &ObjectValue, length: u64) -> Result<()> {
+  engine.set_or_throw(object, PropertyKey::from("length"), index_value(length))
+}
+
+// moves the element at `from` to `to`, a hole is moved as a hole.
+fn move_element(engine: &mut Engine, object: &ObjectValue, from: u64, to: u64) -> Result<()> {
+  if object.has_property(&index_key(from)) {
+    let value = get_element(engine, object, from)?;
+    return set_element(engine, object, to, value);
+  }
+  engine.delete_property_or_throw(object, &index_key(to))
+}
+
+// 10.4.2.2 ArrayCreate ( length [ , proto ] )
+fn array_create(engine: &mut Engine, length: u64) -> Result<ObjectValue> {
+  if length > u32::MAX as u64 {
+    return Err(EngineError::RangeError("Invalid array length".to_owned()));
+  }
+  let array = engine.create_array();
+  if length > 0 {
+    set_length(engine, &array, length)?;
+  }
+  Ok(array)
+}
+
+/*
+23.1.1.1 Array ( ...values )
+  2. If NewTarget is undefined, let newTarget be the active function object; else let newTarget be NewTarget.
+  3. Let proto be ? GetPrototypeFromConstructor(newTarget, "%Array.prototype%").
+  4. If numberOfArgs = 0, then
+    a. Return ! ArrayCreate(0, proto).
+  5. Else if numberOfArgs = 1, then
+    c. If len is not a Number, then
+      i. Perform ! CreateDataPropertyOrThrow(array, "0", len).
+    d. Else,
+      i. Let intLen be ! ToUint32(len).
+      ii. If SameValueZero(intLen, len) is false, throw a RangeError exception.
+  6. Else,
+    d. Repeat, while k < numberOfArgs,
+      iii. Perform ! CreateDataPropertyOrThrow(array, Pk, itemK).
+*/
+fn array_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let prototype = match engine.get_new_target() {
+    Some(new_target) => engine.get_prototype_from_constructor(&new_target, "%Array.prototype%")?,
+    None => engine.get_intrinsic("%Array.prototype%"),
+  };
+  let array = match arguments {
+    [Value::Number(length)] => {
+      let length = length.as_f64();
+      if to_uint32(length) as f64 != length {
+        return Err(EngineError::RangeError("Invalid array length".to_owned()));
+      }
+      array_create(engine, length as u64)?
+    }
+    values => engine.create_array_from_list(values.to_vec()),
+  };
+  array.set_prototype(prototype);
+  Ok(Value::Object(array))
+}
+
+/*
+23.1.2.1 Array.from ( items [ , mapper [ , thisArg ] ] )
+  4. Let usingIterator be ? GetMethod(items, @@iterator).
+  5. If usingIterator is not undefined, then
+    b. Let iteratorRecord be ? GetIteratorFromMethod(items, usingIterator).
+  6. NOTE: items is not an Iterable so assume it is an array-like object.
+*/
+fn from(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let items = get_argument(arguments, 0);
+  let mapper = get_argument(arguments, 1);
+  let this_argument = get_argument(arguments, 2);
+  if !mapper.is_undefined() && !mapper.is_function() {
+    return Err(EngineError::TypeError(format!("{} is not a function", mapper)));
+  }
+  let key = engine.get_well_known_symbol("Symbol.iterator");
+  let values = match engine.get_method(&items, &key)? {
+    Some(method) => {
+      let record = engine.get_iterator_from_method(&items, &method)?;
+      engine.iterator_to_list(&record)?
+    }
+    None => {
+      let (object, length) = to_array_like(engine, &items)?;
+      let mut values = Vec::with_capacity(length as usize);
+      for index in 0..length {
+        values.push(get_element(engine, &object, index)?);
+      }
+      values
+    }
+  };
+  if mapper.is_undefined() {
+    return Ok(Value::Object(engine.create_array_from_list(values)));
+  }
+  let mut mapped = Vec::with_capacity(values.len());
+  for (index, value) in values.into_iter().enumerate() {
+    mapped.push(engine.call_function(&mapper, &this_argument, &[value, index_value(index as u64)])?);
+  }
+  Ok(Value::Object(engine.create_array_from_list(mapped)))
+}
+
+// 23.1.2.2 Array.isArray ( arg )
+fn is_array(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let is_array = get_argument(arguments, 0)
+    .get_object()
+    .is_some_and(ObjectValue::is_array);
+  Ok(Value::create_boolean_value(is_array))
+}
+
+// 23.1.2.3 Array.of ( ...items )
+fn of(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  Ok(Value::Object(engine.create_array_from_list(arguments.to_vec())))
+}
+
+/*
+23.1.3.1 Array.prototype.at ( index )
+  4. If relativeIndex ≥ 0, then
+    a. Let k be relativeIndex.
+  5. Else,
+    a. Let k be len + relativeIndex.
+  6. If k < 0 or k ≥ len, return undefined.
+*/
+fn at(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let relative = to_integer_or_infinity(engine, &get_argument(arguments, 0))?;
+  let index = if relative >= 0.0 {
+    relative
+  } else {
+    length as f64 + relative
+  };
+  if index < 0.0 || index >= length as f64 {
+    return Ok(Value::create_undefined_value());
+  }
+  get_element(engine, &object, index as u64)
+}
+
+/*
+23.1.3.2 Array.prototype.concat ( ...items )
+  5. For each element E of items, do
+    a. Let spreadable be ? IsConcatSpreadable(E).
+    b. If spreadable is true, then
+      iii. Repeat, while k < len,
+        1. Let P be ! ToString(𝔽(k)).
+        2. Let exists be ? HasProperty(E, P).
+        3. If exists is true, then
+          a. Let subElement be ? Get(E, P).
+          b. Perform ? CreateDataPropertyOrThrow(A, ! ToString(𝔽(n)), subElement).
+    c. Else,
+      ii. Perform ? CreateDataPropertyOrThrow(A, ! ToString(𝔽(n)), E).
+  6. Perform ? Set(A, "length", 𝔽(n), true).
+*/
+fn concat(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let array = array_create(engine, 0)?;
+  let mut count = 0;
+  let items = std::iter::once(Value::Object(object)).chain(arguments.iter().cloned());
+  for item in items {
+    let Some(spread) = item.get_object().filter(|item| item.is_array()).cloned() else {
+      engine.create_data_property_or_throw(&array, index_key(count), item)?;
+      count += 1;
+      continue;
+    };
+    let length = engine.length_of_array_like(&spread)?;
+    if count + length > MAX_SAFE_LENGTH {
+      return Err(EngineError::TypeError("Invalid array length".to_owned()));
+    }
+    for index in 0..length {
+      if spread.has_property(&index_key(index)) {
+        let value = get_element(engine, &spread, index)?;
+        engine.create_data_property_or_throw(&array, index_key(count), value)?;
+      }
+      count += 1;
+    }
+  }
+  set_length(engine, &array, count)?;
+  Ok(Value::Object(array))
+}
+
+/*
+23.1.3.4 Array.prototype.copyWithin ( target, start [ , end ] )
+  12. If from < to and to < from + count, then
+    a. Let direction be -1.
+    b. Set from to from + count - 1.
+    c. Set to to to + count - 1.
+*/
+fn copy_within(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let mut to = get_relative_index(engine, &get_argument(arguments, 0), length, 0)?;
+  let mut from = get_relative_index(engine, &get_argument(arguments, 1), length, 0)?;
+  let end = get_relative_index(engine, &get_argument(arguments, 2), length, length)?;
+  let count = end.saturating_sub(from).min(length - to);
+  let backwards = from < to && to < from + count;
+  if backwards {
+    from += count;
+    to += count;
+  }
+  for _ in 0..count {
+    if backwards {
+      from -= 1;
+      to -= 1;
+    }
+    move_element(engine, &object, from, to)?;
+    if !backwards {
+      from += 1;
+      to += 1;
+    }
+  }
+  Ok(Value::Object(object))
+}
+
+// the shared loop of the iteration methods, holes are skipped and `visit` sees every element
+// with its index, stopping the loop once it returns false.
+fn for_each_element<F>(engine: &mut Engine, this: &Value, arguments: &[Value], mut visit: F) -> Result<()>
+where
+  F: FnMut(&mut Engine, Value, u64, Value) -> Result<bool>,
+{
+  let (object, length) = to_array_like(engine, this)?;
+  let callback = get_callback(&get_argument(arguments, 0))?;
+  let this_argument = get_argument(arguments, 1);
+  for index in 0..length {
+    if !object.has_property(&index_key(index)) {
+      continue;
+    }
+    let value = get_element(engine, &object, index)?;
+    let arguments = [value.clone(), index_value(index), Value::Object(object.clone())];
+    let result = engine.call_function(&callback, &this_argument, &arguments)?;
+    if !visit(engine, value, index, result)? {
+      break;
+    }
+  }
+  Ok(())
+}
+
+// 23.1.3.6 Array.prototype.every ( callbackfn [ , thisArg ] )
+fn every(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let mut every = true;
+  for_each_element(engine, this, arguments, |_, _, _, result| {
+    every = result.is_truthy();
+    Ok(every)
+  })?;
+  Ok(Value::create_boolean_value(every))
+}
+
+// 23.1.3.29 Array.prototype.some ( callbackfn [ , thisArg ] )
+fn some(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let mut some = false;
+  for_each_element(engine, this, arguments, |_, _, _, result| {
+    some = result.is_truthy();
+    Ok(!some)
+  })?;
+  Ok(Value::create_boolean_value(some))
+}
+
+// 23.1.3.15 Array.prototype.forEach ( callbackfn [ , thisArg ] )
+fn for_each(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  for_each_element(engine, this, arguments, |_, _, _, _| Ok(true))?;
+  Ok(Value::create_undefined_value())
+}
+
+// 23.1.3.8 Array.prototype.filter ( callbackfn [ , thisArg ] )
+fn filter(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let array = array_create(engine, 0)?;
+  let mut count = 0;
+  for_each_element(engine, this, arguments, |engine, value, _, result| {
+    if result.is_truthy() {
+      engine.create_data_property_or_throw(&array, index_key(count), value)?;
+      count += 1;
+    }
+    Ok(true)
+  })?;
+  Ok(Value::Object(array))
+}
+
+// 23.1.3.21 Array.prototype.map ( callbackfn [ , thisArg ] ), holes stay holes.
+fn map(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (_, length) = to_array_like(engine, this)?;
+  let array = array_create(engine, length)?;
+  for_each_element(engine, this, arguments, |engine, _, index, result| {
+    engine.create_data_property_or_throw(&array, index_key(index), result)?;
+    Ok(true)
+  })?;
+  Ok(Value::Object(array))
+}
+
+/*
+23.1.3.9 Array.prototype.find ( predicate [ , thisArg ] ) and the other find methods
+7.3.35 FindViaPredicate ( O, len, direction, predicate, thisArg )
+Holes are visited as undefined, the element and its index are returned when the predicate holds.
+*/
+fn find_via_predicate(
+  engine: &mut Engine,
+  this: &Value,
+  arguments: &[Value],
+  ascending: bool,
+) -> Result<Option<(u64, Value)>> {
+  let (object, length) = to_array_like(engine, this)?;
+  let predicate = get_callback(&get_argument(arguments, 0))?;
+  let this_argument = get_argument(arguments, 1);
+  for step in 0..length {
+    let index = if ascending { step } else { length - step - 1 };
+    let value = get_element(engine, &object, index)?;
+    let arguments = [value.clone(), index_value(index), Value::Object(object.clone())];
+    if engine
+      .call_function(&predicate, &this_argument, &arguments)?
+      .is_truthy()
+    {
+      return Ok(Some((index, value)));
+    }
+  }
+  Ok(None)
+}
+
+fn find(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let found = find_via_predicate(engine, this, arguments, true)?;
+  Ok(found.map_or_else(Value::create_undefined_value, |(_, value)| value))
+}
+
+fn find_index(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let found = find_via_predicate(engine, this, arguments, true)?;
+  Ok(found.map_or_else(|| Value::create_number_value(-1), |(index, _)| index_value(index)))
+}
+
+fn find_last(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let found = find_via_predicate(engine, this, arguments, false)?;
+  Ok(found.map_or_else(Value::create_undefined_value, |(_, value)| value))
+}
+
+fn find_last_index(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let found = find_via_predicate(engine, this, arguments, false)?;
+  Ok(found.map_or_else(|| Value::create_number_value(-1), |(index, _)| index_value(index)))
+}
+
+// 23.1.3.7 Array.prototype.fill ( value [ , start [ , end ] ] )
+fn fill(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let value = get_argument(arguments, 0);
+  let start = get_relative_index(engine, &get_argument(arguments, 1), length, 0)?;
+  let end = get_relative_index(engine, &get_argument(arguments, 2), length, length)?;
+  for index in start..end {
+    set_element(engine, &object, index, value.clone())?;
+  }
+  Ok(Value::Object(object))
+}
+
+/*
+23.1.3.13.1 FlattenIntoArray ( target, source, sourceLen, start, depth [ , mapperFunction [ , thisArg ] ] )
+  3. Repeat, while sourceIndex < sourceLen,
+    b. Let exists be ? HasProperty(source, P).
+    c. If exists is true, then
+      ii. If mapperFunction is present, then
+        1. Set element to ? Call(mapperFunction, thisArg, « element, sourceIndex, source »).
+      iv. If depth > 0, then
+        1. Set shouldFlatten to ? IsArray(element).
+      v. If shouldFlatten is true, then
+        3. Set targetIndex to ? FlattenIntoArray(target, element, elementLen, targetIndex, newDepth).
+      vi. Else,
+        2. Perform ? CreateDataPropertyOrThrow(target, ! ToString(𝔽(targetIndex)), element).
+*/
+fn flatten_into_array(
+  engine: &mut Engine,
+  target: &ObjectValue,
+  source: &ObjectValue,
+  start: u64,
+  depth: f64,
+  mapper: Option<(&Value, &Value)>,
+) -> Result<u64> {
+  let length = engine.length_of_array_like(source)?;
+  let mut target_index = start;
+  for index in 0..length {
+    if !source.has_property(&index_key(index)) {
+      continue;
+    }
+    let mut element = get_element(engine, source, index)?;
+    if let Some((mapper, this_argument)) = mapper {
+      let arguments = [element, index_value(index), Value::Object(source.clone())];
+      element = engine.call_function(mapper, this_argument, &arguments)?;
+    }
+    match element.get_object().filter(|element| depth > 0.0 && element.is_array()) {
+      Some(nested) => target_index = flatten_into_array(engine, target, nested, target_index, depth - 1.0, None)?,
+      None => {
+        if target_index >= MAX_SAFE_LENGTH {
+          return Err(EngineError::TypeError("Invalid array length".to_owned()));
+        }
+        engine.create_data_property_or_throw(target, index_key(target_index), element)?;
+        target_index += 1;
+      }
+    }
+  }
+  Ok(target_index)
+}
+
+// 23.1.3.13 Array.prototype.flat ( [ depth ] )
+fn flat(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let depth = match get_argument(arguments, 0) {
+    depth if depth.is_undefined() => 1.0,
+    depth => to_integer_or_infinity(engine, &depth)?.max(0.0),
+  };
+  let array = array_create(engine, 0)?;
+  flatten_into_array(engine, &array, &object, 0, depth, None)?;
+  Ok(Value::Object(array))
+}
+
+// 23.1.3.14 Array.prototype.flatMap ( mapperFunction [ , thisArg ] )
+fn flat_map(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let mapper = get_callback(&get_argument(arguments, 0))?;
+  let this_argument = get_argument(arguments, 1);
+  let array = array_create(engine, 0)?;
+  flatten_into_array(engine, &array, &object, 0, 1.0, Some((&mapper, &this_argument)))?;
+  Ok(Value::Object(array))
+}
+
+// where the search of indexOf and includes starts, None when it starts past the end.
+fn get_search_start(engine: &mut Engine, value: &Value, length: u64) -> Result<Option<u64>> {
+  let start = to_integer_or_infinity(engine, value)?;
+  if start >= length as f64 {
+    return Ok(None);
+  }
+  if start >= 0.0 {
+    return Ok(Some(start as u64));
+  }
+  Ok(Some((length as f64 + start).max(0.0) as u64))
+}
+
+/*
+23.1.3.16 Array.prototype.includes ( searchElement [ , fromIndex ] )
+Holes are read as undefined and the elements are compared with SameValueZero, so NaN is found.
+*/
+fn includes(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let search = get_argument(arguments, 0);
+  let Some(start) = get_search_start(engine, &get_argument(arguments, 1), length)? else {
+    return Ok(Value::create_boolean_value(false));
+  };
+  for index in start..length {
+    let value = get_element(engine, &object, index)?;
+    if same_value_zero(&value, &search) {
+      return Ok(Value::create_boolean_value(true));
+    }
+  }
+  Ok(Value::create_boolean_value(false))
+}
+
+/*
+23.1.3.17 Array.prototype.indexOf ( searchElement [ , fromIndex ] )
+Holes are skipped and the elements are compared with IsStrictlyEqual.
+*/
+fn index_of(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let search = get_argument(arguments, 0);
+  let Some(start) = get_search_start(engine, &get_argument(arguments, 1), length)? else {
+    return Ok(Value::create_number_value(-1));
+  };
+  for index in start..length {
+    if object.has_property(&index_key(index)) && is_strictly_equal(&get_element(engine, &object, index)?, &search) {
+      return Ok(index_value(index));
+    }
+  }
+  Ok(Value::create_number_value(-1))
+}
+
+/*
+23.1.3.20 Array.prototype.lastIndexOf ( searchElement [ , fromIndex ] )
+  4. If fromIndex is present, let n be ? ToIntegerOrInfinity(fromIndex); else let n be len - 1.
+  5. If n = -∞, return -1𝔽.
+  6. If n ≥ 0, then
+    a. Let k be min(n, len - 1).
+  7. Else,
+    a. Let k be len + n.
+*/
+fn last_index_of(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let search = get_argument(arguments, 0);
+  let start = match arguments.get(1) {
+    Some(start) => to_integer_or_infinity(engine, start)?,
+    None => length as f64 - 1.0,
+  };
+  let mut index = if start >= 0.0 {
+    start.min(length as f64 - 1.0)
+  } else {
+    length as f64 + start
+  };
+  while index >= 0.0 {
+    let current = index as u64;
+    if object.has_property(&index_key(current)) && is_strictly_equal(&get_element(engine, &object, current)?, &search) {
+      return Ok(index_value(current));
+    }
+    index -= 1.0;
+  }
+  Ok(Value::create_number_value(-1))
+}
+
+/*
+23.1.3.18 Array.prototype.join ( separator )
+  3. If separator is undefined, let sep be ",".
+  7. Repeat, while k < len,
+    c. If element is neither undefined nor null, then
+      i. Let S be ? ToString(element).
+*/
+fn join(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let separator = match get_argument(arguments, 0) {
+    separator if separator.is_undefined() => StringValue::from(","),
+    separator => to_string(engine, &separator)?,
+  };
+  let mut result = Vec::new();
+  for index in 0..length {
+    if index > 0 {
+      result.extend_from_slice(separator.get_units());
+    }
+    let element = get_element(engine, &object, index)?;
+    if !element.is_undefined() && !element.is_null() {
+      result.extend_from_slice(to_string(engine, &element)?.get_units());
+    }
+  }
+  Ok(Value::String(StringValue::from_utf16(result)))
+}
+
+// 23.1.3.36 Array.prototype.toString ( ), falls back to Object.prototype.toString without `join`.
+fn array_to_string(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let join = engine.get(&object, &PropertyKey::from("join"), &Value::Object(object.clone()))?;
+  if !join.is_function() {
+    return Ok(Value::create_string_value("[object Array]".to_owned()));
+  }
+  engine.call_function(&join, &Value::Object(object), &[])
+}
+
+// 23.1.3.32 Array.prototype.toLocaleString ( ), every element is converted with its own method.
+fn to_locale_string(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let mut parts = Vec::with_capacity(length as usize);
+  for index in 0..length {
+    let element = get_element(engine, &object, index)?;
+    if element.is_undefined() || element.is_null() {
+      parts.push(String::new());
+      continue;
+    }
+    let target = engine.to_object(&element)?;
+    let method = engine.get(&target, &PropertyKey::from("toLocaleString"), &element)?;
+    let value = engine.call_function(&method, &element, &[])?;
+    parts.push(to_string(engine, &value)?.get());
+  }
+  Ok(Value::create_string_value(parts.join(",")))
+}
+
+/*
+23.1.3.22 Array.prototype.pop ( )
+  3. If len = 0, then
+    a. Perform ? Set(O, "length", +0𝔽, true).
+    b. Return undefined.
+  4. Else,
+    c. Let element be ? Get(O, index).
+    d. Perform ? DeletePropertyOrThrow(O, index).
+    e. Perform ? Set(O, "length", 𝔽(newLen), true).
+*/
+fn pop(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  if length == 0 {
+    set_length(engine, &object, 0)?;
+    return Ok(Value::create_undefined_value());
+  }
+  let element = get_element(engine, &object, length - 1)?;
+  engine.delete_property_or_throw(&object, &index_key(length - 1))?;
+  set_length(engine, &object, length - 1)?;
+  Ok(element)
+}
+
+// 23.1.3.23 Array.prototype.push ( ...items )
+fn push(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  if length + arguments.len() as u64 > MAX_SAFE_LENGTH {
+    return Err(EngineError::TypeError(
+      "Pushing elements past the maximum length of an array".to_owned(),
+    ));
+  }
+  for (offset, item) in arguments.iter().enumerate() {
+    set_element(engine, &object, length + offset as u64, item.clone())?;
+  }
+  let length = length + arguments.len() as u64;
+  set_length(engine, &object, length)?;
+  Ok(index_value(length))
+}
+
+/*
+23.1.3.24 Array.prototype.reduce ( callbackfn [ , initialValue ] )
+  4. If len = 0 and initialValue is not present, throw a TypeError exception.
+  7. Else,
+    b. Repeat, while kPresent is false and k < len,
+      i. Let Pk be ! ToString(𝔽(k)).
+      ii. Set kPresent to ? HasProperty(O, Pk).
+      iii. If kPresent is true, then
+        1. Set accumulator to ? Get(O, Pk).
+    c. If kPresent is false, throw a TypeError exception.
+*/
+fn reduce_elements(engine: &mut Engine, this: &Value, arguments: &[Value], ascending: bool) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let callback = get_callback(&get_argument(arguments, 0))?;
+  let mut indices: Box<dyn Iterator<Item = u64>> = if ascending {
+    Box::new(0..length)
+  } else {
+    Box::new((0..length).rev())
+  };
+  let mut accumulator = match arguments.get(1) {
+    Some(initial) => initial.clone(),
+    None => {
+      let Some(first) = indices.by_ref().find(|index| object.has_property(&index_key(*index))) else {
+        return Err(EngineError::TypeError(
+          "Reduce of empty array with no initial value".to_owned(),
+        ));
+      };
+      get_element(engine, &object, first)?
+    }
+  };
+  for index in indices {
+    if !object.has_property(&index_key(index)) {
+      continue;
+    }
+    let value = get_element(engine, &object, index)?;
+    let arguments = [accumulator, value, index_value(index), Value::Object(object.clone())];
+    accumulator = engine.call_function(&callback, &Value::create_undefined_value(), &arguments)?;
+  }
+  Ok(accumulator)
+}
+
+fn reduce(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  reduce_elements(engine, this, arguments, true)
+}
+
+// 23.1.3.25 Array.prototype.reduceRight ( callbackfn [ , initialValue ] )
+fn reduce_right(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  reduce_elements(engine, this, arguments, false)
+}
+
+/*
+23.1.3.26 Array.prototype.reverse ( )
+The elements are swapped in place from both ends, a hole on one side moves to the other.
+*/
+fn reverse(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let middle = length / 2;
+  for lower in 0..middle {
+    let upper = length - lower - 1;
+    let lower_exists = object.has_property(&index_key(lower));
+    let upper_exists = object.has_property(&index_key(upper));
+    let lower_value = if lower_exists {
+      Some(get_element(engine, &object, lower)?)
+    } else {
+      None
+    };
+    let upper_value = if upper_exists {
+      Some(get_element(engine, &object, upper)?)
+    } else {
+      None
+    };
+    match upper_value {
+      Some(value) => set_element(engine, &object, lower, value)?,
+      None if lower_exists => engine.delete_property_or_throw(&object, &index_key(lower))?,
+      None => {}
+    }
+    match lower_value {
+      Some(value) => set_element(engine, &object, upper, value)?,
+      None if upper_exists => engine.delete_property_or_throw(&object, &index_key(upper))?,
+      None => {}
+    }
+  }
+  Ok(Value::Object(object))
+}
+
+// 23.1.3.27 Array.prototype.shift ( )
+fn shift(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  if length == 0 {
+    set_length(engine, &object, 0)?;
+    return Ok(Value::create_undefined_value());
+  }
+  let first = get_element(engine, &object, 0)?;
+  for index in 1..length {
+    move_element(engine, &object, index, index - 1)?;
+  }
+  engine.delete_property_or_throw(&object, &index_key(length - 1))?;
+  set_length(engine, &object, length - 1)?;
+  Ok(first)
+}
+
+// 23.1.3.28 Array.prototype.slice ( start, end )
+fn slice(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let start = get_relative_index(engine, &get_argument(arguments, 0), length, 0)?;
+  let end = get_relative_index(engine, &get_argument(arguments, 1), length, length)?;
+  let array = array_create(engine, end.saturating_sub(start))?;
+  let mut count = 0;
+  for index in start..end {
+    if object.has_property(&index_key(index)) {
+      let value = get_element(engine, &object, index)?;
+      engine.create_data_property_or_throw(&array, index_key(count), value)?;
+    }
+    count += 1;
+  }
+  set_length(engine, &array, count)?;
+  Ok(Value::Object(array))
+}
+
+/*
+23.1.3.30.2 SortCompare ( x, y )
+  1. If x and y are both undefined, return +0𝔽.
+  2. If x is undefined, return 1𝔽.
+  3. If y is undefined, return -1𝔽.
+  4. If comparefn is not undefined, then
+    a. Let v be ? ToNumber(? Call(comparefn, undefined, « x, y »)).
+    b. If v is NaN, return +0𝔽.
+  5. Let xString be ? ToString(x).
+  6. Let yString be ? ToString(y).
+*/
+fn sort_compare(engine: &mut Engine, compare: &Value, left: &Value, right: &Value) -> Result<Ordering> {
+  match (left.is_undefined(), right.is_undefined()) {
+    (true, true) => return Ok(Ordering::Equal),
+    (true, false) => return Ok(Ordering::Greater),
+    (false, true) => return Ok(Ordering::Less),
+    _ => {}
+  }
+  if !compare.is_undefined() {
+    let arguments = [left.clone(), right.clone()];
+    let result = engine.call_function(compare, &Value::create_undefined_value(), &arguments)?;
+    let order = to_number(engine, &result)?;
+    return Ok(order.partial_cmp(&0.0).unwrap_or(Ordering::Equal));
+  }
+  let left = to_string(engine, left)?;
+  let right = to_string(engine, right)?;
+  Ok(left.get_units().cmp(right.get_units()))
+}
+
+// a stable merge sort, the comparison can call into script code and fail.
+fn merge_sort(engine: &mut Engine, compare: &Value, items: Vec<Value>) -> Result<Vec<Value>> {
+  if items.len() <= 1 {
+    return Ok(items);
+  }
+  let mut left = items;
+  let right = left.split_off(left.len() / 2);
+  let left = merge_sort(engine, compare, left)?;
+  let right = merge_sort(engine, compare, right)?;
+  let mut merged = Vec::with_capacity(left.len() + right.len());
+  let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+  while let (Some(first), Some(second)) = (left.peek(), right.peek()) {
+    if sort_compare(engine, compare, second, first)? == Ordering::Less {
+      merged.extend(right.next());
+    } else {
+      merged.extend(left.next());
+    }
+  }
+  merged.extend(left);
+  merged.extend(right);
+  Ok(merged)
+}
+
+/*
+23.1.3.30.1 SortIndexedProperties ( obj, len, SortCompare, holes )
+The elements are read into a list and sorted there, holes are skipped when asked to.
+*/
+fn sort_indexed_properties(
+  engine: &mut Engine,
+  object: &ObjectValue,
+  length: u64,
+  compare: &Value,
+  skip_holes: bool,
+) -> Result<Vec<Value>> {
+  let mut items = Vec::new();
+  for index in 0..length {
+    if skip_holes && !object.has_property(&index_key(index)) {
+      continue;
+    }
+    items.push(get_element(engine, object, index)?);
+  }
+  merge_sort(engine, compare, items)
+}
+
+fn get_compare_function(value: Value) -> Result<Value> {
+  if !value.is_undefined() && !value.is_function() {
+    let message = "The comparison function must be either a function or undefined";
+    return Err(EngineError::TypeError(message.to_owned()));
+  }
+  Ok(value)
+}
+
+/*
+23.1.3.30 Array.prototype.sort ( comparefn )
+  6. Let sortedList be ? SortIndexedProperties(obj, len, SortCompare, skip-holes).
+  9. Repeat, while j < itemCount,
+    a. Perform ? Set(obj, ! ToString(𝔽(j)), sortedList[j], true).
+  11. Repeat, while j < len,
+    a. Perform ? DeletePropertyOrThrow(obj, ! ToString(𝔽(j))).
+*/
+fn sort(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let compare = get_compare_function(get_argument(arguments, 0))?;
+  let (object, length) = to_array_like(engine, this)?;
+  let sorted = sort_indexed_properties(engine, &object, length, &compare, true)?;
+  let count = sorted.len() as u64;
+  for (index, value) in sorted.into_iter().enumerate() {
+    set_element(engine, &object, index as u64, value)?;
+  }
+  for index in count..length {
+    engine.delete_property_or_throw(&object, &index_key(index))?;
+  }
+  Ok(Value::Object(object))
+}
+
+// the start, the count of elements removed and the items inserted by splice and toSpliced.
+fn get_splice_range(engine: &mut Engine, arguments: &[Value], length: u64) -> Result<(u64, u64, Vec<Value>)> {
+  let start = get_relative_index(engine, &get_argument(arguments, 0), length, 0)?;
+  let delete_count = match arguments.len() {
+    0 => 0,
+    1 => length - start,
+    _ => to_integer_or_infinity(engine, &arguments[1])?.clamp(0.0, (length - start) as f64) as u64,
+  };
+  let items = arguments.iter().skip(2).cloned().collect();
+  Ok((start, delete_count, items))
+}
+
+/*
+23.1.3.31 Array.prototype.splice ( start, deleteCount, ...items )
+The removed elements are returned in a new array, the elements after them are moved to make room for the items.
+*/
+fn splice(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let (start, delete_count, items) = get_splice_range(engine, arguments, length)?;
+  let item_count = items.len() as u64;
+  if length + item_count - delete_count > MAX_SAFE_LENGTH {
+    return Err(EngineError::TypeError("Invalid array length".to_owned()));
+  }
+  let removed = array_create(engine, delete_count)?;
+  for offset in 0..delete_count {
+    if object.has_property(&index_key(start + offset)) {
+      let value = get_element(engine, &object, start + offset)?;
+      engine.create_data_property_or_throw(&removed, index_key(offset), value)?;
+    }
+  }
+  set_length(engine, &removed, delete_count)?;
+  if item_count < delete_count {
+    for index in start..length - delete_count {
+      move_element(engine, &object, index + delete_count, index + item_count)?;
+    }
+    for index in (length - delete_count + item_count..length).rev() {
+      engine.delete_property_or_throw(&object, &index_key(index))?;
+    }
+  } else if item_count > delete_count {
+    for index in (start..length - delete_count).rev() {
+      move_element(engine, &object, index + delete_count, index + item_count)?;
+    }
+  }
+  for (offset, item) in items.into_iter().enumerate() {
+    set_element(engine, &object, start + offset as u64, item)?;
+  }
+  set_length(engine, &object, length - delete_count + item_count)?;
+  Ok(Value::Object(removed))
+}
+
+// 23.1.3.33 Array.prototype.toReversed ( )
+fn to_reversed(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let array = array_create(engine, length)?;
+  for index in 0..length {
+    let value = get_element(engine, &object, length - index - 1)?;
+    engine.create_data_property_or_throw(&array, index_key(index), value)?;
+  }
+  Ok(Value::Object(array))
+}
+
+// 23.1.3.34 Array.prototype.toSorted ( comparefn ), holes are sorted as undefined.
+fn to_sorted(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let compare = get_compare_function(get_argument(arguments, 0))?;
+  let (object, length) = to_array_like(engine, this)?;
+  let sorted = sort_indexed_properties(engine, &object, length, &compare, false)?;
+  Ok(Value::Object(engine.create_array_from_list(sorted)))
+}
+
+// 23.1.3.35 Array.prototype.toSpliced ( start, skipCount, ...items )
+fn to_spliced(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let (start, skip_count, items) = get_splice_range(engine, arguments, length)?;
+  let new_length = length + items.len() as u64 - skip_count;
+  if new_length > MAX_SAFE_LENGTH {
+    return Err(EngineError::TypeError("Invalid array length".to_owned()));
+  }
+  let mut values = Vec::with_capacity(new_length as usize);
+  for index in 0..start {
+    values.push(get_element(engine, &object, index)?);
+  }
+  values.extend(items);
+  for index in start + skip_count..length {
+    values.push(get_element(engine, &object, index)?);
+  }
+  Ok(Value::Object(engine.create_array_from_list(values)))
+}
+
+// 23.1.3.37 Array.prototype.unshift ( ...items )
+fn unshift(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let count = arguments.len() as u64;
+  if count > 0 {
+    if length + count > MAX_SAFE_LENGTH {
+      return Err(EngineError::TypeError("Invalid array length".to_owned()));
+    }
+    for index in (0..length).rev() {
+      move_element(engine, &object, index, index + count)?;
+    }
+    for (index, item) in arguments.iter().enumerate() {
+      set_element(engine, &object, index as u64, item.clone())?;
+    }
+  }
+  set_length(engine, &object, length + count)?;
+  Ok(index_value(length + count))
+}
+
+/*
+23.1.3.5 Array.prototype.entries ( )
+23.1.3.19 Array.prototype.keys ( )
+23.1.3.38 Array.prototype.values ( )
+  1. Let O be ? ToObject(this value).
+  2. Return CreateArrayIterator(O, kind).
+*/
+fn create_array_iterator(engine: &mut Engine, this: &Value, kind: IterationKind) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let prototype = engine.get_intrinsic("%ArrayIteratorPrototype%");
+  Ok(Value::Object(ObjectValue::new_array_iterator(object, kind, prototype)))
+}
+
+fn entries(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_array_iterator(engine, this, IterationKind::KeyValue)
+}
+
+fn keys(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_array_iterator(engine, this, IterationKind::Key)
+}
+
+fn values(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_array_iterator(engine, this, IterationKind::Value)
+}
+
+/*
+23.1.3.39 Array.prototype.with ( index, value )
+  5. If relativeIndex ≥ 0, let actualIndex be relativeIndex.
+  6. Else, let actualIndex be len + relativeIndex.
+  7. If actualIndex ≥ len or actualIndex < 0, throw a RangeError exception.
+*/
+fn with(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let (object, length) = to_array_like(engine, this)?;
+  let relative = to_integer_or_infinity(engine, &get_argument(arguments, 0))?;
+  let index = if relative >= 0.0 {
+    relative
+  } else {
+    length as f64 + relative
+  };
+  if index < 0.0 || index >= length as f64 {
+    return Err(EngineError::RangeError(format!("Invalid index : {}", relative)));
+  }
+  let mut values = Vec::with_capacity(length as usize);
+  for current in 0..length {
+    match current == index as u64 {
+      true => values.push(get_argument(arguments, 1)),
+      false => values.push(get_element(engine, &object, current)?),
+    }
+  }
+  Ok(Value::Object(engine.create_array_from_list(values)))
+}
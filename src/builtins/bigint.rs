@@ -0,0 +1,157 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+21.2 BigInt Objects
+The BigInt constructor is %BigInt%. It performs a type conversion when called as a function, it is not intended
+  to be used with the `new` operator.
+The BigInt prototype object is %BigInt.prototype%. It is an ordinary object, it is not a BigInt object and does
+  not have a [[BigIntData]] internal slot.
+
+@links:
+- https://tc39.es/ecma262/#sec-bigint-objects
+*/
+use super::{create_constructor, define_method, get_argument};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{BigIntValue, ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::{number_to_string, to_bigint, to_index, to_integer_or_infinity, to_primitive, PreferredType};
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  let bigint = create_constructor("BigInt", 1, bigint_constructor, &prototype);
+  define_method(&bigint, "asIntN", 2, as_int_n);
+  define_method(&bigint, "asUintN", 2, as_uint_n);
+  define_method(&prototype, "toLocaleString", 0, to_locale_string);
+  define_method(&prototype, "toString", 0, bigint_to_string);
+  define_method(&prototype, "valueOf", 0, value_of);
+
+  // 21.2.3.5 BigInt.prototype [ @@toStringTag ]
+  let to_string_tag = ctx
+    .get_well_known_symbol("Symbol.toStringTag")
+    .cloned()
+    .expect("well-known symbol to exist");
+  let tag = Value::create_string_value("BigInt".to_owned());
+  let descriptor = PropertyDescriptor::new_data_with(tag, false, false, true);
+  prototype.define_own_property(PropertyKey::Symbol(to_string_tag), descriptor);
+
+  ctx.define_builtin("BigInt", Value::Object(bigint));
+  ctx.set_intrinsic("%BigInt.prototype%", prototype);
+}
+
+/*
+21.2.1.1 BigInt ( value )
+  1. If NewTarget is not undefined, throw a TypeError exception.
+  2. Let prim be ? ToPrimitive(value, number).
+  3. If prim is a Number, return ? NumberToBigInt(prim).
+  4. Otherwise, return ? ToBigInt(prim).
+*/
+fn bigint_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  if engine.get_new_target().is_some() {
+    return Err(EngineError::TypeError("BigInt is not a constructor".to_owned()));
+  }
+  match to_primitive(engine, &get_argument(arguments, 0), PreferredType::Number)? {
+    Value::Number(number) => Ok(Value::BigInt(number_to_bigint(number.as_f64())?)),
+    primitive => Ok(Value::BigInt(to_bigint(engine, &primitive)?)),
+  }
+}
+
+/*
+21.2.1.1.1 NumberToBigInt ( number )
+  1. If IsIntegralNumber(number) is false, throw a RangeError exception.
+  2. Return ℤ(ℝ(number)).
+*/
+fn number_to_bigint(number: f64) -> Result<BigIntValue> {
+  if !number.is_finite() || number.fract() != 0.0 {
+    return Err(EngineError::RangeError(format!(
+      "The number {} cannot be converted to a BigInt because it is not an integer",
+      number_to_string(number)
+    )));
+  }
+  Ok(BigIntValue::from_f64(number))
+}
+
+/*
+21.2.2.1 BigInt.asIntN ( bits, bigint )
+  1. Set bits to ? ToIndex(bits).
+  2. Set bigint to ? ToBigInt(bigint).
+  3. Let mod be ℝ(bigint) modulo 2^bits.
+  4. If mod ≥ 2^(bits - 1), return ℤ(mod - 2^bits); otherwise, return ℤ(mod).
+*/
+fn as_int_n(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let bits = to_index(engine, &get_argument(arguments, 0))?;
+  let bigint = to_bigint(engine, &get_argument(arguments, 1))?;
+  Ok(Value::BigInt(bigint.as_int_n(bits)))
+}
+
+/*
+21.2.2.2 BigInt.asUintN ( bits, bigint )
+  1. Set bits to ? ToIndex(bits).
+  2. Set bigint to ? ToBigInt(bigint).
+  3. Return ℤ(ℝ(bigint) modulo 2^bits).
+*/
+fn as_uint_n(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let bits = to_index(engine, &get_argument(arguments, 0))?;
+  let bigint = to_bigint(engine, &get_argument(arguments, 1))?;
+  Ok(Value::BigInt(bigint.as_uint_n(bits)?))
+}
+
+/*
+21.2.3.4.1 ThisBigIntValue ( value )
+  1. If value is a BigInt, return value.
+  2. If value is an Object and value has a [[BigIntData]] internal slot, then
+    b. Return value.[[BigIntData]].
+  3. Throw a TypeError exception.
+*/
+fn this_bigint_value(this: &Value, method: &str) -> Result<BigIntValue> {
+  let bigint = match this {
+    Value::BigInt(bigint) => Some(bigint),
+    Value::Object(object) => object.get_bigint_data(),
+    _ => None,
+  };
+  bigint.cloned().ok_or_else(|| {
+    EngineError::TypeError(format!(
+      "BigInt.prototype.{} requires that 'this' be a BigInt: {}",
+      method, this
+    ))
+  })
+}
+
+// 21.2.3.2 BigInt.prototype.toLocaleString ( [ reserved1 [ , reserved2 ] ] ), without a locale it is toString.
+fn to_locale_string(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let bigint = this_bigint_value(this, "toLocaleString")?;
+  Ok(Value::create_string_value(bigint.to_string_radix(10)))
+}
+
+/*
+21.2.3.3 BigInt.prototype.toString ( [ radix ] )
+  1. Let x be ? ThisBigIntValue(this value).
+  2. If radix is undefined, let radixMV be 10.
+  3. Else, let radixMV be ? ToIntegerOrInfinity(radix).
+  4. If radixMV is not in the inclusive interval from 2 to 36, throw a RangeError exception.
+  5. Return BigInt::toString(x, radixMV).
+*/
+fn bigint_to_string(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let bigint = this_bigint_value(this, "toString")?;
+  let radix = match get_argument(arguments, 0) {
+    radix if radix.is_undefined() => 10.0,
+    radix => to_integer_or_infinity(engine, &radix)?,
+  };
+  if !(2.0..=36.0).contains(&radix) {
+    return Err(EngineError::RangeError(
+      "toString() radix must be between 2 and 36".to_owned(),
+    ));
+  }
+  Ok(Value::create_string_value(bigint.to_string_radix(radix as u32)))
+}
+
+// 21.2.3.4 BigInt.prototype.valueOf ( )
+fn value_of(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::BigInt(this_bigint_value(this, "valueOf")?))
+}
@@ -0,0 +1,65 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+20.3 Boolean Objects
+The Boolean prototype object is %Boolean.prototype%. It is itself a Boolean object whose [[BooleanData]] is false,
+  the this value of its methods is either a Boolean or a Boolean object.
+Primitive booleans reach these methods through ToObject, there is no Boolean constructor yet.
+
+@links:
+- https://tc39.es/ecma262/#sec-boolean-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-boolean-prototype-object
+*/
+use super::define_method;
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::{BooleanValue, ObjectKind, ObjectValue, Value};
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Boolean(BooleanValue::new(false)), object_prototype);
+  define_method(&prototype, "toString", 0, boolean_to_string);
+  define_method(&prototype, "valueOf", 0, value_of);
+  ctx.set_intrinsic("%Boolean.prototype%", prototype);
+}
+
+/*
+20.3.3.3.1 ThisBooleanValue ( value )
+  1. If value is a Boolean, return value.
+  2. If value is an Object and value has a [[BooleanData]] internal slot, then
+    b. Return value.[[BooleanData]].
+  3. Throw a TypeError exception.
+*/
+fn this_boolean_value(this: &Value, method: &str) -> Result<bool> {
+  let boolean = match this {
+    Value::Boolean(boolean) => Some(boolean),
+    Value::Object(object) => object.get_boolean_data(),
+    _ => None,
+  };
+  boolean.map(BooleanValue::get).ok_or_else(|| {
+    EngineError::TypeError(format!(
+      "Boolean.prototype.{} requires that 'this' be a Boolean: {}",
+      method, this
+    ))
+  })
+}
+
+/*
+20.3.3.2 Boolean.prototype.toString ( )
+  1. Let b be ? ThisBooleanValue(this value).
+  2. If b is true, return "true"; else return "false".
+*/
+fn boolean_to_string(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let boolean = this_boolean_value(this, "toString")?;
+  Ok(Value::create_string_value(boolean.to_string()))
+}
+
+// 20.3.3.3 Boolean.prototype.valueOf ( )
+fn value_of(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::create_boolean_value(this_boolean_value(this, "valueOf")?))
+}
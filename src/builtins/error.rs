@@ -0,0 +1,201 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+20.5 Error Objects
+Instances of Error objects are thrown as exceptions when runtime errors occur. The Error objects may also serve as
+  base objects for user-defined exception classes.
+When an ECMAScript implementation detects a runtime error, it throws a new instance of one of the NativeError
+  objects defined in 20.5.5 or a new instance of the AggregateError object defined in 20.5.7.
+
+20.5.6 NativeError Object Structure
+Each of these objects has the structure described below, differing only in the name used as the constructor name
+  and in the "name" property of the prototype object.
+
+@links:
+- https://tc39.es/ecma262/#sec-error-objects
+- https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard
+- https://tc39.es/ecma262/#sec-aggregate-error-objects
+*/
+use super::{create_constructor, define_method};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::function::NativeFn;
+use crate::values::{ObjectKind, ObjectValue, Value};
+use crate::vm::core::Engine;
+
+const NATIVE_ERRORS: [(&str, NativeFn); 6] = [
+  ("EvalError", eval_error_constructor),
+  ("RangeError", range_error_constructor),
+  ("ReferenceError", reference_error_constructor),
+  ("SyntaxError", syntax_error_constructor),
+  ("TypeError", type_error_constructor),
+  ("URIError", uri_error_constructor),
+];
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let error_prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  let error = create_constructor("Error", 1, error_constructor, &error_prototype);
+  error_prototype.define_builtin_property("name", Value::create_string_value("Error".to_owned()));
+  error_prototype.define_builtin_property("message", Value::create_string_value(String::new()));
+  define_method(&error_prototype, "toString", 0, error_to_string);
+  ctx.define_builtin("Error", Value::Object(error.clone()));
+  ctx.set_intrinsic("%Error.prototype%", error_prototype.clone());
+
+  let mut native_errors: Vec<(&str, NativeFn)> = NATIVE_ERRORS.to_vec();
+  native_errors.push(("AggregateError", aggregate_error_constructor));
+  for (name, function) in native_errors {
+    // every native error inherits from Error, both the constructor and its prototype
+    let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, Some(error_prototype.clone()));
+    let arity = if name == "AggregateError" { 2 } else { 1 };
+    let constructor = create_constructor(name, arity, function, &prototype);
+    constructor.set_prototype(Some(error.clone()));
+    prototype.define_builtin_property("name", Value::create_string_value(name.to_owned()));
+    prototype.define_builtin_property("message", Value::create_string_value(String::new()));
+    ctx.define_builtin(name, Value::Object(constructor));
+    ctx.set_intrinsic(&format!("%{}.prototype%", name), prototype);
+  }
+}
+
+// an instance of the error constructor called `name`, with the stack of the running code.
+pub fn create_error(engine: &Engine, name: &str, message: Option<String>) -> ObjectValue {
+  let prototype = engine.get_intrinsic(&format!("%{}.prototype%", name));
+  let error = ObjectValue::new_with_kind(ObjectKind::Error, prototype);
+  if let Some(message) = message {
+    error.define_builtin_property("message", Value::create_string_value(message));
+  }
+  let stack = format!("{}\n{}", get_error_summary(&error), engine.get_stack_trace());
+  error.define_builtin_property("stack", Value::create_string_value(stack.trim_end().to_owned()));
+  error
+}
+
+/*
+20.5.1.1 Error ( message [ , options ] )
+  1. If NewTarget is undefined, let newTarget be the active function object; else let newTarget be NewTarget.
+  2. Let O be ? OrdinaryCreateFromConstructor(newTarget, "%Error.prototype%", « [[ErrorData]] »).
+  3. If message is not undefined, then
+    a. Let msg be ? ToString(message).
+    b. Perform CreateNonEnumerableDataPropertyOrThrow(O, "message", msg).
+  4. Perform ? InstallErrorCause(O, options).
+*/
+fn construct_error(
+  engine: &mut Engine,
+  name: &str,
+  message: Option<&Value>,
+  options: Option<&Value>,
+) -> Result<ObjectValue> {
+  let prototype = match engine.get_new_target() {
+    Some(new_target) => Some(engine.get_prototype_from_constructor(&new_target, &format!("%{}.prototype%", name))?),
+    None => None,
+  };
+  let message = message.filter(|message| !message.is_undefined()).map(Value::to_string);
+  let error = create_error(engine, name, message);
+  if let Some(prototype) = prototype {
+    error.set_prototype(prototype);
+  }
+  install_error_cause(&error, options);
+  Ok(error)
+}
+
+/*
+20.5.8.1 InstallErrorCause ( O, options )
+  1. If options is an Object and ? HasProperty(options, "cause") is true, then
+    a. Let cause be ? Get(options, "cause").
+    b. Perform CreateNonEnumerableDataPropertyOrThrow(O, "cause", cause).
+*/
+fn install_error_cause(error: &ObjectValue, options: Option<&Value>) {
+  let Some(options) = options.and_then(Value::get_object) else {
+    return;
+  };
+  if let Some(cause) = options.get_property("cause") {
+    error.define_builtin_property("cause", cause);
+  }
+}
+
+/*
+20.5.3.4 Error.prototype.toString ( )
+  5. If name is undefined, set name to "Error"; otherwise set name to ? ToString(name).
+  7. If msg is undefined, set msg to the empty String; otherwise set msg to ? ToString(msg).
+  8. If name is the empty String, return msg.
+  9. If msg is the empty String, return name.
+  10. Return the string-concatenation of name, the code unit 0x003A (COLON), the code unit 0x0020 (SPACE), and msg.
+*/
+fn get_error_summary(error: &ObjectValue) -> String {
+  let name = match error.get_property("name") {
+    Some(name) if !name.is_undefined() => name.to_string(),
+    _ => "Error".to_owned(),
+  };
+  let message = match error.get_property("message") {
+    Some(message) if !message.is_undefined() => message.to_string(),
+    _ => String::new(),
+  };
+  match (name.is_empty(), message.is_empty()) {
+    (true, _) => message,
+    (_, true) => name,
+    _ => format!("{}: {}", name, message),
+  }
+}
+
+fn error_to_string(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(error) = this.get_object() else {
+    let message = "Error.prototype.toString requires that 'this' be an Object".to_owned();
+    return Err(EngineError::TypeError(message));
+  };
+  Ok(Value::create_string_value(get_error_summary(error)))
+}
+
+// the constructors of the native errors only differ in the prototype of their instances.
+fn construct_native_error(engine: &mut Engine, name: &str, arguments: &[Value]) -> Result<Value> {
+  Ok(Value::Object(construct_error(
+    engine,
+    name,
+    arguments.first(),
+    arguments.get(1),
+  )?))
+}
+
+fn error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "Error", arguments)
+}
+
+fn eval_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "EvalError", arguments)
+}
+
+fn range_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "RangeError", arguments)
+}
+
+fn reference_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "ReferenceError", arguments)
+}
+
+fn syntax_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "SyntaxError", arguments)
+}
+
+fn type_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "TypeError", arguments)
+}
+
+fn uri_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  construct_native_error(engine, "URIError", arguments)
+}
+
+/*
+20.5.7.1.1 AggregateError ( errors, message [ , options ] )
+  5. Let errorsList be ? IteratorToList(? GetIterator(errors, sync)).
+  6. Perform ! DefinePropertyOrThrow(O, "errors", PropertyDescriptor { [[Configurable]]: true, [[Enumerable]]: false,
+    [[Writable]]: true, [[Value]]: CreateArrayFromList(errorsList) }).
+*/
+fn aggregate_error_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let error = construct_error(engine, "AggregateError", arguments.get(1), arguments.get(2))?;
+  let errors = arguments.first().cloned().unwrap_or_else(Value::create_undefined_value);
+  let errors = engine.iterate_to_list(&errors)?;
+  error.define_builtin_property("errors", Value::Object(engine.create_array_from_list(errors)));
+  Ok(Value::Object(error))
+}
@@ -0,0 +1,219 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+20.2 Function Objects
+The Function prototype object is %Function.prototype%. It is itself a built-in function object, it accepts any
+  arguments and returns undefined when invoked. The value of its [[Prototype]] internal slot is %Object.prototype%.
+Unless otherwise specified every built-in function object has %Function.prototype% as the initial value of its
+  [[Prototype]] internal slot.
+
+@links:
+- https://tc39.es/ecma262/#sec-function-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-function-prototype-object
+*/
+use super::error::create_error;
+use super::{create_builtin_function, create_constructor, define_method, define_readonly_property, get_argument};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::function::BoundFunction;
+use crate::values::property::{PropertyDescriptor, PropertyKey, PropertySlot};
+use crate::values::{NativeFunction, ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::to_integer_or_infinity;
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = create_builtin_function(NativeFunction::new("", 0, function_prototype));
+  prototype.set_prototype(object_prototype);
+  let function = create_constructor("Function", 1, function_constructor, &prototype);
+  define_method(&prototype, "apply", 2, apply);
+  define_method(&prototype, "bind", 1, bind);
+  define_method(&prototype, "call", 1, call);
+
+  // 20.2.3.6 Function.prototype [ @@hasInstance ] ( V )
+  let has_instance = ctx
+    .get_well_known_symbol("Symbol.hasInstance")
+    .cloned()
+    .expect("well-known symbol to exist");
+  let method = create_builtin_function(NativeFunction::new("[Symbol.hasInstance]", 1, function_has_instance));
+  let descriptor = PropertyDescriptor::new_data_with(Value::Object(method), false, false, false);
+  prototype.define_own_property(PropertyKey::Symbol(has_instance), descriptor);
+
+  ctx.define_builtin("Function", Value::Object(function));
+  ctx.set_intrinsic("%Function.prototype%", prototype);
+}
+
+/*
+10.3 Built-in Function Objects
+The built-ins are created before %Function.prototype% exists, so their [[Prototype]] is set once the realm has
+  all of them. Every object reachable from the global bindings and the intrinsics is visited once.
+*/
+pub fn link_function_prototype(ctx: &Context) {
+  let Some(function_prototype) = ctx.get_intrinsic("%Function.prototype%").cloned() else {
+    return;
+  };
+  let mut pending: Vec<ObjectValue> = ctx.get_intrinsics().cloned().collect();
+  pending.extend(ctx.get_global_values().filter_map(Value::get_object).cloned());
+  let mut visited: Vec<ObjectValue> = Vec::new();
+  while let Some(object) = pending.pop() {
+    if visited.iter().any(|seen| seen.is_same(&object)) {
+      continue;
+    }
+    if object.is_function() && object.get_prototype().is_none() && !object.is_same(&function_prototype) {
+      object.set_prototype(Some(function_prototype.clone()));
+    }
+    for key in object.own_property_keys() {
+      let Some(property) = object.get_own_property(&key) else {
+        continue;
+      };
+      let values = match property.slot {
+        PropertySlot::Data { value, .. } => vec![value],
+        PropertySlot::Accessor { get, set } => vec![get, set],
+      };
+      pending.extend(values.iter().filter_map(Value::get_object).cloned());
+    }
+    pending.extend(object.get_prototype());
+    visited.push(object);
+  }
+}
+
+// 20.2.3 Properties of the Function Prototype Object, it accepts any arguments and returns undefined.
+fn function_prototype(_engine: &mut Engine, _this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::create_undefined_value())
+}
+
+/*
+20.2.1.1 Function ( ...parameterArgs, bodyArg )
+  2. Return ? CreateDynamicFunction(C, NewTarget, normal, parameterArgs, bodyArg).
+20.2.1.1.1 CreateDynamicFunction ( constructor, newTarget, kind, parameterArgs, bodyArg )
+  2. Perform ? HostEnsureCanCompileStrings(currentRealm, parameterStrings, bodyString, false).
+The host does not compile code from strings, like a Content Security Policy without 'unsafe-eval'.
+*/
+fn function_constructor(engine: &mut Engine, _this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let message = "Code generation from strings disallowed for this context".to_owned();
+  Err(EngineError::Exception(Value::Object(create_error(
+    engine,
+    "EvalError",
+    Some(message),
+  ))))
+}
+
+/*
+20.2.3.1 Function.prototype.apply ( thisArg, argArray )
+  1. Let func be the this value.
+  2. If IsCallable(func) is false, throw a TypeError exception.
+  3. If argArray is either undefined or null, then
+    b. Return ? Call(func, thisArg).
+  4. Let argList be ? CreateListFromArrayLike(argArray).
+  6. Return ? Call(func, thisArg, argList).
+*/
+fn apply(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  if !this.is_function() {
+    return Err(EngineError::TypeError(format!(
+      "Function.prototype.apply was called on {}, which is not a function",
+      this
+    )));
+  }
+  let this_argument = get_argument(arguments, 0);
+  let arguments = match get_argument(arguments, 1) {
+    array if array.is_undefined() || array.is_null() => Vec::new(),
+    array => engine.create_list_from_array_like(&array)?,
+  };
+  engine.call_function(this, &this_argument, &arguments)
+}
+
+/*
+20.2.3.2 Function.prototype.bind ( thisArg, ...args )
+  1. Let Target be the this value.
+  2. If IsCallable(Target) is false, throw a TypeError exception.
+  3. Let F be ? BoundFunctionCreate(Target, thisArg, args).
+  4. Let L be 0.
+  5. Let targetHasLength be ? HasOwnProperty(Target, "length").
+  6. If targetHasLength is true, then
+    a. Let targetLen be ? Get(Target, "length").
+    b. If targetLen is a Number, then
+      i. If targetLen is +∞𝔽, then
+        1. Set L to +∞.
+      iii. Else,
+        1. Let targetLenAsInt be ! ToIntegerOrInfinity(targetLen).
+        2. Set L to max(targetLenAsInt - argCount, 0).
+  7. Perform SetFunctionLength(F, L).
+  8. Let targetName be ? Get(Target, "name").
+  9. If targetName is not a String, set targetName to the empty String.
+  10. Perform SetFunctionName(F, targetName, "bound").
+*/
+fn bind(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Some(target) = this.get_object().filter(|object| object.is_function()).cloned() else {
+    return Err(EngineError::TypeError("Bind must be called on a function".to_owned()));
+  };
+  let this_argument = get_argument(arguments, 0);
+  let bound_arguments = arguments.iter().skip(1).cloned().collect::<Vec<_>>();
+  let argument_count = bound_arguments.len() as f64;
+  let function = bound_function_create(&target, this_argument, bound_arguments);
+  let mut length = 0.0;
+  if target.get_own_property(&PropertyKey::from("length")).is_some() {
+    let target_length = engine.get(&target, &PropertyKey::from("length"), this)?;
+    if target_length.is_number() {
+      length = (to_integer_or_infinity(engine, &target_length)? - argument_count).max(0.0);
+    }
+  }
+  let name = match engine.get(&target, &PropertyKey::from("name"), this)? {
+    Value::String(name) => name.to_string(),
+    _ => String::new(),
+  };
+  define_readonly_property(&function, "length", Value::create_float_value(length), true);
+  define_readonly_property(
+    &function,
+    "name",
+    Value::create_string_value(format!("bound {}", name)),
+    true,
+  );
+  Ok(Value::Object(function))
+}
+
+/*
+10.4.1.3 BoundFunctionCreate ( targetFunction, boundThis, boundArgs )
+  1. Let proto be ? targetFunction.[[GetPrototypeOf]]().
+  5. Set obj.[[Prototype]] to proto.
+  7. Set obj.[[BoundTargetFunction]] to targetFunction.
+  8. Set obj.[[BoundThis]] to boundThis.
+  9. Set obj.[[BoundArguments]] to boundArgs.
+*/
+fn bound_function_create(target: &ObjectValue, this: Value, arguments: Vec<Value>) -> ObjectValue {
+  let function = BoundFunction::new(target.clone(), this, arguments);
+  ObjectValue::new_with_kind(ObjectKind::BoundFunction(function), target.get_prototype())
+}
+
+/*
+20.2.3.3 Function.prototype.call ( thisArg, ...args )
+  1. Let func be the this value.
+  2. If IsCallable(func) is false, throw a TypeError exception.
+  4. Return ? Call(func, thisArg, args).
+*/
+fn call(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  if !this.is_function() {
+    return Err(EngineError::TypeError(format!(
+      "Function.prototype.call was called on {}, which is not a function",
+      this
+    )));
+  }
+  let this_argument = get_argument(arguments, 0);
+  engine.call_function(this, &this_argument, arguments.get(1..).unwrap_or_default())
+}
+
+/*
+20.2.3.6 Function.prototype [ @@hasInstance ] ( V )
+  1. Let F be the this value.
+  2. Return ? OrdinaryHasInstance(F, V).
+*/
+fn function_has_instance(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Some(function) = this.get_object().filter(|object| object.is_function()) else {
+    return Ok(Value::create_boolean_value(false));
+  };
+  let result = engine.ordinary_has_instance(function, &get_argument(arguments, 0))?;
+  Ok(Value::create_boolean_value(result))
+}
@@ -0,0 +1,177 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+27.1.2 The %IteratorPrototype% Object
+23.1.5.2 The %ArrayIteratorPrototype% Object
+22.1.5.1 The %StringIteratorPrototype% Object
+24.1.5.2 The %MapIteratorPrototype% Object
+24.2.6.2 The %SetIteratorPrototype% Object
+14.7.5.10.2 The %ForInIteratorPrototype% Object
+The built-in iterators inherit from %IteratorPrototype%, their `next` methods produce one iterator result
+  object per step and keep returning done results once the iteration is over.
+
+@links:
+- https://tc39.es/ecma262/#sec-%iteratorprototype%-object
+- https://tc39.es/ecma262/#sec-%arrayiteratorprototype%-object
+- https://tc39.es/ecma262/#sec-%stringiteratorprototype%-object
+- https://tc39.es/ecma262/#sec-%mapiteratorprototype%-object
+- https://tc39.es/ecma262/#sec-%setiteratorprototype%-object
+- https://tc39.es/ecma262/#sec-%foriniteratorprototype%-object
+*/
+use super::{define_method, define_symbol_method, define_to_string_tag};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::function::NativeFn;
+use crate::values::iterator::IterationKind;
+use crate::values::property::PropertyKey;
+use crate::values::{ObjectKind, ObjectValue, Value};
+use crate::vm::core::Engine;
+
+// the iterators with a prototype of their own, by intrinsic name and @@toStringTag.
+const ITERATOR_PROTOTYPES: [(&str, &str, NativeFn); 4] = [
+  ("%ArrayIteratorPrototype%", "Array Iterator", array_iterator_next),
+  ("%StringIteratorPrototype%", "String Iterator", string_iterator_next),
+  ("%MapIteratorPrototype%", "Map Iterator", map_iterator_next),
+  ("%SetIteratorPrototype%", "Set Iterator", set_iterator_next),
+];
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let iterator_prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  define_symbol_method(
+    ctx,
+    &iterator_prototype,
+    "Symbol.iterator",
+    0,
+    iterator_prototype_iterator,
+  );
+  for (name, tag, next) in ITERATOR_PROTOTYPES {
+    let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, Some(iterator_prototype.clone()));
+    define_method(&prototype, "next", 0, next);
+    define_to_string_tag(ctx, &prototype, tag);
+    ctx.set_intrinsic(name, prototype);
+  }
+  // 14.7.5.10.2 it is never directly accessible to ECMAScript code
+  let for_in_iterator_prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, Some(iterator_prototype.clone()));
+  define_method(&for_in_iterator_prototype, "next", 0, for_in_iterator_next);
+  ctx.set_intrinsic("%IteratorPrototype%", iterator_prototype);
+  ctx.set_intrinsic("%ForInIteratorPrototype%", for_in_iterator_prototype);
+}
+
+// 27.1.2.1 %IteratorPrototype% [ @@iterator ] ( ), an iterator is an iterable of itself.
+fn iterator_prototype_iterator(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(this.clone())
+}
+
+// the value an iterator over a collection produces for an entry.
+fn iteration_value(engine: &Engine, kind: IterationKind, key: Value, value: Value) -> Value {
+  match kind {
+    IterationKind::Key => key,
+    IterationKind::Value => value,
+    IterationKind::KeyValue => Value::Object(engine.create_array_from_list(vec![key, value])),
+  }
+}
+
+fn incompatible_receiver(method: &str, this: &Value) -> EngineError {
+  EngineError::TypeError(format!("Method {} called on incompatible receiver {}", method, this))
+}
+
+/*
+23.1.5.2.1 %ArrayIteratorPrototype%.next ( )
+  1. Let a be O.[[IteratedArrayLike]].
+  2. If a is undefined, return CreateIteratorResultObject(undefined, true).
+  4. Let len be ? LengthOfArrayLike(a).
+  5. If index ≥ len, then
+    a. Set O.[[IteratedArrayLike]] to undefined.
+    b. Return CreateIteratorResultObject(undefined, true).
+  6. Set O.[[ArrayLikeNextIndex]] to index + 1.
+  7. Let indexNumber be 𝔽(index).
+  8. If kind is key, then
+    a. Let result be indexNumber.
+  9. Else,
+    a. Let elementKey be ! ToString(indexNumber).
+    b. Let elementValue be ? Get(a, elementKey).
+    c. If kind is value, then
+      i. Let result be elementValue.
+    d. Else,
+      ii. Let result be CreateArrayFromList(« indexNumber, elementValue »).
+*/
+fn array_iterator_next(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(ObjectKind::ArrayIterator(iterator)) = this.get_object().map(ObjectValue::get_kind) else {
+    return Err(incompatible_receiver("Array Iterator.prototype.next", this));
+  };
+  let Some(array) = iterator.get_iterated() else {
+    return Ok(engine.create_iter_result_object(Value::create_undefined_value(), true));
+  };
+  let length = engine.length_of_array_like(&array)?;
+  let index = iterator.advance();
+  if index >= length {
+    iterator.finish();
+    return Ok(engine.create_iter_result_object(Value::create_undefined_value(), true));
+  }
+  let key = Value::create_float_value(index as f64);
+  if iterator.get_kind() == IterationKind::Key {
+    return Ok(engine.create_iter_result_object(key, false));
+  }
+  let value = engine.get(
+    &array,
+    &PropertyKey::from(index.to_string()),
+    &Value::Object(array.clone()),
+  )?;
+  let result = iteration_value(engine, iterator.get_kind(), key, value);
+  Ok(engine.create_iter_result_object(result, false))
+}
+
+// 22.1.5.1.1 %StringIteratorPrototype%.next ( )
+fn string_iterator_next(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(ObjectKind::StringIterator(iterator)) = this.get_object().map(ObjectValue::get_kind) else {
+    return Err(incompatible_receiver("String Iterator.prototype.next", this));
+  };
+  match iterator.next_code_point() {
+    Some(code_point) => Ok(engine.create_iter_result_object(Value::String(code_point), false)),
+    None => Ok(engine.create_iter_result_object(Value::create_undefined_value(), true)),
+  }
+}
+
+// 24.1.5.2.1 %MapIteratorPrototype%.next ( )
+fn map_iterator_next(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(ObjectKind::MapIterator(iterator)) = this.get_object().map(ObjectValue::get_kind) else {
+    return Err(incompatible_receiver("Map Iterator.prototype.next", this));
+  };
+  match iterator.next_entry() {
+    Some((key, value)) => {
+      let result = iteration_value(engine, iterator.get_kind(), key, value);
+      Ok(engine.create_iter_result_object(result, false))
+    }
+    None => Ok(engine.create_iter_result_object(Value::create_undefined_value(), true)),
+  }
+}
+
+// 24.2.6.2.1 %SetIteratorPrototype%.next ( ), a value is its own key.
+fn set_iterator_next(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(ObjectKind::SetIterator(iterator)) = this.get_object().map(ObjectValue::get_kind) else {
+    return Err(incompatible_receiver("Set Iterator.prototype.next", this));
+  };
+  match iterator.next_entry() {
+    Some((key, value)) => {
+      let result = iteration_value(engine, iterator.get_kind(), key, value);
+      Ok(engine.create_iter_result_object(result, false))
+    }
+    None => Ok(engine.create_iter_result_object(Value::create_undefined_value(), true)),
+  }
+}
+
+// 14.7.5.10.2.1 %ForInIteratorPrototype%.next ( )
+fn for_in_iterator_next(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let Some(ObjectKind::ForInIterator(iterator)) = this.get_object().map(ObjectValue::get_kind) else {
+    return Err(incompatible_receiver("For-In Iterator.prototype.next", this));
+  };
+  match iterator.next_key() {
+    Some(PropertyKey::String(key)) => Ok(engine.create_iter_result_object(Value::String(key), false)),
+    _ => Ok(engine.create_iter_result_object(Value::create_undefined_value(), true)),
+  }
+}
@@ -0,0 +1,221 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+24.1 Map Objects
+Maps are collections of key/value pairs where both the keys and values may be arbitrary ECMAScript language
+  values. A distinct key value may only occur in one key/value pair within the Map's collection.
+
+@links:
+- https://tc39.es/ecma262/#sec-map-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-map-prototype-object
+*/
+use super::{
+  create_constructor, create_function, define_method, define_symbol_property, define_to_string_tag, get_argument,
+};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::iterator::IterationKind;
+use crate::values::map::MapData;
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectKind, ObjectValue, Value};
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  let map = create_constructor("Map", 0, map_constructor, &prototype);
+  define_method(&prototype, "clear", 0, clear);
+  define_method(&prototype, "delete", 1, delete);
+  define_method(&prototype, "entries", 0, entries);
+  define_method(&prototype, "forEach", 1, for_each);
+  define_method(&prototype, "get", 1, get);
+  define_method(&prototype, "has", 1, has);
+  define_method(&prototype, "keys", 0, keys);
+  define_method(&prototype, "set", 2, set);
+  define_method(&prototype, "values", 0, values);
+  let getter = create_function("get size", 0, size);
+  let descriptor = PropertyDescriptor::new_accessor(Some(Value::Object(getter)), None, false);
+  prototype.define_own_property(PropertyKey::from("size"), descriptor);
+
+  // 24.1.3.12 Map.prototype [ @@iterator ] ( ), the initial value is %Map.prototype.entries%
+  let entries = prototype.get_property("entries").expect("entries method to be defined");
+  define_symbol_property(ctx, &prototype, "Symbol.iterator", entries);
+  define_to_string_tag(ctx, &prototype, "Map");
+
+  ctx.define_builtin("Map", Value::Object(map));
+  ctx.set_intrinsic("%Map.prototype%", prototype);
+}
+
+// the [[MapData]] of the this value, the methods only work on Maps.
+fn this_map_data<'v>(this: &'v Value, method: &str) -> Result<&'v MapData> {
+  match this.get_object().map(ObjectValue::get_kind) {
+    Some(ObjectKind::Map(data)) => Ok(data),
+    _ => Err(EngineError::TypeError(format!(
+      "Method Map.prototype.{} called on incompatible receiver {}",
+      method, this
+    ))),
+  }
+}
+
+/*
+24.1.1.1 Map ( [ iterable ] )
+  1. If NewTarget is undefined, throw a TypeError exception.
+  2. Let map be ? OrdinaryCreateFromConstructor(NewTarget, "%Map.prototype%", « [[MapData]] »).
+  4. If iterable is either undefined or null, return map.
+  5. Let adder be ? Get(map, "set").
+  6. If IsCallable(adder) is false, throw a TypeError exception.
+  7. Return ? AddEntriesFromIterable(map, iterable, adder).
+*/
+fn map_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Some(new_target) = engine.get_new_target() else {
+    return Err(EngineError::TypeError("Constructor Map requires 'new'".to_owned()));
+  };
+  let prototype = engine.get_prototype_from_constructor(&new_target, "%Map.prototype%")?;
+  let map = Value::Object(ObjectValue::new_map(prototype));
+  let iterable = get_argument(arguments, 0);
+  if iterable.is_undefined() || iterable.is_null() {
+    return Ok(map);
+  }
+  let adder = engine.get_value(&map, &PropertyKey::from("set"))?;
+  if !adder.is_function() {
+    return Err(EngineError::TypeError(format!(
+      "'{}' returned for property 'set' of object '#<Map>' is not a function",
+      adder
+    )));
+  }
+  add_entries_from_iterable(engine, &map, &iterable, &adder)?;
+  Ok(map)
+}
+
+/*
+24.1.1.2 AddEntriesFromIterable ( target, iterable, adder )
+  1. Let iteratorRecord be ? GetIterator(iterable, sync).
+  2. Repeat,
+    a. Let next be ? IteratorStepValue(iteratorRecord).
+    b. If next is done, return target.
+    c. If next is not an Object, then
+      i. Let error be ThrowCompletion(a newly created TypeError object).
+      ii. Return ? IteratorClose(iteratorRecord, error).
+    d. Let k be Completion(Get(next, "0")).
+    f. Let v be Completion(Get(next, "1")).
+    h. Let status be Completion(Call(adder, target, « k, v »)).
+    i. IfAbruptCloseIterator(status, iteratorRecord).
+*/
+fn add_entries_from_iterable(engine: &mut Engine, target: &Value, iterable: &Value, adder: &Value) -> Result<()> {
+  let record = engine.get_iterator(iterable)?;
+  while let Some(next) = engine.iterator_step_value(&record)? {
+    if let Err(error) = add_entry(engine, target, &next, adder) {
+      return Err(engine.iterator_close_with_error(&record, error));
+    }
+  }
+  Ok(())
+}
+
+// adds the `[key, value]` entry an iterator produced, the iterator is closed when this fails.
+fn add_entry(engine: &mut Engine, target: &Value, next: &Value, adder: &Value) -> Result<Value> {
+  let Value::Object(entry) = next else {
+    return Err(EngineError::TypeError(format!(
+      "Iterator value {} is not an entry object",
+      next
+    )));
+  };
+  let key = engine.get(entry, &PropertyKey::from("0"), next)?;
+  let value = engine.get(entry, &PropertyKey::from("1"), next)?;
+  engine.call_function(adder, target, &[key, value])
+}
+
+// 24.1.3.1 Map.prototype.clear ( )
+fn clear(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  this_map_data(this, "clear")?.clear();
+  Ok(Value::create_undefined_value())
+}
+
+// 24.1.3.3 Map.prototype.delete ( key )
+fn delete(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let deleted = this_map_data(this, "delete")?.delete(&get_argument(arguments, 0));
+  Ok(Value::create_boolean_value(deleted))
+}
+
+/*
+24.1.3.4 Map.prototype.entries ( )
+24.1.3.8 Map.prototype.keys ( )
+24.1.3.11 Map.prototype.values ( )
+  1. Let M be the this value.
+  2. Return ? CreateMapIterator(M, kind).
+*/
+fn create_map_iterator(engine: &mut Engine, this: &Value, kind: IterationKind, method: &str) -> Result<Value> {
+  this_map_data(this, method)?;
+  let map = this.get_object().cloned().expect("map to be an object");
+  let prototype = engine.get_intrinsic("%MapIteratorPrototype%");
+  Ok(Value::Object(ObjectValue::new_collection_iterator(
+    map, kind, prototype,
+  )))
+}
+
+fn entries(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_map_iterator(engine, this, IterationKind::KeyValue, "entries")
+}
+
+fn keys(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_map_iterator(engine, this, IterationKind::Key, "keys")
+}
+
+fn values(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_map_iterator(engine, this, IterationKind::Value, "values")
+}
+
+/*
+24.1.3.5 Map.prototype.forEach ( callbackfn [ , thisArg ] )
+  3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  4. Let entries be M.[[MapData]].
+  5. Let numEntries be the number of elements in entries.
+  6. Let index be 0.
+  7. Repeat, while index < numEntries,
+    a. Let e be entries[index].
+    b. Set index to index + 1.
+    c. If e.[[Key]] is not empty, then
+      i. Perform ? Call(callbackfn, thisArg, « e.[[Value]], e.[[Key]], M »).
+      ii. NOTE: The number of elements in entries may have increased during execution of callbackfn.
+      iii. Set numEntries to the number of elements in entries.
+*/
+fn for_each(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let data = this_map_data(this, "forEach")?;
+  let callback = get_argument(arguments, 0);
+  if !callback.is_function() {
+    return Err(EngineError::TypeError(format!("{} is not a function", callback)));
+  }
+  let this_argument = get_argument(arguments, 1);
+  data.for_each_entry(|key, value| {
+    engine.call_function(&callback, &this_argument, &[value, key, this.clone()])?;
+    Ok(())
+  })?;
+  Ok(Value::create_undefined_value())
+}
+
+// 24.1.3.6 Map.prototype.get ( key )
+fn get(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = this_map_data(this, "get")?.get(&get_argument(arguments, 0));
+  Ok(value.unwrap_or_else(Value::create_undefined_value))
+}
+
+// 24.1.3.7 Map.prototype.has ( key )
+fn has(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let has = this_map_data(this, "has")?.has(&get_argument(arguments, 0));
+  Ok(Value::create_boolean_value(has))
+}
+
+// 24.1.3.9 Map.prototype.set ( key, value )
+fn set(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  this_map_data(this, "set")?.set(get_argument(arguments, 0), get_argument(arguments, 1));
+  Ok(this.clone())
+}
+
+// 24.1.3.10 get Map.prototype.size
+fn size(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let size = this_map_data(this, "size")?.size();
+  Ok(Value::create_number_value(size as i64))
+}
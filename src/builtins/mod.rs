@@ -0,0 +1,163 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+9.3 Realms
+Before it is evaluated, all ECMAScript code must be associated with a realm. Conceptually, a realm consists of
+  a set of intrinsic objects, an ECMAScript global environment, all of the ECMAScript code that is loaded within
+  the scope of that global environment, and other associated state and resources.
+
+@links:
+- https://tc39.es/ecma262/#sec-code-realms
+- https://tc39.es/ecma262/#sec-well-known-intrinsic-objects
+*/
+#![allow(dead_code)]
+pub mod array;
+pub mod bigint;
+pub mod boolean;
+pub mod error;
+pub mod function;
+pub mod iterator;
+pub mod map;
+pub mod number;
+pub mod object;
+pub mod set;
+pub mod string;
+pub mod symbol;
+
+use crate::context::Context;
+use crate::errors::Result;
+use crate::values::function::NativeFn;
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{NativeFunction, ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::to_integer_or_infinity;
+use crate::vm::core::Engine;
+
+// creates the intrinsics and binds the constructors in the global environment.
+pub fn install(ctx: &mut Context) {
+  object::install(ctx);
+  function::install(ctx);
+  symbol::install(ctx);
+  iterator::install(ctx);
+  array::install(ctx);
+  boolean::install(ctx);
+  number::install(ctx);
+  string::install(ctx);
+  bigint::install(ctx);
+  error::install(ctx);
+  map::install(ctx);
+  set::install(ctx);
+  install_global_object(ctx);
+  function::link_function_prototype(ctx);
+}
+
+/*
+19.1.1 globalThis
+The initial value of the "globalThis" property of the global object in a Realm Record realm is
+  realm.[[GlobalEnv]].[[GlobalThisValue]].
+The object is the [[ObjectRecord]] of the global environment, it holds the built-ins and the `var`s of scripts and
+  is the this value of scripts and sloppy functions.
+*/
+fn install_global_object(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let global = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  for (name, value) in ctx.get_global_bindings() {
+    // 19.1 Value Properties of the Global Object, undefined, NaN and Infinity can't be changed
+    match value {
+      Value::Object(_) => global.define_builtin_property(name, value.clone()),
+      value => {
+        let descriptor = PropertyDescriptor::new_data_with(value.clone(), false, false, false);
+        global.define_own_property(PropertyKey::from(name), descriptor);
+      }
+    }
+  }
+  global.define_builtin_property("globalThis", Value::Object(global.clone()));
+  ctx.define_builtin("globalThis", Value::Object(global.clone()));
+  ctx.set_intrinsic("%GlobalThis%", global);
+}
+
+// a built-in function object with its `name` and `length`.
+pub fn create_function(name: &str, arity: usize, function: NativeFn) -> ObjectValue {
+  create_builtin_function(NativeFunction::new(name, arity, function))
+}
+
+pub(crate) fn create_builtin_function(function: NativeFunction) -> ObjectValue {
+  let name = Value::create_string_value(function.get_name().to_owned());
+  let length = Value::create_number_value(function.get_arity() as i64);
+  let value = Value::create_native_function_value(function);
+  let object = value.get_object().cloned().expect("function to be an object");
+  define_readonly_property(&object, "name", name, true);
+  define_readonly_property(&object, "length", length, true);
+  object
+}
+
+// a constructor and its prototype object pointing at each other.
+pub fn create_constructor(name: &str, arity: usize, function: NativeFn, prototype: &ObjectValue) -> ObjectValue {
+  let constructor = create_builtin_function(NativeFunction::new_constructor(name, arity, function));
+  define_readonly_property(&constructor, "prototype", Value::Object(prototype.clone()), false);
+  prototype.define_builtin_property("constructor", Value::Object(constructor.clone()));
+  constructor
+}
+
+// the argument at `index`, undefined when the caller passed fewer.
+pub fn get_argument(arguments: &[Value], index: usize) -> Value {
+  arguments
+    .get(index)
+    .cloned()
+    .unwrap_or_else(Value::create_undefined_value)
+}
+
+// a start or end argument: negative values count back from the end, the result is within the length.
+pub fn get_relative_index(engine: &mut Engine, value: &Value, length: u64, default: u64) -> Result<u64> {
+  if value.is_undefined() {
+    return Ok(default);
+  }
+  let relative = to_integer_or_infinity(engine, value)?;
+  if relative < 0.0 {
+    return Ok((length as f64 + relative).max(0.0) as u64);
+  }
+  Ok(relative.min(length as f64) as u64)
+}
+
+// adds a built-in method to an object.
+pub fn define_method(object: &ObjectValue, name: &str, arity: usize, function: NativeFn) {
+  let method = create_function(name, arity, function);
+  object.define_builtin_property(name, Value::Object(method));
+}
+
+// the property key of a well-known symbol of the realm, like `Symbol.iterator`.
+pub fn get_symbol_key(ctx: &Context, description: &str) -> PropertyKey {
+  let symbol = ctx
+    .get_well_known_symbol(description)
+    .cloned()
+    .expect("well-known symbol to exist");
+  PropertyKey::Symbol(symbol)
+}
+
+// adds a built-in method keyed by a well-known symbol, its name is the description in brackets.
+pub fn define_symbol_method(ctx: &Context, object: &ObjectValue, description: &str, arity: usize, function: NativeFn) {
+  let method = create_function(&format!("[{}]", description), arity, function);
+  define_symbol_property(ctx, object, description, Value::Object(method));
+}
+
+// a property keyed by a well-known symbol, writable and configurable like a built-in method.
+pub fn define_symbol_property(ctx: &Context, object: &ObjectValue, description: &str, value: Value) {
+  let descriptor = PropertyDescriptor::new_data_with(value, true, false, true);
+  object.define_own_property(get_symbol_key(ctx, description), descriptor);
+}
+
+// the @@toStringTag of a prototype, the name `Object.prototype.toString` shows for its objects.
+pub fn define_to_string_tag(ctx: &Context, object: &ObjectValue, tag: &str) {
+  let tag = Value::create_string_value(tag.to_owned());
+  let descriptor = PropertyDescriptor::new_data_with(tag, false, false, true);
+  object.define_own_property(get_symbol_key(ctx, "Symbol.toStringTag"), descriptor);
+}
+
+// a property no assignment can change, like the `length` of a function.
+pub fn define_readonly_property(object: &ObjectValue, key: &str, value: Value, configurable: bool) {
+  let descriptor = PropertyDescriptor::new_data_with(value, false, false, configurable);
+  object.define_own_property(PropertyKey::from(key), descriptor);
+}
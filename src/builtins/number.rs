@@ -0,0 +1,89 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+21.1 Number Objects
+The Number prototype object is %Number.prototype%. It is itself a Number object whose [[NumberData]] is +0𝔽, the
+  this value of its methods is either a Number or a Number object.
+Primitive numbers reach these methods through ToObject, there is no Number constructor yet.
+
+@links:
+- https://tc39.es/ecma262/#sec-number-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-number-prototype-object
+*/
+use super::{define_method, get_argument};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::{NumberValue, ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::{number_to_radix_string, to_integer_or_infinity};
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let zero = ObjectKind::Number(NumberValue::new(0));
+  let prototype = ObjectValue::new_with_kind(zero, object_prototype);
+  define_method(&prototype, "toLocaleString", 0, to_locale_string);
+  define_method(&prototype, "toString", 1, number_to_string);
+  define_method(&prototype, "valueOf", 0, value_of);
+  ctx.set_intrinsic("%Number.prototype%", prototype);
+}
+
+/*
+21.1.3.7.1 ThisNumberValue ( value )
+  1. If value is a Number, return value.
+  2. If value is an Object and value has a [[NumberData]] internal slot, then
+    b. Return value.[[NumberData]].
+  3. Throw a TypeError exception.
+*/
+fn this_number_value(this: &Value, method: &str) -> Result<NumberValue> {
+  let number = match this {
+    Value::Number(number) => Some(number),
+    Value::Object(object) => object.get_number_data(),
+    _ => None,
+  };
+  number.copied().ok_or_else(|| {
+    EngineError::TypeError(format!(
+      "Number.prototype.{} requires that 'this' be a Number: {}",
+      method, this
+    ))
+  })
+}
+
+// 21.1.3.4 Number.prototype.toLocaleString ( [ reserved1 [ , reserved2 ] ] ), without a locale it is toString.
+fn to_locale_string(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let number = this_number_value(this, "toLocaleString")?;
+  Ok(Value::create_string_value(number_to_radix_string(number.as_f64(), 10)))
+}
+
+/*
+21.1.3.6 Number.prototype.toString ( [ radix ] )
+  1. Let x be ? ThisNumberValue(this value).
+  2. If radix is undefined, let radixMV be 10.
+  3. Else, let radixMV be ? ToIntegerOrInfinity(radix).
+  4. If radixMV is not in the inclusive interval from 2 to 36, throw a RangeError exception.
+  5. Return Number::toString(x, radixMV).
+*/
+fn number_to_string(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let number = this_number_value(this, "toString")?;
+  let radix = match get_argument(arguments, 0) {
+    radix if radix.is_undefined() => 10.0,
+    radix => to_integer_or_infinity(engine, &radix)?,
+  };
+  if !(2.0..=36.0).contains(&radix) {
+    return Err(EngineError::RangeError(
+      "toString() radix must be between 2 and 36".to_owned(),
+    ));
+  }
+  Ok(Value::create_string_value(number_to_radix_string(
+    number.as_f64(),
+    radix as u32,
+  )))
+}
+
+// 21.1.3.7 Number.prototype.valueOf ( )
+fn value_of(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::Number(this_number_value(this, "valueOf")?))
+}
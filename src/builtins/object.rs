@@ -0,0 +1,559 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+20.1 Object Objects
+The Object constructor is %Object%. It creates a new ordinary object when called as a function and performs a
+  type conversion when called with a value.
+The Object prototype object is %Object.prototype%. It is an immutable prototype exotic object whose [[Prototype]]
+  internal slot is null, every other object of the realm inherits from it.
+
+@links:
+- https://tc39.es/ecma262/#sec-object-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-object-prototype-object
+*/
+use super::{create_constructor, define_method, get_argument};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::to_property_key;
+use crate::vm::comparison::same_value;
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, None);
+  let object = create_constructor("Object", 1, object_constructor, &prototype);
+  define_method(&prototype, "hasOwnProperty", 1, has_own_property);
+  define_method(&prototype, "isPrototypeOf", 1, is_prototype_of);
+  define_method(&prototype, "propertyIsEnumerable", 1, property_is_enumerable);
+  define_method(&prototype, "toLocaleString", 0, to_locale_string);
+  define_method(&prototype, "toString", 0, object_to_string);
+  define_method(&prototype, "valueOf", 0, value_of);
+
+  define_method(&object, "assign", 2, assign);
+  define_method(&object, "create", 2, create);
+  define_method(&object, "defineProperties", 2, define_properties);
+  define_method(&object, "defineProperty", 3, define_property);
+  define_method(&object, "freeze", 1, freeze);
+  define_method(&object, "getOwnPropertyDescriptor", 2, get_own_property_descriptor);
+  define_method(&object, "getOwnPropertyNames", 1, get_own_property_names);
+  define_method(&object, "getOwnPropertySymbols", 1, get_own_property_symbols);
+  define_method(&object, "getPrototypeOf", 1, get_prototype_of);
+  define_method(&object, "is", 2, is);
+  define_method(&object, "isExtensible", 1, is_extensible);
+  define_method(&object, "isFrozen", 1, is_frozen);
+  define_method(&object, "isSealed", 1, is_sealed);
+  define_method(&object, "preventExtensions", 1, prevent_extensions);
+  define_method(&object, "seal", 1, seal);
+  define_method(&object, "setPrototypeOf", 2, set_prototype_of);
+  ctx.define_builtin("Object", Value::Object(object));
+  ctx.set_intrinsic("%Object.prototype%", prototype);
+}
+
+/*
+20.1.1.1 Object ( [ value ] )
+  1. If NewTarget is neither undefined nor the active function object, then
+    a. Return ? OrdinaryCreateFromConstructor(NewTarget, "%Object.prototype%").
+  2. If value is either undefined or null, return OrdinaryObjectCreate(%Object.prototype%).
+  3. Return ! ToObject(value).
+*/
+fn object_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  // only a subclass constructor can be a new target other than Object itself
+  if let Some(new_target) = engine
+    .get_new_target()
+    .filter(|new_target| new_target.get_function().is_some())
+  {
+    return Ok(Value::Object(
+      engine.ordinary_create_from_constructor(&new_target, "%Object.prototype%")?,
+    ));
+  }
+  let value = get_argument(arguments, 0);
+  if value.is_undefined() || value.is_null() {
+    return Ok(Value::Object(engine.create_object()));
+  }
+  Ok(Value::Object(engine.to_object(&value)?))
+}
+
+/*
+20.1.2.1 Object.assign ( target, ...sources )
+  1. Let to be ? ToObject(target).
+  3. For each element nextSource of sources, do
+    a. If nextSource is neither undefined nor null, then
+      i. Let from be ! ToObject(nextSource).
+      ii. Let keys be ? from.[[OwnPropertyKeys]]().
+      iii. For each element nextKey of keys, do
+        1. Let desc be ? from.[[GetOwnProperty]](nextKey).
+        2. If desc is not undefined and desc.[[Enumerable]] is true, then
+          a. Let propValue be ? Get(from, nextKey).
+          b. Perform ? Set(to, nextKey, propValue, true).
+*/
+fn assign(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let target = engine.to_object(&get_argument(arguments, 0))?;
+  for source in arguments.iter().skip(1) {
+    if source.is_undefined() || source.is_null() {
+      continue;
+    }
+    let from = engine.to_object(source)?;
+    let receiver = Value::Object(from.clone());
+    for key in from.own_property_keys() {
+      if from.get_own_property(&key).is_some_and(|property| property.enumerable) {
+        let value = engine.get(&from, &key, &receiver)?;
+        engine.set_or_throw(&target, key, value)?;
+      }
+    }
+  }
+  Ok(Value::Object(target))
+}
+
+// the argument every prototype has to be, an object or null.
+fn get_prototype_argument(value: &Value) -> Result<Option<ObjectValue>> {
+  match value {
+    Value::Object(object) => Ok(Some(object.clone())),
+    Value::Null(_) => Ok(None),
+    _ => Err(EngineError::TypeError(format!(
+      "Object prototype may only be an Object or null: {}",
+      value
+    ))),
+  }
+}
+
+/*
+20.1.2.2 Object.create ( O, Properties )
+  1. If O is not an Object and O is not null, throw a TypeError exception.
+  2. Let obj be OrdinaryObjectCreate(O).
+  3. If Properties is not undefined, then
+    a. Return ? ObjectDefineProperties(obj, Properties).
+*/
+fn create(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let prototype = get_prototype_argument(&get_argument(arguments, 0))?;
+  let object = ObjectValue::new_with_kind(ObjectKind::Ordinary, prototype);
+  let properties = get_argument(arguments, 1);
+  if !properties.is_undefined() {
+    object_define_properties(engine, &object, &properties)?;
+  }
+  Ok(Value::Object(object))
+}
+
+/*
+20.1.2.3.1 ObjectDefineProperties ( O, Properties )
+  1. Let props be ? ToObject(Properties).
+  2. Let keys be ? props.[[OwnPropertyKeys]]().
+  4. For each element nextKey of keys, do
+    a. Let propDesc be ? props.[[GetOwnProperty]](nextKey).
+    b. If propDesc is not undefined and propDesc.[[Enumerable]] is true, then
+      i. Let descObj be ? Get(props, nextKey).
+      ii. Let desc be ? ToPropertyDescriptor(descObj).
+  5. For each element property of descriptors, do
+    a. Perform ? DefinePropertyOrThrow(O, property.[[Key]], property.[[Descriptor]]).
+*/
+fn object_define_properties(engine: &mut Engine, object: &ObjectValue, properties: &Value) -> Result<()> {
+  let properties = engine.to_object(properties)?;
+  let receiver = Value::Object(properties.clone());
+  let mut descriptors = Vec::new();
+  for key in properties.own_property_keys() {
+    if properties
+      .get_own_property(&key)
+      .is_some_and(|property| property.enumerable)
+    {
+      let value = engine.get(&properties, &key, &receiver)?;
+      descriptors.push((key, to_property_descriptor(engine, &value)?));
+    }
+  }
+  for (key, descriptor) in descriptors {
+    engine.define_property_or_throw(object, key, descriptor)?;
+  }
+  Ok(())
+}
+
+/*
+6.2.6.5 ToPropertyDescriptor ( Obj )
+  1. If Obj is not an Object, throw a TypeError exception.
+  ...
+  15. If desc has a [[Get]] field or desc has a [[Set]] field, then
+    a. If desc has a [[Value]] field or desc has a [[Writable]] field, throw a TypeError exception.
+*/
+fn to_property_descriptor(engine: &mut Engine, value: &Value) -> Result<PropertyDescriptor> {
+  let Some(object) = value.get_object().cloned() else {
+    return Err(EngineError::TypeError(format!(
+      "Property description must be an object: {}",
+      value
+    )));
+  };
+  let mut descriptor = PropertyDescriptor::default();
+  let field = |engine: &mut Engine, name: &str| -> Result<Option<Value>> {
+    let key = PropertyKey::from(name);
+    if !object.has_property(&key) {
+      return Ok(None);
+    }
+    engine.get(&object, &key, &Value::Object(object.clone())).map(Some)
+  };
+  descriptor.enumerable = field(engine, "enumerable")?.map(|value| value.is_truthy());
+  descriptor.configurable = field(engine, "configurable")?.map(|value| value.is_truthy());
+  descriptor.value = field(engine, "value")?;
+  descriptor.writable = field(engine, "writable")?.map(|value| value.is_truthy());
+  for (name, accessor) in [("get", &mut descriptor.get), ("set", &mut descriptor.set)] {
+    let Some(function) = field(engine, name)? else {
+      continue;
+    };
+    if !function.is_function() && !function.is_undefined() {
+      return Err(EngineError::TypeError(format!(
+        "{} must be a function: {}",
+        name, function
+      )));
+    }
+    *accessor = Some(function);
+  }
+  if descriptor.is_accessor_descriptor() && descriptor.is_data_descriptor() {
+    let message = "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute";
+    return Err(EngineError::TypeError(message.to_owned()));
+  }
+  Ok(descriptor)
+}
+
+/*
+6.2.6.4 FromPropertyDescriptor ( Desc )
+An object with a property for every field of the descriptor.
+*/
+fn from_property_descriptor(engine: &Engine, descriptor: &PropertyDescriptor) -> ObjectValue {
+  let object = engine.create_object();
+  let boolean = |value: Option<bool>| value.map(Value::create_boolean_value);
+  let fields = [
+    ("value", descriptor.value.clone()),
+    ("writable", boolean(descriptor.writable)),
+    ("get", descriptor.get.clone()),
+    ("set", descriptor.set.clone()),
+    ("enumerable", boolean(descriptor.enumerable)),
+    ("configurable", boolean(descriptor.configurable)),
+  ];
+  for (name, value) in fields {
+    if let Some(value) = value {
+      object.set_property(name, value);
+    }
+  }
+  object
+}
+
+// 20.1.2.3 Object.defineProperties ( O, Properties )
+fn define_properties(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  let Some(object) = value.get_object() else {
+    return Err(EngineError::TypeError(
+      "Object.defineProperties called on non-object".to_owned(),
+    ));
+  };
+  object_define_properties(engine, object, &get_argument(arguments, 1))?;
+  Ok(value)
+}
+
+/*
+20.1.2.4 Object.defineProperty ( O, P, Attributes )
+  1. If O is not an Object, throw a TypeError exception.
+  2. Let key be ? ToPropertyKey(P).
+  3. Let desc be ? ToPropertyDescriptor(Attributes).
+  4. Perform ? DefinePropertyOrThrow(O, key, desc).
+  5. Return O.
+*/
+fn define_property(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  let Some(object) = value.get_object() else {
+    return Err(EngineError::TypeError(
+      "Object.defineProperty called on non-object".to_owned(),
+    ));
+  };
+  let key = to_property_key(engine, &get_argument(arguments, 1))?;
+  let descriptor = to_property_descriptor(engine, &get_argument(arguments, 2))?;
+  engine.define_property_or_throw(object, key, descriptor)?;
+  Ok(value)
+}
+
+/*
+7.3.15 SetIntegrityLevel ( O, level )
+  1. Let status be ? O.[[PreventExtensions]]().
+  3. Let keys be ? O.[[OwnPropertyKeys]]().
+  4. If level is sealed, then
+    a. For each element k of keys, do
+      i. Perform ? DefinePropertyOrThrow(O, k, PropertyDescriptor { [[Configurable]]: false }).
+  5. Else,
+    b. For each element k of keys, do
+      i. Let currentDesc be ? O.[[GetOwnProperty]](k).
+      ii. If currentDesc is not undefined, then
+        1. If IsAccessorDescriptor(currentDesc) is true, then
+          a. Let desc be the PropertyDescriptor { [[Configurable]]: false }.
+        2. Else,
+          a. Let desc be the PropertyDescriptor { [[Configurable]]: false, [[Writable]]: false }.
+*/
+fn set_integrity_level(engine: &mut Engine, object: &ObjectValue, frozen: bool) -> Result<()> {
+  object.prevent_extensions();
+  for key in object.own_property_keys() {
+    let Some(property) = object.get_own_property(&key) else {
+      continue;
+    };
+    let writable = if frozen && !property.is_accessor() {
+      Some(false)
+    } else {
+      None
+    };
+    let descriptor = PropertyDescriptor { configurable: Some(false), writable, ..PropertyDescriptor::default() };
+    engine.define_property_or_throw(object, key, descriptor)?;
+  }
+  Ok(())
+}
+
+/*
+7.3.16 TestIntegrityLevel ( O, level )
+  3. If extensible is true, return false.
+  5. For each element k of keys, do
+    a. Let currentDesc be ? O.[[GetOwnProperty]](k).
+    b. If currentDesc is not undefined, then
+      i. If currentDesc.[[Configurable]] is true, return false.
+      ii. If level is frozen and IsDataDescriptor(currentDesc) is true, then
+        1. If currentDesc.[[Writable]] is true, return false.
+*/
+fn test_integrity_level(object: &ObjectValue, frozen: bool) -> bool {
+  if object.is_extensible() {
+    return false;
+  }
+  object
+    .own_property_keys()
+    .iter()
+    .filter_map(|key| object.get_own_property(key))
+    .all(|property| !(property.configurable || frozen && property.is_writable()))
+}
+
+// 20.1.2.6 Object.freeze ( O ), primitives are returned as they are.
+fn freeze(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  if let Some(object) = value.get_object() {
+    set_integrity_level(engine, object, true)?;
+  }
+  Ok(value)
+}
+
+// 20.1.2.22 Object.seal ( O )
+fn seal(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  if let Some(object) = value.get_object() {
+    set_integrity_level(engine, object, false)?;
+  }
+  Ok(value)
+}
+
+// 20.1.2.17 Object.isFrozen ( O ), primitives are frozen.
+fn is_frozen(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let frozen = get_argument(arguments, 0)
+    .get_object()
+    .is_none_or(|object| test_integrity_level(object, true));
+  Ok(Value::create_boolean_value(frozen))
+}
+
+// 20.1.2.18 Object.isSealed ( O )
+fn is_sealed(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let sealed = get_argument(arguments, 0)
+    .get_object()
+    .is_none_or(|object| test_integrity_level(object, false));
+  Ok(Value::create_boolean_value(sealed))
+}
+
+// 20.1.2.15 Object.is ( value1, value2 )
+fn is(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let result = same_value(&get_argument(arguments, 0), &get_argument(arguments, 1));
+  Ok(Value::create_boolean_value(result))
+}
+
+// 20.1.2.16 Object.isExtensible ( O )
+fn is_extensible(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let extensible = get_argument(arguments, 0)
+    .get_object()
+    .is_some_and(ObjectValue::is_extensible);
+  Ok(Value::create_boolean_value(extensible))
+}
+
+// 20.1.2.20 Object.preventExtensions ( O )
+fn prevent_extensions(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  if let Some(object) = value.get_object() {
+    object.prevent_extensions();
+  }
+  Ok(value)
+}
+
+/*
+20.1.2.8 Object.getOwnPropertyDescriptor ( O, P )
+  1. Let obj be ? ToObject(O).
+  2. Let key be ? ToPropertyKey(P).
+  3. Let desc be ? obj.[[GetOwnProperty]](key).
+  4. Return FromPropertyDescriptor(desc).
+*/
+fn get_own_property_descriptor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(&get_argument(arguments, 0))?;
+  let key = to_property_key(engine, &get_argument(arguments, 1))?;
+  let Some(property) = object.get_own_property(&key) else {
+    return Ok(Value::create_undefined_value());
+  };
+  Ok(Value::Object(from_property_descriptor(
+    engine,
+    &property.to_descriptor(),
+  )))
+}
+
+/*
+20.1.2.11.1 GetOwnPropertyKeys ( O, type )
+  1. Let obj be ? ToObject(O).
+  2. Let keys be ? obj.[[OwnPropertyKeys]]().
+  3. Let nameList be a new empty List.
+  4. For each element nextKey of keys, do
+    a. If nextKey is a Symbol and type is symbol, or if nextKey is a String and type is string, then
+      i. Append nextKey to nameList.
+  5. Return CreateArrayFromList(nameList).
+*/
+fn get_own_property_keys(engine: &mut Engine, value: &Value, symbols: bool) -> Result<Value> {
+  let object = engine.to_object(value)?;
+  let keys = object.own_property_keys().into_iter();
+  let names = keys.filter(|key| key.is_symbol() == symbols).map(|key| key.to_value());
+  Ok(Value::Object(engine.create_array_from_list(names.collect())))
+}
+
+// 20.1.2.10 Object.getOwnPropertyNames ( O )
+fn get_own_property_names(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  get_own_property_keys(engine, &get_argument(arguments, 0), false)
+}
+
+// 20.1.2.11 Object.getOwnPropertySymbols ( O )
+fn get_own_property_symbols(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  get_own_property_keys(engine, &get_argument(arguments, 0), true)
+}
+
+// 20.1.2.12 Object.getPrototypeOf ( O )
+fn get_prototype_of(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(&get_argument(arguments, 0))?;
+  Ok(
+    object
+      .get_prototype()
+      .map_or_else(Value::create_null_value, Value::Object),
+  )
+}
+
+/*
+20.1.2.23 Object.setPrototypeOf ( O, proto )
+  1. Set O to ? RequireObjectCoercible(O).
+  2. If proto is not an Object and proto is not null, throw a TypeError exception.
+  3. If O is not an Object, return O.
+  4. Let status be ? O.[[SetPrototypeOf]](proto).
+  5. If status is false, throw a TypeError exception.
+*/
+fn set_prototype_of(_engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  if value.is_undefined() || value.is_null() {
+    return Err(EngineError::TypeError(
+      "Object.setPrototypeOf called on null or undefined".to_owned(),
+    ));
+  }
+  let prototype = get_prototype_argument(&get_argument(arguments, 1))?;
+  let Some(object) = value.get_object() else {
+    return Ok(value);
+  };
+  if !object.set_prototype_of(prototype) {
+    let message = if object.is_extensible() {
+      "Cyclic __proto__ value"
+    } else {
+      "#<Object> is not extensible"
+    };
+    return Err(EngineError::TypeError(message.to_owned()));
+  }
+  Ok(value)
+}
+
+/*
+20.1.3.2 Object.prototype.hasOwnProperty ( V )
+  1. Let P be ? ToPropertyKey(V).
+  2. Let O be ? ToObject(this value).
+  3. Return ? HasOwnProperty(O, P).
+*/
+fn has_own_property(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let key = to_property_key(engine, &get_argument(arguments, 0))?;
+  let object = engine.to_object(this)?;
+  Ok(Value::create_boolean_value(object.get_own_property(&key).is_some()))
+}
+
+/*
+20.1.3.3 Object.prototype.isPrototypeOf ( V )
+  1. If V is not an Object, return false.
+  2. Let O be ? ToObject(this value).
+  3. Repeat,
+    a. Set V to ? V.[[GetPrototypeOf]]().
+    b. If V is null, return false.
+    c. If SameValue(O, V) is true, return true.
+*/
+fn is_prototype_of(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Some(value) = get_argument(arguments, 0).get_object().cloned() else {
+    return Ok(Value::create_boolean_value(false));
+  };
+  let object = engine.to_object(this)?;
+  let mut next = value.get_prototype();
+  while let Some(prototype) = next {
+    if prototype.is_same(&object) {
+      return Ok(Value::create_boolean_value(true));
+    }
+    next = prototype.get_prototype();
+  }
+  Ok(Value::create_boolean_value(false))
+}
+
+// 20.1.3.4 Object.prototype.propertyIsEnumerable ( V )
+fn property_is_enumerable(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let key = to_property_key(engine, &get_argument(arguments, 0))?;
+  let object = engine.to_object(this)?;
+  let enumerable = object
+    .get_own_property(&key)
+    .is_some_and(|property| property.enumerable);
+  Ok(Value::create_boolean_value(enumerable))
+}
+
+// 20.1.3.5 Object.prototype.toLocaleString ( ), the `toString` of the value is used.
+fn to_locale_string(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let object = engine.to_object(this)?;
+  let method = engine.get(&object, &PropertyKey::from("toString"), this)?;
+  engine.call_function(&method, this, &[])
+}
+
+/*
+20.1.3.6 Object.prototype.toString ( )
+  1. If the this value is undefined, return "[object Undefined]".
+  2. If the this value is null, return "[object Null]".
+  3. Let O be ! ToObject(this value).
+  ...
+  14. Let tag be ? Get(O, @@toStringTag).
+  15. If tag is not a String, set tag to builtinTag.
+  16. Return the string-concatenation of "[object ", tag, and "]".
+*/
+fn object_to_string(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let object = match this {
+    Value::Undefined(_) => return Ok(Value::create_string_value("[object Undefined]".to_owned())),
+    Value::Null(_) => return Ok(Value::create_string_value("[object Null]".to_owned())),
+    value => engine.to_object(value)?,
+  };
+  let builtin_tag = match object.get_kind() {
+    _ if object.is_function() => "Function",
+    ObjectKind::Error => "Error",
+    ObjectKind::Array => "Array",
+    ObjectKind::Boolean(_) => "Boolean",
+    ObjectKind::Number(_) => "Number",
+    ObjectKind::String(_) => "String",
+    _ => "Object",
+  };
+  let key = engine.get_well_known_symbol("Symbol.toStringTag");
+  let tag = match engine.get(&object, &key, &Value::Object(object.clone()))? {
+    Value::String(tag) => tag.get(),
+    _ => builtin_tag.to_owned(),
+  };
+  Ok(Value::create_string_value(format!("[object {}]", tag)))
+}
+
+// 20.1.3.7 Object.prototype.valueOf ( )
+fn value_of(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::Object(engine.to_object(this)?))
+}
@@ -0,0 +1,181 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+24.2 Set Objects
+Set objects are collections of ECMAScript language values. A distinct value may only occur once as an element
+  of a Set's collection.
+
+@links:
+- https://tc39.es/ecma262/#sec-set-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-set-prototype-object
+*/
+use super::{
+  create_constructor, create_function, define_method, define_symbol_property, define_to_string_tag, get_argument,
+};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::iterator::IterationKind;
+use crate::values::map::MapData;
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectKind, ObjectValue, Value};
+use crate::vm::core::Engine;
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  let set = create_constructor("Set", 0, set_constructor, &prototype);
+  define_method(&prototype, "add", 1, add);
+  define_method(&prototype, "clear", 0, clear);
+  define_method(&prototype, "delete", 1, delete);
+  define_method(&prototype, "entries", 0, entries);
+  define_method(&prototype, "forEach", 1, for_each);
+  define_method(&prototype, "has", 1, has);
+  define_method(&prototype, "values", 0, values);
+  let getter = create_function("get size", 0, size);
+  let descriptor = PropertyDescriptor::new_accessor(Some(Value::Object(getter)), None, false);
+  prototype.define_own_property(PropertyKey::from("size"), descriptor);
+
+  // 24.2.4.8 Set.prototype.keys ( ) and 24.2.4.11 Set.prototype [ @@iterator ] ( ), both are %Set.prototype.values%
+  let values = prototype.get_property("values").expect("values method to be defined");
+  prototype.define_builtin_property("keys", values.clone());
+  define_symbol_property(ctx, &prototype, "Symbol.iterator", values);
+  define_to_string_tag(ctx, &prototype, "Set");
+
+  ctx.define_builtin("Set", Value::Object(set));
+  ctx.set_intrinsic("%Set.prototype%", prototype);
+}
+
+// the [[SetData]] of the this value, the methods only work on Sets.
+fn this_set_data<'v>(this: &'v Value, method: &str) -> Result<&'v MapData> {
+  match this.get_object().map(ObjectValue::get_kind) {
+    Some(ObjectKind::Set(data)) => Ok(data),
+    _ => Err(EngineError::TypeError(format!(
+      "Method Set.prototype.{} called on incompatible receiver {}",
+      method, this
+    ))),
+  }
+}
+
+/*
+24.2.2.1 Set ( [ iterable ] )
+  1. If NewTarget is undefined, throw a TypeError exception.
+  2. Let set be ? OrdinaryCreateFromConstructor(NewTarget, "%Set.prototype%", « [[SetData]] »).
+  4. If iterable is either undefined or null, return set.
+  5. Let adder be ? Get(set, "add").
+  6. If IsCallable(adder) is false, throw a TypeError exception.
+  7. Let iteratorRecord be ? GetIterator(iterable, sync).
+  8. Repeat,
+    a. Let next be ? IteratorStepValue(iteratorRecord).
+    b. If next is done, return set.
+    c. Let status be Completion(Call(adder, set, « next »)).
+    d. IfAbruptCloseIterator(status, iteratorRecord).
+*/
+fn set_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Some(new_target) = engine.get_new_target() else {
+    return Err(EngineError::TypeError("Constructor Set requires 'new'".to_owned()));
+  };
+  let prototype = engine.get_prototype_from_constructor(&new_target, "%Set.prototype%")?;
+  let set = Value::Object(ObjectValue::new_set(prototype));
+  let iterable = get_argument(arguments, 0);
+  if iterable.is_undefined() || iterable.is_null() {
+    return Ok(set);
+  }
+  let adder = engine.get_value(&set, &PropertyKey::from("add"))?;
+  if !adder.is_function() {
+    return Err(EngineError::TypeError(format!(
+      "'{}' returned for property 'add' of object '#<Set>' is not a function",
+      adder
+    )));
+  }
+  let record = engine.get_iterator(&iterable)?;
+  while let Some(next) = engine.iterator_step_value(&record)? {
+    if let Err(error) = engine.call_function(&adder, &set, &[next]) {
+      return Err(engine.iterator_close_with_error(&record, error));
+    }
+  }
+  Ok(set)
+}
+
+// 24.2.4.1 Set.prototype.add ( value )
+fn add(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let value = get_argument(arguments, 0);
+  let data = this_set_data(this, "add")?;
+  if !data.has(&value) {
+    data.set(value.clone(), value);
+  }
+  Ok(this.clone())
+}
+
+// 24.2.4.2 Set.prototype.clear ( )
+fn clear(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  this_set_data(this, "clear")?.clear();
+  Ok(Value::create_undefined_value())
+}
+
+// 24.2.4.4 Set.prototype.delete ( value )
+fn delete(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let deleted = this_set_data(this, "delete")?.delete(&get_argument(arguments, 0));
+  Ok(Value::create_boolean_value(deleted))
+}
+
+/*
+24.2.4.5 Set.prototype.entries ( )
+24.2.4.10 Set.prototype.values ( )
+  1. Let S be the this value.
+  2. Return ? CreateSetIterator(S, kind).
+*/
+fn create_set_iterator(engine: &mut Engine, this: &Value, kind: IterationKind, method: &str) -> Result<Value> {
+  this_set_data(this, method)?;
+  let set = this.get_object().cloned().expect("set to be an object");
+  let prototype = engine.get_intrinsic("%SetIteratorPrototype%");
+  Ok(Value::Object(ObjectValue::new_collection_iterator(
+    set, kind, prototype,
+  )))
+}
+
+fn entries(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_set_iterator(engine, this, IterationKind::KeyValue, "entries")
+}
+
+fn values(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  create_set_iterator(engine, this, IterationKind::Value, "values")
+}
+
+/*
+24.2.4.6 Set.prototype.forEach ( callbackfn [ , thisArg ] )
+  7. Repeat, while index < numEntries,
+    a. Let e be entries[index].
+    b. Set index to index + 1.
+    c. If e is not empty, then
+      i. Perform ? Call(callbackfn, thisArg, « e, e, S »).
+      iv. Set numEntries to the number of elements in entries.
+*/
+fn for_each(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let data = this_set_data(this, "forEach")?;
+  let callback = get_argument(arguments, 0);
+  if !callback.is_function() {
+    return Err(EngineError::TypeError(format!("{} is not a function", callback)));
+  }
+  let this_argument = get_argument(arguments, 1);
+  data.for_each_entry(|value, _| {
+    engine.call_function(&callback, &this_argument, &[value.clone(), value, this.clone()])?;
+    Ok(())
+  })?;
+  Ok(Value::create_undefined_value())
+}
+
+// 24.2.4.7 Set.prototype.has ( value )
+fn has(_engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let has = this_set_data(this, "has")?.has(&get_argument(arguments, 0));
+  Ok(Value::create_boolean_value(has))
+}
+
+// 24.2.4.9 get Set.prototype.size
+fn size(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let size = this_set_data(this, "size")?.size();
+  Ok(Value::create_number_value(size as i64))
+}
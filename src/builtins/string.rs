@@ -0,0 +1,904 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+22.1 String Objects
+The String constructor is %String%. It performs a type conversion when called as a function and creates a String
+  object when called as part of a `new` expression.
+The String prototype object is %String.prototype%. It is itself a String exotic object whose [[StringData]] is the
+  empty String, its methods are generic and convert the this value with ToString.
+
+@links:
+- https://tc39.es/ecma262/#sec-string-objects
+- https://tc39.es/ecma262/#sec-properties-of-the-string-prototype-object
+*/
+use super::{create_constructor, define_method, define_symbol_method, get_argument, get_relative_index};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::function::NativeFn;
+use crate::values::property::PropertyKey;
+use crate::values::string::encode_code_point;
+use crate::values::{ObjectValue, StringValue, Value};
+use crate::vm::coercion::{
+  is_white_space_or_line_terminator, to_integer_or_infinity, to_length, to_number, to_string, to_uint32,
+};
+use crate::vm::core::Engine;
+use std::cmp::Ordering;
+use unicode_normalization::UnicodeNormalization;
+
+// the longest string that can be created, like the limit of the engines of the browsers.
+const MAX_STRING_LENGTH: usize = (1 << 29) - 24;
+
+const PROTOTYPE_METHODS: [(&str, usize, NativeFn); 30] = [
+  ("at", 1, at),
+  ("charAt", 1, char_at),
+  ("charCodeAt", 1, char_code_at),
+  ("codePointAt", 1, code_point_at),
+  ("concat", 1, concat),
+  ("endsWith", 1, ends_with),
+  ("includes", 1, includes),
+  ("indexOf", 1, index_of),
+  ("isWellFormed", 0, is_well_formed),
+  ("lastIndexOf", 1, last_index_of),
+  ("localeCompare", 1, locale_compare),
+  ("normalize", 0, normalize),
+  ("padEnd", 1, pad_end),
+  ("padStart", 1, pad_start),
+  ("repeat", 1, repeat),
+  ("replace", 2, replace),
+  ("replaceAll", 2, replace_all),
+  ("slice", 2, slice),
+  ("split", 2, split),
+  ("startsWith", 1, starts_with),
+  ("substring", 2, substring),
+  ("toLocaleLowerCase", 0, to_lower_case),
+  ("toLocaleUpperCase", 0, to_upper_case),
+  ("toLowerCase", 0, to_lower_case),
+  ("toString", 0, value_of),
+  ("toUpperCase", 0, to_upper_case),
+  ("trim", 0, trim),
+  ("trimEnd", 0, trim_end),
+  ("trimStart", 0, trim_start),
+  ("valueOf", 0, value_of),
+];
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_string(StringValue::from(""), object_prototype);
+  let string = create_constructor("String", 1, string_constructor, &prototype);
+  for (name, arity, function) in PROTOTYPE_METHODS {
+    define_method(&prototype, name, arity, function);
+  }
+  define_method(&string, "fromCharCode", 1, from_char_code);
+  define_method(&string, "fromCodePoint", 1, from_code_point);
+  define_method(&string, "raw", 1, raw);
+  define_symbol_method(ctx, &prototype, "Symbol.iterator", 0, string_iterator);
+  ctx.define_builtin("String", Value::Object(string));
+  ctx.set_intrinsic("%String.prototype%", prototype);
+}
+
+fn string_value(units: Vec<u16>) -> Value {
+  Value::String(StringValue::from_utf16(units))
+}
+
+/*
+22.1.3 the steps most methods start with
+  1. Let O be ? RequireObjectCoercible(this value).
+  2. Let S be ? ToString(O).
+*/
+fn this_to_string(engine: &mut Engine, this: &Value, method: &str) -> Result<StringValue> {
+  if this.is_undefined() || this.is_null() {
+    return Err(EngineError::TypeError(format!(
+      "String.prototype.{} called on null or undefined",
+      method
+    )));
+  }
+  to_string(engine, this)
+}
+
+// a position argument made an integer and clamped to the bounds of the string.
+fn get_position(engine: &mut Engine, value: &Value, length: usize) -> Result<usize> {
+  let position = to_integer_or_infinity(engine, value)?;
+  Ok(position.clamp(0.0, length as f64) as usize)
+}
+
+// the index a position argument names when it is inside the string.
+fn get_index(engine: &mut Engine, value: &Value, length: usize) -> Result<Option<usize>> {
+  let position = to_integer_or_infinity(engine, value)?;
+  if position < 0.0 || position >= length as f64 {
+    return Ok(None);
+  }
+  Ok(Some(position as usize))
+}
+
+/*
+22.1.1.1 String ( value )
+  1. If value is not present, then
+    a. Let s be the empty String.
+  2. Else,
+    a. If NewTarget is undefined and value is a Symbol, return SymbolDescriptiveString(value).
+    b. Let s be ? ToString(value).
+  3. If NewTarget is undefined, return s.
+  4. Return StringCreate(s, ? GetPrototypeFromConstructor(NewTarget, "%String.prototype%")).
+*/
+fn string_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let new_target = engine.get_new_target();
+  let value = match arguments.first() {
+    None => StringValue::from(""),
+    Some(Value::Symbol(symbol)) if new_target.is_none() => StringValue::new(symbol.get_descriptive_string()),
+    Some(value) => to_string(engine, value)?,
+  };
+  if new_target.is_none() {
+    return Ok(Value::String(value));
+  }
+  let prototype = match new_target {
+    Some(new_target) => engine.get_prototype_from_constructor(&new_target, "%String.prototype%")?,
+    None => None,
+  };
+  Ok(Value::Object(ObjectValue::new_string(value, prototype)))
+}
+
+/*
+22.1.2.1 String.fromCharCode ( ...codeUnits )
+  1. Let result be the empty String.
+  2. For each element next of codeUnits, do
+    a. Let nextCU be the code unit whose numeric value is ℝ(? ToUint16(next)).
+    b. Set result to the string-concatenation of result and nextCU.
+*/
+fn from_char_code(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let mut units = Vec::with_capacity(arguments.len());
+  for argument in arguments {
+    units.push(to_uint32(to_number(engine, argument)?) as u16);
+  }
+  Ok(string_value(units))
+}
+
+/*
+22.1.2.2 String.fromCodePoint ( ...codePoints )
+  2. For each element next of codePoints, do
+    a. Let nextCP be ? ToNumber(next).
+    b. If IsIntegralNumber(nextCP) is false, throw a RangeError exception.
+    c. If ℝ(nextCP) < 0 or ℝ(nextCP) > 0x10FFFF, throw a RangeError exception.
+    d. Set result to the string-concatenation of result and UTF16EncodeCodePoint(ℝ(nextCP)).
+*/
+fn from_code_point(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let mut units = Vec::with_capacity(arguments.len());
+  for argument in arguments {
+    let code_point = to_number(engine, argument)?;
+    if code_point.fract() != 0.0 || !(0.0..=1114111.0).contains(&code_point) {
+      return Err(EngineError::RangeError(format!("Invalid code point {}", argument)));
+    }
+    encode_code_point(code_point as u32, &mut units);
+  }
+  Ok(string_value(units))
+}
+
+/*
+22.1.2.4 String.raw ( template, ...substitutions )
+  3. Let cooked be ? ToObject(template).
+  4. Let literals be ? ToObject(? Get(cooked, "raw")).
+  5. Let literalCount be ? LengthOfArrayLike(literals).
+  6. If literalCount ≤ 0, return the empty String.
+  9. Repeat,
+    a. Let nextLiteralVal be ? Get(literals, ! ToString(𝔽(nextIndex))).
+    b. Let nextLiteral be ? ToString(nextLiteralVal).
+    c. Set R to the string-concatenation of R and nextLiteral.
+    d. If nextIndex + 1 = literalCount, return R.
+    e. If nextIndex < substitutionCount, then
+      i. Let nextSubVal be substitutions[nextIndex].
+      ii. Let nextSub be ? ToString(nextSubVal).
+      iii. Set R to the string-concatenation of R and nextSub.
+    f. Set nextIndex to nextIndex + 1.
+*/
+fn raw(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let cooked = engine.to_object(&get_argument(arguments, 0))?;
+  let raw = engine.get(&cooked, &PropertyKey::from("raw"), &Value::Object(cooked.clone()))?;
+  let literals = engine.to_object(&raw)?;
+  let count = engine.length_of_array_like(&literals)?;
+  let mut result = Vec::new();
+  let substitutions = arguments.get(1..).unwrap_or_default();
+  for index in 0..count {
+    let key = PropertyKey::from(index.to_string());
+    let literal = engine.get(&literals, &key, &Value::Object(literals.clone()))?;
+    result.extend_from_slice(to_string(engine, &literal)?.get_units());
+    if index + 1 == count {
+      break;
+    }
+    if let Some(substitution) = substitutions.get(index as usize) {
+      result.extend_from_slice(to_string(engine, substitution)?.get_units());
+    }
+  }
+  Ok(string_value(result))
+}
+
+/*
+22.1.3.1 String.prototype.at ( index )
+  4. Let relativeIndex be ? ToIntegerOrInfinity(index).
+  5. If relativeIndex ≥ 0, then
+    a. Let k be relativeIndex.
+  6. Else,
+    a. Let k be len + relativeIndex.
+  7. If k < 0 or k ≥ len, return undefined.
+  8. Return the substring of S from k to k + 1.
+*/
+fn at(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "at")?;
+  let relative = to_integer_or_infinity(engine, &get_argument(arguments, 0))?;
+  let index = if relative >= 0.0 {
+    relative
+  } else {
+    string.len() as f64 + relative
+  };
+  if index < 0.0 || index >= string.len() as f64 {
+    return Ok(Value::create_undefined_value());
+  }
+  let index = index as usize;
+  Ok(Value::String(string.substring(index, index + 1)))
+}
+
+/*
+22.1.3.2 String.prototype.charAt ( pos )
+  3. Let position be ? ToIntegerOrInfinity(pos).
+  4. Let size be the length of S.
+  5. If position < 0 or position ≥ size, return the empty String.
+  6. Return the substring of S from position to position + 1.
+*/
+fn char_at(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "charAt")?;
+  match get_index(engine, &get_argument(arguments, 0), string.len())? {
+    Some(index) => Ok(Value::String(string.substring(index, index + 1))),
+    None => Ok(Value::String(StringValue::from(""))),
+  }
+}
+
+/*
+22.1.3.3 String.prototype.charCodeAt ( pos )
+  5. If position < 0 or position ≥ size, return NaN.
+  6. Return the Number value for the numeric value of the code unit at index position within the String S.
+*/
+fn char_code_at(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "charCodeAt")?;
+  match get_index(engine, &get_argument(arguments, 0), string.len())? {
+    Some(index) => Ok(Value::create_number_value(i64::from(string.get_units()[index]))),
+    None => Ok(Value::create_float_value(f64::NAN)),
+  }
+}
+
+/*
+22.1.3.4 String.prototype.codePointAt ( pos )
+  5. If position < 0 or position ≥ size, return undefined.
+  6. Let cp be CodePointAt(S, position).
+  7. Return 𝔽(cp.[[CodePoint]]).
+*/
+fn code_point_at(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "codePointAt")?;
+  match get_index(engine, &get_argument(arguments, 0), string.len())? {
+    Some(index) => Ok(Value::create_number_value(i64::from(
+      string.code_point_at(index).code_point,
+    ))),
+    None => Ok(Value::create_undefined_value()),
+  }
+}
+
+/*
+22.1.3.5 String.prototype.concat ( ...args )
+  3. Let R be S.
+  4. For each element next of args, do
+    a. Let nextString be ? ToString(next).
+    b. Set R to the string-concatenation of R and nextString.
+*/
+fn concat(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let mut result = this_to_string(engine, this, "concat")?;
+  for argument in arguments {
+    result = result.concat(&to_string(engine, argument)?);
+  }
+  Ok(Value::String(result))
+}
+
+/*
+22.1.3.7 String.prototype.endsWith ( searchString [ , endPosition ] )
+  7. If endPosition is undefined, let pos be len; else let pos be ? ToIntegerOrInfinity(endPosition).
+  8. Let end be the result of clamping pos between 0 and len.
+  9. Let searchLength be the length of searchStr.
+  10. If searchLength = 0, return true.
+  11. Let start be end - searchLength.
+  12. If start < 0, return false.
+  13. Let substring be the substring of S from start to end.
+  14. If substring is searchStr, return true.
+*/
+fn ends_with(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "endsWith")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let end = match get_argument(arguments, 1) {
+    position if position.is_undefined() => string.len(),
+    position => get_position(engine, &position, string.len())?,
+  };
+  let Some(start) = end.checked_sub(search.len()) else {
+    return Ok(Value::create_boolean_value(false));
+  };
+  Ok(Value::create_boolean_value(
+    string.get_units()[start..end] == *search.get_units(),
+  ))
+}
+
+/*
+22.1.3.8 String.prototype.includes ( searchString [ , position ] )
+  6. Let pos be ? ToIntegerOrInfinity(position).
+  8. Let start be the result of clamping pos between 0 and len.
+  9. Let index be StringIndexOf(S, searchStr, start).
+  10. If index is not-found, return false.
+*/
+fn includes(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "includes")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let start = get_position(engine, &get_argument(arguments, 1), string.len())?;
+  Ok(Value::create_boolean_value(string.index_of(&search, start).is_some()))
+}
+
+fn index_value(index: Option<usize>) -> Value {
+  Value::create_number_value(index.map_or(-1, |index| index as i64))
+}
+
+/*
+22.1.3.9 String.prototype.indexOf ( searchString [ , position ] )
+  4. Let pos be ? ToIntegerOrInfinity(position).
+  6. Let start be the result of clamping pos between 0 and len.
+  7. Let result be StringIndexOf(S, searchStr, start).
+  8. If result is not-found, return -1𝔽.
+*/
+fn index_of(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "indexOf")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let start = get_position(engine, &get_argument(arguments, 1), string.len())?;
+  Ok(index_value(string.index_of(&search, start)))
+}
+
+/*
+22.1.3.11 String.prototype.lastIndexOf ( searchString [ , position ] )
+  4. Let numPos be ? ToNumber(position).
+  5. If numPos is NaN, let pos be +∞; otherwise, let pos be ! ToIntegerOrInfinity(numPos).
+  7. Let searchLen be the length of searchStr.
+  8. Let start be the result of clamping pos between 0 and len - searchLen.
+  9. If searchStr is the empty String, return 𝔽(start).
+  10. For each integer i such that 0 ≤ i ≤ start, in descending order, do
+    a. Let candidate be the substring of S from i to i + searchLen.
+    b. If candidate is searchStr, return 𝔽(i).
+  11. Return -1𝔽.
+*/
+fn last_index_of(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "lastIndexOf")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let position = to_number(engine, &get_argument(arguments, 1))?;
+  let position = if position.is_nan() {
+    f64::INFINITY
+  } else {
+    position.trunc()
+  };
+  let start = position.clamp(0.0, string.len() as f64) as usize;
+  Ok(index_value(string.last_index_of(&search, start)))
+}
+
+/*
+22.1.3.12 String.prototype.localeCompare ( that [ , reserved1 [ , reserved2 ] ] )
+Without the internationalization API the order is implementation-defined, the code points are compared after
+  the strings are put in the canonical composition so canonically equivalent strings are equal.
+*/
+fn locale_compare(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "localeCompare")?;
+  let that = to_string(engine, &get_argument(arguments, 0))?;
+  let order = string.get().nfc().cmp(that.get().nfc());
+  Ok(Value::create_number_value(match order {
+    Ordering::Less => -1,
+    Ordering::Equal => 0,
+    Ordering::Greater => 1,
+  }))
+}
+
+/*
+22.1.3.15 String.prototype.normalize ( [ form ] )
+  3. If form is undefined, let f be "NFC".
+  4. Else, let f be ? ToString(form).
+  5. If f is not one of "NFC", "NFD", "NFKC", or "NFKD", throw a RangeError exception.
+  6. Let ns be the String value that is the result of normalizing S into the normalization form named by f as
+    specified in the latest Unicode Standard, Normalization Forms.
+*/
+fn normalize(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "normalize")?;
+  let form = match get_argument(arguments, 0) {
+    form if form.is_undefined() => "NFC".to_owned(),
+    form => to_string(engine, &form)?.get(),
+  };
+  let normalize: fn(&str) -> String = match form.as_str() {
+    "NFC" => |text| text.nfc().collect(),
+    "NFD" => |text| text.nfd().collect(),
+    "NFKC" => |text| text.nfkc().collect(),
+    "NFKD" => |text| text.nfkd().collect(),
+    _ => {
+      return Err(EngineError::RangeError(format!(
+        "The normalization form should be one of NFC, NFD, NFKC, NFKD: {}",
+        form
+      )))
+    }
+  };
+  Ok(Value::String(map_text(&string, normalize)))
+}
+
+// the well-formed runs of the string transformed as rust text, a lone surrogate is kept as it is.
+fn map_text(string: &StringValue, transform: fn(&str) -> String) -> StringValue {
+  let mut units = Vec::with_capacity(string.len());
+  let mut text = String::new();
+  for char in char::decode_utf16(string.get_units().iter().copied()) {
+    match char {
+      Ok(char) => text.push(char),
+      Err(error) => {
+        units.extend(transform(&text).encode_utf16());
+        text.clear();
+        units.push(error.unpaired_surrogate());
+      }
+    }
+  }
+  units.extend(transform(&text).encode_utf16());
+  StringValue::from_utf16(units)
+}
+
+/*
+22.1.3.17.1 StringPaddingBuiltinsImpl ( O, maxLength, fillString, placement )
+  2. Let intMaxLength be ℝ(? ToLength(maxLength)).
+  3. Let stringLength be the length of S.
+  4. If intMaxLength ≤ stringLength, return S.
+  5. If fillString is undefined, set fillString to the String value consisting solely of the code unit 0x0020 (SPACE).
+  6. Else, set fillString to ? ToString(fillString).
+22.1.3.17.2 StringPad ( S, maxLength, fillString, placement )
+  2. If maxLength ≤ stringLength, return S.
+  3. If fillString is the empty String, return S.
+  4. Let fillLen be maxLength - stringLength.
+  5. Let truncatedStringFiller be the String value consisting of repeated concatenations of fillString truncated
+    to length fillLen.
+  6. If placement is start, return the string-concatenation of truncatedStringFiller and S.
+  7. Else, return the string-concatenation of S and truncatedStringFiller.
+*/
+fn string_pad(engine: &mut Engine, this: &Value, arguments: &[Value], at_start: bool) -> Result<Value> {
+  let method = if at_start { "padStart" } else { "padEnd" };
+  let string = this_to_string(engine, this, method)?;
+  let max_length = to_length(engine, &get_argument(arguments, 0))? as usize;
+  if max_length <= string.len() {
+    return Ok(Value::String(string));
+  }
+  let filler = match get_argument(arguments, 1) {
+    filler if filler.is_undefined() => StringValue::from(" "),
+    filler => to_string(engine, &filler)?,
+  };
+  if filler.is_empty() {
+    return Ok(Value::String(string));
+  }
+  if max_length > MAX_STRING_LENGTH {
+    return Err(EngineError::RangeError("Invalid string length".to_owned()));
+  }
+  let filler = filler
+    .get_units()
+    .iter()
+    .copied()
+    .cycle()
+    .take(max_length - string.len());
+  let units = match at_start {
+    true => filler.chain(string.get_units().iter().copied()).collect(),
+    false => string.get_units().iter().copied().chain(filler).collect(),
+  };
+  Ok(string_value(units))
+}
+
+// 22.1.3.16 String.prototype.padEnd ( maxLength [ , fillString ] )
+fn pad_end(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  string_pad(engine, this, arguments, false)
+}
+
+// 22.1.3.17 String.prototype.padStart ( maxLength [ , fillString ] )
+fn pad_start(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  string_pad(engine, this, arguments, true)
+}
+
+/*
+22.1.3.18 String.prototype.repeat ( count )
+  3. Let n be ? ToIntegerOrInfinity(count).
+  4. If n < 0 or n = +∞, throw a RangeError exception.
+  5. If n = 0, return the empty String.
+  6. Return the String value that is made from n copies of S appended together.
+*/
+fn repeat(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "repeat")?;
+  let count = to_integer_or_infinity(engine, &get_argument(arguments, 0))?;
+  if count < 0.0 || count == f64::INFINITY {
+    return Err(EngineError::RangeError(format!(
+      "Invalid count value: {}",
+      get_argument(arguments, 0)
+    )));
+  }
+  if string.is_empty() || count == 0.0 {
+    return Ok(Value::String(StringValue::from("")));
+  }
+  if string.len() as f64 * count > MAX_STRING_LENGTH as f64 {
+    return Err(EngineError::RangeError("Invalid string length".to_owned()));
+  }
+  Ok(string_value(string.get_units().repeat(count as usize)))
+}
+
+/*
+22.1.3.19.1 GetSubstitution ( matched, str, position, captures, namedCaptures, replacementTemplate )
+  | $$ | "$" |
+  | $& | matched |
+  | $` | the substring of str from 0 to position |
+  | $' | the substring of str from matchLength + position |
+Only string patterns exist, so there are no captures and `$n` and `$<` are kept as they are.
+*/
+fn get_substitution(matched: &StringValue, string: &StringValue, position: usize, template: &StringValue) -> Vec<u16> {
+  let template = template.get_units();
+  let mut result = Vec::with_capacity(template.len());
+  let mut index = 0;
+  while index < template.len() {
+    let unit = template[index];
+    let next = template.get(index + 1).copied();
+    index += 1;
+    if unit != u16::from(b'$') {
+      result.push(unit);
+      continue;
+    }
+    match next.and_then(|next| u8::try_from(next).ok()) {
+      Some(b'$') => result.push(unit),
+      Some(b'&') => result.extend_from_slice(matched.get_units()),
+      Some(b'`') => result.extend_from_slice(&string.get_units()[..position]),
+      Some(b'\'') => {
+        let end = (position + matched.len()).min(string.len());
+        result.extend_from_slice(&string.get_units()[end..]);
+      }
+      _ => {
+        result.push(unit);
+        continue;
+      }
+    }
+    index += 1;
+  }
+  result
+}
+
+// the replacement of one match, what the function returns or the template with its substitutions.
+fn get_replacement(
+  engine: &mut Engine,
+  replace_value: &Value,
+  search: &StringValue,
+  string: &StringValue,
+  position: usize,
+) -> Result<Vec<u16>> {
+  if replace_value.is_function() {
+    let arguments = [
+      Value::String(search.clone()),
+      Value::create_number_value(position as i64),
+      Value::String(string.clone()),
+    ];
+    let replacement = engine.call_function(replace_value, &Value::create_undefined_value(), &arguments)?;
+    return Ok(to_string(engine, &replacement)?.get_units().to_vec());
+  }
+  let template = to_string(engine, replace_value)?;
+  Ok(get_substitution(search, string, position, &template))
+}
+
+/*
+22.1.3.19 String.prototype.replace ( searchValue, replaceValue )
+  3. Let string be ? ToString(O).
+  4. Let searchString be ? ToString(searchValue).
+  5. Let functionalReplace be IsCallable(replaceValue).
+  6. If functionalReplace is false, then
+    a. Set replaceValue to ? ToString(replaceValue).
+  7. Let searchLength be the length of searchString.
+  8. Let position be StringIndexOf(string, searchString, 0).
+  9. If position is not-found, return string.
+  10. Let preceding be the substring of string from 0 to position.
+  11. Let following be the substring of string from position + searchLength.
+  12. If functionalReplace is true, then
+    a. Let replacement be ? ToString(? Call(replaceValue, undefined, « searchString, 𝔽(position), string »)).
+  13. Else,
+    b. Let replacement be ! GetSubstitution(searchString, string, position, captures, undefined, replaceValue).
+  14. Return the string-concatenation of preceding, replacement, and following.
+*/
+fn replace(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "replace")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let replace_value = replace_argument(engine, &get_argument(arguments, 1))?;
+  let Some(position) = string.index_of(&search, 0) else {
+    return Ok(Value::String(string));
+  };
+  let replacement = get_replacement(engine, &replace_value, &search, &string, position)?;
+  let units = string.get_units();
+  let result = [&units[..position], &replacement, &units[position + search.len()..]].concat();
+  Ok(string_value(result))
+}
+
+// a replace value that is not a function is converted to a string once, before the string is searched.
+fn replace_argument(engine: &mut Engine, value: &Value) -> Result<Value> {
+  if value.is_function() {
+    return Ok(value.clone());
+  }
+  Ok(Value::String(to_string(engine, value)?))
+}
+
+/*
+22.1.3.20 String.prototype.replaceAll ( searchValue, replaceValue )
+  9. Let searchLength be the length of searchString.
+  10. Let advanceBy be max(1, searchLength).
+  11. Let matchPositions be a new empty List.
+  12. Let position be StringIndexOf(string, searchString, 0).
+  13. Repeat, while position is not not-found,
+    a. Append position to matchPositions.
+    b. Set position to StringIndexOf(string, searchString, position + advanceBy).
+  14. Let endOfLastMatch be 0.
+  16. For each element p of matchPositions, do
+    a. Let preserved be the substring of string from endOfLastMatch to p.
+    d. Set result to the string-concatenation of result, preserved, and replacement.
+    e. Set endOfLastMatch to p + searchLength.
+  17. If endOfLastMatch < the length of string, then
+    a. Set result to the string-concatenation of result and the substring of string from endOfLastMatch.
+*/
+fn replace_all(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "replaceAll")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let replace_value = replace_argument(engine, &get_argument(arguments, 1))?;
+  let advance_by = search.len().max(1);
+  let mut positions = Vec::new();
+  let mut position = string.index_of(&search, 0);
+  while let Some(index) = position {
+    positions.push(index);
+    position = string.index_of(&search, index + advance_by);
+  }
+  let units = string.get_units();
+  let mut result = Vec::with_capacity(units.len());
+  let mut end_of_last_match = 0;
+  for position in positions {
+    result.extend_from_slice(&units[end_of_last_match..position]);
+    result.extend(get_replacement(engine, &replace_value, &search, &string, position)?);
+    end_of_last_match = position + search.len();
+  }
+  if end_of_last_match < units.len() {
+    result.extend_from_slice(&units[end_of_last_match..]);
+  }
+  Ok(string_value(result))
+}
+
+/*
+22.1.3.22 String.prototype.slice ( start, end )
+  4. Let intStart be ? ToIntegerOrInfinity(start).
+  5. If intStart = -∞, let from be 0.
+  6. Else if intStart < 0, let from be max(len + intStart, 0).
+  7. Else, let from be min(intStart, len).
+  8. If end is undefined, let intEnd be len; else let intEnd be ? ToIntegerOrInfinity(end).
+  12. If from ≥ to, return the empty String.
+  13. Return the substring of S from from to to.
+*/
+fn slice(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "slice")?;
+  let length = string.len() as u64;
+  let from = get_relative_index(engine, &get_argument(arguments, 0), length, 0)? as usize;
+  let to = get_relative_index(engine, &get_argument(arguments, 1), length, length)? as usize;
+  if from >= to {
+    return Ok(Value::String(StringValue::from("")));
+  }
+  Ok(Value::String(string.substring(from, to)))
+}
+
+/*
+22.1.3.23 String.prototype.split ( separator, limit )
+  3. Let S be ? ToString(O).
+  4. If limit is undefined, let lim be 2^32 - 1; else let lim be ℝ(? ToUint32(limit)).
+  5. Let R be ? ToString(separator).
+  6. If lim = 0, then
+    a. Return CreateArrayFromList(« »).
+  7. If separator is undefined, then
+    a. Return CreateArrayFromList(« S »).
+  8. Let separatorLength be the length of R.
+  9. If separatorLength = 0, then
+    a. Let head be the substring of S from 0 to lim.
+    b. Let codeUnits be a List consisting of the sequence of code units that are the elements of head.
+    c. Return CreateArrayFromList(codeUnits).
+  10. If S is the empty String, return CreateArrayFromList(« S »).
+  11. Let substrings be a new empty List.
+  12. Let i be 0.
+  13. Let j be StringIndexOf(S, R, 0).
+  14. Repeat, while j is not not-found,
+    a. Let T be the substring of S from i to j.
+    b. Append T to substrings.
+    c. If the number of elements in substrings is lim, return CreateArrayFromList(substrings).
+    d. Set i to j + separatorLength.
+    e. Set j to StringIndexOf(S, R, i).
+  15. Let T be the substring of S from i.
+  16. Append T to substrings.
+*/
+fn split(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "split")?;
+  let limit = match get_argument(arguments, 1) {
+    limit if limit.is_undefined() => u32::MAX as usize,
+    limit => to_uint32(to_number(engine, &limit)?) as usize,
+  };
+  let separator = get_argument(arguments, 0);
+  let pattern = to_string(engine, &separator)?;
+  if limit == 0 {
+    return Ok(Value::Object(engine.create_array()));
+  }
+  if separator.is_undefined() {
+    return Ok(Value::Object(
+      engine.create_array_from_list(vec![Value::String(string)]),
+    ));
+  }
+  if pattern.is_empty() {
+    let units = (0..string.len().min(limit)).map(|index| Value::String(string.substring(index, index + 1)));
+    return Ok(Value::Object(engine.create_array_from_list(units.collect())));
+  }
+  if string.is_empty() {
+    return Ok(Value::Object(
+      engine.create_array_from_list(vec![Value::String(string)]),
+    ));
+  }
+  let mut substrings = Vec::new();
+  let mut start = 0;
+  let mut found = string.index_of(&pattern, 0);
+  while let Some(index) = found {
+    substrings.push(Value::String(string.substring(start, index)));
+    if substrings.len() == limit {
+      return Ok(Value::Object(engine.create_array_from_list(substrings)));
+    }
+    start = index + pattern.len();
+    found = string.index_of(&pattern, start);
+  }
+  substrings.push(Value::String(string.substring(start, string.len())));
+  Ok(Value::Object(engine.create_array_from_list(substrings)))
+}
+
+/*
+22.1.3.24 String.prototype.startsWith ( searchString [ , position ] )
+  7. If position is undefined, let pos be 0; else let pos be ? ToIntegerOrInfinity(position).
+  8. Let start be the result of clamping pos between 0 and len.
+  9. Let searchLength be the length of searchStr.
+  10. If searchLength = 0, return true.
+  11. Let end be start + searchLength.
+  12. If end > len, return false.
+  13. Let substring be the substring of S from start to end.
+  14. If substring is searchStr, return true.
+*/
+fn starts_with(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "startsWith")?;
+  let search = to_string(engine, &get_argument(arguments, 0))?;
+  let start = get_position(engine, &get_argument(arguments, 1), string.len())?;
+  let end = start + search.len();
+  if end > string.len() {
+    return Ok(Value::create_boolean_value(false));
+  }
+  Ok(Value::create_boolean_value(
+    string.get_units()[start..end] == *search.get_units(),
+  ))
+}
+
+/*
+22.1.3.25 String.prototype.substring ( start, end )
+  4. Let intStart be ? ToIntegerOrInfinity(start).
+  5. If end is undefined, let intEnd be len; else let intEnd be ? ToIntegerOrInfinity(end).
+  6. Let finalStart be the result of clamping intStart between 0 and len.
+  7. Let finalEnd be the result of clamping intEnd between 0 and len.
+  8. Let from be min(finalStart, finalEnd).
+  9. Let to be max(finalStart, finalEnd).
+  10. Return the substring of S from from to to.
+*/
+fn substring(engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "substring")?;
+  let start = get_position(engine, &get_argument(arguments, 0), string.len())?;
+  let end = match get_argument(arguments, 1) {
+    end if end.is_undefined() => string.len(),
+    end => get_position(engine, &end, string.len())?,
+  };
+  Ok(Value::String(string.substring(start.min(end), start.max(end))))
+}
+
+/*
+22.1.3.28 String.prototype.toLowerCase ( )
+  4. Let sText be StringToCodePoints(S).
+  5. Let lowerText be toLowercase(sText) according to the Unicode Default Case Conversion algorithm.
+*/
+fn to_lower_case(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "toLowerCase")?;
+  Ok(Value::String(map_text(&string, str::to_lowercase)))
+}
+
+// 22.1.3.30 String.prototype.toUpperCase ( )
+fn to_upper_case(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "toUpperCase")?;
+  Ok(Value::String(map_text(&string, str::to_uppercase)))
+}
+
+/*
+22.1.3.32.1 TrimString ( string, where )
+  3. If where is start, then
+    a. Let T be the String value that is a copy of S with leading white space removed.
+  4. Else if where is end, then
+    a. Let T be the String value that is a copy of S with trailing white space removed.
+  5. Else,
+    b. Let T be the String value that is a copy of S with both leading and trailing white space removed.
+The white space and the line terminators are all in the basic multilingual plane, one code unit each.
+*/
+fn trim_string(engine: &mut Engine, this: &Value, method: &str, start: bool, end: bool) -> Result<Value> {
+  let string = this_to_string(engine, this, method)?;
+  let is_space = |unit: &u16| char::from_u32(u32::from(*unit)).is_some_and(is_white_space_or_line_terminator);
+  let units = string.get_units();
+  let from = match start {
+    true => units.iter().position(|unit| !is_space(unit)).unwrap_or(units.len()),
+    false => 0,
+  };
+  let to = match end {
+    true => units
+      .iter()
+      .rposition(|unit| !is_space(unit))
+      .map_or(from, |index| index + 1),
+    false => units.len(),
+  };
+  Ok(Value::String(string.substring(from, to.max(from))))
+}
+
+// 22.1.3.32 String.prototype.trim ( )
+fn trim(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  trim_string(engine, this, "trim", true, true)
+}
+
+// 22.1.3.33 String.prototype.trimEnd ( )
+fn trim_end(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  trim_string(engine, this, "trimEnd", false, true)
+}
+
+// 22.1.3.34 String.prototype.trimStart ( )
+fn trim_start(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  trim_string(engine, this, "trimStart", true, false)
+}
+
+/*
+22.1.3.10 String.prototype.isWellFormed ( )
+  3. Return IsStringWellFormedUnicode(S).
+*/
+fn is_well_formed(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "isWellFormed")?;
+  let well_formed = char::decode_utf16(string.get_units().iter().copied()).all(|char| char.is_ok());
+  Ok(Value::create_boolean_value(well_formed))
+}
+
+/*
+22.1.3.35.1 ThisStringValue ( value )
+  1. If value is a String, return value.
+  2. If value is an Object and value has a [[StringData]] internal slot, then
+    a. Let s be value.[[StringData]].
+    c. Return s.
+  3. Throw a TypeError exception.
+22.1.3.29 String.prototype.toString ( ) and 22.1.3.35 String.prototype.valueOf ( ) both return it.
+*/
+fn value_of(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let value = match this {
+    Value::String(value) => Some(value),
+    Value::Object(object) => object.get_string_data(),
+    _ => None,
+  };
+  match value {
+    Some(value) => Ok(Value::String(value.clone())),
+    None => Err(EngineError::TypeError(format!(
+      "String.prototype.valueOf requires that 'this' be a String: {}",
+      this
+    ))),
+  }
+}
+
+/*
+22.1.3.36 String.prototype [ @@iterator ] ( )
+  1. Let O be ? RequireObjectCoercible(this value).
+  2. Let s be ? ToString(O).
+  3. Let closure be a new Abstract Closure with no parameters that captures s and performs the following steps when called:
+  4. Return CreateIteratorFromClosure(closure, "%StringIteratorPrototype%", %StringIteratorPrototype%).
+*/
+fn string_iterator(engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let string = this_to_string(engine, this, "[Symbol.iterator]")?;
+  let prototype = engine.get_intrinsic("%StringIteratorPrototype%");
+  Ok(Value::Object(ObjectValue::new_string_iterator(string, prototype)))
+}
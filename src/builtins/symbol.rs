@@ -0,0 +1,170 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+20.4 Symbol Objects
+The Symbol constructor is %Symbol%. It returns a new Symbol value when called as a function, it is not intended
+  to be used with the `new` operator.
+The Symbol prototype object is %Symbol.prototype%. It is an ordinary object, it is not a Symbol instance and does
+  not have a [[SymbolData]] internal slot.
+
+@links:
+- https://tc39.es/ecma262/#sec-symbol-objects
+- https://tc39.es/ecma262/#sec-well-known-symbols
+*/
+use super::{create_constructor, create_function, define_method, get_argument};
+use crate::context::Context;
+use crate::errors::{EngineError, Result};
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectKind, ObjectValue, SymbolValue, Value};
+use crate::vm::coercion::to_string;
+use crate::vm::core::Engine;
+
+// the value properties of the constructor and the well-known symbols they hold.
+const WELL_KNOWN_SYMBOLS: [(&str, &str); 6] = [
+  ("asyncIterator", "Symbol.asyncIterator"),
+  ("hasInstance", "Symbol.hasInstance"),
+  ("iterator", "Symbol.iterator"),
+  ("species", "Symbol.species"),
+  ("toPrimitive", "Symbol.toPrimitive"),
+  ("toStringTag", "Symbol.toStringTag"),
+];
+
+pub fn install(ctx: &mut Context) {
+  let object_prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+  let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, object_prototype);
+  let symbol = create_constructor("Symbol", 0, symbol_constructor, &prototype);
+  define_method(&prototype, "toString", 0, symbol_to_string);
+  define_method(&prototype, "valueOf", 0, value_of);
+  let getter = create_function("get description", 0, description);
+  let descriptor = PropertyDescriptor::new_accessor(Some(Value::Object(getter)), None, false);
+  prototype.define_own_property(PropertyKey::from("description"), descriptor);
+
+  define_method(&symbol, "for", 1, symbol_for);
+  define_method(&symbol, "keyFor", 1, key_for);
+  for (name, description) in WELL_KNOWN_SYMBOLS {
+    let well_known = ctx
+      .get_well_known_symbol(description)
+      .cloned()
+      .expect("well-known symbol to exist");
+    let descriptor = PropertyDescriptor::new_data_with(Value::Symbol(well_known), false, false, false);
+    symbol.define_own_property(PropertyKey::from(name), descriptor);
+  }
+
+  // 20.4.3.5 Symbol.prototype [ @@toPrimitive ] ( hint ) and 20.4.3.6 Symbol.prototype [ @@toStringTag ]
+  let to_primitive = ctx
+    .get_well_known_symbol("Symbol.toPrimitive")
+    .cloned()
+    .expect("well-known symbol to exist");
+  let function = create_function("[Symbol.toPrimitive]", 1, value_of);
+  let descriptor = PropertyDescriptor::new_data_with(Value::Object(function), false, false, true);
+  prototype.define_own_property(PropertyKey::Symbol(to_primitive), descriptor);
+  let to_string_tag = ctx
+    .get_well_known_symbol("Symbol.toStringTag")
+    .cloned()
+    .expect("well-known symbol to exist");
+  let tag = Value::create_string_value("Symbol".to_owned());
+  let descriptor = PropertyDescriptor::new_data_with(tag, false, false, true);
+  prototype.define_own_property(PropertyKey::Symbol(to_string_tag), descriptor);
+
+  ctx.define_builtin("Symbol", Value::Object(symbol));
+  ctx.set_intrinsic("%Symbol.prototype%", prototype);
+}
+
+/*
+20.4.1.1 Symbol ( [ description ] )
+  1. If NewTarget is not undefined, throw a TypeError exception.
+  2. If description is undefined, let descString be undefined.
+  3. Else, let descString be ? ToString(description).
+  4. Return a new Symbol whose [[Description]] is descString.
+*/
+fn symbol_constructor(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  if engine.get_new_target().is_some() {
+    return Err(EngineError::TypeError("Symbol is not a constructor".to_owned()));
+  }
+  let description = match get_argument(arguments, 0) {
+    description if description.is_undefined() => None,
+    description => Some(to_string(engine, &description)?.get()),
+  };
+  Ok(Value::create_symbol_value(description))
+}
+
+/*
+20.4.2.2 Symbol.for ( key )
+  1. Let stringKey be ? ToString(key).
+  2. For each element e of the GlobalSymbolRegistry List, do
+    a. If e.[[Key]] is stringKey, return e.[[Symbol]].
+  4. Let newSymbol be a new Symbol whose [[Description]] is stringKey.
+  5. Append the Record { [[Key]]: stringKey, [[Symbol]]: newSymbol } to the GlobalSymbolRegistry List.
+  6. Return newSymbol.
+*/
+fn symbol_for(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let key = to_string(engine, &get_argument(arguments, 0))?.get();
+  Ok(Value::Symbol(engine.get_registered_symbol(&key)))
+}
+
+/*
+20.4.2.6 Symbol.keyFor ( sym )
+  1. If sym is not a Symbol, throw a TypeError exception.
+  2. Return KeyForSymbol(sym).
+*/
+fn key_for(engine: &mut Engine, _this: &Value, arguments: &[Value]) -> Result<Value> {
+  let Value::Symbol(symbol) = get_argument(arguments, 0) else {
+    return Err(EngineError::TypeError(format!(
+      "{} is not a symbol",
+      get_argument(arguments, 0)
+    )));
+  };
+  match engine.get_symbol_registry_key(&symbol) {
+    Some(key) => Ok(Value::create_string_value(key)),
+    None => Ok(Value::create_undefined_value()),
+  }
+}
+
+/*
+20.4.3.8.1 ThisSymbolValue ( value )
+  1. If value is a Symbol, return value.
+  2. If value is an Object and value has a [[SymbolData]] internal slot, then
+    a. Let s be value.[[SymbolData]].
+    c. Return s.
+  3. Throw a TypeError exception.
+*/
+fn this_symbol_value(this: &Value, method: &str) -> Result<SymbolValue> {
+  let symbol = match this {
+    Value::Symbol(symbol) => Some(symbol),
+    Value::Object(object) => object.get_symbol_data(),
+    _ => None,
+  };
+  symbol.cloned().ok_or_else(|| {
+    EngineError::TypeError(format!(
+      "Symbol.prototype.{} requires that 'this' be a Symbol: {}",
+      method, this
+    ))
+  })
+}
+
+/*
+20.4.3.2 get Symbol.prototype.description
+  2. Let sym be ? ThisSymbolValue(s).
+  3. Return sym.[[Description]].
+*/
+fn description(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  match this_symbol_value(this, "description")?.get_description() {
+    Some(description) => Ok(Value::create_string_value(description.clone())),
+    None => Ok(Value::create_undefined_value()),
+  }
+}
+
+// 20.4.3.3 Symbol.prototype.toString ( ), the SymbolDescriptiveString of the symbol.
+fn symbol_to_string(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  let symbol = this_symbol_value(this, "toString")?;
+  Ok(Value::create_string_value(symbol.get_descriptive_string()))
+}
+
+// 20.4.3.4 Symbol.prototype.valueOf ( ), also the @@toPrimitive method.
+fn value_of(_engine: &mut Engine, this: &Value, _arguments: &[Value]) -> Result<Value> {
+  Ok(Value::Symbol(this_symbol_value(this, "valueOf")?))
+}
@@ -31,3 +31,86 @@ pub const OPCODE_LOAD_LOCAL_SCOPE: usize = 0x19; // Load a local variable
 pub const OPCODE_SET_LOCAL_SCOPE: usize = 0x1A; // Store a local variable
 pub const OPCODE_POP: usize = 0x1B; // Pop a value from the stack
 pub const OPCODE_SCOPE_EXIT: usize = 0x1C; // Exit the current scope
+pub const OPCODE_CALL: usize = 0x1D; // Call a function with the given number of arguments
+pub const OPCODE_RETURN: usize = 0x1E; // Return from the current function
+pub const OPCODE_CLOSURE: usize = 0x1F; // Create a function value capturing its upvalues
+pub const OPCODE_LOAD_UPVALUE: usize = 0x20; // Load a captured variable of the current function
+pub const OPCODE_SET_UPVALUE: usize = 0x21; // Set a captured variable of the current function
+pub const OPCODE_CLOSE_UPVALUES: usize = 0x22; // Move captured variables from the given slot up off the stack
+pub const OPCODE_SCOPE_ENTER: usize = 0x23; // Reserve the slots of the scope being entered
+pub const OPCODE_CHECK_INITIALIZED: usize = 0x24; // Throw if the value on top of the stack is an uninitialized binding
+pub const OPCODE_THROW: usize = 0x25; // Throw the value on top of the stack
+pub const OPCODE_THROW_TYPE_ERROR: usize = 0x26; // Throw a TypeError with the given message
+pub const OPCODE_THROW_REFERENCE_ERROR: usize = 0x27; // Throw a ReferenceError with the given message
+pub const OPCODE_NEW: usize = 0x28; // Construct an object calling a constructor with the given number of arguments
+pub const OPCODE_OBJECT: usize = 0x29; // Create an empty ordinary object
+pub const OPCODE_DEFINE_PROPERTY: usize = 0x2A; // Define a property with the given name on the object below the value
+pub const OPCODE_DEFINE_COMPUTED_PROPERTY: usize = 0x2B; // Define a property on the object below the key and the value
+pub const OPCODE_DEFINE_GETTER: usize = 0x2C; // Define a getter on the object below the key and the function
+pub const OPCODE_DEFINE_SETTER: usize = 0x2D; // Define a setter on the object below the key and the function
+pub const OPCODE_COPY_DATA_PROPERTIES: usize = 0x2E; // Copy the own enumerable properties of a value to the object
+pub const OPCODE_SET_PROTOTYPE: usize = 0x2F; // Set the prototype of the object below the value
+pub const OPCODE_DUP: usize = 0x30; // Duplicate the value on top of the stack
+pub const OPCODE_GET_PROP: usize = 0x31; // Get the property with the given name of the value on top of the stack
+pub const OPCODE_SET_PROP: usize = 0x32; // Set the property with the given name of the object below the value
+pub const OPCODE_GET_COMPUTED_PROP: usize = 0x33; // Get the property of the object below the key
+pub const OPCODE_SET_COMPUTED_PROP: usize = 0x34; // Set the property of the object below the key and the value
+pub const OPCODE_DELETE_PROP: usize = 0x35; // Delete the property of the object below the key
+pub const OPCODE_CALL_METHOD: usize = 0x36; // Call a function with the receiver below it and the given number of arguments
+pub const OPCODE_ARRAY: usize = 0x37; // Create an empty array
+pub const OPCODE_ARRAY_PUSH: usize = 0x38; // Append the value on top of the stack to the array below it
+pub const OPCODE_ARRAY_HOLE: usize = 0x39; // Append a hole to the array on top of the stack
+pub const OPCODE_ARRAY_SPREAD: usize = 0x3A; // Append the elements of an iterable to the array below it
+pub const OPCODE_PICK: usize = 0x3B; // Push a copy of the value the given number of slots below the top of the stack
+pub const OPCODE_SWAP: usize = 0x3C; // Swap the two values on top of the stack
+pub const OPCODE_TO_PROPERTY_KEY: usize = 0x3D; // Convert the value on top of the stack to a property key
+pub const OPCODE_REQUIRE_OBJECT_COERCIBLE: usize = 0x3E; // Throw if the value on top of the stack is undefined or null
+pub const OPCODE_GET_ITERATOR: usize = 0x3F; // Replace the iterable on top of the stack with its iterator record
+pub const OPCODE_ITERATOR_VALUE: usize = 0x40; // Replace the iterator record on top of the stack with its next value
+pub const OPCODE_ITERATOR_REST: usize = 0x41; // Replace the iterator record on top of the stack with an array of its remaining values
+pub const OPCODE_ITERATOR_CLOSE: usize = 0x42; // Close the iterator record on top of the stack unless it is done
+pub const OPCODE_OBJECT_REST: usize = 0x43; // Copy the object below the given number of excluded keys into a new object
+pub const OPCODE_EXP: usize = 0x44; // Raise a value to the power of another
+pub const OPCODE_USHR: usize = 0x45; // Unsigned shift right
+pub const OPCODE_BIT_NOT: usize = 0x46; // Bitwise not
+pub const OPCODE_TO_NUMBER: usize = 0x47; // Convert the value on top of the stack to a number
+pub const OPCODE_TYPEOF: usize = 0x48; // Replace the value on top of the stack with the name of its type
+pub const OPCODE_IN: usize = 0x49; // Test if the object on top of the stack has the property below it
+pub const OPCODE_INSTANCEOF: usize = 0x4A; // Test if the value below is an instance of the constructor on top of the stack
+pub const OPCODE_TO_STRING: usize = 0x4B; // Convert the value on top of the stack to a string
+pub const OPCODE_TO_NUMERIC: usize = 0x4C; // Convert the value on top of the stack to a number or a bigint
+pub const OPCODE_INC: usize = 0x4D; // Add one to the number or bigint on top of the stack
+pub const OPCODE_DEC: usize = 0x4E; // Subtract one from the number or bigint on top of the stack
+pub const OPCODE_THIS: usize = 0x4F; // Push the this value of the running function
+pub const OPCODE_NEW_TARGET: usize = 0x50; // Push the new.target of the running function
+pub const OPCODE_SUPER_CALL: usize = 0x51; // Construct the parent class with the array of arguments on top of the stack and bind this
+pub const OPCODE_GET_SUPER_PROP: usize = 0x52; // Get the property with the given name of the home object's prototype, for the receiver on top of the stack
+pub const OPCODE_GET_SUPER_COMPUTED_PROP: usize = 0x53; // Get the property of the home object's prototype, for the receiver below the key
+pub const OPCODE_SET_SUPER_PROP: usize = 0x54; // Set the property with the given name of the home object's prototype, for the receiver below the value
+pub const OPCODE_SET_SUPER_COMPUTED_PROP: usize = 0x55; // Set the property of the home object's prototype, for the receiver below the key and the value
+pub const OPCODE_CLASS: usize = 0x56; // Create a class constructor from the given function and its prototype, a derived one extends the value on top of the stack
+pub const OPCODE_METHOD: usize = 0x57; // Create a closure of the given function with the object on top of the stack as its home object
+pub const OPCODE_DEFINE_METHOD: usize = 0x58; // Define the method below the function on the class below it, see the CLASS_ELEMENT flags
+pub const OPCODE_DEFINE_FIELD: usize = 0x59; // Define the field with the key and the initializer on top of the stack on the class below them
+pub const OPCODE_DEFINE_STATIC_BLOCK: usize = 0x5A; // Add the static block on top of the stack to the class below it
+pub const OPCODE_INITIALIZE_CLASS: usize = 0x5B; // Define the static fields and run the static blocks of the class on top of the stack
+pub const OPCODE_PRIVATE_NAME: usize = 0x5C; // Create a new Private Name with the given description
+pub const OPCODE_GET_PRIVATE: usize = 0x5D; // Get the private element of the object below the Private Name
+pub const OPCODE_SET_PRIVATE: usize = 0x5E; // Set the private element of the object below the Private Name and the value
+pub const OPCODE_HAS_PRIVATE: usize = 0x5F; // Test if the object below the Private Name has the private element
+pub const OPCODE_ITERATOR_STEP: usize = 0x60; // Replace the iterator record on top of the stack with its next value, or pop it and jump when it is done
+pub const OPCODE_FOR_IN_ITERATOR: usize = 0x61; // Replace the value on top of the stack with an iterator record over its enumerable string keys
+pub const OPCODE_ITERATOR_CLOSE_THROW: usize = 0x62; // Close the iterator record on top of the stack for a throw, ignoring what closing it does
+pub const OPCODE_CALL_SPREAD: usize = 0x63; // Call a function with the elements of the array of arguments on top of the stack
+pub const OPCODE_CALL_METHOD_SPREAD: usize = 0x64; // Call a method with the elements of the array of arguments on top of the stack
+pub const OPCODE_NEW_SPREAD: usize = 0x65; // Construct an object with the elements of the array of arguments on top of the stack
+pub const OPCODE_LOAD_GLOBAL_NAME: usize = 0x66; // Push the property of the global object with the given name, throw a ReferenceError if it is missing
+pub const OPCODE_SET_GLOBAL_NAME: usize = 0x67; // Set the property of the global object with the given name to the value on top of the stack
+pub const OPCODE_TYPEOF_GLOBAL_NAME: usize = 0x68; // Push the type of the property of the global object with the given name, "undefined" if it is missing
+pub const OPCODE_DELETE_GLOBAL_NAME: usize = 0x69; // Delete the property of the global object with the given name and push if it was deleted
+
+// flags of the operand of OPCODE_DEFINE_METHOD and OPCODE_DEFINE_FIELD
+pub const CLASS_ELEMENT_GETTER: usize = 0x01; // The method is a getter
+pub const CLASS_ELEMENT_SETTER: usize = 0x02; // The method is a setter
+pub const CLASS_ELEMENT_STATIC: usize = 0x04; // The element belongs to the constructor instead of the instances
+pub const CLASS_ELEMENT_PRIVATE: usize = 0x08; // The key is a Private Name
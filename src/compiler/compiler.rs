@@ -1,287 +1,2677 @@
 #![allow(dead_code)]
+use super::is_simple_parameter_list;
 use crate::bytecode::opcode;
-use crate::context::{Context, Kind};
-use crate::values::Value;
+use crate::context::{Address, Capture, Context, Kind, Reference, Scope};
+use crate::errors::{EngineError, Result};
+use crate::values::function::FunctionKind;
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{BigIntValue, FunctionValue, ObjectValue, StringValue, Value};
 use oxc_ast::ast::{self, AssignmentTarget, Program};
-use oxc_syntax::NumberBase;
+use oxc_ast::syntax_directed_operations::BoundNames;
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::operator::{LogicalOperator, UnaryOperator, UpdateOperator};
+use std::rc::Rc;
 
-pub struct Compiler<'ctx> {
+pub struct Compiler<'a, 'ctx> {
   code: Vec<usize>,
   name: String,
+  kind: FunctionKind,
+  arity: usize,
+  length: usize,
+  has_rest_parameter: bool,
   constants: Vec<Value>,
   ctx: &'ctx mut Context,
+  jump_targets: Vec<JumpTarget>,
+  pending_labels: Vec<String>,
+  // slots reserved by each block scope open in this function, innermost last
+  scopes: Vec<usize>,
+  upvalues: Vec<Capture>,
+  // slots of the frame below the block scopes: the callee, the arguments and the function scope
+  locals: usize,
+  handlers: Vec<Handler>,
+  // try statements open in this function, innermost last
+  try_contexts: Vec<TryContext<'a>>,
+  positions: Vec<(usize, u32)>,
+  // optional chains being compiled, innermost last, with the jumps taken when one of them short-circuits
+  chains: Vec<Vec<usize>>,
+  // TypeScript parameter properties of a constructor, assigned to `this` once it exists
+  parameter_properties: Vec<&'a ast::FormalParameter<'a>>,
+  // strict mode code, module and class code always is
+  is_strict: bool,
+  // the text of the script, string literals are read from it
+  source: &'a str,
+  // early errors the parser leaves to the compiler, the program is not run when there is any
+  errors: Vec<String>,
 }
 
+// a statement `break` and `continue` can jump out of, with the jumps waiting to be patched.
+struct JumpTarget {
+  labels: Vec<String>,
+  is_loop: bool,
+  scope: usize,
+  break_addresses: Vec<usize>,
+  continue_addresses: Vec<usize>,
+}
+
+// a try statement being compiled. its `finally` block is copied onto every path leaving the
+// statement, those copies are left out of the code its handler protects.
+struct TryContext<'a> {
+  finalizer: Option<Finalizer<'a>>,
+  // position of the scope of the statement in `scopes`
+  level: usize,
+  // hidden slot keeping the pending completion while the `finally` block runs
+  slot: usize,
+  // jump targets open when the statement was entered
+  jump_targets: usize,
+  ranges: Vec<(usize, usize)>,
+  start: Option<usize>,
+}
+
+// what runs on the paths leaving a try context: a copy of the `finally` block, or the closing of
+// the iterator a for-of loop keeps in its hidden slot.
+#[derive(Clone, Copy)]
+enum Finalizer<'a> {
+  Block(&'a ast::BlockStatement<'a>),
+  Iterator,
+}
+
+// where a destructuring pattern stores one of the values it takes apart.
+#[derive(Clone, Copy)]
+enum PatternTarget<'a> {
+  Binding(&'a ast::BindingPattern<'a>),
+  Assignment(&'a ast::AssignmentTarget<'a>),
+  Identifier(&'a ast::IdentifierReference<'a>),
+}
+
+// an element or a property value of a pattern, with the default replacing undefined.
+struct PatternElement<'a> {
+  target: PatternTarget<'a>,
+  init: Option<&'a ast::Expression<'a>>,
+}
+
+enum PatternKey<'a> {
+  Static(String),
+  Computed(&'a ast::Expression<'a>),
+}
+
+// how the key of a member expression was evaluated, which decides the instruction reading or writing it. a static
+// key is a constant of the instruction, the others are on the stack above the object.
+#[derive(Clone, Copy)]
+enum MemberKey {
+  Static(usize),
+  Computed,
+  Private,
+  // `super.name` and `super[key]`, the object below the key is the this value
+  SuperStatic(usize),
+  SuperComputed,
+}
+
+impl MemberKey {
+  // the values the reference leaves on the stack.
+  fn depth(&self) -> usize {
+    match self {
+      MemberKey::Static(_) | MemberKey::SuperStatic(_) => 1,
+      _ => 2,
+    }
+  }
+}
+
+// an entry of the exception table: a throw from an instruction in `start..end` continues at
+// `target`, with the frame cut down to `depth` slots and the thrown value pushed on top.
+#[derive(Debug, Clone)]
+pub struct Handler {
+  pub start: usize,
+  pub end: usize,
+  pub target: usize,
+  pub depth: usize,
+}
+
+// a compiled code object: the script itself or the body of a function.
+#[derive(Debug)]
 pub struct CompilerReturn {
-  name: String,
+  pub name: String,
+  pub kind: FunctionKind,
+  pub arity: usize,
+  // the `length` of the function, the parameters before the first one with a default value
+  pub length: usize,
+  // the arguments past the arity are collected into an array in the slot after them
+  pub has_rest_parameter: bool,
+  pub is_strict: bool,
   pub code: Vec<usize>,
   pub constants: Vec<Value>,
+  pub upvalues: Vec<Capture>,
+  // innermost handlers come first
+  pub handlers: Vec<Handler>,
+  // source offsets of the instructions that may throw, by the address where each one starts
+  pub positions: Vec<(usize, u32)>,
 }
 
-impl<'ctx> Compiler<'ctx> {
-  pub fn new(name: String, ctx: &'ctx mut Context) -> Self {
-    Self { name, code: Vec::new(), constants: Vec::new(), ctx }
+impl<'a, 'ctx> Compiler<'a, 'ctx> {
+  pub fn new(name: String, source: &'a str, ctx: &'ctx mut Context) -> Self {
+    Self {
+      name,
+      kind: FunctionKind::Normal,
+      arity: 0,
+      length: 0,
+      has_rest_parameter: false,
+      code: Vec::new(),
+      constants: Vec::new(),
+      ctx,
+      jump_targets: Vec::new(),
+      pending_labels: Vec::new(),
+      scopes: Vec::new(),
+      upvalues: Vec::new(),
+      locals: 0,
+      handlers: Vec::new(),
+      try_contexts: Vec::new(),
+      positions: Vec::new(),
+      chains: Vec::new(),
+      parameter_properties: Vec::new(),
+      is_strict: false,
+      source,
+      errors: Vec::new(),
+    }
+  }
+
+  pub fn compile(program: &'a Program<'a>, source: &'a str, ctx: &'ctx mut Context) -> Result<CompilerReturn> {
+    let mut compiler = Compiler::new("main".to_string(), source, ctx);
+    compiler.is_strict = program.is_strict();
+    compiler.generate_program(program);
+    match std::mem::take(&mut compiler.errors).into_iter().next() {
+      Some(message) => Err(EngineError::SyntaxError(message)),
+      None => Ok(compiler.finish()),
+    }
+  }
+
+  // the code keeps being generated after an early error, it is thrown away with the program.
+  fn report_syntax_error(&mut self, message: String) {
+    self.errors.push(message);
+  }
+
+  fn finish(self) -> CompilerReturn {
+    CompilerReturn {
+      name: self.name,
+      kind: self.kind,
+      arity: self.arity,
+      length: self.length,
+      has_rest_parameter: self.has_rest_parameter,
+      is_strict: self.is_strict,
+      code: self.code,
+      constants: self.constants,
+      upvalues: self.upvalues,
+      handlers: self.handlers,
+      positions: self.positions,
+    }
+  }
+
+  fn generate_program(&mut self, program: &'a Program<'a>) {
+    self.generate_function_declarations(&program.body);
+    if let Some((last, statements)) = program.body.split_last() {
+      for statement in statements {
+        self.generate_statement(statement);
+      }
+      // keep the value of a trailing expression as the completion value of the script
+      match last {
+        ast::Statement::ExpressionStatement(stmt) => self.generate_expression(&stmt.expression),
+        _ => self.generate_statement(last),
+      }
+    }
+    self.emit(opcode::OPCODE_HALF);
+  }
+
+  fn generate_statement(&mut self, statement: &'a ast::Statement<'a>) {
+    match statement {
+      ast::Statement::ExpressionStatement(stmt) => self.generate_expression_statement(stmt),
+      ast::Statement::Declaration(decl) => self.generate_declaration(decl),
+      ast::Statement::IfStatement(stmt) => self.generate_if_statement(stmt),
+      ast::Statement::EmptyStatement(_) => self.generate_empty_statement(),
+      ast::Statement::BlockStatement(stmt) => self.generate_block_statement(stmt),
+      ast::Statement::WhileStatement(stmt) => self.generate_while_statement(stmt),
+      ast::Statement::DoWhileStatement(stmt) => self.generate_do_while_statement(stmt),
+      ast::Statement::ForStatement(stmt) => self.generate_for_statement(stmt),
+      ast::Statement::ForInStatement(stmt) => self.generate_for_in_statement(stmt),
+      ast::Statement::ForOfStatement(stmt) => self.generate_for_of_statement(stmt),
+      ast::Statement::BreakStatement(stmt) => self.generate_break_statement(stmt),
+      ast::Statement::ContinueStatement(stmt) => self.generate_continue_statement(stmt),
+      ast::Statement::LabeledStatement(stmt) => self.generate_labeled_statement(stmt),
+      ast::Statement::ReturnStatement(stmt) => self.generate_return_statement(stmt),
+      ast::Statement::ThrowStatement(stmt) => self.generate_throw_statement(stmt),
+      ast::Statement::TryStatement(stmt) => self.generate_try_statement(stmt),
+      _ => panic!("Unknown statement"),
+    }
+  }
+
+  fn generate_expression(&mut self, expression: &'a ast::Expression<'a>) {
+    match expression {
+      ast::Expression::NumericLiteral(value) => self.generate_numeric_literal(value),
+      ast::Expression::BigintLiteral(literal) => self.generate_bigint_literal(literal),
+      ast::Expression::BooleanLiteral(value) => self.generate_boolean_literal(value),
+      ast::Expression::StringLiteral(literal) => self.generate_string_literal(literal),
+      ast::Expression::NullLiteral(_) => self.emit_constant(Value::create_null_value()),
+      ast::Expression::BinaryExpression(binary) => self.generate_binary_expression(binary),
+      ast::Expression::Identifier(identifier) => self.generate_identifier(identifier),
+      ast::Expression::AssignmentExpression(assignment) => self.generate_assignment_expression(assignment),
+      ast::Expression::SequenceExpression(sequence) => self.generate_sequence_expression(sequence),
+      ast::Expression::CallExpression(call) => self.generate_call_expression(call),
+      ast::Expression::NewExpression(new) => self.generate_new_expression(new),
+      ast::Expression::FunctionExpression(function) => self.generate_function_expression(function),
+      ast::Expression::ArrowFunctionExpression(arrow) => self.generate_arrow_function_expression(arrow),
+      ast::Expression::ObjectExpression(object) => self.generate_object_expression(object),
+      ast::Expression::ArrayExpression(array) => self.generate_array_expression(array),
+      ast::Expression::MemberExpression(member) => self.generate_member_expression(member),
+      ast::Expression::UnaryExpression(unary) => self.generate_unary_expression(unary),
+      ast::Expression::ParenthesizedExpression(paren) => self.generate_expression(&paren.expression),
+      ast::Expression::LogicalExpression(logical) => self.generate_logical_expression(logical),
+      ast::Expression::ConditionalExpression(conditional) => self.generate_conditional_expression(conditional),
+      ast::Expression::ChainExpression(chain) => self.generate_chain_expression(chain),
+      ast::Expression::UpdateExpression(update) => self.generate_update_expression(update),
+      ast::Expression::TemplateLiteral(literal) => self.generate_template_literal(literal),
+      ast::Expression::TaggedTemplateExpression(tagged) => self.generate_tagged_template(tagged),
+      ast::Expression::ClassExpression(class) => {
+        let name = class.id.as_ref().map_or("", |id| id.name.as_str());
+        self.generate_class(class, name);
+      }
+      ast::Expression::ThisExpression(this) => {
+        self.mark_position(this.span);
+        self.emit(opcode::OPCODE_THIS);
+      }
+      // the object of a `super` property reference is the this value, the home object is found when it runs
+      ast::Expression::Super(sup) => {
+        self.mark_position(sup.span);
+        self.emit(opcode::OPCODE_THIS);
+      }
+      ast::Expression::MetaProperty(meta) => self.generate_meta_property(meta),
+      ast::Expression::PrivateInExpression(private_in) => {
+        self.generate_expression(&private_in.right);
+        self.generate_private_name(&private_in.left);
+        self.mark_position(private_in.span);
+        self.emit(opcode::OPCODE_HAS_PRIVATE);
+      }
+      _ => panic!("Unknown expression"),
+    }
+  }
+
+  fn generate_expression_statement(&mut self, statement: &'a ast::ExpressionStatement<'a>) {
+    self.generate_expression(&statement.expression);
+    self.emit(opcode::OPCODE_POP);
+  }
+
+  fn generate_block_statement(&mut self, statement: &'a ast::BlockStatement<'a>) {
+    self.enter_scope(statement.span);
+    self.generate_function_declarations(&statement.body);
+    for stmt in &statement.body {
+      self.generate_statement(stmt);
+    }
+    self.exit_scope();
+  }
+
+  fn generate_assignment_expression(&mut self, assignment: &'a ast::AssignmentExpression<'a>) {
+    match assignment.operator.as_str() {
+      "=" => self.generate_assignment_target(&assignment.left, &assignment.right),
+      "&&=" => self.generate_logical_assignment(assignment, LogicalOperator::And),
+      "||=" => self.generate_logical_assignment(assignment, LogicalOperator::Or),
+      "??=" => self.generate_logical_assignment(assignment, LogicalOperator::Coalesce),
+      operator => self.generate_compound_assignment(assignment, &operator[..operator.len() - 1]),
+    }
+  }
+
+  /*
+  13.15.2 Runtime Semantics: Evaluation, LeftHandSideExpression AssignmentOperator AssignmentExpression
+    1. Let lref be ? Evaluation of LeftHandSideExpression.
+    2. Let lval be ? GetValue(lref).
+    3. Let rref be ? Evaluation of AssignmentExpression.
+    4. Let rval be ? GetValue(rref).
+    5. Let assignmentOpText be the source text matched by AssignmentOperator.
+    6. Let opText be the sequence of Unicode code points associated with assignmentOpText in Table 42.
+    7. Let r be ? ApplyStringOrNumericBinaryOperator(lval, opText, rval).
+    8. Perform ? PutValue(lref, r).
+    9. Return r.
+  */
+  fn generate_compound_assignment(&mut self, assignment: &'a ast::AssignmentExpression<'a>, operator: &str) {
+    let Some(target) = self.get_simple_assignment_target(&assignment.left) else {
+      return;
+    };
+    let (_, key) = self.generate_target_read(target);
+    self.generate_expression(&assignment.right);
+    self.mark_position(assignment.span);
+    self.emit_binary_operator(operator);
+    self.generate_target_write(target, key);
+  }
+
+  /*
+  13.4.2.1 Runtime Semantics: Evaluation, LeftHandSideExpression ++
+    1. Let lhs be ? Evaluation of LeftHandSideExpression.
+    2. Let oldValue be ? ToNumeric(? GetValue(lhs)).
+    3. If oldValue is a Number, then
+      a. Let newValue be Number::add(oldValue, 1𝔽).
+    4. Else,
+      a. Assert: oldValue is a BigInt.
+      b. Let newValue be BigInt::add(oldValue, 1ℤ).
+    5. Perform ? PutValue(lhs, newValue).
+    6. Return oldValue.
+  The prefix forms return newValue, decrements subtract instead.
+  */
+  fn generate_update_expression(&mut self, update: &'a ast::UpdateExpression<'a>) {
+    let (depth, key) = self.generate_target_read(&update.argument);
+    self.mark_position(update.span);
+    self.emit(opcode::OPCODE_TO_NUMERIC);
+    // the old value is kept below a copy of the reference, which the write consumes
+    if !update.prefix {
+      for _ in 0..=depth {
+        self.emit(opcode::OPCODE_PICK);
+        self.emit(depth);
+      }
+    }
+    match update.operator {
+      UpdateOperator::Increment => self.emit(opcode::OPCODE_INC),
+      UpdateOperator::Decrement => self.emit(opcode::OPCODE_DEC),
+    }
+    self.generate_target_write(&update.argument, key);
+    if !update.prefix {
+      self.emit(opcode::OPCODE_POP);
+      for _ in 0..depth {
+        self.emit(opcode::OPCODE_SWAP);
+        self.emit(opcode::OPCODE_POP);
+      }
+    }
+  }
+
+  fn get_simple_assignment_target(
+    &mut self,
+    target: &'a AssignmentTarget<'a>,
+  ) -> Option<&'a ast::SimpleAssignmentTarget<'a>> {
+    match target {
+      AssignmentTarget::SimpleAssignmentTarget(target) => Some(target),
+      AssignmentTarget::AssignmentTargetPattern(_) => {
+        self.report_syntax_error("Invalid left-hand side in assignment".to_owned());
+        None
+      }
+    }
+  }
+
+  /*
+  13.15.2 Runtime Semantics: Evaluation, LeftHandSideExpression &&= AssignmentExpression
+    1. Let lref be ? Evaluation of LeftHandSideExpression.
+    2. Let lval be ? GetValue(lref).
+    3. Let lbool be ToBoolean(lval).
+    4. If lbool is false, return lval.
+    5. If IsAnonymousFunctionDefinition(AssignmentExpression) and IsIdentifierRef of LeftHandSideExpression are
+      both true, then
+      a. Let rval be ? NamedEvaluation of AssignmentExpression with argument lref.[[ReferencedName]].
+    6. Else,
+      a. Let rref be ? Evaluation of AssignmentExpression.
+      b. Let rval be ? GetValue(rref).
+    7. Perform ? PutValue(lref, rval).
+    8. Return rval.
+  `||=` and `??=` only differ in the test, the target is not written when it short-circuits.
+  */
+  fn generate_logical_assignment(&mut self, assignment: &'a ast::AssignmentExpression<'a>, operator: LogicalOperator) {
+    let Some(target) = self.get_simple_assignment_target(&assignment.left) else {
+      return;
+    };
+    let (depth, key) = self.generate_target_read(target);
+    let jump = self.emit_logical_jump(operator);
+    self.emit(opcode::OPCODE_POP);
+    match target {
+      ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+        self.generate_named_expression(&assignment.right, &identifier.name)
+      }
+      _ => self.generate_expression(&assignment.right),
+    }
+    self.generate_target_write(target, key);
+    let end = self.emit_jump(opcode::OPCODE_JUMP);
+    // the reference of a member target is still below the value that is kept
+    self.patch_jump(jump);
+    for _ in 0..depth {
+      self.emit(opcode::OPCODE_SWAP);
+      self.emit(opcode::OPCODE_POP);
+    }
+    self.patch_jump(end);
+  }
+
+  // evaluates the reference of a simple target and reads its value, the reference stays below it.
+  fn generate_target_read(&mut self, target: &'a ast::SimpleAssignmentTarget<'a>) -> (usize, MemberKey) {
+    match target {
+      ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+        self.generate_identifier(identifier);
+        (0, MemberKey::Computed)
+      }
+      ast::SimpleAssignmentTarget::MemberAssignmentTarget(member) => {
+        let key = self.generate_member_base(member);
+        let depth = key.depth();
+        match depth {
+          1 => self.emit(opcode::OPCODE_DUP),
+          _ => {
+            self.emit(opcode::OPCODE_PICK);
+            self.emit(1);
+            self.emit(opcode::OPCODE_PICK);
+            self.emit(1);
+          }
+        }
+        self.mark_position(member.span());
+        self.emit_member_get(key);
+        (depth, key)
+      }
+      _ => {
+        self.report_syntax_error("Invalid left-hand side in assignment".to_owned());
+        (0, MemberKey::Computed)
+      }
+    }
+  }
+
+  // stores the value on top of the stack through the reference `generate_target_read` left below it.
+  fn generate_target_write(&mut self, target: &'a ast::SimpleAssignmentTarget<'a>, key: MemberKey) {
+    match target {
+      ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+        self.emit_identifier_assignment(identifier);
+      }
+      ast::SimpleAssignmentTarget::MemberAssignmentTarget(member) => {
+        self.mark_position(member.span());
+        self.emit_member_set(key);
+      }
+      _ => self.report_syntax_error("Invalid left-hand side in assignment".to_owned()),
+    }
+  }
+
+  /*
+  13.13.1 Runtime Semantics: Evaluation, LogicalANDExpression, LogicalORExpression and CoalesceExpression
+  The left value is kept when it decides the result, otherwise it is dropped for the right one.
+  */
+  fn generate_logical_expression(&mut self, logical: &'a ast::LogicalExpression<'a>) {
+    self.generate_expression(&logical.left);
+    let jump = self.emit_logical_jump(logical.operator);
+    self.emit(opcode::OPCODE_POP);
+    self.generate_expression(&logical.right);
+    self.patch_jump(jump);
+  }
+
+  // jumps when the value on top of the stack decides a logical operator, keeping the value.
+  fn emit_logical_jump(&mut self, operator: LogicalOperator) -> usize {
+    self.emit(opcode::OPCODE_DUP);
+    match operator {
+      LogicalOperator::And => {}
+      LogicalOperator::Or => self.emit(opcode::OPCODE_NOT),
+      LogicalOperator::Coalesce => self.emit_nullish_test(),
+    }
+    self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE)
+  }
+
+  // replaces the value on top of the stack with whether it is undefined or null.
+  fn emit_nullish_test(&mut self) {
+    // null is only loosely equal to itself and undefined
+    self.emit_constant(Value::create_null_value());
+    self.emit(opcode::OPCODE_WEAK_EQ);
+  }
+
+  // 13.14.1 Runtime Semantics: Evaluation, ConditionalExpression
+  fn generate_conditional_expression(&mut self, conditional: &'a ast::ConditionalExpression<'a>) {
+    self.generate_expression(&conditional.test);
+    let jump_if_false_address = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
+    self.generate_expression(&conditional.consequent);
+    let jump_address = self.emit_jump(opcode::OPCODE_JUMP);
+    self.patch_jump(jump_if_false_address);
+    self.generate_expression(&conditional.alternate);
+    self.patch_jump(jump_address);
+  }
+
+  /*
+  13.3.9.1 Runtime Semantics: Evaluation, OptionalExpression
+    1. Let baseReference be ? Evaluation of MemberExpression.
+    2. Let baseValue be ? GetValue(baseReference).
+    3. If baseValue is either undefined or null, then
+      a. Return undefined.
+    4. Return ? ChainEvaluation of OptionalChain with arguments baseValue and baseReference.
+  Every `?.` of the chain jumps to its end once the value before it is nullish, the whole chain is undefined.
+  */
+  fn generate_chain_expression(&mut self, chain: &'a ast::ChainExpression<'a>) {
+    self.chains.push(Vec::new());
+    match &chain.expression {
+      ast::ChainElement::CallExpression(call) => self.generate_call_expression(call),
+      ast::ChainElement::MemberExpression(member) => self.generate_member_expression(member),
+    }
+    let end = self.emit_jump(opcode::OPCODE_JUMP);
+    for jump in self.chains.pop().unwrap_or_default() {
+      self.patch_jump(jump);
+    }
+    self.emit_constant(Value::create_undefined_value());
+    self.patch_jump(end);
+  }
+
+  // short-circuits the chain when the value on top of the stack is nullish, dropping it and the
+  // `count - 1` values below it.
+  fn emit_optional_check(&mut self, count: usize) {
+    self.emit(opcode::OPCODE_DUP);
+    self.emit_nullish_test();
+    let jump = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
+    for _ in 0..count {
+      self.emit(opcode::OPCODE_POP);
+    }
+    let exit = self.emit_jump(opcode::OPCODE_JUMP);
+    self.chains.last_mut().expect("optional chain").push(exit);
+    self.patch_jump(jump);
+  }
+
+  fn generate_assignment_target(&mut self, target: &'a AssignmentTarget<'a>, init: &'a ast::Expression<'a>) {
+    match target {
+      AssignmentTarget::SimpleAssignmentTarget(ast::SimpleAssignmentTarget::MemberAssignmentTarget(member)) => {
+        let key = self.generate_member_base(member);
+        self.generate_expression(init);
+        self.mark_position(member.span());
+        self.emit_member_set(key);
+      }
+      AssignmentTarget::SimpleAssignmentTarget(ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier)) => {
+        self.generate_expression(init);
+        self.emit_identifier_assignment(identifier);
+      }
+      // the pattern takes a copy apart, the assigned value is the value of the expression
+      AssignmentTarget::AssignmentTargetPattern(pattern) => {
+        self.generate_expression(init);
+        self.emit(opcode::OPCODE_DUP);
+        self.generate_assignment_pattern(pattern);
+      }
+      _ => panic!("Unknown left assignment expression"),
+    }
+  }
+
+  // stores the value on top of the stack into the binding a name refers to, leaving it there.
+  // a name no declaration resolved to is a property of the global object, strict mode code can't create it.
+  fn emit_identifier_assignment(&mut self, identifier: &'a ast::IdentifierReference<'a>) {
+    self.mark_position(identifier.span);
+    let Some(reference) = self.get_variable_reference(identifier) else {
+      self.emit_global_name(opcode::OPCODE_SET_GLOBAL_NAME, &identifier.name);
+      return;
+    };
+    // writing a `let` still in its temporal dead zone throws like reading it
+    if reference.is_lexical() {
+      let (load_opcode, index) = self.get_load_operation(&reference);
+      self.emit(load_opcode);
+      self.emit(index);
+      self.emit_initialized_check(&identifier.name);
+      self.emit(opcode::OPCODE_POP);
+    }
+    if reference.get_kind() == &Kind::Const {
+      self.emit_throw_error(opcode::OPCODE_THROW_TYPE_ERROR, "Assignment to constant variable.");
+      return;
+    }
+    let (set_opcode, index) = self.get_set_operation(&reference);
+    self.emit(set_opcode);
+    self.emit(index);
+  }
+
+  /*
+  8.6.2 Runtime Semantics: BindingInitialization
+  14.3.3 Destructuring Binding Patterns
+  The value on top of the stack is bound to the names of the pattern and taken off the stack.
+  */
+  fn generate_binding_pattern(&mut self, pattern: &'a ast::BindingPattern<'a>) {
+    match &pattern.kind {
+      ast::BindingPatternKind::BindingIdentifier(ident) => self.emit_initialize(ident.span),
+      ast::BindingPatternKind::AssignmentPattern(assign) => {
+        let name = assign.left.get_identifier().map(|name| name.as_str());
+        self.emit_default_value(&assign.right, name);
+        self.generate_binding_pattern(&assign.left);
+      }
+      ast::BindingPatternKind::ObjectPattern(object) => {
+        let properties = object.properties.iter().map(|property| {
+          let key = match property.key.static_name() {
+            Some(name) if !property.computed => PatternKey::Static(name.to_string()),
+            _ => self.get_computed_pattern_key(&property.key),
+          };
+          (key, Self::get_binding_element(&property.value))
+        });
+        let properties = properties.collect();
+        let rest = object.rest.as_ref().map(|rest| PatternTarget::Binding(&rest.argument));
+        self.generate_object_pattern(object.span, properties, rest);
+      }
+      ast::BindingPatternKind::ArrayPattern(array) => {
+        let elements = array
+          .elements
+          .iter()
+          .map(|element| element.as_ref().map(Self::get_binding_element));
+        let rest = array.rest.as_ref().map(|rest| PatternTarget::Binding(&rest.argument));
+        self.generate_array_pattern(array.span, elements.collect(), rest);
+      }
+    }
+  }
+
+  fn get_binding_element(pattern: &'a ast::BindingPattern<'a>) -> PatternElement<'a> {
+    match &pattern.kind {
+      ast::BindingPatternKind::AssignmentPattern(assign) => {
+        PatternElement { target: PatternTarget::Binding(&assign.left), init: Some(&assign.right) }
+      }
+      _ => PatternElement { target: PatternTarget::Binding(pattern), init: None },
+    }
+  }
+
+  /*
+  13.15.5 Destructuring Assignment
+  Like a binding pattern the value on top of the stack is taken apart, its values are stored into any target an
+    assignment could store into.
+  */
+  fn generate_assignment_pattern(&mut self, pattern: &'a ast::AssignmentTargetPattern<'a>) {
+    match pattern {
+      ast::AssignmentTargetPattern::ObjectAssignmentTarget(object) => {
+        let properties = object.properties.iter().map(|property| match property {
+          ast::AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(property) => {
+            let target = PatternTarget::Identifier(&property.binding);
+            let element = PatternElement { target, init: property.init.as_ref() };
+            (PatternKey::Static(property.binding.name.to_string()), element)
+          }
+          ast::AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => {
+            let key = match property.name.static_name() {
+              Some(name) => PatternKey::Static(name.to_string()),
+              None => self.get_computed_pattern_key(&property.name),
+            };
+            (key, Self::get_assignment_element(&property.binding))
+          }
+        });
+        let properties = properties.collect();
+        let rest = object.rest.as_ref().map(|rest| PatternTarget::Assignment(&rest.target));
+        self.generate_object_pattern(object.span, properties, rest);
+      }
+      ast::AssignmentTargetPattern::ArrayAssignmentTarget(array) => {
+        let elements = array
+          .elements
+          .iter()
+          .map(|element| element.as_ref().map(Self::get_assignment_element));
+        let rest = array.rest.as_ref().map(|rest| PatternTarget::Assignment(&rest.target));
+        self.generate_array_pattern(array.span, elements.collect(), rest);
+      }
+    }
+  }
+
+  fn get_assignment_element(target: &'a ast::AssignmentTargetMaybeDefault<'a>) -> PatternElement<'a> {
+    match target {
+      ast::AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+        PatternElement { target: PatternTarget::Assignment(target), init: None }
+      }
+      ast::AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+        PatternElement { target: PatternTarget::Assignment(&target.binding), init: Some(&target.init) }
+      }
+    }
+  }
+
+  fn get_computed_pattern_key(&self, key: &'a ast::PropertyKey<'a>) -> PatternKey<'a> {
+    match key {
+      ast::PropertyKey::Expression(expression) => PatternKey::Computed(expression),
+      _ => panic!("[Compiler] Private names are only valid in classes"),
+    }
+  }
+
+  /*
+  14.3.3.1 Runtime Semantics: PropertyBindingInitialization
+  13.15.5.3 Runtime Semantics: PropertyDestructuringAssignmentEvaluation
+  Every property is read from the value, which stays on top of the stack until the pattern is done. With a rest
+    element the keys read so far are kept below the value, they are the keys the rest object leaves out.
+  */
+  fn generate_object_pattern(
+    &mut self,
+    span: Span,
+    properties: Vec<(PatternKey<'a>, PatternElement<'a>)>,
+    rest: Option<PatternTarget<'a>>,
+  ) {
+    self.mark_position(span);
+    self.emit(opcode::OPCODE_REQUIRE_OBJECT_COERCIBLE);
+    let count = properties.len();
+    for (key, element) in properties {
+      match key {
+        PatternKey::Static(name) => {
+          let index = self.get_string_constant_index(&name);
+          let (depth, member) = self.generate_target_reference(element.target);
+          self.emit(opcode::OPCODE_PICK);
+          self.emit(depth);
+          self.mark_position(span);
+          self.emit(opcode::OPCODE_GET_PROP);
+          self.emit(index);
+          self.emit_pattern_value(&element, member);
+          if rest.is_some() {
+            self.emit(opcode::OPCODE_CONST);
+            self.emit(index);
+            self.emit(opcode::OPCODE_SWAP);
+          }
+        }
+        PatternKey::Computed(expression) => {
+          self.generate_expression(expression);
+          self.emit(opcode::OPCODE_TO_PROPERTY_KEY);
+          let (depth, member) = self.generate_target_reference(element.target);
+          self.emit(opcode::OPCODE_PICK);
+          self.emit(depth + 1);
+          self.emit(opcode::OPCODE_PICK);
+          self.emit(depth + 1);
+          self.mark_position(span);
+          self.emit(opcode::OPCODE_GET_COMPUTED_PROP);
+          self.emit_pattern_value(&element, member);
+          self.emit(if rest.is_some() {
+            opcode::OPCODE_SWAP
+          } else {
+            opcode::OPCODE_POP
+          });
+        }
+      }
+    }
+    let Some(target) = rest else {
+      self.emit(opcode::OPCODE_POP);
+      return;
+    };
+    // the rest object is created before the target of an assignment is evaluated
+    self.emit(opcode::OPCODE_OBJECT_REST);
+    self.emit(count);
+    self.emit_value_store(target);
+  }
+
+  /*
+  8.6.3 Runtime Semantics: IteratorBindingInitialization
+  13.15.5.5 Runtime Semantics: IteratorDestructuringAssignmentEvaluation
+  The iterator record stays on top of the stack, every element takes the next value from it. The iterator is
+    closed once the pattern is done, unless it ran out of values or failed.
+  */
+  fn generate_array_pattern(
+    &mut self,
+    span: Span,
+    elements: Vec<Option<PatternElement<'a>>>,
+    rest: Option<PatternTarget<'a>>,
+  ) {
+    self.mark_position(span);
+    self.emit(opcode::OPCODE_GET_ITERATOR);
+    for element in elements {
+      let Some(element) = element else {
+        self.emit(opcode::OPCODE_DUP);
+        self.mark_position(span);
+        self.emit(opcode::OPCODE_ITERATOR_VALUE);
+        self.emit(opcode::OPCODE_POP);
+        continue;
+      };
+      let (depth, member) = self.generate_target_reference(element.target);
+      self.emit(opcode::OPCODE_PICK);
+      self.emit(depth);
+      self.mark_position(span);
+      self.emit(opcode::OPCODE_ITERATOR_VALUE);
+      self.emit_pattern_value(&element, member);
+    }
+    if let Some(target) = rest {
+      let (depth, member) = self.generate_target_reference(target);
+      self.emit(opcode::OPCODE_PICK);
+      self.emit(depth);
+      self.mark_position(span);
+      self.emit(opcode::OPCODE_ITERATOR_REST);
+      self.emit_target_store(target, member);
+    }
+    self.mark_position(span);
+    self.emit(opcode::OPCODE_ITERATOR_CLOSE);
+  }
+
+  // the object and the key a member target stores into are evaluated before its value is read,
+  // the count of values they left on the stack and the constant of a static key.
+  fn generate_target_reference(&mut self, target: PatternTarget<'a>) -> (usize, MemberKey) {
+    let PatternTarget::Assignment(AssignmentTarget::SimpleAssignmentTarget(
+      ast::SimpleAssignmentTarget::MemberAssignmentTarget(member),
+    )) = target
+    else {
+      return (0, MemberKey::Computed);
+    };
+    let key = self.generate_member_base(member);
+    (key.depth(), key)
+  }
+
+  fn emit_pattern_value(&mut self, element: &PatternElement<'a>, member: MemberKey) {
+    if let Some(init) = element.init {
+      let name = match element.target {
+        PatternTarget::Binding(pattern) => pattern.get_identifier().map(|name| name.as_str()),
+        PatternTarget::Identifier(identifier) => Some(identifier.name.as_str()),
+        PatternTarget::Assignment(AssignmentTarget::SimpleAssignmentTarget(
+          ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier),
+        )) => Some(identifier.name.as_str()),
+        PatternTarget::Assignment(_) => None,
+      };
+      self.emit_default_value(init, name);
+    }
+    self.emit_target_store(element.target, member);
+  }
+
+  // the value is on top of the stack, like a rest object, a member target is evaluated above it.
+  fn emit_value_store(&mut self, target: PatternTarget<'a>) {
+    let (depth, member) = self.generate_target_reference(target);
+    if depth == 0 {
+      self.emit_target_store(target, member);
+      return;
+    }
+    self.emit(opcode::OPCODE_PICK);
+    self.emit(depth);
+    self.emit_target_store(target, member);
+    self.emit(opcode::OPCODE_POP);
+  }
+
+  // takes the value on top of the stack, and the reference of a member target below it.
+  fn emit_target_store(&mut self, target: PatternTarget<'a>, member: MemberKey) {
+    match target {
+      PatternTarget::Binding(pattern) => self.generate_binding_pattern(pattern),
+      PatternTarget::Identifier(identifier) => {
+        self.emit_identifier_assignment(identifier);
+        self.emit(opcode::OPCODE_POP);
+      }
+      PatternTarget::Assignment(AssignmentTarget::AssignmentTargetPattern(pattern)) => {
+        self.generate_assignment_pattern(pattern);
+      }
+      PatternTarget::Assignment(AssignmentTarget::SimpleAssignmentTarget(target)) => {
+        match target {
+          ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+            self.emit_identifier_assignment(identifier)
+          }
+          ast::SimpleAssignmentTarget::MemberAssignmentTarget(expression) => {
+            self.mark_position(expression.span());
+            self.emit_member_set(member);
+          }
+          _ => panic!("Unknown left assignment expression"),
+        }
+        self.emit(opcode::OPCODE_POP);
+      }
+    }
+  }
+
+  // replaces an undefined value on top of the stack with the default, other values are kept.
+  fn emit_default_value(&mut self, init: &'a ast::Expression<'a>, name: Option<&str>) {
+    self.emit(opcode::OPCODE_DUP);
+    self.emit_constant(Value::create_undefined_value());
+    self.emit(opcode::OPCODE_EQ);
+    let jump = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
+    self.emit(opcode::OPCODE_POP);
+    match name {
+      Some(name) => self.generate_named_expression(init, name),
+      None => self.generate_expression(init),
+    }
+    self.patch_jump(jump);
+  }
+
+  fn generate_declaration(&mut self, declaration: &'a ast::Declaration<'a>) {
+    match declaration {
+      ast::Declaration::VariableDeclaration(decl) => self.generate_variable_declaration(decl),
+      // functions are instantiated when their scope is entered
+      ast::Declaration::FunctionDeclaration(_) => {}
+      ast::Declaration::ClassDeclaration(class) if class.is_declare() => {}
+      ast::Declaration::ClassDeclaration(class) => {
+        let Some(id) = &class.id else {
+          let message = "A class declaration without the 'default' modifier must have a name";
+          return self.report_syntax_error(message.to_owned());
+        };
+        self.generate_class(class, &id.name);
+        self.emit_initialize(id.span);
+      }
+      _ => panic!("Unknown declaration"),
+    }
+  }
+
+  // function declarations are hoisted with their bodies: every function declared directly in
+  // a block is created as soon as the block is entered, before any of its statements run.
+  fn generate_function_declarations(&mut self, statements: &'a [ast::Statement<'a>]) {
+    for statement in statements {
+      match statement {
+        // an overload signature has no body, the implementation declared with it is what gets created
+        ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(function)) if function.body.is_some() => {
+          self.generate_function_declaration(function);
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn generate_function_declaration(&mut self, function: &'a ast::Function<'a>) {
+    let Some(id) = &function.id else {
+      return self.report_syntax_error("Function statements require a function name".to_owned());
+    };
+    let code = self.compile_function(id.name.as_str(), FunctionKind::Normal, function);
+    self.emit_closure(code);
+    self.emit_initialize(id.span);
+  }
+
+  fn generate_function_expression(&mut self, function: &'a ast::Function<'a>) {
+    let name = function.id.as_ref().map_or("", |id| id.name.as_str());
+    let code = self.compile_function(name, FunctionKind::Normal, function);
+    self.emit_closure(code);
+  }
+
+  fn generate_arrow_function_expression(&mut self, arrow: &'a ast::ArrowFunctionExpression<'a>) {
+    self.generate_named_expression_arrow(arrow, "");
+  }
+
+  fn generate_named_expression_arrow(&mut self, arrow: &'a ast::ArrowFunctionExpression<'a>, name: &str) {
+    let code = self.compile_function_code(
+      name,
+      FunctionKind::Arrow,
+      arrow.span,
+      &arrow.params,
+      &arrow.body,
+      arrow.expression,
+    );
+    self.emit_closure(code);
+  }
+
+  // anonymous functions and classes take the name of what they are assigned to.
+  fn generate_named_expression(&mut self, expression: &'a ast::Expression<'a>, name: &str) {
+    match expression {
+      ast::Expression::FunctionExpression(function) if function.id.is_none() => {
+        let code = self.compile_function(name, FunctionKind::Normal, function);
+        self.emit_closure(code);
+      }
+      ast::Expression::ArrowFunctionExpression(arrow) => self.generate_named_expression_arrow(arrow, name),
+      ast::Expression::ClassExpression(class) if class.id.is_none() => self.generate_class(class, name),
+      _ => self.generate_expression(expression),
+    }
+  }
+
+  // 13.3.12.1 Runtime Semantics: Evaluation, NewTarget : new . target
+  fn generate_meta_property(&mut self, meta: &'a ast::MetaProperty<'a>) {
+    match (meta.meta.name.as_str(), meta.property.name.as_str()) {
+      ("new", "target") => self.emit(opcode::OPCODE_NEW_TARGET),
+      (meta, property) => panic!("[Compiler] {}.{} is not supported", meta, property),
+    }
+  }
+
+  /*
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+  The class is compiled as an arrow function called right where it is defined, its frame holds the binding of the
+    class name and the Private Names. The constructor and the prototype stay on the stack while the elements are
+    defined in source order, the constructor is returned once the static elements ran.
+  */
+  fn generate_class(&mut self, class: &'a ast::Class<'a>, name: &str) {
+    let code = self.compile_code("", FunctionKind::Arrow, class.span, |compiler| {
+      // 11.2.2 Strict Mode Code, all parts of a class are strict mode code
+      compiler.is_strict = true;
+      compiler.generate_class_body(class, name)
+    });
+    self.emit_closure(code);
+    self.mark_position(class.span);
+    self.emit(opcode::OPCODE_CALL);
+    self.emit(0);
+  }
+
+  fn generate_class_body(&mut self, class: &'a ast::Class<'a>, name: &str) {
+    self.locals = 1 + self.emit_reserve(class.span);
+    let bindings = self.ctx.get_scope(class.span).map(|scope| scope.bindings.clone());
+    let bindings = bindings.unwrap_or_default();
+    // 6. a. ii. 1. Let name be a new Private Name whose [[Description]] is dn.
+    for binding in bindings.iter().filter(|binding| binding.name.starts_with('#')) {
+      let index = self.get_string_constant_index(&binding.name);
+      self.emit_with_operand(opcode::OPCODE_PRIVATE_NAME, index);
+      self.emit_with_operand(opcode::OPCODE_SET_LOCAL_SCOPE, binding.slot);
+      self.emit(opcode::OPCODE_POP);
+    }
+    if let Some(super_class) = &class.super_class {
+      self.generate_expression(super_class);
+    }
+    let constructor = self.compile_class_constructor(class, name);
+    let index = self.add_constant(Value::create_function_value(FunctionValue::new(Rc::new(constructor))));
+    self.mark_position(class.span);
+    self.emit_with_operand(opcode::OPCODE_CLASS, index);
+    for element in &class.body.body {
+      self.generate_class_element(element);
+    }
+    self.emit(opcode::OPCODE_POP);
+    // 27. If classBinding is not undefined, then
+    //   a. Perform ! classEnv.InitializeBinding(classBinding, F).
+    let id = class.id.as_ref().map(|id| id.name.as_str());
+    if let Some(binding) = bindings.iter().find(|binding| Some(binding.name.as_str()) == id) {
+      self.emit_with_operand(opcode::OPCODE_SET_LOCAL_SCOPE, binding.slot);
+    }
+    self.mark_position(class.span);
+    self.emit(opcode::OPCODE_INITIALIZE_CLASS);
+    self.emit(opcode::OPCODE_RETURN);
+  }
+
+  // the constructor written in the class body, or the default one.
+  fn compile_class_constructor(&mut self, class: &'a ast::Class<'a>, name: &str) -> CompilerReturn {
+    let kind = match class.super_class {
+      Some(_) => FunctionKind::DerivedConstructor,
+      None => FunctionKind::BaseConstructor,
+    };
+    let constructor = class.body.body.iter().find_map(|element| match element {
+      ast::ClassElement::MethodDefinition(method) if method.kind.is_constructor() && method.value.body.is_some() => {
+        Some(method)
+      }
+      _ => None,
+    });
+    match constructor {
+      Some(method) => self.compile_function(name, kind, &method.value),
+      None => {
+        let mut compiler = Compiler::new(name.to_string(), self.source, self.ctx);
+        compiler.kind = kind;
+        compiler.is_strict = true;
+        compiler.generate_default_constructor();
+        compiler.finish()
+      }
+    }
+  }
+
+  /*
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+    14. a. Let defaultConstructor be a new Abstract Closure with no parameters that captures nothing and performs
+      the following steps when called:
+        a. If F.[[ConstructorKind]] is derived, then
+          i. NOTE: This branch behaves similarly to constructor(...args) { super(...args); }. The most notable
+            distinction is that while the aforementioned ECMAScript source text observably calls the @@iterator
+            method on %Array.prototype%, this function does not.
+        b. Else,
+          i. NOTE: This branch behaves similarly to constructor() {}.
+  */
+  fn generate_default_constructor(&mut self) {
+    if self.kind == FunctionKind::DerivedConstructor {
+      self.has_rest_parameter = true;
+      self.locals = 2;
+      self.emit_with_operand(opcode::OPCODE_LOAD_LOCAL_SCOPE, 1);
+      self.emit(opcode::OPCODE_SUPER_CALL);
+      self.emit(opcode::OPCODE_POP);
+    }
+    self.emit_constant(Value::create_undefined_value());
+    self.emit(opcode::OPCODE_RETURN);
+  }
+
+  // TypeScript only elements, like overloads, abstract members and `declare` fields, leave nothing behind.
+  fn generate_class_element(&mut self, element: &'a ast::ClassElement<'a>) {
+    if element.is_typescript_syntax() || element.is_ts_empty_body_function() {
+      return;
+    }
+    match element {
+      ast::ClassElement::MethodDefinition(method) if method.kind.is_constructor() => {}
+      ast::ClassElement::MethodDefinition(method) => self.generate_class_method(method),
+      ast::ClassElement::PropertyDefinition(property) if property.declare => {}
+      ast::ClassElement::PropertyDefinition(property) => self.generate_class_field(property),
+      ast::ClassElement::StaticBlock(block) => self.generate_class_static_block(block),
+      ast::ClassElement::AccessorProperty(_) => panic!("[Compiler] Auto-accessors are not supported"),
+      ast::ClassElement::TSIndexSignature(_) => {}
+    }
+  }
+
+  /*
+  15.4.4 Runtime Semantics: MethodDefinitionEvaluation
+  The key and the method are pushed above the constructor and the prototype, the home object of the method is
+    the one it is defined on.
+  */
+  fn generate_class_method(&mut self, method: &'a ast::MethodDefinition<'a>) {
+    let name = self.generate_class_element_key(&method.key, method.computed);
+    let (flags, prefix) = match method.kind {
+      ast::MethodDefinitionKind::Get => (opcode::CLASS_ELEMENT_GETTER, "get "),
+      ast::MethodDefinitionKind::Set => (opcode::CLASS_ELEMENT_SETTER, "set "),
+      _ => (0, ""),
+    };
+    let flags = flags | self.get_class_element_flags(&method.key, method.r#static);
+    let name = format!("{}{}", prefix, name.unwrap_or_default());
+    let code = self.compile_function(name.trim_end(), FunctionKind::Method, &method.value);
+    self.emit_with_operand(opcode::OPCODE_PICK, if method.r#static { 2 } else { 1 });
+    self.emit_method(code);
+    self.mark_position(method.span);
+    self.emit_with_operand(opcode::OPCODE_DEFINE_METHOD, flags);
+  }
+
+  /*
+  15.7.10 Runtime Semantics: ClassFieldDefinitionEvaluation
+    2. If Initializer is present, then
+      e. Let initializer be OrdinaryFunctionCreate(%Function.prototype%, sourceText, formalParameterList,
+        Initializer, non-lexical-this, env, privateEnv).
+      f. Perform MakeMethod(initializer, homeObject).
+  */
+  fn generate_class_field(&mut self, property: &'a ast::PropertyDefinition<'a>) {
+    let name = self.generate_class_element_key(&property.key, property.computed);
+    match &property.value {
+      Some(value) => {
+        let code = self.compile_code("", FunctionKind::Method, property.span, |compiler| {
+          compiler.locals = 1 + compiler.emit_reserve(property.span);
+          compiler.generate_named_expression(value, name.as_deref().unwrap_or_default());
+          compiler.emit(opcode::OPCODE_RETURN);
+        });
+        self.emit_with_operand(opcode::OPCODE_PICK, if property.r#static { 2 } else { 1 });
+        self.emit_method(code);
+      }
+      None => self.emit_constant(Value::create_undefined_value()),
+    }
+    let flags = self.get_class_element_flags(&property.key, property.r#static);
+    self.mark_position(property.span);
+    self.emit_with_operand(opcode::OPCODE_DEFINE_FIELD, flags);
+  }
+
+  // 15.7.11 Runtime Semantics: ClassStaticBlockDefinitionEvaluation, the block is a method of the constructor.
+  fn generate_class_static_block(&mut self, block: &'a ast::StaticBlock<'a>) {
+    let code = self.compile_code("", FunctionKind::Method, block.span, |compiler| {
+      compiler.locals = 1 + compiler.emit_reserve(block.span);
+      compiler.generate_function_declarations(&block.body);
+      for statement in &block.body {
+        compiler.generate_statement(statement);
+      }
+      compiler.emit_constant(Value::create_undefined_value());
+      compiler.emit(opcode::OPCODE_RETURN);
+    });
+    self.emit_with_operand(opcode::OPCODE_PICK, 1);
+    self.emit_method(code);
+    self.emit(opcode::OPCODE_DEFINE_STATIC_BLOCK);
+  }
+
+  // the key of a class element onto the stack, with its name when it is known before the class runs.
+  fn generate_class_element_key(&mut self, key: &'a ast::PropertyKey<'a>, computed: bool) -> Option<String> {
+    if let ast::PropertyKey::PrivateIdentifier(identifier) = key {
+      self.generate_private_name(identifier);
+      return Some(format!("#{}", identifier.name));
+    }
+    let name = key.static_name().filter(|_| !computed);
+    self.generate_property_key(key, name.as_deref());
+    if name.is_none() {
+      self.mark_position(key.span());
+      self.emit(opcode::OPCODE_TO_PROPERTY_KEY);
+    }
+    name.map(|name| name.to_string())
+  }
+
+  fn get_class_element_flags(&self, key: &'a ast::PropertyKey<'a>, is_static: bool) -> usize {
+    let mut flags = 0;
+    if is_static {
+      flags |= opcode::CLASS_ELEMENT_STATIC;
+    }
+    if key.is_private_identifier() {
+      flags |= opcode::CLASS_ELEMENT_PRIVATE;
+    }
+    flags
+  }
+
+  /*
+  13.2.5.5 Runtime Semantics: PropertyDefinitionEvaluation
+  The object is created first and stays on the stack while every property definition, in source order, adds its
+    properties to it.
+  */
+  fn generate_object_expression(&mut self, object: &'a ast::ObjectExpression<'a>) {
+    self.emit(opcode::OPCODE_OBJECT);
+    for property in &object.properties {
+      match property {
+        ast::ObjectPropertyKind::ObjectProperty(property) => self.generate_object_property(property),
+        ast::ObjectPropertyKind::SpreadProperty(spread) => {
+          self.generate_expression(&spread.argument);
+          self.mark_position(spread.span);
+          self.emit(opcode::OPCODE_COPY_DATA_PROPERTIES);
+        }
+      }
+    }
+  }
+
+  /*
+  13.2.4.1 Runtime Semantics: ArrayAccumulation
+  Like an object literal the array stays on the stack, elements are appended to it in source order and an elision
+    only grows its length.
+  */
+  fn generate_array_expression(&mut self, array: &'a ast::ArrayExpression<'a>) {
+    self.emit(opcode::OPCODE_ARRAY);
+    for element in &array.elements {
+      match element {
+        ast::ArrayExpressionElement::Elision(_) => self.emit(opcode::OPCODE_ARRAY_HOLE),
+        ast::ArrayExpressionElement::SpreadElement(spread) => {
+          self.generate_expression(&spread.argument);
+          self.mark_position(spread.span);
+          self.emit(opcode::OPCODE_ARRAY_SPREAD);
+        }
+        ast::ArrayExpressionElement::Expression(expression) => {
+          self.generate_expression(expression);
+          self.emit(opcode::OPCODE_ARRAY_PUSH);
+        }
+      }
+    }
+  }
+
+  fn generate_object_property(&mut self, property: &'a ast::ObjectProperty<'a>) {
+    let name = if property.computed {
+      None
+    } else {
+      property.key.static_name()
+    };
+    if let ast::PropertyKind::Get | ast::PropertyKind::Set = property.kind {
+      let (accessor_opcode, prefix) = match property.kind {
+        ast::PropertyKind::Get => (opcode::OPCODE_DEFINE_GETTER, "get"),
+        _ => (opcode::OPCODE_DEFINE_SETTER, "set"),
+      };
+      self.generate_property_key(&property.key, name.as_deref());
+      let name = format!("{} {}", prefix, name.as_deref().unwrap_or_default());
+      self.generate_object_method(&property.value, name.trim_end(), 1);
+      self.emit(accessor_opcode);
+      return;
+    }
+    let Some(name) = name else {
+      self.generate_property_key(&property.key, None);
+      if property.method {
+        self.generate_object_method(&property.value, "", 1);
+      } else {
+        self.generate_expression(&property.value);
+      }
+      self.mark_position(property.span);
+      self.emit(opcode::OPCODE_DEFINE_COMPUTED_PROPERTY);
+      return;
+    };
+    // `__proto__: value` sets the prototype, unless it is a shorthand or a method
+    if name == "__proto__" && !property.shorthand && !property.method {
+      self.generate_expression(&property.value);
+      self.emit(opcode::OPCODE_SET_PROTOTYPE);
+      return;
+    }
+    if property.method {
+      self.generate_object_method(&property.value, &name, 0);
+    } else {
+      self.generate_named_expression(&property.value, &name);
+    }
+    let index = self.get_string_constant_index(&name);
+    self.emit(opcode::OPCODE_DEFINE_PROPERTY);
+    self.emit(index);
+  }
+
+  // 15.4.4 Runtime Semantics: MethodDefinitionEvaluation, the object `depth` slots below is the home object.
+  fn generate_object_method(&mut self, value: &'a ast::Expression<'a>, name: &str, depth: usize) {
+    let ast::Expression::FunctionExpression(function) = value else {
+      return self.generate_named_expression(value, name);
+    };
+    let code = self.compile_function(name, FunctionKind::Method, function);
+    self.emit_with_operand(opcode::OPCODE_PICK, depth);
+    self.emit_method(code);
+  }
+
+  // the key as a value on the stack, computed keys are converted when the property is defined.
+  fn generate_property_key(&mut self, key: &'a ast::PropertyKey<'a>, name: Option<&str>) {
+    if let Some(name) = name {
+      let index = self.get_string_constant_index(name);
+      self.emit(opcode::OPCODE_CONST);
+      self.emit(index);
+      return;
+    }
+    match key {
+      ast::PropertyKey::Expression(expression) => self.generate_expression(expression),
+      _ => panic!("[Compiler] Private names are only valid in classes"),
+    }
+  }
+
+  fn compile_function(&mut self, name: &str, kind: FunctionKind, function: &'a ast::Function<'a>) -> CompilerReturn {
+    let Some(body) = &function.body else {
+      self.report_syntax_error(format!("Function implementation is missing for '{}'", name));
+      return self.compile_code(name, kind, function.span, |_| {});
+    };
+    self.compile_function_code(name, kind, function.span, &function.params, body, false)
+  }
+
+  fn compile_function_code(
+    &mut self,
+    name: &str,
+    kind: FunctionKind,
+    span: Span,
+    params: &'a ast::FormalParameters<'a>,
+    body: &'a ast::FunctionBody<'a>,
+    is_expression: bool,
+  ) -> CompilerReturn {
+    self.compile_code(name, kind, span, |compiler| {
+      compiler.generate_function_body(params, body, is_expression)
+    })
+  }
+
+  // code running in a frame of its own, with the variables the resolver found it captures at `span`.
+  fn compile_code(
+    &mut self,
+    name: &str,
+    kind: FunctionKind,
+    span: Span,
+    generate: impl FnOnce(&mut Compiler<'a, '_>),
+  ) -> CompilerReturn {
+    let upvalues = self
+      .ctx
+      .get_function(span)
+      .map(|function| function.captures.clone())
+      .unwrap_or_default();
+    let mut compiler = Compiler::new(name.to_string(), self.source, self.ctx);
+    compiler.kind = kind;
+    compiler.upvalues = upvalues;
+    compiler.is_strict = self.is_strict;
+    generate(&mut compiler);
+    self.errors.append(&mut compiler.errors);
+    compiler.finish()
+  }
+
+  // the callee and the arguments are already in the first slots of the frame, the rest of
+  // the function scope is reserved on entry.
+  fn generate_function_body(
+    &mut self,
+    params: &'a ast::FormalParameters<'a>,
+    body: &'a ast::FunctionBody<'a>,
+    is_expression: bool,
+  ) {
+    // 11.2.2 Strict Mode Code, a function with a Use Strict Directive in its body
+    if body
+      .directives
+      .iter()
+      .any(|directive| directive.directive == "use strict")
+    {
+      self.is_strict = true;
+    }
+    self.arity = params.items.len();
+    self.length = params
+      .items
+      .iter()
+      .take_while(|param| !matches!(param.pattern.kind, ast::BindingPatternKind::AssignmentPattern(_)))
+      .count();
+    self.has_rest_parameter = params.rest.is_some();
+    let slots = 1 + self.arity + usize::from(self.has_rest_parameter);
+    self.locals = slots + self.emit_reserve(body.span);
+    if !is_simple_parameter_list(params) {
+      self.generate_parameter_bindings(params);
+    }
+    if self.kind.is_class_constructor() {
+      let properties = params
+        .items
+        .iter()
+        .filter(|param| param.accessibility.is_some() || param.readonly);
+      self.parameter_properties = properties.collect();
+      // a derived constructor has no this value before super() returns
+      if self.kind == FunctionKind::BaseConstructor {
+        self.emit_parameter_properties();
+      }
+    }
+    self.generate_function_declarations(&body.statements);
+    if is_expression {
+      if let Some(ast::Statement::ExpressionStatement(stmt)) = body.statements.first() {
+        self.generate_expression(&stmt.expression);
+        self.emit(opcode::OPCODE_RETURN);
+        return;
+      }
+    }
+    for statement in &body.statements {
+      self.generate_statement(statement);
+    }
+    self.emit_constant(Value::create_undefined_value());
+    self.emit(opcode::OPCODE_RETURN);
+  }
+
+  /*
+  10.2.11 FunctionDeclarationInstantiation ( func, argumentsList )
+    21. For each String paramName of parameterNames, do
+      b. If alreadyDeclared is false, then
+        i. Perform ! env.CreateMutableBinding(paramName, false).
+    28. Else,
+      a. Perform ? IteratorBindingInitialization of formals with arguments iteratorRecord and env.
+  the arguments are taken out of their slots before the names are put in their temporal dead zone, then every
+    parameter is bound in order, so a default only sees the parameters before it.
+  */
+  fn generate_parameter_bindings(&mut self, params: &'a ast::FormalParameters<'a>) {
+    let patterns: Vec<_> = params
+      .items
+      .iter()
+      .map(|param| &param.pattern)
+      .chain(params.rest.as_ref().map(|rest| &rest.argument))
+      .collect();
+    // the first argument ends on top of the stack
+    for index in (0..patterns.len()).rev() {
+      self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+      self.emit(1 + index);
+    }
+    let mut names = Vec::new();
+    params.bound_names(&mut |ident| names.push(ident.span));
+    let uninitialized = self.add_constant(Value::create_uninitialized_value());
+    for span in names {
+      self.emit(opcode::OPCODE_CONST);
+      self.emit(uninitialized);
+      self.emit_initialize(span);
+    }
+    for pattern in patterns {
+      self.generate_binding_pattern(pattern);
+    }
+  }
+
+  // TypeScript parameter properties, `constructor(public x)`, assign their parameter to the property of `this`.
+  fn emit_parameter_properties(&mut self) {
+    for param in self.parameter_properties.clone() {
+      let pattern = match &param.pattern.kind {
+        ast::BindingPatternKind::AssignmentPattern(assign) => &assign.left.kind,
+        pattern => pattern,
+      };
+      let ast::BindingPatternKind::BindingIdentifier(identifier) = pattern else {
+        let message = "A parameter property may not be declared using a binding pattern";
+        self.report_syntax_error(message.to_owned());
+        continue;
+      };
+      let reference = self
+        .ctx
+        .get_reference(identifier.span)
+        .cloned()
+        .expect("parameter to be resolved");
+      self.emit(opcode::OPCODE_THIS);
+      let (load_opcode, index) = self.get_load_operation(&reference);
+      self.emit_with_operand(load_opcode, index);
+      let index = self.get_string_constant_index(&identifier.name);
+      self.emit_with_operand(opcode::OPCODE_SET_PROP, index);
+      self.emit(opcode::OPCODE_POP);
+    }
+  }
+
+  fn generate_return_statement(&mut self, statement: &'a ast::ReturnStatement<'a>) {
+    match &statement.argument {
+      Some(argument) => self.generate_expression(argument),
+      None => self.emit_constant(Value::create_undefined_value()),
+    }
+    let suspended = self.emit_completion(0, true);
+    self.emit(opcode::OPCODE_RETURN);
+    self.resume_try(suspended);
+  }
+
+  fn generate_throw_statement(&mut self, statement: &'a ast::ThrowStatement<'a>) {
+    self.generate_expression(&statement.argument);
+    self.mark_position(statement.span);
+    self.emit(opcode::OPCODE_THROW);
+  }
+
+  // a throw in the try block lands in the catch clause, or in the `finally` block when there is
+  // none, and a throw in the catch clause lands in the `finally` block. there the thrown value is
+  // kept in the hidden slot and thrown again once the block is done. every other way out of the
+  // statement runs a copy of the `finally` block.
+  fn generate_try_statement(&mut self, statement: &'a ast::TryStatement<'a>) {
+    self.enter_scope(statement.span);
+    let level = self.scopes.len() - 1;
+    let depth = self.get_stack_depth();
+    let slot = self
+      .ctx
+      .get_scope(statement.span)
+      .and_then(|scope| scope.bindings.first())
+      .map_or(0, |binding| binding.slot);
+    let finalizer = statement.finalizer.as_deref();
+    self.begin_try(finalizer.map(Finalizer::Block), level, slot);
+    self.generate_block_statement(&statement.block);
+    let mut protected = self.end_try();
+    if let Some(finalizer) = finalizer {
+      self.generate_block_statement(finalizer);
+    }
+    let mut exit_addresses = vec![self.emit_jump(opcode::OPCODE_JUMP)];
+    if let Some(handler) = &statement.handler {
+      self.add_handlers(protected, depth);
+      self.begin_try(finalizer.map(Finalizer::Block), level, slot);
+      self.generate_catch_clause(handler);
+      protected = self.end_try();
+      if let Some(finalizer) = finalizer {
+        self.generate_block_statement(finalizer);
+        exit_addresses.push(self.emit_jump(opcode::OPCODE_JUMP));
+      }
+    }
+    if let Some(finalizer) = finalizer {
+      self.add_handlers(protected, depth);
+      self.emit(opcode::OPCODE_SET_LOCAL_SCOPE);
+      self.emit(slot);
+      self.emit(opcode::OPCODE_POP);
+      self.generate_block_statement(finalizer);
+      self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+      self.emit(slot);
+      self.emit(opcode::OPCODE_THROW);
+    }
+    for address in exit_addresses {
+      self.patch_jump(address);
+    }
+    self.exit_scope();
+  }
+
+  // the thrown value is left on top of the stack, in the slot of the parameter.
+  fn generate_catch_clause(&mut self, clause: &'a ast::CatchClause<'a>) {
+    let Some(param) = &clause.param else {
+      self.emit(opcode::OPCODE_POP);
+      self.generate_block_statement(&clause.body);
+      return;
+    };
+    let slot = self.get_stack_depth();
+    let reserved = self.emit_reserve(clause.span);
+    self.scopes.push(1 + reserved);
+    if !param.pattern.kind.is_binding_identifier() {
+      self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+      self.emit(slot);
+      self.generate_binding_pattern(&param.pattern);
+    }
+    self.generate_block_statement(&clause.body);
+    self.exit_scope();
+  }
+
+  fn begin_try(&mut self, finalizer: Option<Finalizer<'a>>, level: usize, slot: usize) {
+    self.try_contexts.push(TryContext {
+      finalizer,
+      level,
+      slot,
+      jump_targets: self.jump_targets.len(),
+      ranges: Vec::new(),
+      start: Some(self.code.len()),
+    });
+  }
+
+  // returns the code the statement protected.
+  fn end_try(&mut self) -> Vec<(usize, usize)> {
+    self.suspend_try(self.try_contexts.len() - 1);
+    let context = self.try_contexts.pop().expect("try statement to be open");
+    context.ranges
+  }
+
+  fn suspend_try(&mut self, index: usize) {
+    let context = &mut self.try_contexts[index];
+    if let Some(start) = context.start.take() {
+      context.ranges.push((start, self.code.len()));
+    }
+  }
+
+  fn resume_try(&mut self, suspended: Vec<usize>) {
+    for index in suspended {
+      self.try_contexts[index].start = Some(self.code.len());
+    }
   }
 
-  pub fn compile(program: &Program, ctx: &'ctx mut Context) -> CompilerReturn {
-    let mut compiler = Compiler::new("main".to_string(), ctx);
-    compiler.generate_program(program);
-    CompilerReturn { name: compiler.name, code: compiler.code, constants: compiler.constants }
+  // the handler starts at the next instruction.
+  fn add_handlers(&mut self, ranges: Vec<(usize, usize)>, depth: usize) {
+    let target = self.code.len();
+    for (start, end) in ranges {
+      if start < end {
+        self.handlers.push(Handler { start, end, target, depth });
+      }
+    }
   }
 
-  fn generate_program(&mut self, program: &Program) {
-    for statement in &program.body {
-      self.generate_statement(statement);
+  // leaves the scopes from `scope` on and runs the `finally` blocks of the try statements left on
+  // the way, innermost first. a pending return value waits in the hidden slot of each of them
+  // while its block runs. the iterators of the for-of loops left are closed in between. the
+  // protection of those statements is suspended until the jump out is emitted, then the caller
+  // resumes it.
+  fn emit_completion(&mut self, scope: usize, has_value: bool) -> Vec<usize> {
+    let mut current = self.scopes.len();
+    let mut suspended = Vec::new();
+    for index in (0..self.try_contexts.len()).rev() {
+      let context = &self.try_contexts[index];
+      if context.level < scope {
+        break;
+      }
+      let (level, slot, finalizer) = (context.level, context.slot, context.finalizer);
+      self.suspend_try(index);
+      suspended.push(index);
+      let finalizer = match finalizer {
+        Some(Finalizer::Block(finalizer)) => finalizer,
+        Some(Finalizer::Iterator) => {
+          self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+          self.emit(slot);
+          self.emit(opcode::OPCODE_ITERATOR_CLOSE);
+          continue;
+        }
+        None => continue,
+      };
+      if has_value {
+        self.emit(opcode::OPCODE_SET_LOCAL_SCOPE);
+        self.emit(slot);
+        self.emit(opcode::OPCODE_POP);
+      }
+      self.emit_scope_unwind(level + 1, current);
+      current = level + 1;
+      self.generate_finalizer(index, finalizer);
+      if has_value {
+        self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+        self.emit(slot);
+      }
     }
-    self.emit(opcode::OPCODE_HALF);
+    if !has_value {
+      self.emit_scope_unwind(scope, current);
+    }
+    suspended
   }
 
-  fn generate_statement(&mut self, statement: &ast::Statement) {
-    match statement {
-      ast::Statement::ExpressionStatement(stmt) => self.generate_expression(&stmt.expression),
-      ast::Statement::Declaration(decl) => self.generate_declaration(decl),
-      ast::Statement::IfStatement(stmt) => self.generate_if_statement(stmt),
-      ast::Statement::EmptyStatement(_) => self.generate_empty_statement(),
-      ast::Statement::BlockStatement(stmt) => self.generate_block_statement(stmt),
-      _ => panic!("Unknown statement"),
+  // compiles a copy of the `finally` block of an open try statement as if it ran right where
+  // the statement is: the try statements, scopes and jump targets inside it are hidden meanwhile.
+  fn generate_finalizer(&mut self, index: usize, finalizer: &'a ast::BlockStatement<'a>) {
+    let (level, jump_targets) = (self.try_contexts[index].level, self.try_contexts[index].jump_targets);
+    let try_contexts = self.try_contexts.split_off(index);
+    let scopes = self.scopes.split_off(level + 1);
+    let targets = self.jump_targets.split_off(jump_targets);
+    self.generate_block_statement(finalizer);
+    self.try_contexts.extend(try_contexts);
+    self.scopes.extend(scopes);
+    self.jump_targets.extend(targets);
+  }
+
+  // a call through a member expression passes the object as the receiver, it is kept below
+  // the callee.
+  fn generate_call_expression(&mut self, call: &'a ast::CallExpression<'a>) {
+    if let ast::Expression::Super(_) = &call.callee {
+      return self.generate_super_call(call);
     }
+    let call_opcode = self.generate_callee(&call.callee);
+    if call.optional {
+      // the receiver of a method is dropped with the function
+      self.emit_optional_check(if call_opcode == opcode::OPCODE_CALL_METHOD {
+        2
+      } else {
+        1
+      });
+    }
+    let spread_opcode = match call_opcode {
+      opcode::OPCODE_CALL_METHOD => opcode::OPCODE_CALL_METHOD_SPREAD,
+      _ => opcode::OPCODE_CALL_SPREAD,
+    };
+    self.generate_arguments(&call.arguments, call.span, call_opcode, spread_opcode);
   }
 
-  fn generate_expression(&mut self, expression: &ast::Expression) {
-    match expression {
-      ast::Expression::NumericLiteral(value) => self.generate_numeric_literal(value),
-      ast::Expression::BooleanLiteral(value) => self.generate_boolean_literal(value),
-      ast::Expression::StringLiteral(literal) => self.generate_string_literal(literal),
-      ast::Expression::BinaryExpression(binary) => self.generate_binary_expression(binary),
-      ast::Expression::Identifier(identifier) => self.generate_identifier(identifier),
-      ast::Expression::AssignmentExpression(assignment) => self.generate_assignment_expression(assignment),
-      _ => panic!("Unknown expression"),
+  /*
+  13.3.8.1 Runtime Semantics: ArgumentListEvaluation
+    ArgumentList : ArgumentList , ... AssignmentExpression
+      1. Let precedingArgs be ? ArgumentListEvaluation of ArgumentList.
+      2. Let spreadRef be ? Evaluation of AssignmentExpression.
+      3. Let iteratorRecord be ? GetIterator(? GetValue(spreadRef), sync).
+      4. Repeat,
+        a. Let next be ? IteratorStepValue(iteratorRecord).
+        b. If next is done, return precedingArgs.
+        c. Set precedingArgs to the list-concatenation of precedingArgs and « next ».
+  The arguments are pushed one by one when their number is known, a spread collects them into an array like
+    an array literal and the call takes its elements.
+  */
+  fn generate_arguments(
+    &mut self,
+    arguments: &'a [ast::Argument<'a>],
+    span: Span,
+    call_opcode: usize,
+    spread_opcode: usize,
+  ) {
+    if !arguments
+      .iter()
+      .any(|argument| matches!(argument, ast::Argument::SpreadElement(_)))
+    {
+      for argument in arguments {
+        if let ast::Argument::Expression(expression) = argument {
+          self.generate_expression(expression);
+        }
+      }
+      self.mark_position(span);
+      self.emit(call_opcode);
+      self.emit(arguments.len());
+      return;
     }
+    self.generate_argument_array(arguments);
+    self.mark_position(span);
+    self.emit(spread_opcode);
   }
 
-  fn generate_block_statement(&mut self, statement: &ast::BlockStatement) {
-    self.enter_scope();
-    for stmt in &statement.body {
-      self.generate_statement(stmt);
+  // the arguments collected into an array, the elements of the spread arguments included.
+  fn generate_argument_array(&mut self, arguments: &'a [ast::Argument<'a>]) {
+    self.emit(opcode::OPCODE_ARRAY);
+    for argument in arguments {
+      match argument {
+        ast::Argument::Expression(expression) => {
+          self.generate_expression(expression);
+          self.emit(opcode::OPCODE_ARRAY_PUSH);
+        }
+        ast::Argument::SpreadElement(spread) => {
+          self.generate_expression(&spread.argument);
+          self.mark_position(spread.span);
+          self.emit(opcode::OPCODE_ARRAY_SPREAD);
+        }
+      }
     }
-    self.emit(opcode::OPCODE_POP);
-    self.exit_scope();
   }
 
-  fn generate_assignment_expression(&mut self, assignment: &ast::AssignmentExpression) {
-    match assignment.operator.as_str() {
-      "=" => self.generate_assignment_target(&assignment.left, &assignment.right),
-      _ => panic!("{} is not supported", assignment.operator.as_str()),
+  /*
+  13.3.7.1 Runtime Semantics: Evaluation, SuperCall : super Arguments
+  The arguments are collected into an array, so spread arguments are fine here. The this value exists once the
+    call returns, so the parameter properties of a derived constructor are assigned right after it.
+  */
+  fn generate_super_call(&mut self, call: &'a ast::CallExpression<'a>) {
+    self.generate_argument_array(&call.arguments);
+    self.mark_position(call.span);
+    self.emit(opcode::OPCODE_SUPER_CALL);
+    if self.kind == FunctionKind::DerivedConstructor {
+      self.emit_parameter_properties();
     }
   }
 
-  fn generate_assignment_target(&mut self, target: &AssignmentTarget, init: &ast::Expression) {
-    if target.is_identifier() {
-      let variable_idx = self.get_assignment_target(target);
-      self.generate_expression(init);
-      self.emit(opcode::OPCODE_SET_GLOBAL_SCOPE);
-      self.emit(variable_idx);
-      return;
+  // the function being called and, for a method, its receiver below it. returns the call instruction to use.
+  // parentheses keep the receiver, `(o.f)()` calls `f` on `o` like `o.f()` does.
+  fn generate_callee(&mut self, callee: &'a ast::Expression<'a>) -> usize {
+    match callee {
+      ast::Expression::ParenthesizedExpression(paren) => self.generate_callee(&paren.expression),
+      ast::Expression::MemberExpression(member) => {
+        self.generate_method_callee(member);
+        opcode::OPCODE_CALL_METHOD
+      }
+      ast::Expression::ChainExpression(chain) => self.generate_chain_callee(chain),
+      callee => {
+        self.generate_expression(callee);
+        opcode::OPCODE_CALL
+      }
     }
-    panic!("Unknown left assignment expression");
   }
 
-  fn get_assignment_target(&mut self, identifier: &ast::AssignmentTarget) -> usize {
-    match identifier {
-      ast::AssignmentTarget::SimpleAssignmentTarget(assign) => self.get_simple_assignment_target(assign),
-      ast::AssignmentTarget::AssignmentTargetPattern(_) => panic!("AssignmentTargetPattern is not supported"),
+  // `(o?.f)()` ends the chain before the call: a short-circuit leaves undefined as both the receiver and the
+  // function, so the call throws.
+  fn generate_chain_callee(&mut self, chain: &'a ast::ChainExpression<'a>) -> usize {
+    let ast::ChainElement::MemberExpression(member) = &chain.expression else {
+      self.generate_chain_expression(chain);
+      return opcode::OPCODE_CALL;
+    };
+    self.chains.push(Vec::new());
+    self.generate_method_callee(member);
+    let end = self.emit_jump(opcode::OPCODE_JUMP);
+    for jump in self.chains.pop().unwrap_or_default() {
+      self.patch_jump(jump);
     }
+    self.emit_constant(Value::create_undefined_value());
+    self.emit_constant(Value::create_undefined_value());
+    self.patch_jump(end);
+    opcode::OPCODE_CALL_METHOD
   }
 
-  fn get_simple_assignment_target(&mut self, target: &ast::SimpleAssignmentTarget) -> usize {
-    match target {
-      ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(id) => {
-        if let Some(kind) = self.ctx.get_kind_variable(&id.name) {
-          if kind == Kind::Const {
-            panic!("[Compiler] TypeError: '{}' is a read-only variable", id.name);
-          }
+  // the object of a member expression and, above it, the method read from it.
+  fn generate_method_callee(&mut self, member: &'a ast::MemberExpression<'a>) {
+    self.generate_expression(self.get_member_object(member));
+    if member.optional() {
+      self.emit_optional_check(1);
+    }
+    self.emit(opcode::OPCODE_DUP);
+    let key = self.generate_member_key(member);
+    self.mark_position(member.span());
+    self.emit_member_get(key);
+  }
+
+  /*
+  13.2.8.6 Runtime Semantics: Evaluation, TemplateLiteral
+    SubstitutionTemplate : TemplateHead Expression TemplateSpans
+      1. Let head be the TV of TemplateHead as defined in 12.9.6.
+      2. Let subRef be ? Evaluation of Expression.
+      3. Let sub be ? GetValue(subRef).
+      4. Let middle be ? ToString(sub).
+      5. Let tail be ? Evaluation of TemplateSpans.
+      6. Return the string-concatenation of head, middle, and tail.
+  Every substitution is converted with ToString before it is added, so `+` only ever sees two strings.
+  */
+  fn generate_template_literal(&mut self, literal: &'a ast::TemplateLiteral<'a>) {
+    let mut quasis = literal
+      .quasis
+      .iter()
+      .map(|quasi| get_cooked_units(quasi).unwrap_or_default());
+    let head = self.get_units_constant_index(quasis.next().unwrap_or_default());
+    self.emit(opcode::OPCODE_CONST);
+    self.emit(head);
+    for (expression, quasi) in literal.expressions.iter().zip(quasis) {
+      self.generate_expression(expression);
+      self.mark_position(expression.span());
+      self.emit(opcode::OPCODE_TO_STRING);
+      self.emit(opcode::OPCODE_ADD);
+      if !quasi.is_empty() {
+        let index = self.get_units_constant_index(quasi);
+        self.emit(opcode::OPCODE_CONST);
+        self.emit(index);
+        self.emit(opcode::OPCODE_ADD);
+      }
+    }
+  }
+
+  /*
+  13.3.11.1 Runtime Semantics: Evaluation, MemberExpression : MemberExpression TemplateLiteral
+    1. Let tagRef be ? Evaluation of MemberExpression.
+    2. Let tagFunc be ? GetValue(tagRef).
+    3. Let thisCall be this MemberExpression.
+    4. Let tailCall be IsInTailPosition(thisCall).
+    5. Return ? EvaluateCall(tagFunc, tagRef, TemplateLiteral, tailCall).
+  The first argument is the template object of the site, followed by the values of the substitutions.
+  */
+  fn generate_tagged_template(&mut self, tagged: &'a ast::TaggedTemplateExpression<'a>) {
+    let call_opcode = self.generate_callee(&tagged.tag);
+    let template = self.create_template_object(&tagged.quasi);
+    self.emit_constant(template);
+    for expression in &tagged.quasi.expressions {
+      self.generate_expression(expression);
+    }
+    self.mark_position(tagged.span);
+    self.emit(call_opcode);
+    self.emit(tagged.quasi.expressions.len() + 1);
+  }
+
+  /*
+  13.2.8.4 GetTemplateObject ( templateLiteral )
+    2. Let templateRegistry be realm.[[TemplateMap]].
+    3. For each element e of templateRegistry, do
+      a. If e.[[Site]] is the same Parse Node as templateLiteral, then
+        i. Return e.[[Array]].
+    4. Let rawStrings be the TemplateStrings of templateLiteral with argument true.
+    5. Let cookedStrings be the TemplateStrings of templateLiteral with argument false.
+    8. Let template be ! ArrayCreate(count).
+    9. Let rawObj be ! ArrayCreate(count).
+    11. Repeat, while index < count,
+      c. Let cookedValue be cookedStrings[index].
+      d. Perform ! DefinePropertyOrThrow(template, prop, PropertyDescriptor { [[Value]]: cookedValue,
+        [[Writable]]: false, [[Enumerable]]: true, [[Configurable]]: false }).
+      e. Let rawValue be the String value rawStrings[index].
+      f. Perform ! DefinePropertyOrThrow(rawObj, prop, PropertyDescriptor { [[Value]]: rawValue,
+        [[Writable]]: false, [[Enumerable]]: true, [[Configurable]]: false }).
+    12. Perform ! SetIntegrityLevel(rawObj, frozen).
+    13. Perform ! DefinePropertyOrThrow(template, "raw", PropertyDescriptor { [[Value]]: rawObj, [[Writable]]: false,
+      [[Enumerable]]: false, [[Configurable]]: false }).
+    14. Perform ! SetIntegrityLevel(template, frozen).
+  The object is made once, when the site is compiled, and kept in the constants of the code it belongs to, so every
+    evaluation of the site gets the same one. A cooked string is undefined when its escape sequence is invalid.
+  */
+  fn create_template_object(&mut self, literal: &'a ast::TemplateLiteral<'a>) -> Value {
+    let prototype = self.ctx.get_intrinsic("%Array.prototype%").cloned();
+    let template = ObjectValue::new_array(prototype.clone());
+    let raw = ObjectValue::new_array(prototype);
+    for (index, quasi) in literal.quasis.iter().enumerate() {
+      let cooked = match get_cooked_units(quasi) {
+        Some(cooked) => Value::String(StringValue::from_utf16(cooked)),
+        None => Value::create_undefined_value(),
+      };
+      let key = PropertyKey::from(index.to_string());
+      template.define_own_property(
+        key.clone(),
+        PropertyDescriptor::new_data_with(cooked, false, true, false),
+      );
+      let raw_value = Value::create_string_value(quasi.value.raw.to_string());
+      raw.define_own_property(key, PropertyDescriptor::new_data_with(raw_value, false, true, false));
+    }
+    raw.freeze();
+    let descriptor = PropertyDescriptor::new_data_with(Value::Object(raw), false, false, false);
+    template.define_own_property(PropertyKey::from("raw"), descriptor);
+    template.freeze();
+    Value::Object(template)
+  }
+
+  fn generate_new_expression(&mut self, new: &'a ast::NewExpression<'a>) {
+    self.generate_expression(&new.callee);
+    self.generate_arguments(&new.arguments, new.span, opcode::OPCODE_NEW, opcode::OPCODE_NEW_SPREAD);
+  }
+
+  fn generate_member_expression(&mut self, member: &'a ast::MemberExpression<'a>) {
+    let key = self.generate_member_base(member);
+    self.mark_position(member.span());
+    self.emit_member_get(key);
+  }
+
+  // the object of a member expression followed by its key, see `generate_member_key`.
+  fn generate_member_base(&mut self, member: &'a ast::MemberExpression<'a>) -> MemberKey {
+    self.generate_expression(self.get_member_object(member));
+    if member.optional() {
+      self.emit_optional_check(1);
+    }
+    self.generate_member_key(member)
+  }
+
+  fn get_member_object(&self, member: &'a ast::MemberExpression<'a>) -> &'a ast::Expression<'a> {
+    match member {
+      ast::MemberExpression::StaticMemberExpression(member) => &member.object,
+      ast::MemberExpression::ComputedMemberExpression(member) => &member.object,
+      ast::MemberExpression::PrivateFieldExpression(member) => &member.object,
+    }
+  }
+
+  // static keys are kept in the constants and given to the instruction, computed keys are
+  // evaluated onto the stack and converted to property keys when the instruction runs.
+  // a `#name` pushes the Private Name it refers to.
+  fn generate_member_key(&mut self, member: &'a ast::MemberExpression<'a>) -> MemberKey {
+    let is_super = matches!(self.get_member_object(member), ast::Expression::Super(_));
+    match member {
+      ast::MemberExpression::StaticMemberExpression(member) => {
+        let index = self.get_string_constant_index(&member.property.name);
+        if is_super {
+          MemberKey::SuperStatic(index)
+        } else {
+          MemberKey::Static(index)
         }
-        self.get_variable_index(id)
       }
-      _ => panic!("Unknown left assignment expression"),
+      ast::MemberExpression::ComputedMemberExpression(member) => {
+        self.generate_expression(&member.expression);
+        if is_super {
+          MemberKey::SuperComputed
+        } else {
+          MemberKey::Computed
+        }
+      }
+      ast::MemberExpression::PrivateFieldExpression(member) => {
+        self.generate_private_name(&member.field);
+        MemberKey::Private
+      }
     }
   }
 
-  fn generate_declaration(&mut self, declaration: &ast::Declaration) {
-    match declaration {
-      ast::Declaration::VariableDeclaration(decl) => self.generate_variable_declaration(decl),
-      _ => panic!("Unknown declaration"),
+  fn emit_member_get(&mut self, key: MemberKey) {
+    match key {
+      MemberKey::Static(index) => self.emit_with_operand(opcode::OPCODE_GET_PROP, index),
+      MemberKey::Computed => self.emit(opcode::OPCODE_GET_COMPUTED_PROP),
+      MemberKey::Private => self.emit(opcode::OPCODE_GET_PRIVATE),
+      MemberKey::SuperStatic(index) => self.emit_with_operand(opcode::OPCODE_GET_SUPER_PROP, index),
+      MemberKey::SuperComputed => self.emit(opcode::OPCODE_GET_SUPER_COMPUTED_PROP),
+    }
+  }
+
+  fn emit_member_set(&mut self, key: MemberKey) {
+    match key {
+      MemberKey::Static(index) => self.emit_with_operand(opcode::OPCODE_SET_PROP, index),
+      MemberKey::Computed => self.emit(opcode::OPCODE_SET_COMPUTED_PROP),
+      MemberKey::Private => self.emit(opcode::OPCODE_SET_PRIVATE),
+      MemberKey::SuperStatic(index) => self.emit_with_operand(opcode::OPCODE_SET_SUPER_PROP, index),
+      MemberKey::SuperComputed => self.emit(opcode::OPCODE_SET_SUPER_COMPUTED_PROP),
+    }
+  }
+
+  // the Private Name a `#name` refers to, held by a binding of the class declaring it.
+  fn generate_private_name(&mut self, identifier: &'a ast::PrivateIdentifier<'a>) {
+    let reference = self
+      .ctx
+      .get_reference(identifier.span)
+      .cloned()
+      .expect("private name to be resolved");
+    let (load_opcode, index) = self.get_load_operation(&reference);
+    self.emit(load_opcode);
+    self.emit(index);
+  }
+
+  fn generate_unary_expression(&mut self, unary: &'a ast::UnaryExpression<'a>) {
+    let operation = match unary.operator {
+      UnaryOperator::Delete => return self.generate_delete_expression(unary),
+      UnaryOperator::Typeof => return self.generate_typeof_expression(unary),
+      UnaryOperator::Void => {
+        self.generate_expression(&unary.argument);
+        self.emit(opcode::OPCODE_POP);
+        self.emit_constant(Value::create_undefined_value());
+        return;
+      }
+      UnaryOperator::UnaryNegation => opcode::OPCODE_NEG,
+      UnaryOperator::UnaryPlus => opcode::OPCODE_TO_NUMBER,
+      UnaryOperator::LogicalNot => opcode::OPCODE_NOT,
+      UnaryOperator::BitwiseNot => opcode::OPCODE_BIT_NOT,
+    };
+    self.generate_expression(&unary.argument);
+    self.mark_position(unary.span);
+    self.emit(operation);
+  }
+
+  /*
+  13.5.3.1 Runtime Semantics: Evaluation, typeof UnaryExpression
+    2. If val is a Reference Record, then
+      a. If IsUnresolvableReference(val) is true, return "undefined".
+  */
+  fn generate_typeof_expression(&mut self, unary: &'a ast::UnaryExpression<'a>) {
+    if let ast::Expression::Identifier(identifier) = &unary.argument {
+      if self.get_variable_reference(identifier).is_none() {
+        self.mark_position(identifier.span);
+        self.emit_global_name(opcode::OPCODE_TYPEOF_GLOBAL_NAME, &identifier.name);
+        return;
+      }
+    }
+    self.generate_expression(&unary.argument);
+    self.emit(opcode::OPCODE_TYPEOF);
+  }
+
+  /*
+  13.5.1.2 Runtime Semantics: Evaluation, delete UnaryExpression
+    2. If ref is not a Reference Record, return true.
+    3. If IsUnresolvableReference(ref) is true, then
+      a. Assert: ref.[[Strict]] is false.
+      b. Return true.
+    6. Else,
+      c. Return ? base.DeleteBinding(ref.[[ReferencedName]]).
+  Deleting a binding is an early error in strict mode code. A `var` or a name no declaration resolved to is a
+    property of the global object, deleting it deletes the property, the other bindings can not be deleted.
+  */
+  fn generate_delete_expression(&mut self, unary: &'a ast::UnaryExpression<'a>) {
+    if let ast::Expression::Identifier(identifier) = &unary.argument {
+      let is_object_binding = match self.get_variable_reference(identifier) {
+        Some(Reference::Global { index, .. }) => self.ctx.get_global_object_key(index).is_some(),
+        Some(_) => false,
+        None => true,
+      };
+      match is_object_binding {
+        true => self.emit_global_name(opcode::OPCODE_DELETE_GLOBAL_NAME, &identifier.name),
+        false => self.emit_constant(Value::create_boolean_value(false)),
+      }
+      return;
+    }
+    let ast::Expression::MemberExpression(member) = &unary.argument else {
+      self.generate_expression(&unary.argument);
+      self.emit(opcode::OPCODE_POP);
+      self.emit_constant(Value::create_boolean_value(true));
+      return;
+    };
+    // 13.5.1.2 step 5.a: If IsSuperReference(ref) is true, throw a ReferenceError exception.
+    if let ast::Expression::Super(_) = self.get_member_object(member) {
+      self.mark_position(unary.span);
+      self.emit_throw_error(opcode::OPCODE_THROW_REFERENCE_ERROR, "Unsupported reference to 'super'");
+      return;
+    }
+    if let MemberKey::Static(index) = self.generate_member_base(member) {
+      self.emit(opcode::OPCODE_CONST);
+      self.emit(index);
     }
+    self.mark_position(unary.span);
+    self.emit(opcode::OPCODE_DELETE_PROP);
   }
 
-  fn generate_if_statement(&mut self, statement: &ast::IfStatement) {
+  fn generate_if_statement(&mut self, statement: &'a ast::IfStatement<'a>) {
     self.generate_expression(&statement.test);
-    self.emit(opcode::OPCODE_JUMP_IF_FALSE);
-    let jump_if_false_address = self.code.len();
-    self.emit(0);
+    let jump_if_false_address = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
     self.generate_statement(&statement.consequent);
-    self.emit(opcode::OPCODE_JUMP);
-    let jump_address = self.code.len();
-    self.emit(0);
-    self.code[jump_if_false_address] = self.code.len();
+    let jump_address = self.emit_jump(opcode::OPCODE_JUMP);
+    self.patch_jump(jump_if_false_address);
     if let Some(alternate) = &statement.alternate {
       self.generate_statement(alternate);
     }
-    self.code[jump_address] = self.code.len();
+    self.patch_jump(jump_address);
+  }
+
+  fn generate_while_statement(&mut self, statement: &'a ast::WhileStatement<'a>) {
+    self.begin_jump_target(true);
+    let loop_start = self.code.len();
+    self.generate_expression(&statement.test);
+    let exit_address = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
+    self.generate_statement(&statement.body);
+    self.emit_loop(loop_start);
+    self.patch_jump(exit_address);
+    self.end_jump_target(loop_start);
+  }
+
+  fn generate_do_while_statement(&mut self, statement: &'a ast::DoWhileStatement<'a>) {
+    self.begin_jump_target(true);
+    let loop_start = self.code.len();
+    self.generate_statement(&statement.body);
+    let continue_address = self.code.len();
+    self.generate_expression(&statement.test);
+    let exit_address = self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE);
+    self.emit_loop(loop_start);
+    self.patch_jump(exit_address);
+    self.end_jump_target(continue_address);
+  }
+
+  // the `let` bindings of the init live in a scope wrapping the whole loop, so the
+  // body and the update see them and they are released once the loop is done.
+  // when a closure captured one of them, the captured cells are closed before the
+  // update runs, so every iteration gets its own copy of the bindings.
+  fn generate_for_statement(&mut self, statement: &'a ast::ForStatement<'a>) {
+    self.enter_scope(statement.span);
+    self.begin_jump_target(true);
+    if let Some(init) = &statement.init {
+      self.generate_for_statement_init(init);
+    }
+    let loop_start = self.code.len();
+    let exit_address = statement.test.as_ref().map(|test| {
+      self.generate_expression(test);
+      self.emit_jump(opcode::OPCODE_JUMP_IF_FALSE)
+    });
+    self.generate_statement(&statement.body);
+    let continue_address = self.code.len();
+    if let Some(slot) = self.ctx.get_scope(statement.span).and_then(Scope::get_captured_slot) {
+      self.emit(opcode::OPCODE_CLOSE_UPVALUES);
+      self.emit(slot);
+    }
+    if let Some(update) = &statement.update {
+      self.generate_expression(update);
+      self.emit(opcode::OPCODE_POP);
+    }
+    self.emit_loop(loop_start);
+    if let Some(exit_address) = exit_address {
+      self.patch_jump(exit_address);
+    }
+    self.end_jump_target(continue_address);
+    self.exit_scope();
+  }
+
+  fn generate_for_statement_init(&mut self, init: &'a ast::ForStatementInit<'a>) {
+    match init {
+      ast::ForStatementInit::VariableDeclaration(decl) => self.generate_variable_declaration(decl),
+      ast::ForStatementInit::Expression(expression) => {
+        self.generate_expression(expression);
+        self.emit(opcode::OPCODE_POP);
+      }
+      ast::ForStatementInit::UsingDeclaration(_) => panic!("Using declaration is not supported"),
+    }
+  }
+
+  /*
+  14.7.5.5 Runtime Semantics: ForInOfLoopEvaluation
+    ForInOfStatement : for ( LeftHandSideExpression in Expression ) Statement
+      1. Let keyResult be ? ForIn/OfHeadEvaluation(« », Expression, enumerate).
+      2. Return ? ForIn/OfBodyEvaluation(LeftHandSideExpression, Statement, keyResult, enumerate, assignment, labelSet).
+  14.7.5.6 ForIn/OfHeadEvaluation ( uninitializedBoundNames, expr, iterationKind )
+    6. If iterationKind is enumerate, then
+      a. If exprValue is either undefined or null, then
+        i. Return Completion Record { [[Type]]: break, [[Value]]: empty, [[Target]]: empty }.
+      b. Let obj be ! ToObject(exprValue).
+      c. Let iterator be EnumerateObjectProperties(obj).
+  The keys are read through an iterator record like the values of a for-of loop, it never needs closing.
+  */
+  fn generate_for_in_statement(&mut self, statement: &'a ast::ForInStatement<'a>) {
+    let (left, right, body) = (&statement.left, &statement.right, &statement.body);
+    self.generate_for_each_statement(statement.span, left, right, body, opcode::OPCODE_FOR_IN_ITERATOR);
+  }
+
+  /*
+  14.7.5.7 ForIn/OfBodyEvaluation ( lhs, stmt, iteratorRecord, iterationKind, lhsKind, labelSet [ , iteratorKind ] )
+    6. Repeat,
+      a. Let nextResult be ? Call(iteratorRecord.[[NextMethod]], iteratorRecord.[[Iterator]]).
+      d. If done is true, return V.
+      l. Let result be Completion(Evaluation of stmt).
+      n. If LoopContinues(result, labelSet) is false, then
+        i. If iterationKind is enumerate, then
+          1. Return ? UpdateEmpty(result, V).
+        ii. Else,
+          2. Set status to Completion(UpdateEmpty(result, V)).
+          4. Return ? IteratorClose(iteratorRecord, status).
+  Every way out of the body but the iterator running out of values closes it: a throw goes through a handler,
+    a break lands on the close after the loop and the jumps leaving the loop further close it on the way, like
+    the `finally` block of a try statement.
+  */
+  fn generate_for_of_statement(&mut self, statement: &'a ast::ForOfStatement<'a>) {
+    let (left, right, body) = (&statement.left, &statement.right, &statement.body);
+    self.generate_for_each_statement(statement.span, left, right, body, opcode::OPCODE_GET_ITERATOR);
+  }
+
+  // the iterator record lives in the hidden slot of the loop scope, the bindings of the left side
+  // after it. like in a for statement, the captured cells are closed at the end of every iteration.
+  fn generate_for_each_statement(
+    &mut self,
+    span: Span,
+    left: &'a ast::ForStatementLeft<'a>,
+    right: &'a ast::Expression<'a>,
+    body: &'a ast::Statement<'a>,
+    iterator_opcode: usize,
+  ) {
+    let closes_iterator = iterator_opcode == opcode::OPCODE_GET_ITERATOR;
+    self.enter_scope(span);
+    let level = self.scopes.len() - 1;
+    let depth = self.get_stack_depth();
+    let slot = self
+      .ctx
+      .get_scope(span)
+      .and_then(|scope| scope.bindings.first())
+      .map_or(0, |binding| binding.slot);
+    self.generate_expression(right);
+    self.mark_position(right.span());
+    self.emit(iterator_opcode);
+    self.emit(opcode::OPCODE_SET_LOCAL_SCOPE);
+    self.emit(slot);
+    self.emit(opcode::OPCODE_POP);
+    self.begin_jump_target(true);
+    let loop_start = self.code.len();
+    self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+    self.emit(slot);
+    self.mark_position(right.span());
+    let exit_address = self.emit_jump(opcode::OPCODE_ITERATOR_STEP);
+    if closes_iterator {
+      self.begin_try(Some(Finalizer::Iterator), level, slot);
+    }
+    self.generate_for_each_left(left);
+    self.generate_statement(body);
+    let protected = if closes_iterator { self.end_try() } else { Vec::new() };
+    let continue_address = self.code.len();
+    if let Some(slot) = self.ctx.get_scope(span).and_then(Scope::get_captured_slot) {
+      self.emit(opcode::OPCODE_CLOSE_UPVALUES);
+      self.emit(slot);
+    }
+    self.emit_loop(loop_start);
+    self.patch_jump(exit_address);
+    self.end_jump_target(continue_address);
+    if closes_iterator {
+      self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+      self.emit(slot);
+      self.mark_position(span);
+      self.emit(opcode::OPCODE_ITERATOR_CLOSE);
+      let exit_address = self.emit_jump(opcode::OPCODE_JUMP);
+      // 7.4.11 IteratorClose, when the completion is a throw it wins over whatever closing does
+      self.add_handlers(protected, depth);
+      self.emit(opcode::OPCODE_LOAD_LOCAL_SCOPE);
+      self.emit(slot);
+      self.emit(opcode::OPCODE_ITERATOR_CLOSE_THROW);
+      self.emit(opcode::OPCODE_THROW);
+      self.patch_jump(exit_address);
+    }
+    self.exit_scope();
+  }
+
+  // stores the value on top of the stack into the left side of a for-in or for-of loop.
+  fn generate_for_each_left(&mut self, left: &'a ast::ForStatementLeft<'a>) {
+    match left {
+      ast::ForStatementLeft::VariableDeclaration(decl) => {
+        let declarator = decl
+          .declarations
+          .first()
+          .expect("loop declaration to have a declarator");
+        self.generate_binding_pattern(&declarator.id);
+      }
+      ast::ForStatementLeft::AssignmentTarget(target) => self.emit_value_store(PatternTarget::Assignment(target)),
+      ast::ForStatementLeft::UsingDeclaration(_) => panic!("Using declaration is not supported"),
+    }
+  }
+
+  // labels of a loop are handed to it through `pending_labels`, any other statement
+  // gets its own target so `break label` can leave it.
+  fn generate_labeled_statement(&mut self, statement: &'a ast::LabeledStatement<'a>) {
+    self.pending_labels.push(statement.label.name.to_string());
+    if statement.body.is_iteration_statement() || matches!(statement.body, ast::Statement::LabeledStatement(_)) {
+      self.generate_statement(&statement.body);
+      return;
+    }
+    self.begin_jump_target(false);
+    self.generate_statement(&statement.body);
+    let end_address = self.code.len();
+    self.end_jump_target(end_address);
+  }
+
+  fn generate_break_statement(&mut self, statement: &'a ast::BreakStatement<'a>) {
+    let label = statement.label.as_ref().map(|label| label.name.as_str());
+    let Some(index) = self.find_jump_target(label, false) else {
+      let message = match label {
+        Some(label) => format!("Undefined label '{}'", label),
+        None => "Illegal break statement".to_owned(),
+      };
+      return self.report_syntax_error(message);
+    };
+    let suspended = self.emit_completion(self.jump_targets[index].scope, false);
+    let address = self.emit_jump(opcode::OPCODE_JUMP);
+    self.jump_targets[index].break_addresses.push(address);
+    self.resume_try(suspended);
+  }
+
+  fn generate_continue_statement(&mut self, statement: &'a ast::ContinueStatement<'a>) {
+    let label = statement.label.as_ref().map(|label| label.name.as_str());
+    let Some(index) = self.find_jump_target(label, true) else {
+      let message = match label {
+        Some(label) => format!(
+          "Illegal continue statement: '{}' does not denote an iteration statement",
+          label
+        ),
+        None => "Illegal continue statement: no surrounding iteration statement".to_owned(),
+      };
+      return self.report_syntax_error(message);
+    };
+    let suspended = self.emit_completion(self.jump_targets[index].scope, false);
+    let address = self.emit_jump(opcode::OPCODE_JUMP);
+    self.jump_targets[index].continue_addresses.push(address);
+    self.resume_try(suspended);
+  }
+
+  fn begin_jump_target(&mut self, is_loop: bool) {
+    let labels = std::mem::take(&mut self.pending_labels);
+    let scope = self.scopes.len();
+    self.jump_targets.push(JumpTarget {
+      labels,
+      is_loop,
+      scope,
+      break_addresses: Vec::new(),
+      continue_addresses: Vec::new(),
+    });
+  }
+
+  // breaks land after the statement, continues on `continue_address`.
+  fn end_jump_target(&mut self, continue_address: usize) {
+    let target = self.jump_targets.pop().expect("jump target to be open");
+    for address in target.break_addresses {
+      self.patch_jump(address);
+    }
+    for address in target.continue_addresses {
+      self.code[address] = continue_address;
+    }
+  }
+
+  fn find_jump_target(&self, label: Option<&str>, is_continue: bool) -> Option<usize> {
+    let index = match label {
+      Some(label) => self
+        .jump_targets
+        .iter()
+        .rposition(|target| target.labels.iter().any(|name| name == label))?,
+      None => self.jump_targets.iter().rposition(|target| target.is_loop)?,
+    };
+    if is_continue && !self.jump_targets[index].is_loop {
+      return None;
+    }
+    Some(index)
   }
 
-  fn generate_variable_declaration(&mut self, declaration: &ast::VariableDeclaration) {
+  // releases the slots of the block scopes a jump leaves, the scopes from `from` up to `to`.
+  fn emit_scope_unwind(&mut self, from: usize, to: usize) {
+    let count: usize = self.scopes[from..to].iter().sum();
+    if count > 0 {
+      self.emit(opcode::OPCODE_SCOPE_EXIT);
+      self.emit(count);
+    }
+  }
+
+  fn generate_variable_declaration(&mut self, declaration: &'a ast::VariableDeclaration<'a>) {
     match declaration.kind {
       ast::VariableDeclarationKind::Let => self.handle_variable_declaration(declaration, Kind::Let),
       ast::VariableDeclarationKind::Const => self.handle_variable_declaration(declaration, Kind::Const),
-      _ => panic!("Unknown variable declaration kind"),
+      ast::VariableDeclarationKind::Var => self.handle_variable_declaration(declaration, Kind::Var),
     }
   }
 
-  fn handle_variable_declaration(&mut self, declaration: &ast::VariableDeclaration, kind: Kind) {
+  fn handle_variable_declaration(&mut self, declaration: &'a ast::VariableDeclaration<'a>, kind: Kind) {
     for declarator in &declaration.declarations {
       self.handle_variable_declarator(&declarator.id, &declarator.init, &kind);
     }
   }
 
-  fn handle_variable_declarator(&mut self, pattern: &ast::BindingPattern, init: &Option<ast::Expression>, kind: &Kind) {
+  fn handle_variable_declarator(
+    &mut self,
+    pattern: &'a ast::BindingPattern<'a>,
+    init: &'a Option<ast::Expression<'a>>,
+    kind: &Kind,
+  ) {
     match &pattern.kind {
       ast::BindingPatternKind::BindingIdentifier(ident) => {
         if kind == &Kind::Const && init.is_none() {
-          panic!(
-            "[Compiler] SyntaxError: 'const' declarations must be initialized at '{}'",
-            ident.name
-          );
+          let message = format!("'const' declarations must be initialized at '{}'", ident.name);
+          return self.report_syntax_error(message);
         }
-        let idx = self.define_variable(ident.name.as_str(), kind.clone());
-        self.initialize_declarator(init, idx);
-      }
-      ast::BindingPatternKind::ArrayPattern(elem) => {
-        for element in &elem.elements {
-          if let Some(element) = element {
-            self.handle_variable_declarator(element, init, kind);
-          }
+        // the binding was hoisted, so a `var` without initializer keeps its value
+        if kind == &Kind::Var && init.is_none() {
+          return;
         }
+        self.initialize_declarator(init, ident);
       }
-      ast::BindingPatternKind::ObjectPattern(objects) => {
-        for property in &objects.properties {
-          match &property.key {
-            ast::PropertyKey::Identifier(ident) => {
-              let idx = self.define_variable(ident.name.as_str(), kind.clone());
-              self.initialize_declarator(init, idx);
-            }
-            ast::PropertyKey::Expression(_) => panic!("Expression key not supported"),
-            _ => panic!("Unknown property key"),
-          }
-        }
+      _ => {
+        let Some(init) = init else {
+          return self.report_syntax_error("Missing initializer in destructuring declaration".to_owned());
+        };
+        self.generate_expression(init);
+        self.generate_binding_pattern(pattern);
       }
-      ast::BindingPatternKind::AssignmentPattern(_) => panic!("Assignment pattern not supported"),
     }
   }
 
-  fn initialize_declarator(&mut self, init: &Option<ast::Expression>, idx: usize) {
+  fn initialize_declarator(&mut self, init: &'a Option<ast::Expression<'a>>, ident: &'a ast::BindingIdentifier) {
     if let Some(init) = init {
-      self.generate_expression(init);
-    } else {
-      self.constants.push(Value::new_undefined())
-    }
-    if self.ctx.is_global_scope() {
-      self.emit(opcode::OPCODE_SET_GLOBAL_SCOPE);
+      self.generate_named_expression(init, &ident.name);
     } else {
-      self.emit(opcode::OPCODE_SET_LOCAL_SCOPE);
+      self.emit_constant(Value::create_undefined_value());
     }
-    self.emit(idx);
+    self.emit_initialize(ident.span);
   }
 
-  fn generate_empty_statement(&mut self) {
-    self.emit(opcode::OPCODE_HALF);
+  // stores the value on top of the stack into a declared binding, which may be a `const`.
+  fn emit_initialize(&mut self, span: Span) {
+    let reference = self
+      .ctx
+      .get_reference(span)
+      .cloned()
+      .expect("declaration to be resolved");
+    let (set_opcode, index) = self.get_set_operation(&reference);
+    self.emit(set_opcode);
+    self.emit(index);
+    self.emit(opcode::OPCODE_POP);
   }
 
-  fn generate_identifier(&mut self, identifier: &ast::IdentifierReference) {
-    if let Some(index) = self.ctx.get_variable_index(&identifier.name) {
+  fn generate_empty_statement(&mut self) {}
+
+  // names no declaration resolved to are looked up in the global object.
+  fn generate_identifier(&mut self, identifier: &'a ast::IdentifierReference<'a>) {
+    self.mark_position(identifier.span);
+    if let Some(reference) = self.ctx.get_reference(identifier.span).cloned() {
+      let (load_opcode, index) = self.get_load_operation(&reference);
+      self.emit(load_opcode);
+      self.emit(index);
+      if reference.is_lexical() {
+        self.emit_initialized_check(&identifier.name);
+      }
+      return;
+    }
+    if let Some(index) = self.ctx.get_global_variable_index(&identifier.name) {
       self.emit(opcode::OPCODE_LOAD_GLOBAL_SCOPE);
       self.emit(index);
       return;
     }
-    if !self.ctx.is_internal(&identifier.name) {
-      panic!("[Compiler] {} is not implemented yet", identifier.name);
+    self.emit_global_name(opcode::OPCODE_LOAD_GLOBAL_NAME, &identifier.name);
+  }
+
+  fn get_variable_reference(&mut self, identifier: &'a ast::IdentifierReference<'a>) -> Option<Reference> {
+    if let Some(reference) = self.ctx.get_reference(identifier.span) {
+      return Some(reference.clone());
+    }
+    let index = self.ctx.get_global_variable_index(&identifier.name)?;
+    let kind = self.ctx.get_global_variable(index)?.kind.clone();
+    Some(Reference::Global { index, kind })
+  }
+
+  fn get_load_operation(&self, reference: &Reference) -> (usize, usize) {
+    match reference {
+      Reference::Global { index, .. } => (opcode::OPCODE_LOAD_GLOBAL_SCOPE, *index),
+      Reference::Lexical { address, .. } if address.depth == 0 => (opcode::OPCODE_LOAD_LOCAL_SCOPE, address.slot),
+      Reference::Lexical { address, .. } => (opcode::OPCODE_LOAD_UPVALUE, self.get_upvalue_index(address)),
     }
-    panic!("[Compiler] Reference Error: {} is not defined", identifier.name);
   }
 
-  fn get_variable_index(&mut self, identifier: &ast::IdentifierReference) -> usize {
-    if let Some(index) = self.ctx.get_variable_index(&identifier.name) {
-      return index;
+  fn get_set_operation(&self, reference: &Reference) -> (usize, usize) {
+    match reference {
+      Reference::Global { index, .. } => (opcode::OPCODE_SET_GLOBAL_SCOPE, *index),
+      Reference::Lexical { address, .. } if address.depth == 0 => (opcode::OPCODE_SET_LOCAL_SCOPE, address.slot),
+      Reference::Lexical { address, .. } => (opcode::OPCODE_SET_UPVALUE, self.get_upvalue_index(address)),
     }
-    panic!("[Compiler] Reference Error: {} is not defined", identifier.name);
   }
 
-  fn generate_numeric_literal(&mut self, literal: &ast::NumericLiteral) {
+  fn get_upvalue_index(&self, address: &Address) -> usize {
+    let index = self.upvalues.iter().position(|capture| capture.address == *address);
+    index.expect("captured binding to be resolved")
+  }
+
+  fn generate_numeric_literal(&mut self, literal: &'a ast::NumericLiteral<'a>) {
     let index = self.get_numeric_constant_index(literal);
     self.emit(opcode::OPCODE_CONST);
     self.emit(index);
   }
 
+  // 12.9.3.2 Static Semantics: BigInt Value, the parser keeps the digits as they were written.
+  fn generate_bigint_literal(&mut self, literal: &'a ast::BigIntLiteral<'a>) {
+    let value = BigIntValue::from_literal(&literal.raw).expect("the parser to give a valid BigInt literal");
+    self.emit_constant(Value::BigInt(value));
+  }
+
   // fn generate_boolean_literal(&mut self, literal: &ast::BooleanLiteral) {
   //     self.constants.push(Value::new_boolean(literal.value));
   //     let index = self.constants.len() - 1;
   //     self.emit(op
-  fn generate_boolean_literal(&mut self, literal: &ast::BooleanLiteral) {
-    self.constants.push(Value::new_boolean(literal.value));
-    let index = self.constants.len() - 1;
+  fn generate_boolean_literal(&mut self, literal: &'a ast::BooleanLiteral) {
+    let index = self.add_constant(Value::create_boolean_value(literal.value));
     self.emit(opcode::OPCODE_CONST);
     self.emit(index);
   }
 
-  fn generate_string_literal(&mut self, literal: &ast::StringLiteral) {
-    let index = self.get_string_constant_index(&literal.value);
+  // the value is decoded again from the source, the parser can not keep the lone surrogates escapes make.
+  fn generate_string_literal(&mut self, literal: &'a ast::StringLiteral<'a>) {
+    let units = self.get_string_literal_units(literal);
+    let index = self.get_units_constant_index(units);
     self.emit(opcode::OPCODE_CONST);
     self.emit(index);
   }
 
-  fn generate_binary_expression(&mut self, binary: &ast::BinaryExpression) {
+  /*
+  12.9.4.2 Static Semantics: SV
+  The SV of a StringLiteral is the sequence of code units its characters and escape sequences stand for.
+  */
+  fn get_string_literal_units(&self, literal: &ast::StringLiteral<'a>) -> Vec<u16> {
+    let start = literal.span.start as usize + 1;
+    let end = (literal.span.end as usize).saturating_sub(1);
+    match self.source.get(start..end) {
+      Some(raw) if raw.contains('\\') => decode_escapes(raw),
+      _ => literal.value.encode_utf16().collect(),
+    }
+  }
+
+  fn generate_binary_expression(&mut self, binary: &'a ast::BinaryExpression<'a>) {
     self.generate_expression(&binary.left);
     self.generate_expression(&binary.right);
-    match binary.operator.as_str() {
+    self.mark_position(binary.span);
+    self.emit_binary_operator(binary.operator.as_str());
+  }
+
+  fn emit_binary_operator(&mut self, operator: &str) {
+    match operator {
       "+" => self.emit(opcode::OPCODE_ADD),
       "-" => self.emit(opcode::OPCODE_SUB),
       "*" => self.emit(opcode::OPCODE_MUL),
       "/" => self.emit(opcode::OPCODE_DIV),
+      "%" => self.emit(opcode::OPCODE_MOD),
+      "**" => self.emit(opcode::OPCODE_EXP),
+      "<<" => self.emit(opcode::OPCODE_SHL),
+      ">>" => self.emit(opcode::OPCODE_SHR),
+      ">>>" => self.emit(opcode::OPCODE_USHR),
+      "&" => self.emit(opcode::OPCODE_AND),
+      "|" => self.emit(opcode::OPCODE_OR),
+      "^" => self.emit(opcode::OPCODE_XOR),
+      "<" => self.emit(opcode::OPCODE_LT),
+      "<=" => self.emit(opcode::OPCODE_LE),
+      ">" => self.emit(opcode::OPCODE_GT),
+      ">=" => self.emit(opcode::OPCODE_GE),
+      "in" => self.emit(opcode::OPCODE_IN),
+      "instanceof" => self.emit(opcode::OPCODE_INSTANCEOF),
       "===" => self.emit(opcode::OPCODE_EQ),
+      "!==" => {
+        self.emit(opcode::OPCODE_EQ);
+        self.emit(opcode::OPCODE_NOT);
+      }
+      "==" => self.emit(opcode::OPCODE_WEAK_EQ),
+      "!=" => self.emit(opcode::OPCODE_NE),
       _ => panic!("Unknown binary operator"),
     }
   }
 
+  /*
+  13.16.1 Runtime Semantics: Evaluation, Expression : Expression , AssignmentExpression
+    1. Let lref be ? Evaluation of Expression.
+    2. Perform ? GetValue(lref).
+    3. Let rref be ? Evaluation of AssignmentExpression.
+    4. Return ? GetValue(rref).
+  */
+  fn generate_sequence_expression(&mut self, sequence: &'a ast::SequenceExpression<'a>) {
+    for (index, expression) in sequence.expressions.iter().enumerate() {
+      if index > 0 {
+        self.emit(opcode::OPCODE_POP);
+      }
+      self.generate_expression(expression);
+    }
+  }
+
+  // the next instruction reports `span` as its position in stack traces.
+  fn mark_position(&mut self, span: Span) {
+    let address = self.code.len();
+    match self.positions.last_mut() {
+      Some((last, start)) if *last == address => *start = span.start,
+      _ => self.positions.push((address, span.start)),
+    }
+  }
+
   fn emit(&mut self, byte: usize) {
     self.code.push(byte);
   }
 
-  fn exit_scope(&mut self) {
-    let len_of_variable_exit = self.ctx.deallocate_variable_in_scope();
-    if len_of_variable_exit > 0 {
-      self.emit(opcode::OPCODE_SCOPE_EXIT);
-      self.emit(len_of_variable_exit);
+  fn emit_with_operand(&mut self, instruction: usize, operand: usize) {
+    self.emit(instruction);
+    self.emit(operand);
+  }
+
+  // emits a jump with a placeholder target and returns the address to patch.
+  fn emit_jump(&mut self, jump_opcode: usize) -> usize {
+    self.emit(jump_opcode);
+    let address = self.code.len();
+    self.emit(0);
+    address
+  }
+
+  fn patch_jump(&mut self, address: usize) {
+    self.code[address] = self.code.len();
+  }
+
+  fn emit_loop(&mut self, loop_start: usize) {
+    self.emit(opcode::OPCODE_JUMP);
+    self.emit(loop_start);
+  }
+
+  fn add_constant(&mut self, value: Value) -> usize {
+    self.constants.push(value);
+    self.constants.len() - 1
+  }
+
+  fn emit_constant(&mut self, value: Value) {
+    let index = self.add_constant(value);
+    self.emit(opcode::OPCODE_CONST);
+    self.emit(index);
+  }
+
+  // the compiled function is kept as a template in the constants, CLOSURE turns it into
+  // a function value with its captured variables when it runs.
+  fn emit_closure(&mut self, code: CompilerReturn) {
+    let index = self.add_constant(Value::create_function_value(FunctionValue::new(Rc::new(code))));
+    self.emit(opcode::OPCODE_CLOSURE);
+    self.emit(index);
+  }
+
+  // like a closure, with the object on top of the stack as its home object.
+  fn emit_method(&mut self, code: CompilerReturn) {
+    let index = self.add_constant(Value::create_function_value(FunctionValue::new(Rc::new(code))));
+    self.emit(opcode::OPCODE_METHOD);
+    self.emit(index);
+  }
+
+  // the bindings of a block live in slots pushed when it is entered and popped when it is left.
+  fn enter_scope(&mut self, span: Span) {
+    let reserved = self.emit_reserve(span);
+    self.scopes.push(reserved);
+  }
+
+  // `var` bindings come first in a scope and start as undefined, the others start in their
+  // temporal dead zone until the declaration runs.
+  fn emit_reserve(&mut self, span: Span) -> usize {
+    let Some(scope) = self.ctx.get_scope(span) else {
+      return 0;
+    };
+    let reserved = scope.reserved();
+    let bindings = &scope.bindings[scope.parameters..];
+    let vars = bindings.iter().take_while(|binding| binding.kind == Kind::Var).count();
+    if vars > 0 {
+      let index = self.add_constant(Value::create_undefined_value());
+      for _ in 0..vars {
+        self.emit(opcode::OPCODE_CONST);
+        self.emit(index);
+      }
     }
-    self.ctx.exit_scope();
+    if reserved > vars {
+      self.emit(opcode::OPCODE_SCOPE_ENTER);
+      self.emit(reserved - vars);
+    }
+    reserved
+  }
+
+  // the error is created when the instruction runs, so it is catchable like any other throw.
+  fn emit_throw_error(&mut self, error_opcode: usize, message: &str) {
+    let index = self.get_string_constant_index(message);
+    self.emit(error_opcode);
+    self.emit(index);
+  }
+
+  // an operation on the property of the global object a name no declaration resolved to refers to.
+  fn emit_global_name(&mut self, name_opcode: usize, name: &str) {
+    let index = self.get_string_constant_index(name);
+    self.emit(name_opcode);
+    self.emit(index);
+  }
+
+  // frame slots in use: the locals of the function and of every open block scope.
+  fn get_stack_depth(&self) -> usize {
+    self.locals + self.scopes.iter().sum::<usize>()
   }
 
-  fn enter_scope(&mut self) {
-    self.ctx.enter_scope();
+  fn emit_initialized_check(&mut self, name: &str) {
+    let index = self.get_string_constant_index(name);
+    self.emit(opcode::OPCODE_CHECK_INITIALIZED);
+    self.emit(index);
   }
 
-  fn create_value(&mut self, value: &ast::NumericLiteral) -> Value {
-    match value.base {
-      NumberBase::Decimal => Value::new_integer(value.value as i64),
-      NumberBase::Float => Value::new_float(value.value),
-      _ => Value::new_integer(value.value as i64),
+  fn exit_scope(&mut self) {
+    let reserved = self.scopes.pop().unwrap_or_default();
+    if reserved > 0 {
+      self.emit(opcode::OPCODE_SCOPE_EXIT);
+      self.emit(reserved);
     }
   }
 
-  fn get_numeric_constant_index(&mut self, value: &ast::NumericLiteral) -> usize {
+  /*
+  12.9.3.3 Runtime Semantics: MV
+  The parser already gives the mathematical value of decimal, hex, octal and binary literals, rounded to the
+    nearest double.
+  */
+  fn create_value(&mut self, value: &'a ast::NumericLiteral<'a>) -> Value {
+    Value::create_float_value(value.value)
+  }
+
+  fn get_numeric_constant_index(&mut self, value: &'a ast::NumericLiteral<'a>) -> usize {
     let new_value = self.create_value(value);
     for (index, current_value) in self.constants.iter().enumerate() {
-      if current_value.is_number() && current_value.is_equal(&new_value) {
-        return index;
+      if let (Value::Number(current), Value::Number(new)) = (current_value, &new_value) {
+        if current.same_value(new) {
+          return index;
+        }
       }
     }
     self.constants.push(new_value);
@@ -289,20 +2679,101 @@ impl<'ctx> Compiler<'ctx> {
   }
 
   fn get_string_constant_index(&mut self, value: &str) -> usize {
-    let new_value = Value::new_string(value.to_owned());
+    self.get_units_constant_index(value.encode_utf16().collect())
+  }
+
+  fn get_units_constant_index(&mut self, units: Vec<u16>) -> usize {
+    let new_value = StringValue::from_utf16(units);
     for (index, current_value) in self.constants.iter().enumerate() {
-      if current_value.is_string() && current_value.get_string() == new_value.get_string() {
+      if matches!(current_value, Value::String(current) if *current == new_value) {
         return index;
       }
     }
-    self.constants.push(new_value);
+    self.constants.push(Value::String(new_value));
     self.constants.len() - 1
   }
+}
+
+// the TV of a template element, decoded from its raw text. None when an escape sequence in it is invalid.
+fn get_cooked_units(quasi: &ast::TemplateElement) -> Option<Vec<u16>> {
+  quasi.value.cooked.as_ref()?;
+  Some(decode_escapes(&quasi.value.raw))
+}
 
-  fn define_variable(&mut self, name: &str, kind: Kind) -> usize {
-    if self.ctx.is_exist_variable(name) {
-      panic!("[Compiler] SyntaxError: '{}' has already been declared.", name);
+/*
+12.9.4 String Literals
+12.9.6 Template Literal Lexical Components
+  TV: the TV of a TemplateCharacter is its TRV, the line terminators <CR><LF> and <CR> become <LF>.
+The code units of the characters and escape sequences of a literal, its text between the delimiters. Legacy octal
+  escapes are only found in string literals, a template with one has no cooked value and is never decoded.
+*/
+fn decode_escapes(raw: &str) -> Vec<u16> {
+  let mut units = Vec::with_capacity(raw.len());
+  let mut chars = raw.chars().peekable();
+  while let Some(char) = chars.next() {
+    if char == '\r' {
+      chars.next_if_eq(&'\n');
+      units.push(0x0A);
+      continue;
+    }
+    if char != '\\' {
+      let mut buffer = [0; 2];
+      units.extend_from_slice(char.encode_utf16(&mut buffer));
+      continue;
+    }
+    let Some(escape) = chars.next() else {
+      break;
+    };
+    match escape {
+      'b' => units.push(0x08),
+      't' => units.push(0x09),
+      'n' => units.push(0x0A),
+      'v' => units.push(0x0B),
+      'f' => units.push(0x0C),
+      'r' => units.push(0x0D),
+      // a LineContinuation contributes no code units
+      '\r' => {
+        chars.next_if_eq(&'\n');
+      }
+      '\n' | '\u{2028}' | '\u{2029}' => {}
+      'x' => {
+        let digits: String = chars.by_ref().take(2).collect();
+        units.push(u16::from_str_radix(&digits, 16).unwrap_or_default());
+      }
+      'u' if chars.next_if_eq(&'{').is_some() => {
+        let digits: String = chars.by_ref().take_while(|char| *char != '}').collect();
+        let code_point = u32::from_str_radix(&digits, 16).unwrap_or_default();
+        let mut buffer = [0; 2];
+        match char::from_u32(code_point) {
+          Some(char) => units.extend_from_slice(char.encode_utf16(&mut buffer)),
+          // a surrogate written as a code point is a lone surrogate too
+          None => units.push(code_point as u16),
+        }
+      }
+      'u' => {
+        let digits: String = chars.by_ref().take(4).collect();
+        units.push(u16::from_str_radix(&digits, 16).unwrap_or_default());
+      }
+      // B.1.2 LegacyOctalEscapeSequence, \0 not followed by a digit is the null character
+      '0'..='7' => {
+        let mut value = escape.to_digit(8).unwrap_or_default();
+        let longest = if escape <= '3' { 2 } else { 1 };
+        for _ in 0..longest {
+          match chars.peek().and_then(|char| char.to_digit(8)) {
+            Some(digit) => {
+              value = value * 8 + digit;
+              chars.next();
+            }
+            None => break,
+          }
+        }
+        units.push(value as u16);
+      }
+      escape => {
+        let mut buffer = [0; 2];
+        units.extend_from_slice(escape.encode_utf16(&mut buffer));
+      }
     }
-    self.ctx.define_variable(name.to_owned(), None, kind)
   }
+  units
 }
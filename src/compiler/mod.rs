@@ -1,14 +1,68 @@
 use oxc_allocator::Allocator;
 use oxc_span::SourceType;
 pub mod compiler;
+pub mod resolver;
 use crate::context::Context;
+use crate::errors::{EngineError, Result};
 use compiler::Compiler;
+use resolver::Resolver;
 
 use self::compiler::CompilerReturn;
 
-pub fn compile(arena_allocator: &Allocator, source: &String, ctx: &mut Context) -> CompilerReturn {
-  let source_type = SourceType::default().with_module(true).with_typescript(true);
-  let parser = oxc_parser::Parser::new(&arena_allocator, source, source_type);
+// `.js` and `.cjs` files are scripts, with sloppy mode code, anything else is a module.
+pub fn get_source_type(file: &str) -> SourceType {
+  let is_script = file.ends_with(".js") || file.ends_with(".cjs");
+  SourceType::default().with_module(!is_script).with_typescript(true)
+}
+
+// 15.1.3 Static Semantics: IsSimpleParameterList, only plain names without defaults or a rest parameter.
+pub fn is_simple_parameter_list(params: &oxc_ast::ast::FormalParameters) -> bool {
+  params.rest.is_none()
+    && params
+      .items
+      .iter()
+      .all(|param| param.pattern.kind.is_binding_identifier())
+}
+
+pub fn compile(
+  arena_allocator: &Allocator,
+  source: &str,
+  source_type: SourceType,
+  ctx: &mut Context,
+) -> Result<CompilerReturn> {
+  let parser = oxc_parser::Parser::new(arena_allocator, source, source_type);
   let result = parser.parse();
-  Compiler::compile(&result.program, ctx)
+  if let Some(error) = result.errors.first() {
+    return Err(EngineError::SyntaxError(error.to_string()));
+  }
+  Resolver::resolve(&result.program, ctx)?;
+  Compiler::compile(&result.program, source, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn compile_error(source: &str) -> Option<String> {
+    let mut ctx = Context::new();
+    let allocator = Allocator::default();
+    match compile(&allocator, source, SourceType::default(), &mut ctx) {
+      Err(EngineError::SyntaxError(message)) => Some(message),
+      _ => None,
+    }
+  }
+
+  #[test]
+  fn jumps_without_a_target_are_syntax_errors() {
+    assert_eq!(compile_error("break;").as_deref(), Some("Illegal break statement"));
+    assert_eq!(
+      compile_error("while (false) { continue bar; }").as_deref(),
+      Some("Illegal continue statement: 'bar' does not denote an iteration statement")
+    );
+    assert_eq!(
+      compile_error("function f() { for (;;) { break bar; } }").as_deref(),
+      Some("Undefined label 'bar'")
+    );
+    assert_eq!(compile_error("bar: for (;;) { break bar; }"), None);
+  }
 }
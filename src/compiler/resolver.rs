@@ -0,0 +1,545 @@
+#![allow(dead_code)]
+use super::is_simple_parameter_list;
+use crate::context::{Address, Binding, Capture, Context, FunctionEnvironment, Kind, Reference, Scope};
+use crate::errors::{EngineError, Result};
+use oxc_ast::ast::{self, Program};
+use oxc_ast::syntax_directed_operations::BoundNames;
+use oxc_ast::Visit;
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
+
+// an environment being resolved. `span` is the node the compiler finds it by, the global
+// scope and the scope holding the callee are never looked up.
+struct OpenScope {
+  span: Option<Span>,
+  scope: Scope,
+  function: usize,
+  // first free slot of the function when the scope was entered
+  base: usize,
+  is_global: bool,
+}
+
+struct OpenFunction {
+  environment: FunctionEnvironment,
+  next_slot: usize,
+}
+
+// walks the program before it is compiled and gives every binding its place: a global, or a
+// slot of the frame of the function declaring it. `var` is hoisted to the function scope and
+// `let`/`const` to the block declaring them, so a name always resolves to the closest binding.
+pub struct Resolver<'ctx> {
+  ctx: &'ctx mut Context,
+  scopes: Vec<OpenScope>,
+  functions: Vec<OpenFunction>,
+  // early errors, the program is not compiled when there is any
+  errors: Vec<String>,
+  // scopes whose parameter list is being resolved, with the names it binds. a list with defaults or patterns
+  // binds them one after the other, so what refers to them from the list may find them uninitialized.
+  parameter_scopes: Vec<(usize, Vec<String>)>,
+}
+
+impl<'ctx> Resolver<'ctx> {
+  pub fn resolve(program: &Program, ctx: &'ctx mut Context) -> Result<()> {
+    let mut resolver =
+      Resolver { ctx, scopes: Vec::new(), functions: Vec::new(), errors: Vec::new(), parameter_scopes: Vec::new() };
+    resolver.visit_program(program);
+    match resolver.errors.into_iter().next() {
+      Some(message) => Err(EngineError::SyntaxError(message)),
+      None => Ok(()),
+    }
+  }
+
+  fn enter_scope(&mut self, span: Option<Span>, is_global: bool) {
+    let function = self.functions.len() - 1;
+    let base = self.functions[function].next_slot;
+    self
+      .scopes
+      .push(OpenScope { span, scope: Scope::default(), function, base, is_global });
+  }
+
+  // slots of a block are free again once it is left, so sibling blocks share them.
+  fn exit_scope(&mut self) {
+    let open = self.scopes.pop().expect("scope to be open");
+    self.functions[open.function].next_slot = open.base;
+    if let Some(span) = open.span {
+      self.ctx.set_scope(span, open.scope);
+    }
+  }
+
+  // the callee takes the first slot of the frame, a named function expression can refer to
+  // itself through it, the parameters and the body get a scope of their own.
+  fn enter_function(&mut self, callee: &str, body: Span, params: &ast::FormalParameters) {
+    self.enter_code(callee, body);
+    for param in &params.items {
+      self.declare_parameter(&param.pattern);
+    }
+    if let Some(rest) = &params.rest {
+      self.declare_parameter(&rest.argument);
+    }
+    let open = self.scopes.last_mut().expect("function scope to be open");
+    open.scope.parameters = open.scope.bindings.len();
+    params.bound_names(&mut |ident| self.declare(&ident.name, Kind::Var));
+  }
+
+  // a frame without parameters, also what the code of a class body, a field initializer or a
+  // static block runs in.
+  fn enter_code(&mut self, callee: &str, body: Span) {
+    self
+      .functions
+      .push(OpenFunction { environment: FunctionEnvironment::default(), next_slot: 0 });
+    self.enter_scope(None, false);
+    self.declare(callee, Kind::Const);
+    self.enter_scope(Some(body), false);
+  }
+
+  // a parameter takes the slot its argument is passed in, a pattern is bound from a hidden
+  // slot to the names declared after the parameters.
+  fn declare_parameter(&mut self, pattern: &ast::BindingPattern) {
+    match &pattern.kind {
+      ast::BindingPatternKind::BindingIdentifier(ident) => self.declare(&ident.name, Kind::Var),
+      ast::BindingPatternKind::AssignmentPattern(assign) if assign.left.kind.is_binding_identifier() => {
+        self.declare_parameter(&assign.left);
+      }
+      _ => self.declare_slot(String::new(), Kind::Var),
+    }
+  }
+
+  // the names of a parameter list that is not simple are created uninitialized, see generate_parameter_bindings.
+  fn visit_parameters<'a>(&mut self, params: &ast::FormalParameters<'a>) {
+    if is_simple_parameter_list(params) {
+      return self.visit_formal_parameters(params);
+    }
+    let mut names = Vec::new();
+    params.bound_names(&mut |ident| names.push(ident.name.to_string()));
+    self.parameter_scopes.push((self.scopes.len() - 1, names));
+    self.visit_formal_parameters(params);
+    self.parameter_scopes.pop();
+  }
+
+  fn exit_function(&mut self, span: Span) {
+    self.exit_scope();
+    self.exit_scope();
+    let function = self.functions.pop().expect("function to be open");
+    self.ctx.set_function(span, function.environment);
+  }
+
+  // there are no promises nor suspended frames, async functions and generators can't run.
+  fn check_function_kind(&mut self, is_async: bool, is_generator: bool) {
+    let kind = match (is_async, is_generator) {
+      (true, true) => "Async generator functions",
+      (true, false) => "Async functions",
+      (false, true) => "Generator functions",
+      (false, false) => return,
+    };
+    self.errors.push(format!("{} are not supported", kind));
+  }
+
+  fn declare(&mut self, name: &str, kind: Kind) {
+    let open = self.scopes.last_mut().expect("scope to be open");
+    if let Some(binding) = open.scope.find(name) {
+      if binding.kind == Kind::Var && kind == Kind::Var {
+        return;
+      }
+      self
+        .errors
+        .push(format!("Identifier '{}' has already been declared", name));
+      return;
+    }
+    self.declare_slot(name.to_owned(), kind);
+  }
+
+  // a new binding in the innermost scope, hidden slots have an empty name nothing refers to.
+  fn declare_slot(&mut self, name: String, kind: Kind) {
+    let open = self.scopes.last_mut().expect("scope to be open");
+    let slot = if open.is_global {
+      self.ctx.define_global_variable(name.clone(), kind.clone())
+    } else {
+      let function = &mut self.functions[open.function];
+      function.next_slot += 1;
+      function.next_slot - 1
+    };
+    open.scope.bindings.push(Binding { name, kind, slot, captured: false });
+  }
+
+  fn declare_variable_declaration(&mut self, declaration: &ast::VariableDeclaration) {
+    let kind = match declaration.kind {
+      ast::VariableDeclarationKind::Var => Kind::Var,
+      ast::VariableDeclarationKind::Let => Kind::Let,
+      ast::VariableDeclarationKind::Const => Kind::Const,
+    };
+    declaration.bound_names(&mut |ident| self.declare(&ident.name, kind.clone()));
+  }
+
+  // declarations scoped to the block itself: `let`, `const` and functions.
+  fn declare_lexical_statements(&mut self, statements: &[ast::Statement]) {
+    for statement in statements {
+      match statement {
+        ast::Statement::Declaration(ast::Declaration::VariableDeclaration(decl)) if decl.kind.is_lexical() => {
+          self.declare_variable_declaration(decl);
+        }
+        // a declaration without a body is a TypeScript overload signature, only the implementation binds the name
+        ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(function)) if function.body.is_some() => {
+          if let Some(id) = &function.id {
+            self.declare(&id.name, Kind::Var);
+          }
+        }
+        ast::Statement::Declaration(ast::Declaration::ClassDeclaration(class)) if !class.is_declare() => {
+          if let Some(id) = &class.id {
+            self.declare(&id.name, Kind::Let);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  // `var` declarations anywhere in the function body, nested functions excluded.
+  fn declare_var_statements(&mut self, statements: &[ast::Statement]) {
+    for statement in statements {
+      self.declare_var_statement(statement);
+    }
+  }
+
+  fn declare_var_statement(&mut self, statement: &ast::Statement) {
+    match statement {
+      ast::Statement::Declaration(ast::Declaration::VariableDeclaration(decl)) if decl.kind.is_var() => {
+        self.declare_variable_declaration(decl);
+      }
+      ast::Statement::BlockStatement(block) => self.declare_var_statements(&block.body),
+      ast::Statement::IfStatement(stmt) => {
+        self.declare_var_statement(&stmt.consequent);
+        if let Some(alternate) = &stmt.alternate {
+          self.declare_var_statement(alternate);
+        }
+      }
+      ast::Statement::WhileStatement(stmt) => self.declare_var_statement(&stmt.body),
+      ast::Statement::DoWhileStatement(stmt) => self.declare_var_statement(&stmt.body),
+      ast::Statement::ForStatement(stmt) => {
+        if let Some(ast::ForStatementInit::VariableDeclaration(decl)) = &stmt.init {
+          if decl.kind.is_var() {
+            self.declare_variable_declaration(decl);
+          }
+        }
+        self.declare_var_statement(&stmt.body);
+      }
+      ast::Statement::ForInStatement(stmt) => {
+        if let ast::ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+          if decl.kind.is_var() {
+            self.declare_variable_declaration(decl);
+          }
+        }
+        self.declare_var_statement(&stmt.body);
+      }
+      ast::Statement::ForOfStatement(stmt) => {
+        if let ast::ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+          if decl.kind.is_var() {
+            self.declare_variable_declaration(decl);
+          }
+        }
+        self.declare_var_statement(&stmt.body);
+      }
+      ast::Statement::LabeledStatement(stmt) => self.declare_var_statement(&stmt.body),
+      ast::Statement::TryStatement(stmt) => {
+        self.declare_var_statements(&stmt.block.body);
+        if let Some(handler) = &stmt.handler {
+          self.declare_var_statements(&handler.body.body);
+        }
+        if let Some(finalizer) = &stmt.finalizer {
+          self.declare_var_statements(&finalizer.body);
+        }
+      }
+      ast::Statement::SwitchStatement(stmt) => {
+        for case in &stmt.cases {
+          self.declare_var_statements(&case.consequent);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  // the loop gets a scope of its own holding a hidden slot with the iterator, then the `let` and
+  // `const` bindings of its left side. the right side is evaluated in that scope, while they are
+  // still in their temporal dead zone.
+  fn visit_for_each_statement<'a>(
+    &mut self,
+    span: Span,
+    left: &ast::ForStatementLeft<'a>,
+    right: &ast::Expression<'a>,
+    body: &ast::Statement<'a>,
+  ) {
+    self.enter_scope(Some(span), false);
+    self.declare("", Kind::Var);
+    if let ast::ForStatementLeft::VariableDeclaration(decl) = left {
+      if decl.kind.is_lexical() {
+        self.declare_variable_declaration(decl);
+      }
+    }
+    self.visit_expression(right);
+    self.visit_for_statement_left(left);
+    self.visit_statement(body);
+    self.exit_scope();
+  }
+
+  fn resolve_identifier(&mut self, span: Span, name: &str) {
+    if let Some(reference) = self.lookup(name) {
+      self.ctx.set_reference(span, reference);
+    }
+  }
+
+  // searches the open scopes from the innermost outwards, a binding of an enclosing function
+  // is marked as captured and threaded through the upvalues of every function in between.
+  fn lookup(&mut self, name: &str) -> Option<Reference> {
+    let current = self.functions.len() - 1;
+    let position = self.scopes.iter().rposition(|open| open.scope.find(name).is_some())?;
+    let is_parameter = self
+      .parameter_scopes
+      .iter()
+      .any(|(scope, names)| *scope == position && names.iter().any(|parameter| parameter == name));
+    let open = &mut self.scopes[position];
+    let binding = open.scope.bindings.iter_mut().find(|binding| binding.name == name)?;
+    // checked like a `let` binding, the parameter may not be bound yet
+    let kind = if is_parameter { Kind::Let } else { binding.kind.clone() };
+    if open.is_global {
+      return Some(Reference::Global { index: binding.slot, kind });
+    }
+    let address = Address { depth: current - open.function, slot: binding.slot };
+    if address.depth > 0 {
+      binding.captured = true;
+      self.capture(current, address, name);
+    }
+    Some(Reference::Lexical { address, kind })
+  }
+
+  fn capture(&mut self, function: usize, address: Address, name: &str) -> usize {
+    if let Some(index) = self.functions[function].environment.get_upvalue_index(address) {
+      return index;
+    }
+    let (is_local, index) = if address.depth == 1 {
+      (true, address.slot)
+    } else {
+      let parent = Address { depth: address.depth - 1, slot: address.slot };
+      (false, self.capture(function - 1, parent, name))
+    };
+    let captures = &mut self.functions[function].environment.captures;
+    captures.push(Capture { name: name.to_owned(), address, is_local, index });
+    captures.len() - 1
+  }
+}
+
+impl<'a, 'ctx> Visit<'a> for Resolver<'ctx> {
+  fn visit_program(&mut self, program: &Program<'a>) {
+    self
+      .functions
+      .push(OpenFunction { environment: FunctionEnvironment::default(), next_slot: 0 });
+    self.enter_scope(None, true);
+    self.declare_var_statements(&program.body);
+    self.declare_lexical_statements(&program.body);
+    self.visit_statements(&program.body);
+    self.scopes.pop();
+    self.functions.pop();
+  }
+
+  fn visit_block_statement(&mut self, statement: &ast::BlockStatement<'a>) {
+    self.enter_scope(Some(statement.span), false);
+    self.declare_lexical_statements(&statement.body);
+    self.visit_statements(&statement.body);
+    self.exit_scope();
+  }
+
+  fn visit_for_statement(&mut self, statement: &ast::ForStatement<'a>) {
+    let lexical = match &statement.init {
+      Some(ast::ForStatementInit::VariableDeclaration(decl)) if decl.kind.is_lexical() => Some(decl),
+      _ => None,
+    };
+    if let Some(decl) = lexical {
+      self.enter_scope(Some(statement.span), false);
+      self.declare_variable_declaration(decl);
+    }
+    if let Some(init) = &statement.init {
+      self.visit_for_statement_init(init);
+    }
+    if let Some(test) = &statement.test {
+      self.visit_expression(test);
+    }
+    if let Some(update) = &statement.update {
+      self.visit_expression(update);
+    }
+    self.visit_statement(&statement.body);
+    if lexical.is_some() {
+      self.exit_scope();
+    }
+  }
+
+  fn visit_for_in_statement(&mut self, statement: &ast::ForInStatement<'a>) {
+    self.visit_for_each_statement(statement.span, &statement.left, &statement.right, &statement.body);
+  }
+
+  fn visit_for_of_statement(&mut self, statement: &ast::ForOfStatement<'a>) {
+    if statement.r#await {
+      self.errors.push("for await loops are not supported".to_owned());
+    }
+    self.visit_for_each_statement(statement.span, &statement.left, &statement.right, &statement.body);
+  }
+
+  // the try statement gets a scope of its own holding a hidden slot, where the completion
+  // that is pending while the `finally` block runs is kept.
+  fn visit_try_statement(&mut self, statement: &ast::TryStatement<'a>) {
+    self.enter_scope(Some(statement.span), false);
+    if statement.finalizer.is_some() {
+      self.declare("", Kind::Var);
+    }
+    self.visit_block_statement(&statement.block);
+    if let Some(handler) = &statement.handler {
+      self.visit_catch_clause(handler);
+    }
+    if let Some(finalizer) = &statement.finalizer {
+      self.visit_block_statement(finalizer);
+    }
+    self.exit_scope();
+  }
+
+  // the parameter takes the slot where the thrown value is left, a pattern is bound from that
+  // slot to the names after it. the body is a block of its own.
+  fn visit_catch_clause(&mut self, clause: &ast::CatchClause<'a>) {
+    self.enter_scope(Some(clause.span), false);
+    if let Some(param) = &clause.param {
+      if !param.pattern.kind.is_binding_identifier() {
+        self.declare_slot(String::new(), Kind::Var);
+      }
+      param
+        .pattern
+        .bound_names(&mut |ident| self.declare(&ident.name, Kind::Var));
+      let open = self.scopes.last_mut().expect("catch scope to be open");
+      open.scope.parameters = 1;
+      self.visit_binding_pattern(&param.pattern);
+    }
+    self.visit_block_statement(&clause.body);
+    self.exit_scope();
+  }
+
+  fn visit_function(&mut self, function: &ast::Function<'a>, _flags: Option<ScopeFlags>) {
+    let Some(body) = &function.body else {
+      return;
+    };
+    self.check_function_kind(function.r#async, function.generator);
+    let is_declaration = function.r#type == ast::FunctionType::FunctionDeclaration;
+    if is_declaration {
+      if let Some(id) = &function.id {
+        self.visit_binding_identifier(id);
+      }
+    }
+    let callee = match &function.id {
+      Some(id) if !is_declaration => id.name.as_str(),
+      _ => "",
+    };
+    self.enter_function(callee, body.span, &function.params);
+    self.declare_var_statements(&body.statements);
+    self.declare_lexical_statements(&body.statements);
+    self.visit_parameters(&function.params);
+    self.visit_statements(&body.statements);
+    self.exit_function(function.span);
+  }
+
+  fn visit_arrow_expression(&mut self, arrow: &ast::ArrowFunctionExpression<'a>) {
+    self.check_function_kind(arrow.r#async, false);
+    self.enter_function("", arrow.body.span, &arrow.params);
+    self.declare_var_statements(&arrow.body.statements);
+    self.declare_lexical_statements(&arrow.body.statements);
+    self.visit_parameters(&arrow.params);
+    self.visit_statements(&arrow.body.statements);
+    self.exit_function(arrow.span);
+  }
+
+  /*
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+    1. Let env be the LexicalEnvironment of the running execution context.
+    2. Let classEnv be NewDeclarativeEnvironment(env).
+    3. If classBinding is not undefined, then
+      a. Perform ! classEnv.CreateImmutableBinding(classBinding, true).
+    4. Let outerPrivateEnvironment be the running execution context's PrivateEnvironment.
+    5. Let classPrivateEnvironment be NewPrivateEnvironment(outerPrivateEnvironment).
+    6. If ClassBody is present, then
+      a. For each String dn of the PrivateBoundIdentifiers of ClassBody, do
+        i. If classPrivateEnvironment.[[Names]] contains pn, where pn.[[Description]] is dn, then
+          1. Assert: This is only possible for getter/setter pairs.
+        ii. Else,
+          1. Let name be a new Private Name whose [[Description]] is dn.
+          2. Append name to classPrivateEnvironment.[[Names]].
+  The class is resolved like a function called where it is defined, its scope holds both environments: the
+    binding of the class name and one binding for each Private Name, known by its `#name`.
+  */
+  fn visit_class(&mut self, class: &ast::Class<'a>) {
+    if class.is_declare() {
+      return;
+    }
+    if class.is_declaration() {
+      if let Some(id) = &class.id {
+        self.visit_binding_identifier(id);
+      }
+    }
+    self.enter_code("", class.span);
+    if let Some(id) = &class.id {
+      self.declare(&id.name, Kind::Const);
+    }
+    for element in &class.body.body {
+      let Some(name) = element.property_key().and_then(ast::PropertyKey::private_name) else {
+        continue;
+      };
+      let name = format!("#{}", name);
+      if self.scopes.last().is_some_and(|open| open.scope.find(&name).is_none()) {
+        self.declare(&name, Kind::Const);
+      }
+    }
+    if let Some(super_class) = &class.super_class {
+      self.visit_expression(super_class);
+    }
+    for element in &class.body.body {
+      self.visit_class_element(element);
+    }
+    self.exit_function(class.span);
+  }
+
+  // the initializer of a field runs as a method of its own, with the instance as `this`.
+  fn visit_property_definition(&mut self, definition: &ast::PropertyDefinition<'a>) {
+    self.visit_property_key(&definition.key);
+    if let Some(value) = &definition.value {
+      self.enter_code("", definition.span);
+      self.visit_expression(value);
+      self.exit_function(definition.span);
+    }
+  }
+
+  fn visit_static_block(&mut self, block: &ast::StaticBlock<'a>) {
+    self.enter_code("", block.span);
+    self.declare_var_statements(&block.body);
+    self.declare_lexical_statements(&block.body);
+    self.visit_statements(&block.body);
+    self.exit_function(block.span);
+  }
+
+  // `await` in a module outside of any function, an await in an async function is already rejected with it.
+  fn visit_await_expression(&mut self, expression: &ast::AwaitExpression<'a>) {
+    self.errors.push("Top-level await is not supported".to_owned());
+    self.visit_expression(&expression.argument);
+  }
+
+  // 15.7.1 Static Semantics: Early Errors, every `#name` used must be declared by an enclosing class.
+  fn visit_private_identifier(&mut self, ident: &ast::PrivateIdentifier<'a>) {
+    let name = format!("#{}", ident.name);
+    match self.lookup(&name) {
+      Some(reference) => self.ctx.set_reference(ident.span, reference),
+      None => self.errors.push(format!(
+        "Private field '{}' must be declared in an enclosing class",
+        name
+      )),
+    }
+  }
+
+  fn visit_binding_identifier(&mut self, ident: &ast::BindingIdentifier<'a>) {
+    self.resolve_identifier(ident.span, &ident.name);
+  }
+
+  fn visit_identifier_reference(&mut self, ident: &ast::IdentifierReference<'a>) {
+    self.resolve_identifier(ident.span, &ident.name);
+  }
+}
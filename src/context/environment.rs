@@ -0,0 +1,109 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+9.1 Environment Records
+Environment Record is a specification type used to define the association of Identifiers to specific variables
+  and functions, based upon the lexical nesting structure of ECMAScript code. Usually an Environment Record is
+  associated with some specific syntactic structure of ECMAScript code such as a FunctionDeclaration, a
+  BlockStatement, or a Catch clause of a TryStatement. Each time such code is evaluated, a new Environment Record
+  is created to record the identifier bindings that are created by that code.
+
+Every binding is resolved ahead of time, so at runtime a declarative record is just a run of stack slots
+  of the frame that created it.
+
+@links:
+- https://tc39.es/ecma262/#sec-environment-records
+- https://tc39.es/ecma262/#sec-declarative-environment-records
+*/
+use super::Kind;
+
+// where a binding lives at runtime: `depth` counts the functions between the reference and
+// the function that declares the binding, `slot` is the binding's position in that function's frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Address {
+  pub depth: usize,
+  pub slot: usize,
+}
+
+// what an identifier resolved to.
+#[derive(Debug, Clone)]
+pub enum Reference {
+  Lexical { address: Address, kind: Kind },
+  Global { index: usize, kind: Kind },
+}
+
+impl Reference {
+  // `let` and `const` bindings, which have a temporal dead zone.
+  pub fn is_lexical(&self) -> bool {
+    matches!(self.get_kind(), Kind::Let | Kind::Const)
+  }
+
+  pub fn get_kind(&self) -> &Kind {
+    match self {
+      Reference::Lexical { kind, .. } | Reference::Global { kind, .. } => kind,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+  pub name: String,
+  pub kind: Kind,
+  pub slot: usize,
+  pub captured: bool,
+}
+
+// a declarative environment record: the bindings a block or a function body creates when it is entered.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+  pub bindings: Vec<Binding>,
+  // leading bindings filled by the caller (the parameters of a function body)
+  pub parameters: usize,
+}
+
+impl Scope {
+  pub fn find(&self, name: &str) -> Option<&Binding> {
+    self.bindings.iter().find(|binding| binding.name == name)
+  }
+
+  // slots pushed when the scope is entered.
+  pub fn reserved(&self) -> usize {
+    self.bindings.len() - self.parameters
+  }
+
+  // the first slot of the scope when a closure captured any of its bindings.
+  pub fn get_captured_slot(&self) -> Option<usize> {
+    let first = self.bindings.first()?;
+    self
+      .bindings
+      .iter()
+      .any(|binding| binding.captured)
+      .then_some(first.slot)
+  }
+}
+
+// where a closure finds a captured binding when it is created: a slot of the enclosing
+// function's frame or one of the enclosing function's own upvalues.
+#[derive(Debug, Clone)]
+pub struct Capture {
+  pub name: String,
+  pub address: Address,
+  pub is_local: bool,
+  pub index: usize,
+}
+
+// the bindings a function reaches outside of its own frame.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionEnvironment {
+  pub captures: Vec<Capture>,
+}
+
+impl FunctionEnvironment {
+  pub fn get_upvalue_index(&self, address: Address) -> Option<usize> {
+    self.captures.iter().position(|capture| capture.address == address)
+  }
+}
@@ -1,5 +1,14 @@
 #![allow(dead_code)]
-use crate::{utils::is_internal_variable, values::Value};
+pub mod environment;
+use crate::{
+  builtins,
+  values::property::{PropertyDescriptor, PropertyKey},
+  values::{ObjectValue, SymbolValue, Value},
+};
+use oxc_span::Span;
+use std::collections::HashMap;
+
+pub use self::environment::{Address, Binding, Capture, FunctionEnvironment, Reference, Scope};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Kind {
@@ -8,24 +17,67 @@ pub enum Kind {
   Var,
 }
 
+// a binding of the global environment record, the value of a named `var` lives in the global object instead.
 pub struct Store {
   pub name: String,
   pub value: Value,
   pub kind: Kind,
-  pub level: usize,
+}
+
+// `Span` hashes to nothing, so nodes are keyed by their offsets.
+type NodeKey = (u32, u32);
+
+fn node_key(span: Span) -> NodeKey {
+  (span.start, span.end)
 }
 
 pub struct Context {
   global: Vec<Store>,
-  local: Vec<Store>,
-  current_scope: usize,
+  // environments resolved ahead of compilation, keyed by the node that creates them
+  scopes: HashMap<NodeKey, Scope>,
+  functions: HashMap<NodeKey, FunctionEnvironment>,
+  // what every identifier, declared or referenced, resolved to
+  references: HashMap<NodeKey, Reference>,
+  // well-known intrinsic objects of the realm, like %TypeError.prototype%
+  intrinsics: HashMap<String, ObjectValue>,
+  // well-known symbols keyed by their description, like Symbol.toPrimitive
+  symbols: HashMap<String, SymbolValue>,
+  // the GlobalSymbolRegistry of `Symbol.for`, symbols keyed by the string they were registered with
+  registry: HashMap<String, SymbolValue>,
 }
 
+// 6.1.5.1 Well-Known Symbols, the ones the engine looks up.
+const WELL_KNOWN_SYMBOLS: [&str; 6] = [
+  "Symbol.asyncIterator",
+  "Symbol.hasInstance",
+  "Symbol.iterator",
+  "Symbol.species",
+  "Symbol.toPrimitive",
+  "Symbol.toStringTag",
+];
+
 impl Default for Context {
   fn default() -> Self {
-    let global =
-      vec![Store { name: "undefined".to_string(), value: Value::new_undefined(), level: 0, kind: Kind::Const }];
-    Self { global, current_scope: 0, local: vec![] }
+    // 19.1 Value Properties of the Global Object, their properties can't be written
+    let global = vec![
+      Store { name: "undefined".to_string(), value: Value::create_undefined_value(), kind: Kind::Var },
+      Store { name: "NaN".to_string(), value: Value::create_float_value(f64::NAN), kind: Kind::Var },
+      Store { name: "Infinity".to_string(), value: Value::create_float_value(f64::INFINITY), kind: Kind::Var },
+    ];
+    let mut ctx = Self {
+      global,
+      scopes: HashMap::new(),
+      functions: HashMap::new(),
+      references: HashMap::new(),
+      intrinsics: HashMap::new(),
+      symbols: HashMap::new(),
+      registry: HashMap::new(),
+    };
+    for description in WELL_KNOWN_SYMBOLS {
+      ctx.set_well_known_symbol(description);
+    }
+    builtins::install(&mut ctx);
+    ctx
   }
 }
 
@@ -34,167 +86,123 @@ impl Context {
     Self::default()
   }
 
-  pub fn set_variable(&mut self, index: usize, value: Value) {
-    if self.is_global_scope() && index >= self.global.len() {
-      panic!("[Context]: {} does not exist in global scope.", index);
-    }
-    if self.is_global_scope() {
-      return self.set_global_variable(index, value);
-    }
-
-    if index >= self.local.len() {
-      panic!("[Context]: {} does not exist in local scope.", index);
-    }
-    return self.set_local_variable(index, value);
-  }
-
-  fn set_global_variable(&mut self, index: usize, value: Value) {
+  pub fn set_global_variable(&mut self, index: usize, value: Value) {
     self.global[index].value = value;
   }
-  fn set_local_variable(&mut self, index: usize, value: Value) {
-    // todo: validate index in local  scope
-    if index >= self.local.len() && self.local[index].level != self.get_current_scope() {
-      self.set_global_variable(index, value);
-      return;
-    }
 
-    self.local[index].value = value;
+  pub fn get_global_variable(&self, index: usize) -> Option<&Store> {
+    self.global.get(index)
   }
 
-  pub fn set_local(&mut self, index: usize, value: Value) {
-    if index >= self.local.len() {
-      panic!("[Context]: {} does not exist", index);
-    }
-    self.local[index].value = value;
+  // the key of a global binding backed by the global object, the named `var`s.
+  pub fn get_global_object_key(&self, index: usize) -> Option<PropertyKey> {
+    let store = self.global.get(index)?;
+    let is_object_binding = store.kind == Kind::Var && !store.name.is_empty();
+    is_object_binding.then(|| PropertyKey::from(store.name.as_str()))
   }
 
-  pub fn deallocate_variable_in_scope(&mut self) -> usize {
-    let mut count = 0;
-    let total = self.local.len();
-    for index in (0..total).rev() {
-      if self.local[index].level == self.current_scope {
-        self.local.pop();
-        count += 1;
-      }
-    }
-    count
+  pub fn get_global_variable_index(&self, name: &str) -> Option<usize> {
+    self.global.iter().position(|s| s.name == name)
   }
 
-  pub fn get_variable_name(&self, index: usize) -> &String {
-    &self.get_variable(index).name
+  // the names and values of the global bindings, in the order they were declared.
+  pub fn get_global_bindings(&self) -> impl Iterator<Item = (&str, &Value)> {
+    self.global.iter().map(|store| (store.name.as_str(), &store.value))
   }
 
-  pub fn get_variable_value(&self, index: usize) -> &Value {
-    &self.get_variable(index).value
+  pub fn get_global_values(&self) -> impl Iterator<Item = &Value> {
+    self.global.iter().map(|store| &store.value)
   }
 
-  pub fn get_global_variable(&self, index: usize) -> Option<&Store> {
-    if index > self.global.len() {
-      return None;
+  /*
+  9.1.1.4.17 CreateGlobalVarBinding ( N, D )
+    5. If hasProperty is false and extensible is true, then
+      a. Perform ? ObjRec.CreateMutableBinding(N, D).
+      b. Perform ? ObjRec.InitializeBinding(N, undefined).
+  The built-ins are bound before the global object exists, it is created with them.
+  */
+  pub fn define_global_variable(&mut self, name: String, kind: Kind) -> usize {
+    if let Some(global) = self
+      .get_intrinsic("%GlobalThis%")
+      .filter(|_| kind == Kind::Var && !name.is_empty())
+    {
+      let key = PropertyKey::from(name.as_str());
+      if global.get_own_property(&key).is_none() {
+        let descriptor = PropertyDescriptor::new_data_with(Value::create_undefined_value(), true, true, false);
+        global.define_own_property(key, descriptor);
+      }
     }
-    return Some(&self.global[index]);
+    let value = match kind {
+      Kind::Var => Value::create_undefined_value(),
+      Kind::Let | Kind::Const => Value::create_uninitialized_value(),
+    };
+    self.global.push(Store { name, kind, value });
+    self.global.len() - 1
   }
-  pub fn get_local_variable(&self, index: usize) -> Option<&Store> {
-    if index > self.local.len() {
-      return None;
-    }
-    return Some(&self.local[index]);
+
+  // a global binding holding a built-in, scripts can overwrite it like any `var`.
+  pub fn define_builtin(&mut self, name: &str, value: Value) {
+    let index = self.define_global_variable(name.to_owned(), Kind::Var);
+    self.set_global_variable(index, value);
   }
 
-  pub fn get_variable(&self, index: usize) -> &Store {
-    if self.is_global_scope() {
-      return self.get_global_variable(index).unwrap();
-    }
-    let local = self.get_local_variable(index);
-    if let Some(var) = local {
-      return var;
-    }
-    return self.get_global_variable(index).unwrap();
+  pub fn set_intrinsic(&mut self, name: &str, object: ObjectValue) {
+    self.intrinsics.insert(name.to_owned(), object);
   }
 
-  pub fn is_exist_variable(&self, name: &str) -> bool {
-    if self.is_global_scope() {
-      self.global.iter().any(|s| s.name == name)
-    } else {
-      self.local.iter().any(|s| s.name == name)
-    }
+  pub fn get_intrinsic(&self, name: &str) -> Option<&ObjectValue> {
+    self.intrinsics.get(name)
   }
 
-  pub fn get_kind_variable(&self, name: &str) -> Option<Kind> {
-    if self.is_global_scope() {
-      self.global.iter().find(|s| s.name == name).map(|s| s.kind.clone())
-    } else {
-      self.local.iter().find(|s| s.name == name).map(|s| s.kind.clone())
-    }
+  pub fn get_intrinsics(&self) -> impl Iterator<Item = &ObjectValue> {
+    self.intrinsics.values()
   }
 
-  pub fn is_internal(&self, name: &str) -> bool {
-    is_internal_variable(name)
+  // 6.1.5.1 Well-Known Symbols, created once and shared by all the code of the realm.
+  pub fn set_well_known_symbol(&mut self, description: &str) {
+    let symbol = SymbolValue::new(Some(description.to_owned()));
+    self.symbols.insert(description.to_owned(), symbol);
   }
 
-  pub fn get_current_scope(&self) -> usize {
-    self.current_scope
+  pub fn get_well_known_symbol(&self, description: &str) -> Option<&SymbolValue> {
+    self.symbols.get(description)
   }
 
-  pub fn get_variable_index(&self, name: &str) -> Option<usize> {
-    if self.is_global_scope() {
-      self.global.iter().position(|s| s.name == name)
-    } else {
-      self
-        .local
-        .iter()
-        .position(|s| s.name == name && s.level == self.current_scope)
-        .or_else(|| self.global.iter().position(|s| s.name == name))
-    }
+  // the symbol registered with `key`, created the first time the key is asked for.
+  pub fn get_registered_symbol(&mut self, key: &str) -> SymbolValue {
+    let symbol = self.registry.entry(key.to_owned());
+    symbol.or_insert_with(|| SymbolValue::new(Some(key.to_owned()))).clone()
   }
 
-  pub fn enter_scope(&mut self) {
-    self.current_scope += 1;
+  // the key a symbol was registered with, None when it is not in the registry.
+  pub fn get_symbol_registry_key(&self, symbol: &SymbolValue) -> Option<&String> {
+    // a registered symbol is described by its key
+    let key = symbol.get_description()?;
+    let (key, registered) = self.registry.get_key_value(key)?;
+    registered.is_same(symbol).then_some(key)
   }
 
-  pub fn exit_scope(&mut self) {
-    if self.current_scope > 0 {
-      self.current_scope -= 1;
-    }
+  pub fn set_scope(&mut self, span: Span, scope: Scope) {
+    self.scopes.insert(node_key(span), scope);
   }
 
-  pub fn is_global_scope(&self) -> bool {
-    self.current_scope == 0
+  pub fn get_scope(&self, span: Span) -> Option<&Scope> {
+    self.scopes.get(&node_key(span))
   }
 
-  pub fn define_variable(&mut self, name: String, value: Option<Value>, kind: Kind) -> usize {
-    if self.is_global_scope() {
-      self.define_global_variable(name, value, kind)
-    } else {
-      self.define_local_variable(name, value, kind)
-    }
+  pub fn set_function(&mut self, span: Span, function: FunctionEnvironment) {
+    self.functions.insert(node_key(span), function);
   }
 
-  fn define_global_variable(&mut self, name: String, value: Option<Value>, kind: Kind) -> usize {
-    if let Some(index) = self.get_variable_index(&name) {
-      index
-    } else {
-      self.global.push(Store {
-        name,
-        kind,
-        level: self.get_current_scope(),
-        value: value.unwrap_or_else(Value::new_undefined),
-      });
-      self.global.len() - 1
-    }
+  pub fn get_function(&self, span: Span) -> Option<&FunctionEnvironment> {
+    self.functions.get(&node_key(span))
   }
 
-  fn define_local_variable(&mut self, name: String, value: Option<Value>, kind: Kind) -> usize {
-    if let Some(index) = self.get_variable_index(&name) {
-      index
-    } else {
-      self.local.push(Store {
-        name,
-        kind,
-        level: self.get_current_scope(),
-        value: value.unwrap_or_else(Value::new_undefined),
-      });
-      self.local.len() - 1
-    }
+  pub fn set_reference(&mut self, span: Span, reference: Reference) {
+    self.references.insert(node_key(span), reference);
+  }
+
+  pub fn get_reference(&self, span: Span) -> Option<&Reference> {
+    self.references.get(&node_key(span))
   }
 }
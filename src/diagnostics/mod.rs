@@ -5,6 +5,7 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 //! Contains JS errors, warnings and related structures
+#![allow(dead_code)]
 
 enum DiagnosticKind {
   Error,
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use crate::bytecode::opcode;
+use crate::compiler::compiler::{CompilerReturn, Handler};
 use crate::context::Context;
 use crate::utils::opcode_to_string;
 use crate::values::Value;
@@ -7,6 +8,7 @@ use crate::values::Value;
 pub struct Disassembler<'ctx> {
   constants: &'ctx Vec<Value>,
   code: &'ctx Vec<usize>,
+  handlers: &'ctx Vec<Handler>,
   instructions: Vec<Vec<String>>,
   name: String,
   line: Vec<String>,
@@ -14,14 +16,15 @@ pub struct Disassembler<'ctx> {
 }
 
 impl<'ctx> Disassembler<'ctx> {
-  pub fn new(code: &'ctx Vec<usize>, name: &str, constants: &'ctx Vec<Value>, ctx: &'ctx mut Context) -> Self {
+  pub fn new(compiled: &'ctx CompilerReturn, name: &str, ctx: &'ctx mut Context) -> Self {
     let instructions = vec![];
     let line = vec![];
-    Self { code, constants, instructions, ctx, line, name: name.to_owned() }
+    let (code, constants, handlers) = (&compiled.code, &compiled.constants, &compiled.handlers);
+    Self { code, constants, handlers, instructions, ctx, line, name: name.to_owned() }
   }
   pub fn disassemble(&mut self) -> () {
     let header = format!("{:<10} {:<12} {:<14} {}", "Offset", "Bytes", "Opcode", "Operand");
-    println!("----------------- Disassembler -----------------");
+    println!("----------------- Disassembler ({}) -----------------", self.name);
     println!("{}", header);
     println!("------------------------------------------------");
     // ------------------------------------------------
@@ -30,6 +33,34 @@ impl<'ctx> Disassembler<'ctx> {
       offset = self.disassemble_instruction(offset);
       self.print_line();
     }
+    self.disassemble_handlers();
+    self.disassemble_functions();
+  }
+
+  // the exception table, a throw inside a range continues at the target.
+  fn disassemble_handlers(&mut self) -> () {
+    if self.handlers.is_empty() {
+      return;
+    }
+    println!("------------------------------------------------");
+    for handler in self.handlers {
+      println!(
+        "{:08X}..{:08X} -> {:08X} (depth {})",
+        handler.start, handler.end, handler.target, handler.depth
+      );
+    }
+  }
+
+  // every function is its own code object, kept in the constants of the code that declares it.
+  fn disassemble_functions(&mut self) -> () {
+    let constants = self.constants;
+    for constant in constants {
+      if let Some(function) = constant.get_function() {
+        let code = function.get_code();
+        println!();
+        Disassembler::new(code, &code.name, self.ctx).disassemble();
+      }
+    }
   }
 
   fn disassemble_instruction(&mut self, offset: usize) -> usize {
@@ -42,10 +73,86 @@ impl<'ctx> Disassembler<'ctx> {
       | opcode::OPCODE_DIV
       | opcode::OPCODE_ADD
       | opcode::OPCODE_POP
+      | opcode::OPCODE_RETURN
+      | opcode::OPCODE_THROW
+      | opcode::OPCODE_OBJECT
+      | opcode::OPCODE_DEFINE_COMPUTED_PROPERTY
+      | opcode::OPCODE_DEFINE_GETTER
+      | opcode::OPCODE_DEFINE_SETTER
+      | opcode::OPCODE_COPY_DATA_PROPERTIES
+      | opcode::OPCODE_SET_PROTOTYPE
+      | opcode::OPCODE_DUP
+      | opcode::OPCODE_GET_COMPUTED_PROP
+      | opcode::OPCODE_SET_COMPUTED_PROP
+      | opcode::OPCODE_DELETE_PROP
+      | opcode::OPCODE_ARRAY
+      | opcode::OPCODE_ARRAY_PUSH
+      | opcode::OPCODE_ARRAY_HOLE
+      | opcode::OPCODE_ARRAY_SPREAD
+      | opcode::OPCODE_SWAP
+      | opcode::OPCODE_TO_PROPERTY_KEY
+      | opcode::OPCODE_REQUIRE_OBJECT_COERCIBLE
+      | opcode::OPCODE_GET_ITERATOR
+      | opcode::OPCODE_ITERATOR_VALUE
+      | opcode::OPCODE_ITERATOR_REST
+      | opcode::OPCODE_ITERATOR_CLOSE
+      | opcode::OPCODE_ITERATOR_CLOSE_THROW
+      | opcode::OPCODE_FOR_IN_ITERATOR
+      | opcode::OPCODE_CALL_SPREAD
+      | opcode::OPCODE_CALL_METHOD_SPREAD
+      | opcode::OPCODE_NEW_SPREAD
+      | opcode::OPCODE_MOD
+      | opcode::OPCODE_NEG
+      | opcode::OPCODE_NOT
+      | opcode::OPCODE_AND
+      | opcode::OPCODE_OR
+      | opcode::OPCODE_XOR
+      | opcode::OPCODE_SHL
+      | opcode::OPCODE_SHR
+      | opcode::OPCODE_LT
+      | opcode::OPCODE_LE
+      | opcode::OPCODE_GT
+      | opcode::OPCODE_GE
+      | opcode::OPCODE_WEAK_EQ
+      | opcode::OPCODE_NE
+      | opcode::OPCODE_EXP
+      | opcode::OPCODE_USHR
+      | opcode::OPCODE_BIT_NOT
+      | opcode::OPCODE_TO_NUMBER
+      | opcode::OPCODE_TYPEOF
+      | opcode::OPCODE_IN
+      | opcode::OPCODE_INSTANCEOF
+      | opcode::OPCODE_TO_STRING
+      | opcode::OPCODE_TO_NUMERIC
+      | opcode::OPCODE_INC
+      | opcode::OPCODE_DEC
+      | opcode::OPCODE_THIS
+      | opcode::OPCODE_NEW_TARGET
+      | opcode::OPCODE_SUPER_CALL
+      | opcode::OPCODE_GET_SUPER_COMPUTED_PROP
+      | opcode::OPCODE_SET_SUPER_COMPUTED_PROP
+      | opcode::OPCODE_DEFINE_STATIC_BLOCK
+      | opcode::OPCODE_INITIALIZE_CLASS
+      | opcode::OPCODE_GET_PRIVATE
+      | opcode::OPCODE_SET_PRIVATE
+      | opcode::OPCODE_HAS_PRIVATE
       | opcode::OPCODE_EQ => {
         return self.disassemble_simple(opcode, offset);
       }
-      opcode::OPCODE_CONST => {
+      opcode::OPCODE_CONST
+      | opcode::OPCODE_CHECK_INITIALIZED
+      | opcode::OPCODE_DEFINE_PROPERTY
+      | opcode::OPCODE_GET_PROP
+      | opcode::OPCODE_SET_PROP
+      | opcode::OPCODE_GET_SUPER_PROP
+      | opcode::OPCODE_SET_SUPER_PROP
+      | opcode::OPCODE_PRIVATE_NAME
+      | opcode::OPCODE_THROW_TYPE_ERROR
+      | opcode::OPCODE_THROW_REFERENCE_ERROR
+      | opcode::OPCODE_LOAD_GLOBAL_NAME
+      | opcode::OPCODE_SET_GLOBAL_NAME
+      | opcode::OPCODE_TYPEOF_GLOBAL_NAME
+      | opcode::OPCODE_DELETE_GLOBAL_NAME => {
         return self.disassemble_const(offset, opcode);
       }
       opcode::OPCODE_SET_GLOBAL_SCOPE | opcode::OPCODE_LOAD_GLOBAL_SCOPE => {
@@ -54,9 +161,27 @@ impl<'ctx> Disassembler<'ctx> {
       opcode::OPCODE_SET_LOCAL_SCOPE | opcode::OPCODE_LOAD_LOCAL_SCOPE => {
         return self.disassemble_local(offset, opcode);
       }
-      opcode::OPCODE_JUMP_IF_FALSE | opcode::OPCODE_JUMP => {
+      opcode::OPCODE_JUMP_IF_FALSE | opcode::OPCODE_JUMP | opcode::OPCODE_ITERATOR_STEP => {
         return self.disassemble_jump(offset, opcode);
       }
+      opcode::OPCODE_SCOPE_ENTER
+      | opcode::OPCODE_SCOPE_EXIT
+      | opcode::OPCODE_CALL
+      | opcode::OPCODE_CALL_METHOD
+      | opcode::OPCODE_PICK
+      | opcode::OPCODE_OBJECT_REST
+      | opcode::OPCODE_NEW
+      | opcode::OPCODE_DEFINE_METHOD
+      | opcode::OPCODE_DEFINE_FIELD
+      | opcode::OPCODE_CLOSE_UPVALUES => {
+        return self.disassemble_operand(offset, opcode);
+      }
+      opcode::OPCODE_CLOSURE | opcode::OPCODE_CLASS | opcode::OPCODE_METHOD => {
+        return self.disassemble_closure(offset, opcode);
+      }
+      opcode::OPCODE_SET_UPVALUE | opcode::OPCODE_LOAD_UPVALUE => {
+        return self.disassemble_upvalue(offset, opcode);
+      }
       _ => {
         print!("[Disassemble] Unknown opcode: {}", opcode_to_string(opcode));
         return offset + 1;
@@ -65,13 +190,34 @@ impl<'ctx> Disassembler<'ctx> {
   }
 
   pub fn disassemble_jump(&mut self, offset: usize, opcode: usize) -> usize {
-    self.dumb_bytecode(offset, 3);
+    self.dumb_bytecode(offset, 2);
+    self.print_opcode(opcode);
+    let jump = self.code[offset + 1];
+    self.print_operand(format!("{:08X}", jump));
+    return offset + 2;
+  }
+
+  pub fn disassemble_operand(&mut self, offset: usize, opcode: usize) -> usize {
+    self.dumb_bytecode(offset, 2);
+    self.print_opcode(opcode);
+    let operand = self.code[offset + 1];
+    self.print_operand(operand.to_string());
+    return offset + 2;
+  }
+  pub fn disassemble_closure(&mut self, offset: usize, opcode: usize) -> usize {
+    self.dumb_bytecode(offset, 2);
+    self.print_opcode(opcode);
+    let function = self.constants[self.code[offset + 1]].get_function().unwrap();
+    self.print_operand(format!("<fn {}>", function.get_name()));
+    return offset + 2;
+  }
+
+  pub fn disassemble_upvalue(&mut self, offset: usize, opcode: usize) -> usize {
+    self.dumb_bytecode(offset, 2);
     self.print_opcode(opcode);
     let index = self.code[offset + 1];
-    let jump = self.code[offset + 2];
-    self.line.push(format!("{:<8}", index));
-    self.line.push(format!("{:<8}", jump));
-    return offset + 3;
+    self.print_operand(format!("upvalue {}", index));
+    return offset + 2;
   }
   pub fn disassemble_global(&mut self, offset: usize, opcode: usize) -> usize {
     self.dumb_bytecode(offset, 2);
@@ -81,12 +227,12 @@ impl<'ctx> Disassembler<'ctx> {
     self.print_operand(var.to_owned());
     return offset + 2;
   }
+  // locals are resolved to frame slots ahead of compilation, so only the slot is known here.
   pub fn disassemble_local(&mut self, offset: usize, opcode: usize) -> usize {
     self.dumb_bytecode(offset, 2);
     self.print_opcode(opcode);
     let index = self.code[offset + 1];
-    let var = &self.ctx.get_local_variable(index).unwrap().name;
-    self.print_operand(var.to_owned());
+    self.print_operand(format!("slot {}", index));
     return offset + 2;
   }
   pub fn disassemble_const(&mut self, offset: usize, opcode: usize) -> usize {
@@ -123,7 +269,7 @@ impl<'ctx> Disassembler<'ctx> {
   }
 
   pub fn print_line(&mut self) -> () {
-    println!("{}", format!("{}", self.line.join("")));
+    println!("{}", self.line.join(""));
     self.line.clear();
   }
 }
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use crate::values::Value;
 use core::fmt;
 use std::error::Error;
 
@@ -6,6 +7,13 @@ use std::error::Error;
 #[allow(dead_code)]
 pub enum EngineError {
   StackUnderflow,
+  StackOverflow,
+  TypeError(String),
+  RangeError(String),
+  ReferenceError(String),
+  SyntaxError(String),
+  // a value thrown by the script that no handler caught
+  Exception(Value),
   Other(Box<dyn Error + 'static>),
 }
 
@@ -19,6 +27,12 @@ impl fmt::Display for EngineError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       EngineError::StackUnderflow => write!(f, "Stack Underflow"),
+      EngineError::StackOverflow => write!(f, "RangeError: Maximum call stack size exceeded"),
+      EngineError::TypeError(message) => write!(f, "TypeError: {message}"),
+      EngineError::RangeError(message) => write!(f, "RangeError: {message}"),
+      EngineError::ReferenceError(message) => write!(f, "ReferenceError: {message}"),
+      EngineError::SyntaxError(message) => write!(f, "SyntaxError: {message}"),
+      EngineError::Exception(value) => write!(f, "{value}"),
       EngineError::Other(e) => write!(f, "{e}"),
     }
   }
@@ -1,8 +1,11 @@
+use crate::values::bigint::BigIntValue;
 use crate::values::boolean::BooleanValue;
 use crate::values::number::NumberValue;
-use crate::values::object::ObjectValue;
+use crate::values::object::{ObjectKind, ObjectValue};
 use crate::values::string::StringValue;
+use crate::values::symbol::SymbolValue;
 use crate::values::Value;
+use crate::vm::coercion::number_to_string;
 use std::fmt;
 use std::fmt::Display;
 
@@ -12,10 +15,12 @@ impl Display for Value {
       Value::String(s) => write!(f, "{}", s),
       Value::Boolean(b) => write!(f, "{}", b),
       Value::Number(n) => write!(f, "{}", n),
-      Value::Undefined => write!(f, "undefined"),
+      Value::Undefined(_) => write!(f, "undefined"),
+      Value::Null(_) => write!(f, "null"),
+      Value::Symbol(s) => write!(f, "{}", s),
+      Value::BigInt(b) => write!(f, "{}", b),
       Value::Object(obj) => write!(f, "{}", obj),
-      Value::EOL => write!(f, "End of Line"),
-      // _ => write!(f, "Unknown Value"),
+      Value::Uninitialized => write!(f, "<uninitialized>"),
     }
   }
 }
@@ -23,35 +28,136 @@ impl Display for NumberValue {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match &self {
       NumberValue::Integer(i) => write!(f, "{}", i),
-      NumberValue::Float(fl) => write!(f, "{}", fl),
-      _ => write!(f, "Unknown Number"),
+      // a zero double is always -0, which is shown with its sign unlike in ToString
+      NumberValue::Float(fl) if *fl == 0.0 => write!(f, "-0"),
+      NumberValue::Float(fl) => write!(f, "{}", number_to_string(*fl)),
     }
   }
 }
 impl Display for BooleanValue {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    match &self {
-      BooleanValue::True => write!(f, "Boolean(true)"),
-      BooleanValue::False => write!(f, "Boolean(false)"),
+    match self.get() {
+      true => write!(f, "Boolean(true)"),
+      false => write!(f, "Boolean(false)"),
     }
   }
 }
 
 impl Display for ObjectValue {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    match &self {
-      // ObjectValue::Object(obj) => write!(f, "{}", obj),
-      // ObjectValue::Array(arr) => write!(f, "{}", arr),
-      // ObjectValue::Function(func) => write!(f, "{}", func),
-      ObjectValue::Null => write!(f, "undefined"),
+    match self.get_kind() {
+      ObjectKind::Function(function) if function.get_kind().is_class_constructor() => {
+        let name = match function.get_name() {
+          "" => "(anonymous)",
+          name => name,
+        };
+        // the parent class is the prototype of the constructor, %Function.prototype% is nameless
+        let parent = self.get_prototype().and_then(|parent| match parent.get_kind() {
+          ObjectKind::Function(function) => Some(function.get_name().to_owned()),
+          ObjectKind::NativeFunction(function) if !function.get_name().is_empty() => {
+            Some(function.get_name().to_owned())
+          }
+          _ => None,
+        });
+        match parent {
+          Some(parent) => write!(f, "[class {} extends {}]", name, parent),
+          None => write!(f, "[class {}]", name),
+        }
+      }
+      ObjectKind::Function(function) => write!(f, "[Function: {}]", function.get_name()),
+      ObjectKind::NativeFunction(function) => write!(f, "[Function: {}]", function.get_name()),
+      // its name is the one given by `bind`
+      ObjectKind::BoundFunction(_) => match self.get_property("name") {
+        Some(Value::String(name)) => write!(f, "[Function: {}]", name),
+        _ => write!(f, "[Function]"),
+      },
+      // errors show their stack, which starts with the name and the message
+      ObjectKind::Error => match self.get_property("stack") {
+        Some(Value::String(stack)) => write!(f, "{}", stack),
+        _ => {
+          let name = self.get_property("name").unwrap_or_else(Value::create_undefined_value);
+          let message = self
+            .get_property("message")
+            .unwrap_or_else(Value::create_undefined_value);
+          write!(f, "{}: {}", name, message)
+        }
+      },
+      // only one level is shown, nested collections could hold the array itself
+      ObjectKind::Array => {
+        let elements = (0..self.get_array_length()).map(|index| match self.get_property(&index.to_string()) {
+          Some(value) => format_element(&value),
+          None => "<empty>".to_owned(),
+        });
+        let elements = elements.collect::<Vec<_>>();
+        match elements.is_empty() {
+          true => write!(f, "[]"),
+          false => write!(f, "[ {} ]", elements.join(", ")),
+        }
+      }
+      ObjectKind::Map(data) => {
+        let entries = data.get_entries();
+        let entries = entries
+          .iter()
+          .map(|(key, value)| format!("{} => {}", format_element(key), format_element(value)));
+        write_collection(f, "Map", entries.collect())
+      }
+      ObjectKind::Set(data) => {
+        let values = data.get_entries();
+        let values = values.iter().map(|(value, _)| format_element(value));
+        write_collection(f, "Set", values.collect())
+      }
+      ObjectKind::Boolean(boolean) => write!(f, "[Boolean: {}]", boolean.get()),
+      ObjectKind::Number(number) => write!(f, "[Number: {}]", number),
+      ObjectKind::String(value) => write!(f, "[String: '{}']", value),
+      ObjectKind::Symbol(symbol) => write!(f, "[Symbol: {}]", symbol),
+      ObjectKind::BigInt(bigint) => write!(f, "[BigInt: {}]", bigint),
+      ObjectKind::ArrayIterator(_) => write!(f, "Object [Array Iterator] {{}}"),
+      ObjectKind::StringIterator(_) => write!(f, "Object [String Iterator] {{}}"),
+      ObjectKind::MapIterator(_) => write!(f, "Object [Map Iterator] {{}}"),
+      ObjectKind::SetIterator(_) => write!(f, "Object [Set Iterator] {{}}"),
+      ObjectKind::Ordinary | ObjectKind::IteratorRecord(_) | ObjectKind::ForInIterator(_) => {
+        write!(f, "[object Object]")
+      }
     }
   }
 }
 
+// a value inside an array or a collection, which are not shown again.
+fn format_element(value: &Value) -> String {
+  match value {
+    Value::Object(object) => match object.get_kind() {
+      ObjectKind::Array => "[Array]".to_owned(),
+      ObjectKind::Map(_) => "[Map]".to_owned(),
+      ObjectKind::Set(_) => "[Set]".to_owned(),
+      _ => object.to_string(),
+    },
+    Value::String(value) => format!("'{}'", value),
+    value => value.to_string(),
+  }
+}
+
+// `Map(2) { 'a' => 1, 'b' => 2 }`, with the number of elements.
+fn write_collection(f: &mut fmt::Formatter<'_>, name: &str, elements: Vec<String>) -> fmt::Result {
+  match elements.is_empty() {
+    true => write!(f, "{}(0) {{}}", name),
+    false => write!(f, "{}({}) {{ {} }}", name, elements.len(), elements.join(", ")),
+  }
+}
+
 impl Display for StringValue {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    match &self {
-      StringValue::String(s) => write!(f, "{}", s),
-    }
+    write!(f, "{}", self.get())
+  }
+}
+
+impl Display for SymbolValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.get_descriptive_string())
+  }
+}
+
+impl Display for BigIntValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}n", self.to_string_radix(10))
   }
 }
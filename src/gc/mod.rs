@@ -3,6 +3,7 @@
 Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 
 */
+#![allow(dead_code, unused_imports)]
 
 mod gc_value;
 mod heap;
@@ -4,30 +4,32 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 
 */
 
-use crate::gc::GCValue;
-use std::collections::HashSet;
+use crate::compiler::compiler::CompilerReturn;
+use crate::values::function::{ThisEnvironment, Upvalue};
+use std::cell::RefCell;
+use std::rc::Rc;
 
+// the state of the caller, restored when the callee returns.
 #[derive(Debug)]
 pub struct StackFrame {
-  locals: Vec<GCValue>,
+  pub code: Rc<CompilerReturn>,
+  pub instruction_pointer: usize,
+  pub frame_pointer: usize,
+  pub upvalues: Vec<Rc<RefCell<Upvalue>>>,
+  pub environment: Rc<RefCell<ThisEnvironment>>,
+  // the frame was entered from native code, which gets the result back instead of the caller
+  pub boundary: bool,
 }
 
 impl StackFrame {
-  pub fn new() -> Self {
-    StackFrame { locals: Vec::new() }
-  }
-
-  pub fn add_local(&mut self, value: GCValue) {
-    self.locals.push(value);
-  }
-
-  pub fn get_roots(&self) -> HashSet<usize> {
-    let mut roots = HashSet::new();
-    for local in &self.locals {
-      if let GCValue::Reference(id) = local {
-        roots.insert(*id);
-      }
-    }
-    roots
+  pub fn new(
+    code: Rc<CompilerReturn>,
+    instruction_pointer: usize,
+    frame_pointer: usize,
+    upvalues: Vec<Rc<RefCell<Upvalue>>>,
+    environment: Rc<RefCell<ThisEnvironment>>,
+    boundary: bool,
+  ) -> Self {
+    StackFrame { code, instruction_pointer, frame_pointer, upvalues, environment, boundary }
   }
 }
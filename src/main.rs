@@ -2,9 +2,11 @@
 Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 
 */
+#![allow(clippy::needless_return, clippy::unused_unit, clippy::module_inception)]
 
 // modules
 mod assembler;
+mod builtins;
 mod bytecode;
 mod cli;
 mod compiler;
@@ -21,12 +23,28 @@ mod values;
 mod vm;
 //  uses
 use cli::command_line;
+use utils::ENGINE_STACK_SIZE;
 use vm::core;
 
-fn run(source: String, is_debug: bool) {
-  let mut ctx = context::Context::new();
-  let result = core::Engine::bootstrap(&mut ctx, &source, is_debug);
-  println!("{:?}", result);
+fn run(file: &str, source: String, is_debug: bool) {
+  let file = file.to_owned();
+  // the engine runs on a thread of its own, the main thread's stack is too small for deep native calls
+  let engine = std::thread::Builder::new()
+    .stack_size(ENGINE_STACK_SIZE)
+    .spawn(move || {
+      let mut ctx = context::Context::new();
+      match core::Engine::bootstrap(&mut ctx, &file, &source, is_debug) {
+        Ok(value) => println!("{:?}", value),
+        Err(error) => {
+          eprintln!("Uncaught {}", error);
+          std::process::exit(1);
+        }
+      }
+    });
+  engine
+    .expect("could not start the engine")
+    .join()
+    .expect("the engine panicked");
 }
 
 fn main() {
@@ -37,13 +55,13 @@ fn main() {
       let file = matches.get_one::<String>("file").unwrap();
       let debug = matches.get_flag("debug");
       let source = std::fs::read_to_string(file).expect("could not read file");
-      run(source, debug);
+      run(file, source, debug);
     }
     Some(("compile", matches)) => {
       let file = matches.get_one::<String>("file").unwrap();
       let debug = matches.get_flag("debug");
       let source = std::fs::read_to_string(file).expect("could not read file");
-      run(source, debug);
+      run(file, source, debug);
     }
     _ => {
       panic!("Unknown command");
@@ -1,3 +1,4 @@
+#![allow(dead_code)]
 use highlight_error::*;
 
 #[macro_export]
@@ -119,7 +120,7 @@ pub trait Lexer<'i> {
 
   /// Checks if the next characters in the input start with the given string.
   fn starts_with(&mut self, text: &str) -> bool {
-    self.peek_many(text.chars().count()).map_or(false, |s| s == text)
+    self.peek_many(text.chars().count()).is_some_and(|s| s == text)
   }
 
   /// Consumes all contiguous characters matching a given predicate.
@@ -162,11 +163,10 @@ pub trait Lexer<'i> {
       _ => 10,
     };
     let num_str = self.take_while(move |c| c.is_digit(radix) || c == '_');
-    let num_str = num_str;
     if num_str.is_empty() {
       self.expected("numeric digit")
     } else {
-      u64::from_str_radix(&num_str, radix).map_err(|e| e.to_string())
+      u64::from_str_radix(num_str, radix).map_err(|e| e.to_string())
     }
   }
 
@@ -176,7 +176,7 @@ pub trait Lexer<'i> {
       Some('\\') => match self.advance_one() {
         Some('u') => {
           self.consume("{")?;
-          let codepoint_str = self.take_while(|c| c.is_digit(16));
+          let codepoint_str = self.take_while(|c| c.is_ascii_hexdigit());
           self.consume("}")?;
           u32::from_str_radix(codepoint_str, 16)
             .ok()
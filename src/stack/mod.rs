@@ -1,14 +1,39 @@
 #![allow(dead_code)]
 use crate::errors::EngineError;
+use crate::gc::StackFrame;
+use crate::utils::CALL_STACK_LIMIT;
 use crate::values::Value;
 
 pub struct Stack {
   stack: Vec<Value>,
+  frames: Vec<StackFrame>,
 }
 
 impl Stack {
   pub fn new(capacity: usize) -> Self {
-    Self { stack: Vec::with_capacity(capacity) }
+    Self { stack: Vec::with_capacity(capacity), frames: Vec::new() }
+  }
+
+  pub fn push_frame(&mut self, frame: StackFrame) -> Result<(), EngineError> {
+    if self.frames.len() >= CALL_STACK_LIMIT {
+      return Err(EngineError::StackOverflow);
+    }
+    self.frames.push(frame);
+    Ok(())
+  }
+
+  pub fn pop_frame(&mut self) -> Option<StackFrame> {
+    self.frames.pop()
+  }
+
+  // the saved frames of the callers, outermost first.
+  pub fn frames(&self) -> &[StackFrame] {
+    &self.frames
+  }
+
+  // drops every value above `len`, used to discard the locals of a returning frame.
+  pub fn truncate(&mut self, len: usize) {
+    self.stack.truncate(len);
   }
 
   #[inline(always)]
@@ -33,9 +58,39 @@ impl Stack {
     let index = self.stack.len() - index - 1;
     self.stack.get(index).ok_or(EngineError::StackUnderflow)
   }
+  // copies of the values from `index` to the top, the arguments of a call.
+  pub fn get_values(&self, index: usize) -> Vec<Value> {
+    self.stack[index..].to_vec()
+  }
+
   pub fn is_empty(&self) -> bool {
     self.stack.is_empty()
   }
+
+  pub fn len(&self) -> usize {
+    self.stack.len()
+  }
+
+  // get and set address a slot from the bottom of the stack, used for locals.
+  #[inline(always)]
+  pub fn get(&self, index: usize) -> Result<&Value, EngineError> {
+    self.stack.get(index).ok_or(EngineError::StackUnderflow)
+  }
+
+  #[inline(always)]
+  pub fn set(&mut self, index: usize, value: Value) -> Result<(), EngineError> {
+    let slot = self.stack.get_mut(index).ok_or(EngineError::StackUnderflow)?;
+    *slot = value;
+    Ok(())
+  }
+  // takes a value out from under the ones above it, like the receiver of a method call.
+  pub fn remove(&mut self, index: usize) -> Result<Value, EngineError> {
+    if index >= self.stack.len() {
+      return Err(EngineError::StackUnderflow);
+    }
+    Ok(self.stack.remove(index))
+  }
+
   pub fn push_in_global_scope(&mut self, value: Value, frame: usize) {
     self.stack.insert(frame, value);
   }
@@ -1,5 +1,12 @@
 use crate::bytecode::opcode;
 pub const STACK_LIMIT: usize = 1024;
+pub const CALL_STACK_LIMIT: usize = 10_000;
+// calls made from native code, like getters and callbacks, that can be running at once
+pub const NATIVE_CALL_LIMIT: usize = 1_000;
+// the Rust stack the engine runs on, every call made from native code takes a part of it
+pub const ENGINE_STACK_SIZE: usize = 64 * 1024 * 1024;
+// frames kept in the `stack` of an error
+pub const STACK_TRACE_LIMIT: usize = 10;
 
 pub fn opcode_to_string(opcode: usize) -> String {
   match opcode {
@@ -17,14 +24,106 @@ pub fn opcode_to_string(opcode: usize) -> String {
     opcode::OPCODE_LOAD_LOCAL_SCOPE => "LOAD_LOCAL".to_string(),
     opcode::OPCODE_SET_LOCAL_SCOPE => "SET_LOCAL".to_string(),
     opcode::OPCODE_POP => "POP".to_string(),
+    opcode::OPCODE_SCOPE_ENTER => "SCOPE_ENTER".to_string(),
+    opcode::OPCODE_SCOPE_EXIT => "SCOPE_EXIT".to_string(),
+    opcode::OPCODE_CHECK_INITIALIZED => "CHECK_INITIALIZED".to_string(),
+    opcode::OPCODE_THROW => "THROW".to_string(),
+    opcode::OPCODE_THROW_TYPE_ERROR => "THROW_TYPE_ERROR".to_string(),
+    opcode::OPCODE_THROW_REFERENCE_ERROR => "THROW_REFERENCE_ERROR".to_string(),
+    opcode::OPCODE_CALL => "CALL".to_string(),
+    opcode::OPCODE_NEW => "NEW".to_string(),
+    opcode::OPCODE_OBJECT => "OBJECT".to_string(),
+    opcode::OPCODE_DEFINE_PROPERTY => "DEFINE_PROPERTY".to_string(),
+    opcode::OPCODE_DEFINE_COMPUTED_PROPERTY => "DEFINE_COMPUTED_PROPERTY".to_string(),
+    opcode::OPCODE_DEFINE_GETTER => "DEFINE_GETTER".to_string(),
+    opcode::OPCODE_DEFINE_SETTER => "DEFINE_SETTER".to_string(),
+    opcode::OPCODE_COPY_DATA_PROPERTIES => "COPY_DATA_PROPERTIES".to_string(),
+    opcode::OPCODE_SET_PROTOTYPE => "SET_PROTOTYPE".to_string(),
+    opcode::OPCODE_DUP => "DUP".to_string(),
+    opcode::OPCODE_GET_PROP => "GET_PROP".to_string(),
+    opcode::OPCODE_SET_PROP => "SET_PROP".to_string(),
+    opcode::OPCODE_GET_COMPUTED_PROP => "GET_COMPUTED_PROP".to_string(),
+    opcode::OPCODE_SET_COMPUTED_PROP => "SET_COMPUTED_PROP".to_string(),
+    opcode::OPCODE_DELETE_PROP => "DELETE_PROP".to_string(),
+    opcode::OPCODE_CALL_METHOD => "CALL_METHOD".to_string(),
+    opcode::OPCODE_ARRAY => "ARRAY".to_string(),
+    opcode::OPCODE_ARRAY_PUSH => "ARRAY_PUSH".to_string(),
+    opcode::OPCODE_ARRAY_HOLE => "ARRAY_HOLE".to_string(),
+    opcode::OPCODE_ARRAY_SPREAD => "ARRAY_SPREAD".to_string(),
+    opcode::OPCODE_PICK => "PICK".to_string(),
+    opcode::OPCODE_SWAP => "SWAP".to_string(),
+    opcode::OPCODE_TO_PROPERTY_KEY => "TO_PROPERTY_KEY".to_string(),
+    opcode::OPCODE_REQUIRE_OBJECT_COERCIBLE => "REQUIRE_OBJECT_COERCIBLE".to_string(),
+    opcode::OPCODE_GET_ITERATOR => "GET_ITERATOR".to_string(),
+    opcode::OPCODE_ITERATOR_VALUE => "ITERATOR_VALUE".to_string(),
+    opcode::OPCODE_ITERATOR_REST => "ITERATOR_REST".to_string(),
+    opcode::OPCODE_ITERATOR_CLOSE => "ITERATOR_CLOSE".to_string(),
+    opcode::OPCODE_OBJECT_REST => "OBJECT_REST".to_string(),
+    opcode::OPCODE_MOD => "MOD".to_string(),
+    opcode::OPCODE_NEG => "NEG".to_string(),
+    opcode::OPCODE_NOT => "NOT".to_string(),
+    opcode::OPCODE_AND => "AND".to_string(),
+    opcode::OPCODE_OR => "OR".to_string(),
+    opcode::OPCODE_XOR => "XOR".to_string(),
+    opcode::OPCODE_SHL => "SHL".to_string(),
+    opcode::OPCODE_SHR => "SHR".to_string(),
+    opcode::OPCODE_LT => "LT".to_string(),
+    opcode::OPCODE_LE => "LE".to_string(),
+    opcode::OPCODE_GT => "GT".to_string(),
+    opcode::OPCODE_GE => "GE".to_string(),
+    opcode::OPCODE_WEAK_EQ => "WEAK_EQ".to_string(),
+    opcode::OPCODE_NE => "NE".to_string(),
+    opcode::OPCODE_EXP => "EXP".to_string(),
+    opcode::OPCODE_USHR => "USHR".to_string(),
+    opcode::OPCODE_BIT_NOT => "BIT_NOT".to_string(),
+    opcode::OPCODE_TO_NUMBER => "TO_NUMBER".to_string(),
+    opcode::OPCODE_TYPEOF => "TYPEOF".to_string(),
+    opcode::OPCODE_IN => "IN".to_string(),
+    opcode::OPCODE_INSTANCEOF => "INSTANCEOF".to_string(),
+    opcode::OPCODE_TO_STRING => "TO_STRING".to_string(),
+    opcode::OPCODE_TO_NUMERIC => "TO_NUMERIC".to_string(),
+    opcode::OPCODE_INC => "INC".to_string(),
+    opcode::OPCODE_DEC => "DEC".to_string(),
+    opcode::OPCODE_THIS => "THIS".to_string(),
+    opcode::OPCODE_NEW_TARGET => "NEW_TARGET".to_string(),
+    opcode::OPCODE_SUPER_CALL => "SUPER_CALL".to_string(),
+    opcode::OPCODE_GET_SUPER_PROP => "GET_SUPER_PROP".to_string(),
+    opcode::OPCODE_GET_SUPER_COMPUTED_PROP => "GET_SUPER_COMPUTED_PROP".to_string(),
+    opcode::OPCODE_SET_SUPER_PROP => "SET_SUPER_PROP".to_string(),
+    opcode::OPCODE_SET_SUPER_COMPUTED_PROP => "SET_SUPER_COMPUTED_PROP".to_string(),
+    opcode::OPCODE_CLASS => "CLASS".to_string(),
+    opcode::OPCODE_METHOD => "METHOD".to_string(),
+    opcode::OPCODE_DEFINE_METHOD => "DEFINE_METHOD".to_string(),
+    opcode::OPCODE_DEFINE_FIELD => "DEFINE_FIELD".to_string(),
+    opcode::OPCODE_DEFINE_STATIC_BLOCK => "DEFINE_STATIC_BLOCK".to_string(),
+    opcode::OPCODE_INITIALIZE_CLASS => "INITIALIZE_CLASS".to_string(),
+    opcode::OPCODE_PRIVATE_NAME => "PRIVATE_NAME".to_string(),
+    opcode::OPCODE_GET_PRIVATE => "GET_PRIVATE".to_string(),
+    opcode::OPCODE_SET_PRIVATE => "SET_PRIVATE".to_string(),
+    opcode::OPCODE_HAS_PRIVATE => "HAS_PRIVATE".to_string(),
+    opcode::OPCODE_ITERATOR_STEP => "ITERATOR_STEP".to_string(),
+    opcode::OPCODE_FOR_IN_ITERATOR => "FOR_IN_ITERATOR".to_string(),
+    opcode::OPCODE_ITERATOR_CLOSE_THROW => "ITERATOR_CLOSE_THROW".to_string(),
+    opcode::OPCODE_CALL_SPREAD => "CALL_SPREAD".to_string(),
+    opcode::OPCODE_CALL_METHOD_SPREAD => "CALL_METHOD_SPREAD".to_string(),
+    opcode::OPCODE_NEW_SPREAD => "NEW_SPREAD".to_string(),
+    opcode::OPCODE_LOAD_GLOBAL_NAME => "LOAD_GLOBAL_NAME".to_string(),
+    opcode::OPCODE_SET_GLOBAL_NAME => "SET_GLOBAL_NAME".to_string(),
+    opcode::OPCODE_TYPEOF_GLOBAL_NAME => "TYPEOF_GLOBAL_NAME".to_string(),
+    opcode::OPCODE_DELETE_GLOBAL_NAME => "DELETE_GLOBAL_NAME".to_string(),
+    opcode::OPCODE_RETURN => "RETURN".to_string(),
+    opcode::OPCODE_CLOSURE => "CLOSURE".to_string(),
+    opcode::OPCODE_LOAD_UPVALUE => "LOAD_UPVALUE".to_string(),
+    opcode::OPCODE_SET_UPVALUE => "SET_UPVALUE".to_string(),
+    opcode::OPCODE_CLOSE_UPVALUES => "CLOSE_UPVALUES".to_string(),
     _ => "UNKNOWN".to_string(),
   }
 }
 
-pub fn is_internal_variable(name: &str) -> bool {
-  match name {
-    "globalThis " | "undefined " | "NaN " | "Infinity " | "Object " | "Function " | "Array " | "String "
-    | "Number " | "Boolean " | "Math " | "Date " | "RegExp " | "Error " | "console " => true,
-    _ => false,
-  }
+// 1-based line and column of a byte offset of the source.
+pub fn get_line_column(source: &str, offset: usize) -> (usize, usize) {
+  let before = &source[..offset.min(source.len())];
+  let line = before.matches('\n').count() + 1;
+  let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+  (line, before[line_start..].chars().count() + 1)
 }
@@ -0,0 +1,139 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+10.4.2 Array Exotic Objects
+An Array is an exotic object that gives special treatment to array index property keys. A property whose property
+  name is an array index is also called an element.
+Every Array has a non-configurable "length" property whose value is always a non-negative integral Number whose
+  mathematical value is strictly less than 2^32.
+
+@links:
+- https://tc39.es/ecma262/#sec-array-exotic-objects
+*/
+use crate::values::object::{ObjectKind, ObjectValue};
+use crate::values::property::{PropertyDescriptor, PropertyKey, PropertySlot};
+use crate::values::Value;
+
+impl ObjectValue {
+  // 10.4.2.2 ArrayCreate ( length [ , proto ] ), always empty, elements are added after.
+  pub fn new_array(prototype: Option<ObjectValue>) -> Self {
+    let array = ObjectValue::new_with_kind(ObjectKind::Array, prototype);
+    let length = PropertyDescriptor::new_data_with(Value::create_number_value(0), true, false, false);
+    array.ordinary_define_own_property(PropertyKey::from("length"), length);
+    array
+  }
+
+  pub fn get_array_length(&self) -> u32 {
+    match self
+      .get_own_property(&PropertyKey::from("length"))
+      .map(|property| property.slot)
+    {
+      Some(PropertySlot::Data { value: Value::Number(length), .. }) => length.as_f64() as u32,
+      _ => 0,
+    }
+  }
+
+  fn is_array_length_writable(&self) -> bool {
+    self
+      .get_own_property(&PropertyKey::from("length"))
+      .is_some_and(|property| property.is_writable())
+  }
+
+  // the new `length` as a value, the attributes of the property are left alone.
+  pub(crate) fn set_array_length(&self, length: u32) {
+    let descriptor =
+      PropertyDescriptor { value: Some(Value::create_number_value(length as i64)), ..Default::default() };
+    self.ordinary_define_own_property(PropertyKey::from("length"), descriptor);
+  }
+
+  /*
+  10.4.2.1 [[DefineOwnProperty]] ( P, Desc )
+    1. If P is "length", then
+      a. Return ? ArraySetLength(A, Desc).
+    2. Else if P is an array index, then
+      f. If index ≥ length and lengthDesc.[[Writable]] is false, return false.
+      g. Let succeeded be ! OrdinaryDefineOwnProperty(A, P, Desc).
+      h. If succeeded is false, return false.
+      i. If index ≥ length, then
+        i. Set lengthDesc.[[Value]] to index + 1𝔽.
+  */
+  pub(crate) fn array_define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+    if key == PropertyKey::from("length") {
+      return self.array_set_length(descriptor);
+    }
+    let Some(index) = key.get_array_index() else {
+      return self.ordinary_define_own_property(key, descriptor);
+    };
+    let length = self.get_array_length();
+    if index >= length && !self.is_array_length_writable() {
+      return false;
+    }
+    if !self.ordinary_define_own_property(key, descriptor) {
+      return false;
+    }
+    if index >= length {
+      self.set_array_length(index + 1);
+    }
+    true
+  }
+
+  /*
+  10.4.2.4 ArraySetLength ( A, Desc )
+  The value of the descriptor has already been validated by the engine, which throws the RangeError.
+    7. If newLen ≥ oldLen, then
+      a. Return ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
+    8. If oldLenDesc.[[Writable]] is false, return false.
+    17. For each own property key P of A such that P is an array index and ! ToUint32(P) ≥ newLen, in descending
+      numeric index order, do
+      a. Let deleteSucceeded be ! A.[[Delete]](P).
+      b. If deleteSucceeded is false, then
+        i. Set newLenDesc.[[Value]] to ! ToUint32(P) + 1𝔽.
+        ii. If newWritable is false, set newLenDesc.[[Writable]] to false.
+        iii. Perform ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
+        iv. Return false.
+  */
+  fn array_set_length(&self, descriptor: PropertyDescriptor) -> bool {
+    let key = PropertyKey::from("length");
+    let Some(Value::Number(new_length)) = &descriptor.value else {
+      return self.ordinary_define_own_property(key, descriptor);
+    };
+    let new_length = new_length.as_f64() as u32;
+    let old_length = self.get_array_length();
+    if new_length >= old_length {
+      return self.ordinary_define_own_property(key, descriptor);
+    }
+    if !self.is_array_length_writable() {
+      return false;
+    }
+    // the elements are deleted while `length` is still writable, it is locked at the end
+    let new_writable = descriptor.writable != Some(false);
+    let descriptor = PropertyDescriptor { writable: descriptor.writable.map(|_| true), ..descriptor };
+    if !self.ordinary_define_own_property(key.clone(), descriptor) {
+      return false;
+    }
+    let mut indices: Vec<u32> = self
+      .own_property_keys()
+      .iter()
+      .filter_map(PropertyKey::get_array_index)
+      .filter(|index| *index >= new_length)
+      .collect();
+    indices.sort_unstable_by(|left, right| right.cmp(left));
+    for index in indices {
+      if !self.delete(&PropertyKey::from(index.to_string())) {
+        self.set_array_length(index + 1);
+        if !new_writable {
+          self.ordinary_define_own_property(key, PropertyDescriptor { writable: Some(false), ..Default::default() });
+        }
+        return false;
+      }
+    }
+    if !new_writable {
+      self.ordinary_define_own_property(key, PropertyDescriptor { writable: Some(false), ..Default::default() });
+    }
+    true
+  }
+}
@@ -5,16 +5,756 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 /*
+6.1.6.2 The BigInt Type
+The BigInt type represents an integer value. The value may be any size and is not limited to a particular
+  bit-width. Generally, where not otherwise noted, operations are designed to return exact mathematically-based
+  answers. For binary operations, BigInts act as two's complement binary strings, with negative numbers treated
+  as having bits set infinitely to the left.
+
+The value is kept as a sign and a magnitude of base 2^32 digits, the bitwise operators convert to the two's
+  complement form only while they run. The digits are shared, so copying a BigInt never copies them.
+
 @links:
+- https://tc39.es/ecma262/#sec-ecmascript-language-types-bigint-type
+- https://tc39.es/ecma262/#sec-bigint-objects
 */
-#[derive(Debug)]
+#![allow(dead_code)]
+use crate::errors::{EngineError, Result};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+// the largest BigInt has 2^30 bits, the operations that would go past it throw a RangeError.
+const MAX_BITS: u64 = 1 << 30;
+
+#[derive(Debug, Clone)]
 pub struct BigIntValue {
-  // todo: check if i128 is correct in t39 spec
-  value: i128,
+  negative: bool,
+  // the least significant digit first, without leading zeros, zero has no digits
+  magnitude: Rc<[u32]>,
 }
 
 impl BigIntValue {
-  pub fn new(value: i128) -> Self {
-    BigIntValue { value }
+  fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+    trim(&mut magnitude);
+    let negative = negative && !magnitude.is_empty();
+    BigIntValue { negative, magnitude: magnitude.into() }
+  }
+
+  pub fn zero() -> Self {
+    BigIntValue::from_parts(false, Vec::new())
+  }
+
+  pub fn from_i64(value: i64) -> Self {
+    let magnitude = value.unsigned_abs();
+    BigIntValue::from_parts(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+  }
+
+  // the digits of an unsigned integer in the radix, None when one of them is not a digit of it.
+  pub fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+    if digits.is_empty() {
+      return None;
+    }
+    let mut magnitude = Vec::new();
+    for char in digits.chars() {
+      multiply_add(&mut magnitude, radix, char.to_digit(radix)?);
+    }
+    Some(BigIntValue::from_parts(false, magnitude))
+  }
+
+  // 12.9.3.2 Static Semantics: BigInt Value of a NumericLiteral with the `n` suffix, separators included.
+  pub fn from_literal(raw: &str) -> Option<Self> {
+    let digits = raw.strip_suffix('n').unwrap_or(raw).replace('_', "");
+    let radix = match digits.get(..2) {
+      Some("0x" | "0X") => 16,
+      Some("0o" | "0O") => 8,
+      Some("0b" | "0B") => 2,
+      _ => return BigIntValue::from_str_radix(&digits, 10),
+    };
+    BigIntValue::from_str_radix(&digits[2..], radix)
+  }
+
+  /*
+  21.2.1.1.1 NumberToBigInt ( number )
+    1. If IsIntegralNumber(number) is false, throw a RangeError exception.
+    2. Return ℤ(ℝ(number)).
+  The caller checks that the number is integral, the double is split into its significand and exponent.
+  */
+  pub fn from_f64(number: f64) -> Self {
+    let bits = number.to_bits();
+    let exponent = ((bits >> 52) & 0x7FF) as i64;
+    if exponent == 0 {
+      return BigIntValue::zero();
+    }
+    let significand = (bits & ((1 << 52) - 1)) | (1 << 52);
+    let significand = [significand as u32, (significand >> 32) as u32];
+    let shift = exponent - 1075;
+    let magnitude = match shift >= 0 {
+      true => shift_left_magnitude(&significand, shift as u64),
+      false => shift_right_magnitude(&significand, shift.unsigned_abs()),
+    };
+    BigIntValue::from_parts(number < 0.0, magnitude)
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.magnitude.is_empty()
+  }
+
+  // 6.1.6.2.1 BigInt::unaryMinus ( x )
+  pub fn unary_minus(&self) -> Self {
+    BigIntValue { negative: !self.negative && !self.is_zero(), magnitude: self.magnitude.clone() }
+  }
+
+  // 6.1.6.2.2 BigInt::bitwiseNOT ( x ), the result is -x - 1.
+  pub fn bitwise_not(&self) -> Self {
+    self.unary_minus().subtract(&BigIntValue::from_i64(1))
+  }
+
+  /*
+  6.1.6.2.3 BigInt::exponentiate ( base, exponent )
+    1. If exponent < 0ℤ, throw a RangeError exception.
+    2. If base is 0ℤ and exponent is 0ℤ, return 1ℤ.
+    3. Return base raised to the power exponent.
+  */
+  pub fn exponentiate(&self, exponent: &BigIntValue) -> Result<Self> {
+    if exponent.negative {
+      return Err(EngineError::RangeError("Exponent must be non-negative".to_owned()));
+    }
+    if exponent.is_zero() {
+      return Ok(BigIntValue::from_i64(1));
+    }
+    if self.is_zero() || *self.magnitude == [1] {
+      let negative = self.negative && exponent.magnitude[0] & 1 == 1;
+      return Ok(BigIntValue::from_parts(negative, self.magnitude.to_vec()));
+    }
+    let base_length = bit_length(&self.magnitude) - 1;
+    if bit_length(&exponent.magnitude) > 64 || base_length.saturating_mul(low_u64(&exponent.magnitude)) > MAX_BITS {
+      return Err(EngineError::RangeError("Maximum BigInt size exceeded".to_owned()));
+    }
+    let mut power = low_u64(&exponent.magnitude);
+    let (mut result, mut base) = (vec![1], self.magnitude.to_vec());
+    while power > 0 {
+      if power & 1 == 1 {
+        result = multiply_magnitudes(&result, &base);
+      }
+      power >>= 1;
+      if power > 0 {
+        base = multiply_magnitudes(&base, &base);
+      }
+    }
+    let negative = self.negative && exponent.magnitude[0] & 1 == 1;
+    Ok(BigIntValue::from_parts(negative, result))
+  }
+
+  // 6.1.6.2.4 BigInt::multiply ( x, y )
+  pub fn multiply(&self, other: &BigIntValue) -> Self {
+    let magnitude = multiply_magnitudes(&self.magnitude, &other.magnitude);
+    BigIntValue::from_parts(self.negative != other.negative, magnitude)
+  }
+
+  /*
+  6.1.6.2.5 BigInt::divide ( x, y )
+    1. If y is 0ℤ, throw a RangeError exception.
+    2. Let quotient be ℝ(x) / ℝ(y).
+    3. Return ℤ(truncate(quotient)).
+  */
+  pub fn divide(&self, other: &BigIntValue) -> Result<Self> {
+    if other.is_zero() {
+      return Err(EngineError::RangeError("Division by zero".to_owned()));
+    }
+    let (quotient, _) = divide_magnitudes(&self.magnitude, &other.magnitude);
+    Ok(BigIntValue::from_parts(self.negative != other.negative, quotient))
+  }
+
+  /*
+  6.1.6.2.6 BigInt::remainder ( n, d )
+    1. If d is 0ℤ, throw a RangeError exception.
+    2. If n is 0ℤ, return 0ℤ.
+    3. Let r be ℝ(n) - (ℝ(d) × q) where q is an integer that is negative if and only if n and d have opposite
+      sign, and whose magnitude is as large as possible without exceeding the magnitude of ℝ(n) / ℝ(d).
+    4. Return ℤ(r).
+  */
+  pub fn remainder(&self, other: &BigIntValue) -> Result<Self> {
+    if other.is_zero() {
+      return Err(EngineError::RangeError("Division by zero".to_owned()));
+    }
+    let (_, remainder) = divide_magnitudes(&self.magnitude, &other.magnitude);
+    Ok(BigIntValue::from_parts(self.negative, remainder))
+  }
+
+  // 6.1.6.2.7 BigInt::add ( x, y )
+  pub fn add(&self, other: &BigIntValue) -> Self {
+    if self.negative == other.negative {
+      return BigIntValue::from_parts(self.negative, add_magnitudes(&self.magnitude, &other.magnitude));
+    }
+    match compare_magnitudes(&self.magnitude, &other.magnitude) {
+      Ordering::Less => BigIntValue::from_parts(other.negative, subtract_magnitudes(&other.magnitude, &self.magnitude)),
+      _ => BigIntValue::from_parts(self.negative, subtract_magnitudes(&self.magnitude, &other.magnitude)),
+    }
+  }
+
+  // 6.1.6.2.8 BigInt::subtract ( x, y )
+  pub fn subtract(&self, other: &BigIntValue) -> Self {
+    self.add(&other.unary_minus())
+  }
+
+  /*
+  6.1.6.2.9 BigInt::leftShift ( x, y )
+    1. If y < 0ℤ, then
+      a. Return ℤ(floor(ℝ(x) / 2^-ℝ(y))).
+    2. Return ℤ(ℝ(x) × 2^ℝ(y)).
+  */
+  pub fn left_shift(&self, other: &BigIntValue) -> Result<Self> {
+    // a shift that does not fit in 64 bits moves every digit out
+    let shift = match bit_length(&other.magnitude) <= 64 {
+      true => low_u64(&other.magnitude),
+      false => u64::MAX,
+    };
+    if other.negative {
+      return Ok(self.shift_right(shift));
+    }
+    if self.is_zero() {
+      return Ok(self.clone());
+    }
+    if bit_length(&self.magnitude).saturating_add(shift) > MAX_BITS {
+      return Err(EngineError::RangeError("Maximum BigInt size exceeded".to_owned()));
+    }
+    Ok(BigIntValue::from_parts(
+      self.negative,
+      shift_left_magnitude(&self.magnitude, shift),
+    ))
+  }
+
+  // 6.1.6.2.10 BigInt::signedRightShift ( x, y ), the result is BigInt::leftShift(x, -y).
+  pub fn signed_right_shift(&self, other: &BigIntValue) -> Result<Self> {
+    self.left_shift(&other.unary_minus())
+  }
+
+  // 6.1.6.2.11 BigInt::unsignedRightShift ( x, y )
+  pub fn unsigned_right_shift(&self, _other: &BigIntValue) -> Result<Self> {
+    Err(EngineError::TypeError(
+      "BigInts have no unsigned right shift, use >> instead".to_owned(),
+    ))
+  }
+
+  // the floor of the value divided by 2^shift, the negative values round away from zero.
+  fn shift_right(&self, shift: u64) -> Self {
+    if !self.negative {
+      return BigIntValue::from_parts(false, shift_right_magnitude(&self.magnitude, shift));
+    }
+    // floor(-a / 2^n) is -(((a - 1) >> n) + 1)
+    let magnitude = subtract_magnitudes(&self.magnitude, &[1]);
+    let magnitude = add_magnitudes(&shift_right_magnitude(&magnitude, shift), &[1]);
+    BigIntValue::from_parts(true, magnitude)
+  }
+
+  // 6.1.6.2.12 BigInt::lessThan ( x, y )
+  pub fn less_than(&self, other: &BigIntValue) -> bool {
+    self < other
+  }
+
+  // 6.1.6.2.18 BigInt::bitwiseAND ( x, y )
+  pub fn bitwise_and(&self, other: &BigIntValue) -> Self {
+    self.bitwise_operation(other, |x, y| x & y)
+  }
+
+  // 6.1.6.2.19 BigInt::bitwiseXOR ( x, y )
+  pub fn bitwise_xor(&self, other: &BigIntValue) -> Self {
+    self.bitwise_operation(other, |x, y| x ^ y)
+  }
+
+  // 6.1.6.2.20 BigInt::bitwiseOR ( x, y )
+  pub fn bitwise_or(&self, other: &BigIntValue) -> Self {
+    self.bitwise_operation(other, |x, y| x | y)
+  }
+
+  /*
+  6.1.6.2.17 BigIntBitwiseOp ( op, x, y )
+  The operands are extended with their sign to one digit past the longest of them, which is enough for the
+    result to keep its sign in the top bit.
+  */
+  fn bitwise_operation(&self, other: &BigIntValue, operation: fn(u32, u32) -> u32) -> Self {
+    let length = self.magnitude.len().max(other.magnitude.len()) + 1;
+    let (left, right) = (self.to_twos_complement(length), other.to_twos_complement(length));
+    let digits = left
+      .iter()
+      .zip(right)
+      .map(|(left, right)| operation(*left, right))
+      .collect();
+    BigIntValue::from_twos_complement(digits)
+  }
+
+  fn to_twos_complement(&self, length: usize) -> Vec<u32> {
+    let mut digits = self.magnitude.to_vec();
+    digits.resize(length, 0);
+    if self.negative {
+      negate_digits(&mut digits);
+    }
+    digits
+  }
+
+  fn from_twos_complement(mut digits: Vec<u32>) -> Self {
+    let negative = digits.last().is_some_and(|digit| digit >> 31 == 1);
+    if negative {
+      negate_digits(&mut digits);
+    }
+    BigIntValue::from_parts(negative, digits)
+  }
+
+  /*
+  21.2.2.2 BigInt.asUintN ( bits, bigint )
+    3. Return ℤ(ℝ(bigint) modulo 2^bits).
+  */
+  pub fn as_uint_n(&self, bits: u64) -> Result<Self> {
+    if !self.negative && bit_length(&self.magnitude) <= bits {
+      return Ok(self.clone());
+    }
+    let low = truncate_magnitude(&self.magnitude, bits);
+    if !self.negative || low.is_empty() {
+      return Ok(BigIntValue::from_parts(false, low));
+    }
+    if bits > MAX_BITS {
+      return Err(EngineError::RangeError("Maximum BigInt size exceeded".to_owned()));
+    }
+    let power = shift_left_magnitude(&[1], bits);
+    Ok(BigIntValue::from_parts(false, subtract_magnitudes(&power, &low)))
+  }
+
+  /*
+  21.2.2.1 BigInt.asIntN ( bits, bigint )
+    3. Let mod be ℝ(bigint) modulo 2^bits.
+    4. If mod ≥ 2^(bits - 1), return ℤ(mod - 2^bits); otherwise, return ℤ(mod).
+  */
+  pub fn as_int_n(&self, bits: u64) -> Self {
+    if bits == 0 {
+      return BigIntValue::zero();
+    }
+    // the values with fewer bits than the width are already in its range
+    if bit_length(&self.magnitude) < bits {
+      return self.clone();
+    }
+    let modulo = self.as_uint_n(bits).expect("the width to be within the digits");
+    if bit_length(&modulo.magnitude) < bits {
+      return modulo;
+    }
+    let power = shift_left_magnitude(&[1], bits);
+    BigIntValue::from_parts(true, subtract_magnitudes(&power, &modulo.magnitude))
+  }
+
+  // the order of the BigInt and a Number, None when the Number is NaN.
+  pub fn compare_number(&self, number: f64) -> Option<Ordering> {
+    if number.is_nan() {
+      return None;
+    }
+    if number.is_infinite() {
+      return Some(if number > 0.0 {
+        Ordering::Less
+      } else {
+        Ordering::Greater
+      });
+    }
+    // a BigInt equal to the floor of a fractional number is below it
+    match self.cmp(&BigIntValue::from_f64(number.floor())) {
+      Ordering::Equal if number.fract() != 0.0 => Some(Ordering::Less),
+      order => Some(order),
+    }
+  }
+
+  /*
+  6.1.6.2.23 BigInt::toString ( x, radix )
+    1. If x < 0ℤ, return the string-concatenation of "-" and BigInt::toString(-x, radix).
+    2. Return the String value consisting of the representation of x using radix radix.
+  The digits are taken a whole u32 worth of them at a time.
+  */
+  pub fn to_string_radix(&self, radix: u32) -> String {
+    if self.is_zero() {
+      return "0".to_owned();
+    }
+    let (mut chunk, mut chunk_digits) = (radix, 1);
+    while let Some(next) = chunk.checked_mul(radix) {
+      (chunk, chunk_digits) = (next, chunk_digits + 1);
+    }
+    let mut magnitude = self.magnitude.to_vec();
+    let mut digits = Vec::new();
+    while !magnitude.is_empty() {
+      let mut remainder = divide_digit(&mut magnitude, chunk);
+      for _ in 0..chunk_digits {
+        if magnitude.is_empty() && remainder == 0 {
+          break;
+        }
+        digits.push(std::char::from_digit(remainder % radix, radix).expect("a digit of the radix"));
+        remainder /= radix;
+      }
+    }
+    if self.negative {
+      digits.push('-');
+    }
+    digits.iter().rev().collect()
+  }
+}
+
+// 6.1.6.2.13 BigInt::equal ( x, y )
+impl PartialEq for BigIntValue {
+  fn eq(&self, other: &Self) -> bool {
+    self.negative == other.negative && self.magnitude == other.magnitude
+  }
+}
+
+impl Eq for BigIntValue {}
+
+impl Hash for BigIntValue {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.negative.hash(state);
+    self.magnitude.hash(state);
+  }
+}
+
+impl PartialOrd for BigIntValue {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for BigIntValue {
+  fn cmp(&self, other: &Self) -> Ordering {
+    match (self.negative, other.negative) {
+      (false, true) => Ordering::Greater,
+      (true, false) => Ordering::Less,
+      (false, false) => compare_magnitudes(&self.magnitude, &other.magnitude),
+      (true, true) => compare_magnitudes(&other.magnitude, &self.magnitude),
+    }
+  }
+}
+
+fn trim(digits: &mut Vec<u32>) {
+  while digits.last() == Some(&0) {
+    digits.pop();
+  }
+}
+
+fn bit_length(digits: &[u32]) -> u64 {
+  match digits.last() {
+    Some(last) => (digits.len() as u64 - 1) * 32 + u64::from(32 - last.leading_zeros()),
+    None => 0,
+  }
+}
+
+fn low_u64(digits: &[u32]) -> u64 {
+  let digit = |index: usize| u64::from(digits.get(index).copied().unwrap_or(0));
+  digit(0) | digit(1) << 32
+}
+
+fn compare_magnitudes(left: &[u32], right: &[u32]) -> Ordering {
+  left
+    .len()
+    .cmp(&right.len())
+    .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+  let (long, short) = if left.len() >= right.len() {
+    (left, right)
+  } else {
+    (right, left)
+  };
+  let mut digits = Vec::with_capacity(long.len() + 1);
+  let mut carry = 0u64;
+  for (index, digit) in long.iter().enumerate() {
+    let sum = u64::from(*digit) + u64::from(short.get(index).copied().unwrap_or(0)) + carry;
+    digits.push(sum as u32);
+    carry = sum >> 32;
+  }
+  if carry != 0 {
+    digits.push(carry as u32);
+  }
+  digits
+}
+
+// the difference of the magnitudes, the left one is not smaller than the right one.
+fn subtract_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+  let mut digits = Vec::with_capacity(left.len());
+  let mut borrow = false;
+  for (index, digit) in left.iter().enumerate() {
+    let (difference, first) = digit.overflowing_sub(right.get(index).copied().unwrap_or(0));
+    let (difference, second) = difference.overflowing_sub(borrow as u32);
+    digits.push(difference);
+    borrow = first || second;
+  }
+  trim(&mut digits);
+  digits
+}
+
+fn multiply_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+  if left.is_empty() || right.is_empty() {
+    return Vec::new();
+  }
+  let mut digits = vec![0u32; left.len() + right.len()];
+  for (i, x) in left.iter().enumerate() {
+    let mut carry = 0u64;
+    for (j, y) in right.iter().enumerate() {
+      let product = u64::from(*x) * u64::from(*y) + u64::from(digits[i + j]) + carry;
+      digits[i + j] = product as u32;
+      carry = product >> 32;
+    }
+    digits[i + right.len()] = carry as u32;
+  }
+  trim(&mut digits);
+  digits
+}
+
+// the digits times the factor plus the addend, in place.
+fn multiply_add(digits: &mut Vec<u32>, factor: u32, addend: u32) {
+  let mut carry = u64::from(addend);
+  for digit in digits.iter_mut() {
+    let product = u64::from(*digit) * u64::from(factor) + carry;
+    *digit = product as u32;
+    carry = product >> 32;
+  }
+  if carry != 0 {
+    digits.push(carry as u32);
+  }
+}
+
+// divides the digits by a single digit in place and returns the remainder.
+fn divide_digit(digits: &mut Vec<u32>, divisor: u32) -> u32 {
+  let mut remainder = 0u64;
+  for digit in digits.iter_mut().rev() {
+    let current = remainder << 32 | u64::from(*digit);
+    *digit = (current / u64::from(divisor)) as u32;
+    remainder = current % u64::from(divisor);
+  }
+  trim(digits);
+  remainder as u32
+}
+
+// the quotient and the remainder of the magnitudes, the divisor is not zero.
+fn divide_magnitudes(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+  if compare_magnitudes(dividend, divisor) == Ordering::Less {
+    return (Vec::new(), dividend.to_vec());
+  }
+  if let [divisor] = divisor {
+    let mut quotient = dividend.to_vec();
+    let remainder = divide_digit(&mut quotient, *divisor);
+    return (quotient, vec![remainder]);
+  }
+  // long division, one bit of the quotient at a time
+  let mut quotient = vec![0u32; dividend.len()];
+  let mut remainder = Vec::new();
+  for bit in (0..bit_length(dividend) as usize).rev() {
+    remainder = shift_left_magnitude(&remainder, 1);
+    if dividend[bit / 32] >> (bit % 32) & 1 == 1 {
+      match remainder.first_mut() {
+        Some(digit) => *digit |= 1,
+        None => remainder.push(1),
+      }
+    }
+    if compare_magnitudes(&remainder, divisor) != Ordering::Less {
+      remainder = subtract_magnitudes(&remainder, divisor);
+      quotient[bit / 32] |= 1 << (bit % 32);
+    }
+  }
+  (quotient, remainder)
+}
+
+fn shift_left_magnitude(digits: &[u32], shift: u64) -> Vec<u32> {
+  if digits.is_empty() {
+    return Vec::new();
+  }
+  let (whole, bits) = ((shift / 32) as usize, (shift % 32) as u32);
+  let mut shifted = vec![0u32; whole];
+  let mut carry = 0u32;
+  for digit in digits {
+    match bits {
+      0 => shifted.push(*digit),
+      _ => {
+        shifted.push(digit << bits | carry);
+        carry = digit >> (32 - bits);
+      }
+    }
+  }
+  if carry != 0 {
+    shifted.push(carry);
+  }
+  shifted
+}
+
+fn shift_right_magnitude(digits: &[u32], shift: u64) -> Vec<u32> {
+  let (whole, bits) = (shift / 32, (shift % 32) as u32);
+  if whole >= digits.len() as u64 {
+    return Vec::new();
+  }
+  let digits = &digits[whole as usize..];
+  let mut shifted = (0..digits.len())
+    .map(|index| match bits {
+      0 => digits[index],
+      _ => digits[index] >> bits | digits.get(index + 1).copied().unwrap_or(0) << (32 - bits),
+    })
+    .collect();
+  trim(&mut shifted);
+  shifted
+}
+
+// the magnitude modulo 2^bits.
+fn truncate_magnitude(digits: &[u32], bits: u64) -> Vec<u32> {
+  let whole = (bits / 32).min(digits.len() as u64) as usize;
+  let mut truncated = digits[..whole].to_vec();
+  let rest = (bits % 32) as u32;
+  if rest != 0 && whole < digits.len() {
+    truncated.push(digits[whole] & ((1 << rest) - 1));
+  }
+  trim(&mut truncated);
+  truncated
+}
+
+// the two's complement negation of fixed width digits, every bit is flipped and one is added.
+fn negate_digits(digits: &mut [u32]) {
+  let mut carry = true;
+  for digit in digits.iter_mut() {
+    let (negated, overflow) = (!*digit).overflowing_add(carry as u32);
+    *digit = negated;
+    carry = overflow;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // a decimal BigInt with an optional minus sign
+  fn bigint(text: &str) -> BigIntValue {
+    match text.strip_prefix('-') {
+      Some(digits) => BigIntValue::from_literal(digits).unwrap().unary_minus(),
+      None => BigIntValue::from_literal(text).unwrap(),
+    }
+  }
+
+  fn decimal(value: &BigIntValue) -> String {
+    value.to_string_radix(10)
+  }
+
+  #[test]
+  fn parses_literals_in_every_radix() {
+    assert_eq!(decimal(&bigint("1_000_000n")), "1000000");
+    assert_eq!(decimal(&bigint("0xFFn")), "255");
+    assert_eq!(decimal(&bigint("0o777n")), "511");
+    assert_eq!(decimal(&bigint("0b1010n")), "10");
+    assert!(BigIntValue::from_literal("0x").is_none());
+    assert!(BigIntValue::from_str_radix("12a", 10).is_none());
+  }
+
+  #[test]
+  fn zero_has_no_sign() {
+    assert_eq!(bigint("-0"), BigIntValue::zero());
+    assert_eq!(decimal(&bigint("5").subtract(&bigint("5"))), "0");
+    assert_eq!(decimal(&BigIntValue::zero().unary_minus()), "0");
+  }
+
+  #[test]
+  fn adds_and_multiplies_across_digits() {
+    let a = bigint("1267650600228229401496703217721");
+    let b = bigint("-18446744073709551609");
+    assert_eq!(decimal(&a.add(&b)), "1267650600209782657422993666112");
+    assert_eq!(decimal(&b.subtract(&a)), "-1267650600246676145570412769330");
+    assert_eq!(
+      decimal(&a.multiply(&b)),
+      "-23384026197294446682385403349587978093962412863089"
+    );
+    assert_eq!(
+      decimal(&bigint("3").exponentiate(&bigint("100")).unwrap()),
+      "515377520732011331036461129765621272702107522001"
+    );
+    assert_eq!(decimal(&bigint("-2").exponentiate(&bigint("3")).unwrap()), "-8");
+  }
+
+  #[test]
+  fn divides_towards_zero() {
+    let a = bigint("1267650600228229401496703217721");
+    let b = bigint("-18446744073709551609");
+    assert_eq!(decimal(&a.divide(&b).unwrap()), "-68719476736");
+    assert_eq!(decimal(&a.remainder(&b).unwrap()), "481036349497");
+    assert_eq!(decimal(&bigint("-7").divide(&bigint("2")).unwrap()), "-3");
+    assert_eq!(decimal(&bigint("-7").remainder(&bigint("2")).unwrap()), "-1");
+    assert!(matches!(
+      a.divide(&BigIntValue::zero()),
+      Err(EngineError::RangeError(_))
+    ));
+    assert!(matches!(
+      a.remainder(&BigIntValue::zero()),
+      Err(EngineError::RangeError(_))
+    ));
+  }
+
+  #[test]
+  fn shifts_round_towards_negative_infinity() {
+    let a = bigint("-1267650600228229401496703217721");
+    assert_eq!(
+      decimal(&a.signed_right_shift(&bigint("3")).unwrap()),
+      "-158456325028528675187087902216"
+    );
+    assert_eq!(decimal(&a.signed_right_shift(&bigint("200")).unwrap()), "-1");
+    assert_eq!(
+      decimal(&bigint("1").left_shift(&bigint("100")).unwrap()),
+      "1267650600228229401496703205376"
+    );
+    assert_eq!(decimal(&bigint("5").left_shift(&bigint("-1")).unwrap()), "2");
+    assert!(matches!(
+      bigint("1").left_shift(&bigint("1073741825")),
+      Err(EngineError::RangeError(_))
+    ));
+    assert!(matches!(
+      bigint("1").unsigned_right_shift(&bigint("1")),
+      Err(EngineError::TypeError(_))
+    ));
+  }
+
+  #[test]
+  fn bitwise_operators_use_twos_complement() {
+    let a = bigint("1267650600228229401496703217721");
+    let b = bigint("-18446744073709551609");
+    assert_eq!(decimal(&a.bitwise_and(&b)), "1267650600228229401496703205377");
+    assert_eq!(decimal(&a.bitwise_or(&b)), "-18446744073709539265");
+    assert_eq!(decimal(&a.bitwise_xor(&b)), "-1267650600246676145570412744642");
+    assert_eq!(decimal(&a.bitwise_not()), "-1267650600228229401496703217722");
+    assert_eq!(decimal(&bigint("-1").bitwise_and(&bigint("255"))), "255");
+  }
+
+  #[test]
+  fn wraps_to_a_width() {
+    let b = bigint("-18446744073709551609");
+    assert_eq!(decimal(&b.as_uint_n(64).unwrap()), "7");
+    assert_eq!(decimal(&b.as_int_n(64)), "7");
+    assert_eq!(decimal(&bigint("-5").as_uint_n(8).unwrap()), "251");
+    assert_eq!(decimal(&bigint("255").as_int_n(8)), "-1");
+    assert_eq!(decimal(&bigint("127").as_int_n(8)), "127");
+    assert_eq!(decimal(&bigint("-128").as_int_n(8)), "-128");
+    assert_eq!(decimal(&bigint("12345").as_int_n(0)), "0");
+  }
+
+  #[test]
+  fn prints_in_any_radix() {
+    let max = bigint("340282366920938463463374607431768211455");
+    assert_eq!(max.to_string_radix(16), "ffffffffffffffffffffffffffffffff");
+    assert_eq!(
+      bigint("1267650600228229401496703217721").to_string_radix(16),
+      "10000000000000000000003039"
+    );
+    assert_eq!(bigint("-35").to_string_radix(36), "-z");
+    assert_eq!(
+      bigint("1180591620717411303424").to_string_radix(8),
+      "200000000000000000000000"
+    );
+    assert_eq!(BigIntValue::zero().to_string_radix(2), "0");
+  }
+
+  #[test]
+  fn converts_and_compares_with_numbers() {
+    assert_eq!(decimal(&BigIntValue::from_f64(-1e20)), "-100000000000000000000");
+    assert_eq!(decimal(&BigIntValue::from_f64(2f64.powi(70))), "1180591620717411303424");
+    assert_eq!(bigint("1").compare_number(1.5), Some(Ordering::Less));
+    assert_eq!(bigint("-2").compare_number(-1.5), Some(Ordering::Less));
+    assert_eq!(bigint("2").compare_number(2.0), Some(Ordering::Equal));
+    assert_eq!(bigint("10").compare_number(f64::NEG_INFINITY), Some(Ordering::Greater));
+    assert_eq!(bigint("10").compare_number(f64::NAN), None);
+    assert!(bigint("-10").less_than(&bigint("3")));
   }
 }
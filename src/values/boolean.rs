@@ -7,7 +7,7 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 /*
 */
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BooleanValue {
   value: bool,
 }
@@ -16,4 +16,8 @@ impl BooleanValue {
   pub fn new(value: bool) -> Self {
     BooleanValue { value }
   }
+
+  pub fn get(&self) -> bool {
+    self.value
+  }
 }
@@ -0,0 +1,303 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+10.2 ECMAScript Function Objects
+ECMAScript function objects encapsulate parameterized ECMAScript code closed over a lexical environment and
+  support the dynamic evaluation of that code.
+
+@links:
+- https://tc39.es/ecma262/#sec-ecmascript-function-objects
+*/
+use crate::compiler::compiler::CompilerReturn;
+use crate::errors::Result;
+use crate::values::property::{PrivateElement, PropertyKey};
+use crate::values::{ObjectValue, SymbolValue, Value};
+use crate::vm::core::Engine;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+// a variable captured by a closure: it points at the stack slot while the declaring frame
+// is alive and owns the value once that slot goes away.
+#[derive(Debug)]
+pub enum Upvalue {
+  Open(usize),
+  Closed(Value),
+}
+
+/*
+10.2 ECMAScript Function Objects, Table 30: Internal Slots of ECMAScript Function Objects
+  [[ConstructorKind]]: base or derived, whether or not the function is a derived class constructor.
+  [[ThisMode]]: lexical, strict or global. lexical means that this refers to the this value of a lexically
+    enclosing function.
+  [[IsClassConstructor]]: a Boolean that indicates whether the function is a class constructor.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionKind {
+  Normal,
+  // [[ThisMode]] lexical, `this`, `super` and `new.target` are the ones of the enclosing code
+  Arrow,
+  // methods of classes and object literals, the code run by a class for its fields and static blocks
+  Method,
+  BaseConstructor,
+  DerivedConstructor,
+}
+
+impl FunctionKind {
+  pub fn is_class_constructor(&self) -> bool {
+    matches!(self, FunctionKind::BaseConstructor | FunctionKind::DerivedConstructor)
+  }
+
+  // 10.2.5 MakeConstructor, function declarations and expressions can be used with `new` as well.
+  pub fn is_constructor(&self) -> bool {
+    matches!(
+      self,
+      FunctionKind::Normal | FunctionKind::BaseConstructor | FunctionKind::DerivedConstructor
+    )
+  }
+}
+
+/*
+9.1.1.3 Function Environment Records
+A Function Environment Record is a Declarative Environment Record that is used to represent the top-level scope of
+  a function and, if the function is not an ArrowFunction, provides a this binding. If a function is not an
+  ArrowFunction function and references super, its Function Environment Record also contains the state that is
+  used to perform super method invocations from within the function.
+
+The bindings themselves live on the stack, only the this binding and what it comes with are kept here. Arrow
+  functions hold on to the one of the code they were created in.
+*/
+#[derive(Debug)]
+pub struct ThisEnvironment {
+  // uninitialized in a derived constructor until super() returns
+  pub this: Value,
+  // [[FunctionObject]], the function whose invocation caused this environment to be created
+  pub function: Option<ObjectValue>,
+  // [[NewTargetValue]], None when the function was called rather than constructed
+  pub new_target: Option<ObjectValue>,
+}
+
+impl ThisEnvironment {
+  pub fn new(this: Value, function: Option<ObjectValue>, new_target: Option<ObjectValue>) -> Self {
+    ThisEnvironment { this, function, new_target }
+  }
+}
+
+/*
+6.2.10 The ClassFieldDefinition Record Specification Type
+  [[Name]]: a Private Name, a String, or a Symbol, the name of the field.
+  [[Initializer]]: an ECMAScript function object or empty, the initializer of the field, if any.
+*/
+#[derive(Debug, Clone)]
+pub enum ClassElementName {
+  Property(PropertyKey),
+  Private(SymbolValue),
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassFieldDefinition {
+  pub name: ClassElementName,
+  pub initializer: Option<Value>,
+}
+
+/*
+6.2.11 The ClassStaticBlockDefinition Record Specification Type
+  [[BodyFunction]]: an ECMAScript function object, the function to be called during static initialization of a
+    class.
+*/
+#[derive(Debug, Clone)]
+pub enum ClassStaticElement {
+  Field(ClassFieldDefinition),
+  Block(Value),
+}
+
+// [[Fields]] and [[PrivateMethods]] of a class constructor, with the static elements run once the class is done.
+#[derive(Debug, Default)]
+pub struct ClassDefinition {
+  pub fields: RefCell<Vec<ClassFieldDefinition>>,
+  pub private_methods: RefCell<Vec<PrivateElement>>,
+  pub static_private_methods: RefCell<Vec<PrivateElement>>,
+  pub static_elements: RefCell<Vec<ClassStaticElement>>,
+}
+
+#[derive(Clone)]
+pub struct FunctionValue {
+  code: Rc<CompilerReturn>,
+  upvalues: Vec<Rc<RefCell<Upvalue>>>,
+  // the this binding of the code an arrow function was created in
+  environment: Option<Rc<RefCell<ThisEnvironment>>>,
+  // [[HomeObject]], the object whose prototype `super` property references start from
+  home_object: Option<ObjectValue>,
+  // the elements a class constructor defines on its instances
+  class: Option<Rc<ClassDefinition>>,
+}
+
+impl FunctionValue {
+  pub fn new(code: Rc<CompilerReturn>) -> Self {
+    FunctionValue::new_closure(code, Vec::new())
+  }
+
+  pub fn new_closure(code: Rc<CompilerReturn>, upvalues: Vec<Rc<RefCell<Upvalue>>>) -> Self {
+    FunctionValue { code, upvalues, environment: None, home_object: None, class: None }
+  }
+
+  pub fn with_environment(mut self, environment: Rc<RefCell<ThisEnvironment>>) -> Self {
+    self.environment = Some(environment);
+    self
+  }
+
+  pub fn with_home_object(mut self, home_object: ObjectValue) -> Self {
+    self.home_object = Some(home_object);
+    self
+  }
+
+  pub fn with_class(mut self, class: Rc<ClassDefinition>) -> Self {
+    self.class = Some(class);
+    self
+  }
+
+  pub fn get_environment(&self) -> Option<&Rc<RefCell<ThisEnvironment>>> {
+    self.environment.as_ref()
+  }
+
+  pub fn get_home_object(&self) -> Option<&ObjectValue> {
+    self.home_object.as_ref()
+  }
+
+  pub fn get_class(&self) -> Option<&Rc<ClassDefinition>> {
+    self.class.as_ref()
+  }
+
+  pub fn get_kind(&self) -> FunctionKind {
+    self.code.kind
+  }
+
+  pub fn get_upvalues(&self) -> &Vec<Rc<RefCell<Upvalue>>> {
+    &self.upvalues
+  }
+
+  pub fn get_code(&self) -> &Rc<CompilerReturn> {
+    &self.code
+  }
+
+  pub fn get_name(&self) -> &str {
+    &self.code.name
+  }
+
+  pub fn get_arity(&self) -> usize {
+    self.code.arity
+  }
+
+  pub fn get_length(&self) -> usize {
+    self.code.length
+  }
+
+  pub fn has_rest_parameter(&self) -> bool {
+    self.code.has_rest_parameter
+  }
+
+  // [[ThisMode]] strict, the this value is used as passed.
+  pub fn is_strict(&self) -> bool {
+    self.code.is_strict
+  }
+}
+
+/*
+10.4.1 Bound Function Exotic Objects
+A bound function exotic object is an exotic object that wraps another function object. A bound function exotic
+  object is callable (it has a [[Call]] internal method and may have a [[Construct]] internal method). Calling a
+  bound function exotic object generally results in a call of its wrapped function.
+
+@links:
+- https://tc39.es/ecma262/#sec-bound-function-exotic-objects
+*/
+#[derive(Debug, Clone)]
+pub struct BoundFunction {
+  // [[BoundTargetFunction]]
+  target: ObjectValue,
+  // [[BoundThis]]
+  this: Value,
+  // [[BoundArguments]], passed before the arguments of the call
+  arguments: Vec<Value>,
+}
+
+impl BoundFunction {
+  pub fn new(target: ObjectValue, this: Value, arguments: Vec<Value>) -> Self {
+    BoundFunction { target, this, arguments }
+  }
+
+  pub fn get_target(&self) -> &ObjectValue {
+    &self.target
+  }
+
+  pub fn get_this(&self) -> &Value {
+    &self.this
+  }
+
+  pub fn get_arguments(&self) -> &[Value] {
+    &self.arguments
+  }
+}
+
+/*
+10.3 Built-in Function Objects
+A built-in function object must have the internal slots described in 10.3. Built-in functions are implemented
+  by the engine itself rather than by ECMAScript code.
+
+@links:
+- https://tc39.es/ecma262/#sec-built-in-function-objects
+*/
+// the engine running the call, the this value and the arguments.
+pub type NativeFn = fn(&mut Engine, &Value, &[Value]) -> Result<Value>;
+
+#[derive(Clone)]
+pub struct NativeFunction {
+  name: String,
+  arity: usize,
+  function: NativeFn,
+  // the built-in has a [[Construct]] method, it can be used with `new`
+  constructor: bool,
+}
+
+impl NativeFunction {
+  pub fn new(name: &str, arity: usize, function: NativeFn) -> Self {
+    NativeFunction { name: name.to_owned(), arity, function, constructor: false }
+  }
+
+  pub fn new_constructor(name: &str, arity: usize, function: NativeFn) -> Self {
+    NativeFunction { name: name.to_owned(), arity, function, constructor: true }
+  }
+
+  pub fn is_constructor(&self) -> bool {
+    self.constructor
+  }
+
+  pub fn get_name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn get_arity(&self) -> usize {
+    self.arity
+  }
+
+  pub fn call(&self, engine: &mut Engine, this: &Value, arguments: &[Value]) -> Result<Value> {
+    (self.function)(engine, this, arguments)
+  }
+}
+
+impl fmt::Debug for NativeFunction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "NativeFunction({})", self.name)
+  }
+}
+
+// the code object holds constants that may hold this function again, so only the name is shown.
+impl fmt::Debug for FunctionValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "FunctionValue({})", self.get_name())
+  }
+}
@@ -0,0 +1,286 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+7.4.1 Iterator Records
+An Iterator Record is a Record value used to encapsulate an iterator or async iterator along with the next method.
+
+23.1.5 Array Iterator Objects
+22.1.5 String Iterator Objects
+24.1.5 Map Iterator Objects
+24.2.6 Set Iterator Objects
+14.7.5.10 For-In Iterator Objects
+An Array Iterator is an object that represents a specific iteration over some specific Array instance object.
+  A String Iterator does the same over a String value, one code point at a time, Map and Set Iterators over the
+  entries of a Map or a Set. A For-In Iterator walks the enumerable string keys of an object and its prototypes.
+
+@links:
+- https://tc39.es/ecma262/#sec-iterator-records
+- https://tc39.es/ecma262/#sec-array-iterator-objects
+- https://tc39.es/ecma262/#sec-string-iterator-objects
+- https://tc39.es/ecma262/#sec-map-iterator-objects
+- https://tc39.es/ecma262/#sec-set-iterator-objects
+- https://tc39.es/ecma262/#sec-for-in-iterator-objects
+*/
+use crate::values::object::{ObjectKind, ObjectValue};
+use crate::values::property::PropertyKey;
+use crate::values::{StringValue, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+// the [[Iterator]], [[NextMethod]] and [[Done]] fields, it only lives on the stack while a
+// destructuring pattern or a spread reads from it.
+#[derive(Debug)]
+pub struct IteratorRecord {
+  pub iterator: ObjectValue,
+  pub next_method: Value,
+  done: Cell<bool>,
+}
+
+impl IteratorRecord {
+  pub fn new(iterator: ObjectValue, next_method: Value) -> Self {
+    IteratorRecord { iterator, next_method, done: Cell::new(false) }
+  }
+
+  pub fn is_done(&self) -> bool {
+    self.done.get()
+  }
+
+  pub fn set_done(&self) {
+    self.done.set(true);
+  }
+}
+
+// what an iterator over a collection produces: its keys, its values or [key, value] arrays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IterationKind {
+  Key,
+  Value,
+  KeyValue,
+}
+
+// the [[IteratedArrayLike]], [[ArrayLikeNextIndex]] and [[ArrayLikeIterationKind]] slots, the
+// object is dropped once the iteration is over so the iterator stays done.
+#[derive(Debug)]
+pub struct ArrayIterator {
+  iterated: RefCell<Option<ObjectValue>>,
+  next_index: Cell<u64>,
+  kind: IterationKind,
+}
+
+impl ArrayIterator {
+  pub fn get_iterated(&self) -> Option<ObjectValue> {
+    self.iterated.borrow().clone()
+  }
+
+  pub fn get_kind(&self) -> IterationKind {
+    self.kind
+  }
+
+  // the index to read next, moving past it.
+  pub fn advance(&self) -> u64 {
+    let index = self.next_index.get();
+    self.next_index.set(index + 1);
+    index
+  }
+
+  pub fn finish(&self) {
+    *self.iterated.borrow_mut() = None;
+  }
+}
+
+// the string being iterated and the index of the code unit the next code point starts at.
+#[derive(Debug)]
+pub struct StringIterator {
+  string: StringValue,
+  position: Cell<usize>,
+}
+
+impl StringIterator {
+  /*
+  22.1.3.36.1 %String.prototype%[@@iterator] ( ), the closure
+    b. Repeat, while position < len,
+      i. Let cp be CodePointAt(s, position).
+      ii. Let nextIndex be position + cp.[[CodeUnitCount]].
+      iii. Let resultString be the substring of s from position to nextIndex.
+  A lone surrogate is a code point of its own.
+  */
+  pub fn next_code_point(&self) -> Option<StringValue> {
+    let position = self.position.get();
+    if position >= self.string.len() {
+      return None;
+    }
+    let next_index = position + self.string.code_point_at(position).code_unit_count;
+    self.position.set(next_index);
+    Some(self.string.substring(position, next_index))
+  }
+}
+
+// the Map or the Set being iterated and the position of the next entry in its list. the list
+// keeps the place of a deleted entry until the iterator is done, so the position stays right while it changes.
+#[derive(Debug)]
+pub struct CollectionIterator {
+  iterated: RefCell<Option<ObjectValue>>,
+  next_index: Cell<usize>,
+  kind: IterationKind,
+}
+
+impl CollectionIterator {
+  pub fn get_kind(&self) -> IterationKind {
+    self.kind
+  }
+
+  /*
+  24.1.5.1 CreateMapIterator ( map, kind ), the closure
+    b. Let entries be map.[[MapData]].
+    c. Let index be 0.
+    d. Let numEntries be the number of elements in entries.
+    e. Repeat, while index < numEntries,
+      i. Let e be entries[index].
+      ii. Set index to index + 1.
+      iii. If e.[[Key]] is not empty, then
+        ...
+      v. NOTE: The number of elements in entries may have increased while execution of this abstract
+        operation was paused by Yield.
+      vi. Set numEntries to the number of elements in entries.
+  Entries added while the iteration runs are produced too, once the end is reached the iterator stays done.
+  */
+  pub fn next_entry(&self) -> Option<(Value, Value)> {
+    let collection = self.iterated.borrow().clone()?;
+    let data = collection.get_map_data().expect("iterated object to be a Map or a Set");
+    loop {
+      let index = self.next_index.get();
+      let Some(entry) = data.get_entry(index) else {
+        *self.iterated.borrow_mut() = None;
+        data.end_iteration();
+        return None;
+      };
+      self.next_index.set(index + 1);
+      if entry.is_some() {
+        return entry;
+      }
+    }
+  }
+}
+
+// an iterator dropped before it is done lets go of its position.
+impl Drop for CollectionIterator {
+  fn drop(&mut self) {
+    if let Some(data) = self.iterated.get_mut().as_ref().and_then(ObjectValue::get_map_data) {
+      data.end_iteration();
+    }
+  }
+}
+
+/*
+14.7.5.10.2.1 %ForInIteratorPrototype%.next ( )
+  1. Let O be the this value.
+  3. Repeat,
+    a. If O.[[ObjectWasVisited]] is false, then
+      i. Let keys be ? object.[[OwnPropertyKeys]]().
+      ii. For each element key of keys, do
+        1. If key is a String, then
+          a. Append key to O.[[RemainingKeys]].
+      iii. Set O.[[ObjectWasVisited]] to true.
+    b. Repeat, while O.[[RemainingKeys]] is not empty,
+      i. Let r be the first element of O.[[RemainingKeys]].
+      ii. Remove the first element from O.[[RemainingKeys]].
+      iii. If there does not exist an element v of O.[[VisitedKeys]] such that SameValue(r, v) is true, then
+        1. Let desc be ? object.[[GetOwnProperty]](r).
+        2. If desc is not undefined, then
+          a. Append r to O.[[VisitedKeys]].
+          b. If desc.[[Enumerable]] is true, return CreateIterResultObject(r, false).
+    c. Set object to ? object.[[GetPrototypeOf]]().
+    d. Set O.[[Object]] to object.
+    e. Set O.[[ObjectWasVisited]] to false.
+    f. If object is null, return CreateIterResultObject(undefined, true).
+A key deleted before it is reached is skipped, a key shadowed by an object closer to the start is visited
+  once, and the iterator of undefined or null has nothing to visit.
+*/
+#[derive(Debug)]
+pub struct ForInIterator {
+  object: RefCell<Option<ObjectValue>>,
+  object_was_visited: Cell<bool>,
+  visited_keys: RefCell<Vec<PropertyKey>>,
+  remaining_keys: RefCell<VecDeque<PropertyKey>>,
+}
+
+impl ForInIterator {
+  pub fn next_key(&self) -> Option<PropertyKey> {
+    loop {
+      let object = self.object.borrow().clone()?;
+      if !self.object_was_visited.get() {
+        let keys = object.own_property_keys().into_iter().filter(|key| !key.is_symbol());
+        self.remaining_keys.borrow_mut().extend(keys);
+        self.object_was_visited.set(true);
+      }
+      while let Some(key) = self.remaining_keys.borrow_mut().pop_front() {
+        if self.visited_keys.borrow().contains(&key) {
+          continue;
+        }
+        let Some(property) = object.get_own_property(&key) else {
+          continue;
+        };
+        self.visited_keys.borrow_mut().push(key.clone());
+        if property.enumerable {
+          return Some(key);
+        }
+      }
+      *self.object.borrow_mut() = object.get_prototype();
+      self.object_was_visited.set(false);
+    }
+  }
+}
+
+impl ObjectValue {
+  pub fn new_iterator_record(record: IteratorRecord) -> Self {
+    ObjectValue::new_with_kind(ObjectKind::IteratorRecord(record), None)
+  }
+
+  // 23.1.5.1 CreateArrayIterator ( array, kind )
+  pub fn new_array_iterator(array: ObjectValue, kind: IterationKind, prototype: Option<ObjectValue>) -> Self {
+    let iterator = ArrayIterator { iterated: RefCell::new(Some(array)), next_index: Cell::new(0), kind };
+    ObjectValue::new_with_kind(ObjectKind::ArrayIterator(iterator), prototype)
+  }
+
+  // 24.1.5.1 CreateMapIterator ( map, kind ) and 24.2.6.1 CreateSetIterator ( set, kind )
+  pub fn new_collection_iterator(collection: ObjectValue, kind: IterationKind, prototype: Option<ObjectValue>) -> Self {
+    let is_map = matches!(collection.get_kind(), ObjectKind::Map(_));
+    collection
+      .get_map_data()
+      .expect("iterated object to be a Map or a Set")
+      .start_iteration();
+    let iterator = CollectionIterator { iterated: RefCell::new(Some(collection)), next_index: Cell::new(0), kind };
+    let kind = match is_map {
+      true => ObjectKind::MapIterator(iterator),
+      false => ObjectKind::SetIterator(iterator),
+    };
+    ObjectValue::new_with_kind(kind, prototype)
+  }
+
+  // 14.7.5.10.1 CreateForInIterator ( object )
+  pub fn new_for_in_iterator(object: Option<ObjectValue>, prototype: Option<ObjectValue>) -> Self {
+    let iterator = ForInIterator {
+      object: RefCell::new(object),
+      object_was_visited: Cell::new(false),
+      visited_keys: RefCell::new(Vec::new()),
+      remaining_keys: RefCell::new(VecDeque::new()),
+    };
+    ObjectValue::new_with_kind(ObjectKind::ForInIterator(iterator), prototype)
+  }
+
+  pub fn new_string_iterator(string: StringValue, prototype: Option<ObjectValue>) -> Self {
+    let iterator = StringIterator { string, position: Cell::new(0) };
+    ObjectValue::new_with_kind(ObjectKind::StringIterator(iterator), prototype)
+  }
+
+  pub fn get_iterator_record(&self) -> Option<&IteratorRecord> {
+    match self.get_kind() {
+      ObjectKind::IteratorRecord(record) => Some(record),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,206 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+24.1 Map Objects
+24.2 Set Objects
+A Map keeps its entries in the [[MapData]] list and a Set its values in the [[SetData]] list, in the order they
+  were added. Keys are told apart with SameValueZero, so NaN is a key of its own and -0 is the same key as +0.
+Maps must be implemented using either hash tables or other mechanisms that, on average, provide access times that
+  are sublinear on the number of elements in the collection.
+
+@links:
+- https://tc39.es/ecma262/#sec-map-objects
+- https://tc39.es/ecma262/#sec-set-objects
+*/
+use crate::errors::Result;
+use crate::values::object::{ObjectKind, ObjectValue};
+use crate::values::{BigIntValue, StringValue, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+// deleted entries are only dropped from the list once they are this many and half of it.
+const COMPACT_THRESHOLD: usize = 16;
+
+// a key as SameValueZero sees it: numbers by their value with every NaN alike and -0 as +0, strings and
+// BigInts by their contents and symbols and objects by their identity.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+  Undefined,
+  Null,
+  Boolean(bool),
+  Number(u64),
+  String(StringValue),
+  BigInt(BigIntValue),
+  Symbol(usize),
+  Object(usize),
+}
+
+impl MapKey {
+  fn new(value: &Value) -> Self {
+    match value {
+      Value::Undefined(_) | Value::Uninitialized => MapKey::Undefined,
+      Value::Null(_) => MapKey::Null,
+      Value::Boolean(boolean) => MapKey::Boolean(boolean.get()),
+      Value::Number(number) if number.is_nan() => MapKey::Number(f64::NAN.to_bits()),
+      // -0 and +0 are equal, adding +0 turns -0 into it
+      Value::Number(number) => MapKey::Number((number.as_f64() + 0.0).to_bits()),
+      Value::String(string) => MapKey::String(string.clone()),
+      Value::BigInt(bigint) => MapKey::BigInt(bigint.clone()),
+      Value::Symbol(symbol) => MapKey::Symbol(symbol.get_id()),
+      Value::Object(object) => MapKey::Object(object.get_id()),
+    }
+  }
+}
+
+// the entries of a Map, or the values of a Set kept as their own key. a deleted entry leaves an
+// empty place behind, the iterators over the list hold positions in it. the places are only
+// dropped while no iteration runs, `index` finds the place of every key.
+#[derive(Debug, Default)]
+pub struct MapData {
+  entries: RefCell<Vec<Option<(Value, Value)>>>,
+  index: RefCell<HashMap<MapKey, usize>>,
+  size: Cell<usize>,
+  iterations: Cell<usize>,
+}
+
+impl MapData {
+  fn find(&self, key: &Value) -> Option<usize> {
+    self.index.borrow().get(&MapKey::new(key)).copied()
+  }
+
+  pub fn get(&self, key: &Value) -> Option<Value> {
+    let index = self.find(key)?;
+    let entries = self.entries.borrow();
+    entries[index].as_ref().map(|(_, value)| value.clone())
+  }
+
+  pub fn has(&self, key: &Value) -> bool {
+    self.find(key).is_some()
+  }
+
+  /*
+  24.1.3.9 Map.prototype.set ( key, value )
+    4. For each Record { [[Key]], [[Value]] } p of M.[[MapData]], do
+      a. If p.[[Key]] is not empty and SameValueZero(p.[[Key]], key) is true, then
+        i. Set p.[[Value]] to value.
+        ii. Return M.
+    5. Set key to CanonicalizeKeyedCollectionKey(key).
+    6. Let p be the Record { [[Key]]: key, [[Value]]: value }.
+    7. Append p to M.[[MapData]].
+  */
+  pub fn set(&self, key: Value, value: Value) {
+    if let Some(index) = self.find(&key) {
+      if let Some(entry) = &mut self.entries.borrow_mut()[index] {
+        entry.1 = value;
+      }
+      return;
+    }
+    let key = match key {
+      Value::Number(number) if number.as_f64() == 0.0 => Value::create_number_value(0),
+      key => key,
+    };
+    let mut entries = self.entries.borrow_mut();
+    self.index.borrow_mut().insert(MapKey::new(&key), entries.len());
+    entries.push(Some((key, value)));
+    self.size.set(self.size.get() + 1);
+  }
+
+  pub fn delete(&self, key: &Value) -> bool {
+    let Some(index) = self.index.borrow_mut().remove(&MapKey::new(key)) else {
+      return false;
+    };
+    self.entries.borrow_mut()[index] = None;
+    self.size.set(self.size.get() - 1);
+    self.compact();
+    true
+  }
+
+  // the places of the entries are kept while an iteration runs, it sees the entries added later.
+  pub fn clear(&self) {
+    self.index.borrow_mut().clear();
+    let mut entries = self.entries.borrow_mut();
+    match self.iterations.get() {
+      0 => entries.clear(),
+      _ => entries.iter_mut().for_each(|entry| *entry = None),
+    }
+    self.size.set(0);
+  }
+
+  pub fn size(&self) -> usize {
+    self.size.get()
+  }
+
+  // the entries left, in the order they were added.
+  pub fn get_entries(&self) -> Vec<(Value, Value)> {
+    self.entries.borrow().iter().flatten().cloned().collect()
+  }
+
+  // the entry at `index` of the list, None past its end and an empty place for a deleted one.
+  pub fn get_entry(&self, index: usize) -> Option<Option<(Value, Value)>> {
+    self.entries.borrow().get(index).cloned()
+  }
+
+  // an iterator holds a position in the list from the time it is created until it is done.
+  pub fn start_iteration(&self) {
+    self.iterations.set(self.iterations.get() + 1);
+  }
+
+  pub fn end_iteration(&self) {
+    self.iterations.set(self.iterations.get() - 1);
+    self.compact();
+  }
+
+  // calls `callback` with the entries in order, the ones added by it included, like forEach does.
+  pub fn for_each_entry(&self, mut callback: impl FnMut(Value, Value) -> Result<()>) -> Result<()> {
+    self.start_iteration();
+    let mut index = 0;
+    let mut result = Ok(());
+    while let Some(entry) = self.get_entry(index) {
+      index += 1;
+      if let Some((key, value)) = entry {
+        result = callback(key, value);
+        if result.is_err() {
+          break;
+        }
+      }
+    }
+    self.end_iteration();
+    result
+  }
+
+  // drops the places of the deleted entries once they are most of the list and no position in it is held.
+  fn compact(&self) {
+    let mut entries = self.entries.borrow_mut();
+    let deleted = entries.len() - self.size.get();
+    if self.iterations.get() > 0 || deleted < COMPACT_THRESHOLD || deleted * 2 < entries.len() {
+      return;
+    }
+    entries.retain(Option::is_some);
+    let mut index = self.index.borrow_mut();
+    for (position, (key, _)) in entries.iter().flatten().enumerate() {
+      index.insert(MapKey::new(key), position);
+    }
+  }
+}
+
+impl ObjectValue {
+  pub fn new_map(prototype: Option<ObjectValue>) -> Self {
+    ObjectValue::new_with_kind(ObjectKind::Map(MapData::default()), prototype)
+  }
+
+  pub fn new_set(prototype: Option<ObjectValue>) -> Self {
+    ObjectValue::new_with_kind(ObjectKind::Set(MapData::default()), prototype)
+  }
+
+  // the [[MapData]] of a Map or the [[SetData]] of a Set.
+  pub fn get_map_data(&self) -> Option<&MapData> {
+    match self.get_kind() {
+      ObjectKind::Map(data) | ObjectKind::Set(data) => Some(data),
+      _ => None,
+    }
+  }
+}
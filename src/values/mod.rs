@@ -13,27 +13,32 @@ An ECMAScript language value is a value that is characterized by an ECMAScript l
 */
 #![allow(dead_code)]
 
+pub mod array;
 pub mod bigint;
 pub mod boolean;
+pub mod function;
+pub mod iterator;
+pub mod map;
 pub mod null;
 pub mod number;
 pub mod object;
+pub mod property;
 pub mod string;
 pub mod symbol;
 pub mod undefined;
 
 // pub use self::array::ArrayValue;
-// pub use self::function::FunctionValue;
 pub use self::bigint::BigIntValue;
 pub use self::boolean::BooleanValue;
+pub use self::function::{FunctionValue, NativeFunction};
 pub use self::null::NullValue;
 pub use self::number::NumberValue;
-pub use self::object::ObjectValue;
+pub use self::object::{ObjectKind, ObjectValue};
 pub use self::string::StringValue;
 pub use self::symbol::SymbolValue;
 pub use self::undefined::UndefinedValue;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Value {
   Undefined(UndefinedValue),
   Null(NullValue),
@@ -43,6 +48,8 @@ pub enum Value {
   Number(NumberValue),
   BigInt(BigIntValue),
   Object(ObjectValue),
+  // a binding in its temporal dead zone, scripts can never observe it
+  Uninitialized,
 }
 
 impl Value {
@@ -50,6 +57,10 @@ impl Value {
     Value::Undefined(UndefinedValue::new())
   }
 
+  pub fn create_uninitialized_value() -> Self {
+    Value::Uninitialized
+  }
+
   pub fn create_null_value() -> Self {
     Value::Null(NullValue::new())
   }
@@ -62,168 +73,153 @@ impl Value {
     Value::String(StringValue::new(value))
   }
 
-  pub fn create_symbol_value(value: String) -> Self {
-    Value::Symbol(SymbolValue::new(value))
+  pub fn create_symbol_value(description: Option<String>) -> Self {
+    Value::Symbol(SymbolValue::new(description))
   }
 
   pub fn create_number_value(value: i64) -> Self {
     Value::Number(NumberValue::new(value))
   }
 
-  pub fn create_bigint_value(value: i128) -> Self {
-    Value::BigInt(BigIntValue::new(value))
+  pub fn create_float_value(value: f64) -> Self {
+    Value::Number(NumberValue::new_float(value))
+  }
+
+  pub fn create_bigint_value(value: i64) -> Self {
+    Value::BigInt(BigIntValue::from_i64(value))
   }
 
   pub fn create_object_value() -> Self {
     Value::Object(ObjectValue::new())
   }
-}
 
-// ----------------
-// pub enum Value {
-//   Number(NumberValue),
-//   String(StringValue),
-//   Boolean(BooleanValue),
-//   Object(ObjectValue),
-//   Undefined,
-//   EOL,
-// }
-// #[allow(dead_code)]
-// impl Value {
-//   pub fn is_number(&self) -> bool {
-//     match self {
-//       Value::Number(_) => true,
-//       _ => false,
-//     }
-//   }
-//   pub fn is_equal(&self, other: &Value) -> bool {
-//     match self {
-//       Value::Number(n) => n.is_equal(other),
-//       Value::String(s) => s.is_equal(other),
-//       Value::Boolean(b) => b.is_equal(other),
-//       Value::Object(o) => o.is_equal(other),
-//       _ => false,
-//     }
-//   }
-//   pub fn is_truthy(&self) -> bool {
-//     match self {
-//       Value::Number(n) => n.is_truthy(),
-//       Value::String(s) => s.is_truthy(),
-//       Value::Boolean(b) => b.is_truthy(),
-//       Value::Object(o) => o.is_truthy(),
-//       Value::Undefined => false,
-//       _ => false,
-//     }
-//   }
-
-//   pub fn is_falsy(&self) -> bool {
-//     !self.is_truthy()
-//   }
-//   pub fn get_integer(&self) -> i64 {
-//     match self {
-//       Value::Number(num) => match &num {
-//         NumberValue::Integer(i) => *i,
-//         _ => 0,
-//       },
-//       _ => panic!("Value is not a number"),
-//     }
-//   }
-//   pub fn is_integer(&self) -> bool {
-//     if let Value::Number(NumberValue::Integer(_)) = self {
-//       return true;
-//     }
-//     return false;
-//   }
-//   pub fn get_float(&self) -> f64 {
-//     if let Value::Number(NumberValue::Float(f)) = self {
-//       return *f;
-//     }
-//     panic!("Value is not a float")
-//   }
-
-//   pub fn is_float(&self) -> bool {
-//     if let Value::Number(NumberValue::Float(_)) = self {
-//       return true;
-//     }
-//     return false;
-//   }
-//   pub fn is_string(&self) -> bool {
-//     if let Value::String(_) = self {
-//       return true;
-//     }
-//     return false;
-//   }
-//   pub fn get_string(&self) -> String {
-//     if let Value::String(StringValue::String(s)) = self {
-//       return s.clone();
-//     }
-//     panic!("Value is not a string")
-//   }
-
-//   pub fn is_boolean(&self) -> bool {
-//     if let Value::Boolean(_) = self {
-//       return true;
-//     }
-//     return false;
-//   }
-//   pub fn get_boolean(&self) -> bool {
-//     if let Value::Boolean(value) = self {
-//       return value.is_truthy();
-//     }
-//     panic!("Value is not a boolean")
-//   }
-//   pub fn is_undefined(&self) -> bool {
-//     if let Value::Undefined = self {
-//       return true;
-//     }
-//     return false;
-//   }
-
-//   pub fn _is_same_type(&self, other: &Value) -> bool {
-//     match (self, other) {
-//       (Value::Number(_), Value::Number(_)) => true,
-//       (Value::String(_), Value::String(_)) => true,
-//       (Value::Boolean(_), Value::Boolean(_)) => true,
-//       (Value::Object(_), Value::Object(_)) => true,
-//       (Value::Undefined, Value::Undefined) => true,
-//       (Value::EOL, Value::EOL) => true,
-//       _ => false,
-//     }
-//   }
-
-//   // built-in functions
-//   pub fn new_integer(value: i64) -> Value {
-//     Value::Number(NumberValue::Integer(value))
-//   }
-//   pub fn new_string(value: String) -> Value {
-//     Value::String(StringValue::String(value))
-//   }
-//   pub fn new_boolean(value: bool) -> Value {
-//     if value {
-//       Value::Boolean(BooleanValue::True)
-//     } else {
-//       Value::Boolean(BooleanValue::False)
-//     }
-//   }
-//   pub fn new_undefined() -> Value {
-//     Value::Undefined
-//   }
-//   pub fn new_eol() -> Value {
-//     Value::EOL
-//   }
-//   pub fn new_float(value: f64) -> Value {
-//     Value::Number(NumberValue::Float(value))
-//   }
-//   pub fn new_null() -> Value {
-//     Value::Object(ObjectValue::Null)
-//   }
-//   pub fn new_hex(value: i64) -> Value {
-//     Value::Number(NumberValue::Hex(value))
-//   }
-//   pub fn new_octal(value: i64) -> Value {
-//     Value::Number(NumberValue::Octal(value))
-//   }
-//   pub fn new_binary(value: i64) -> Value {
-//     Value::Number(NumberValue::Binary(value))
-//   }
-// }
+  pub fn create_function_value(function: FunctionValue) -> Self {
+    Value::Object(ObjectValue::new_function(function))
+  }
+
+  pub fn create_native_function_value(function: NativeFunction) -> Self {
+    Value::Object(ObjectValue::new_with_kind(ObjectKind::NativeFunction(function), None))
+  }
+
+  pub fn is_undefined(&self) -> bool {
+    matches!(self, Value::Undefined(_))
+  }
+
+  pub fn is_uninitialized(&self) -> bool {
+    matches!(self, Value::Uninitialized)
+  }
+
+  pub fn is_null(&self) -> bool {
+    matches!(self, Value::Null(_))
+  }
+
+  pub fn is_boolean(&self) -> bool {
+    matches!(self, Value::Boolean(_))
+  }
+
+  pub fn is_string(&self) -> bool {
+    matches!(self, Value::String(_))
+  }
+
+  pub fn is_number(&self) -> bool {
+    matches!(self, Value::Number(_))
+  }
+
+  pub fn is_symbol(&self) -> bool {
+    matches!(self, Value::Symbol(_))
+  }
+
+  pub fn is_function(&self) -> bool {
+    matches!(self, Value::Object(object) if object.is_function())
+  }
+
+  pub fn get_function(&self) -> Option<&FunctionValue> {
+    match self {
+      Value::Object(object) => object.get_function(),
+      _ => None,
+    }
+  }
+
+  pub fn get_object(&self) -> Option<&ObjectValue> {
+    match self {
+      Value::Object(object) => Some(object),
+      _ => None,
+    }
+  }
+
+  pub fn is_object(&self) -> bool {
+    matches!(self, Value::Object(_))
+  }
+
+  pub fn is_integer(&self) -> bool {
+    matches!(self, Value::Number(number) if number.is_integer())
+  }
+
+  pub fn is_float(&self) -> bool {
+    matches!(self, Value::Number(number) if number.is_float())
+  }
+
+  pub fn get_boolean(&self) -> bool {
+    if let Value::Boolean(value) = self {
+      return value.get();
+    }
+    panic!("Value is not a boolean")
+  }
+
+  pub fn get_string(&self) -> String {
+    if let Value::String(value) = self {
+      return value.get();
+    }
+    panic!("Value is not a string")
+  }
+
+  pub fn get_integer(&self) -> i64 {
+    if let Value::Number(NumberValue::Integer(value)) = self {
+      return *value;
+    }
+    panic!("Value is not an integer")
+  }
+
+  pub fn get_float(&self) -> f64 {
+    if let Value::Number(NumberValue::Float(value)) = self {
+      return *value;
+    }
+    panic!("Value is not a float")
+  }
+
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      Value::Undefined(_) | Value::Null(_) | Value::Uninitialized => false,
+      Value::Boolean(value) => value.get(),
+      Value::Number(value) => {
+        let number = value.as_f64();
+        number != 0.0 && !number.is_nan()
+      }
+      Value::String(value) => !value.is_empty(),
+      Value::BigInt(value) => !value.is_zero(),
+      Value::Symbol(_) | Value::Object(_) => true,
+    }
+  }
+
+  /*
+  13.5.3.1 Runtime Semantics: Evaluation, typeof UnaryExpression
+  The type names of Table 41, objects with a [[Call]] internal method are "function".
+  */
+  pub fn type_of(&self) -> &'static str {
+    match self {
+      Value::Undefined(_) | Value::Uninitialized => "undefined",
+      Value::Null(_) => "object",
+      Value::Boolean(_) => "boolean",
+      Value::Number(_) => "number",
+      Value::String(_) => "string",
+      Value::Symbol(_) => "symbol",
+      Value::BigInt(_) => "bigint",
+      Value::Object(object) if object.is_function() => "function",
+      Value::Object(_) => "object",
+    }
+  }
+
+  pub fn is_falsy(&self) -> bool {
+    !self.is_truthy()
+  }
+}
@@ -5,15 +5,218 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 /*
+6.1.6.1 The Number Type
+The Number type has exactly 18437736874454810627 values, representing the double-precision floating point
+  IEEE 754-2019 binary64 values, including NaN, positive and negative zero and the two infinities.
+
+Integral values in the safe integer range are kept as an i64 so the common arithmetic stays on integers, every
+  operation gives the same result the double would. -0 is never an integer, it is only kept as a double.
+
+@links:
+- https://tc39.es/ecma262/#sec-ecmascript-language-types-number-type
 */
+#![allow(dead_code)]
+use crate::vm::coercion::{to_int32, to_uint32};
+
+// 2^53 - 1, the largest integer every smaller integer is exactly representable below.
+pub const MAX_SAFE_INTEGER: i64 = 9007199254740991;
 
-#[derive(Debug)]
-pub struct NumberValue {
-  value: i64,
+#[derive(Debug, Clone, Copy)]
+pub enum NumberValue {
+  Integer(i64),
+  Float(f64),
 }
 
 impl NumberValue {
   pub fn new(value: i64) -> Self {
-    NumberValue { value }
+    if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&value) {
+      return NumberValue::Integer(value);
+    }
+    NumberValue::Float(value as f64)
+  }
+
+  // the integer representation is used whenever the double holds a safe integer.
+  pub fn new_float(value: f64) -> Self {
+    let is_negative_zero = value == 0.0 && value.is_sign_negative();
+    if value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER as f64 && !is_negative_zero {
+      return NumberValue::Integer(value as i64);
+    }
+    NumberValue::Float(value)
+  }
+
+  pub fn is_integer(&self) -> bool {
+    matches!(self, NumberValue::Integer(_))
+  }
+
+  pub fn is_float(&self) -> bool {
+    matches!(self, NumberValue::Float(_))
+  }
+
+  pub fn as_f64(&self) -> f64 {
+    match self {
+      NumberValue::Integer(value) => *value as f64,
+      NumberValue::Float(value) => *value,
+    }
+  }
+
+  pub fn is_nan(&self) -> bool {
+    matches!(self, NumberValue::Float(value) if value.is_nan())
+  }
+
+  // 6.1.6.1.7 Number::add ( x, y )
+  pub fn add(self, other: NumberValue) -> NumberValue {
+    match (self, other) {
+      // two safe integers never overflow an i64
+      (NumberValue::Integer(left), NumberValue::Integer(right)) => NumberValue::new(left + right),
+      _ => NumberValue::new_float(self.as_f64() + other.as_f64()),
+    }
+  }
+
+  // 6.1.6.1.8 Number::subtract ( x, y )
+  pub fn subtract(self, other: NumberValue) -> NumberValue {
+    match (self, other) {
+      (NumberValue::Integer(left), NumberValue::Integer(right)) => NumberValue::new(left - right),
+      _ => NumberValue::new_float(self.as_f64() - other.as_f64()),
+    }
+  }
+
+  /*
+  6.1.6.1.4 Number::multiply ( x, y )
+  A zero product of a negative operand is -0, which only the double can hold.
+  */
+  pub fn multiply(self, other: NumberValue) -> NumberValue {
+    if let (NumberValue::Integer(left), NumberValue::Integer(right)) = (self, other) {
+      match left.checked_mul(right) {
+        Some(0) if left < 0 || right < 0 => return NumberValue::Float(-0.0),
+        Some(product) => return NumberValue::new(product),
+        None => {}
+      }
+    }
+    NumberValue::new_float(self.as_f64() * other.as_f64())
+  }
+
+  // 6.1.6.1.5 Number::divide ( x, y )
+  pub fn divide(self, other: NumberValue) -> NumberValue {
+    NumberValue::new_float(self.as_f64() / other.as_f64())
+  }
+
+  /*
+  6.1.6.1.1 Number::unaryMinus ( x )
+    1. If x is NaN, return NaN.
+    2. Return the negation of x; that is, compute a Number with the same magnitude but opposite sign.
+  */
+  pub fn unary_minus(self) -> NumberValue {
+    match self {
+      NumberValue::Integer(0) => NumberValue::Float(-0.0),
+      NumberValue::Integer(value) => NumberValue::Integer(-value),
+      NumberValue::Float(value) => NumberValue::new_float(-value),
+    }
+  }
+
+  // 6.1.6.1.2 Number::bitwiseNOT ( x )
+  pub fn bitwise_not(self) -> NumberValue {
+    NumberValue::new(i64::from(!to_int32(self.as_f64())))
+  }
+
+  /*
+  6.1.6.1.3 Number::exponentiate ( base, exponent )
+    1. If exponent is NaN, return NaN.
+    2. If exponent is either +0𝔽 or -0𝔽, return 1𝔽.
+    3. If base is NaN, return NaN.
+    ...
+    10. If exponent is either +∞𝔽 or -∞𝔽, then
+      b. If abs(ℝ(base)) = 1, return NaN.
+  The other cases are what the double power already gives.
+  */
+  pub fn exponentiate(self, exponent: NumberValue) -> NumberValue {
+    if let (NumberValue::Integer(base), NumberValue::Integer(power)) = (self, exponent) {
+      if let Some(result) = u32::try_from(power).ok().and_then(|power| base.checked_pow(power)) {
+        return NumberValue::new(result);
+      }
+    }
+    let (base, exponent) = (self.as_f64(), exponent.as_f64());
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+      return NumberValue::Float(f64::NAN);
+    }
+    NumberValue::new_float(base.powf(exponent))
+  }
+
+  /*
+  6.1.6.1.6 Number::remainder ( n, d )
+  The result takes the sign of the dividend, so a zero remainder of a negative dividend is -0. That is the
+    truncating remainder the double gives as well.
+  */
+  pub fn remainder(self, other: NumberValue) -> NumberValue {
+    if let (NumberValue::Integer(dividend), NumberValue::Integer(divisor)) = (self, other) {
+      match dividend.checked_rem(divisor) {
+        Some(0) if dividend < 0 => return NumberValue::Float(-0.0),
+        Some(remainder) => return NumberValue::Integer(remainder),
+        None => {}
+      }
+    }
+    NumberValue::new_float(self.as_f64() % other.as_f64())
+  }
+
+  /*
+  6.1.6.1.9 Number::leftShift ( x, y )
+    1. Let lnum be ! ToInt32(x).
+    2. Let rnum be ! ToUint32(y).
+    3. Let shiftCount be ℝ(rnum) modulo 32.
+    4. Return the result of left shifting lnum by shiftCount bits.
+  */
+  pub fn left_shift(self, other: NumberValue) -> NumberValue {
+    let shift = to_uint32(other.as_f64()) % 32;
+    NumberValue::new(i64::from(to_int32(self.as_f64()).wrapping_shl(shift)))
+  }
+
+  // 6.1.6.1.10 Number::signedRightShift ( x, y ), the sign bit is copied in from the left.
+  pub fn signed_right_shift(self, other: NumberValue) -> NumberValue {
+    let shift = to_uint32(other.as_f64()) % 32;
+    NumberValue::new(i64::from(to_int32(self.as_f64()) >> shift))
+  }
+
+  // 6.1.6.1.11 Number::unsignedRightShift ( x, y ), zeros are shifted in from the left.
+  pub fn unsigned_right_shift(self, other: NumberValue) -> NumberValue {
+    let shift = to_uint32(other.as_f64()) % 32;
+    NumberValue::new(i64::from(to_uint32(self.as_f64()) >> shift))
+  }
+
+  /*
+  6.1.6.1.12 Number::lessThan ( x, y )
+    1. If x is NaN, return undefined.
+    2. If y is NaN, return undefined.
+  None stands for undefined, the comparisons that see it are false.
+  */
+  pub fn less_than(&self, other: &NumberValue) -> Option<bool> {
+    if let (NumberValue::Integer(left), NumberValue::Integer(right)) = (self, other) {
+      return Some(left < right);
+    }
+    let (left, right) = (self.as_f64(), other.as_f64());
+    if left.is_nan() || right.is_nan() {
+      return None;
+    }
+    Some(left < right)
+  }
+
+  // 6.1.6.1.17 NumberBitwiseOp ( op, x, y ), the operands are converted with ToInt32.
+  pub fn bitwise_and(self, other: NumberValue) -> NumberValue {
+    NumberValue::new(i64::from(to_int32(self.as_f64()) & to_int32(other.as_f64())))
+  }
+
+  pub fn bitwise_xor(self, other: NumberValue) -> NumberValue {
+    NumberValue::new(i64::from(to_int32(self.as_f64()) ^ to_int32(other.as_f64())))
+  }
+
+  pub fn bitwise_or(self, other: NumberValue) -> NumberValue {
+    NumberValue::new(i64::from(to_int32(self.as_f64()) | to_int32(other.as_f64())))
+  }
+
+  // 6.1.6.1.14 Number::sameValue ( x, y ), NaN is the same as itself and -0 differs from +0.
+  pub fn same_value(&self, other: &NumberValue) -> bool {
+    let (left, right) = (self.as_f64(), other.as_f64());
+    if left.is_nan() && right.is_nan() {
+      return true;
+    }
+    left.to_bits() == right.to_bits()
   }
 }
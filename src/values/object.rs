@@ -5,13 +5,503 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 /*
+10.1 Ordinary Object Internal Methods and Internal Slots
+All ordinary objects have an internal slot called [[Prototype]]. The value of this internal slot is either null
+  or an object and is used for implementing inheritance.
+Every ordinary object has a Boolean-valued [[Extensible]] internal slot which is used to fulfill the
+  extensibility-related internal method invariants.
+
+The internal methods calling back into script code, [[Get]] and [[Set]] through accessors, live on the engine.
+
+@links:
+- https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots
 */
+use crate::values::function::{BoundFunction, FunctionValue, NativeFunction};
+use crate::values::iterator::{ArrayIterator, CollectionIterator, ForInIterator, IteratorRecord, StringIterator};
+use crate::values::map::MapData;
+use crate::values::property::{
+  PrivateElement, PrivateElementKind, Property, PropertyDescriptor, PropertyKey, PropertySlot,
+};
+use crate::values::{BigIntValue, BooleanValue, NumberValue, StringValue, SymbolValue, Value};
+use crate::vm::comparison::same_value;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct ObjectValue {
+  object: Rc<Object>,
+}
 
+// what makes an object exotic, or the internal slots it carries.
 #[derive(Debug)]
-pub struct ObjectValue {}
+pub enum ObjectKind {
+  Ordinary,
+  Function(FunctionValue),
+  NativeFunction(NativeFunction),
+  BoundFunction(BoundFunction),
+  // an instance of one of the error constructors, the [[ErrorData]] slot
+  Error,
+  // an array exotic object, its `length` follows the largest index
+  Array,
+  // a Boolean object, the [[BooleanData]] slot
+  Boolean(BooleanValue),
+  // a Number object, the [[NumberData]] slot
+  Number(NumberValue),
+  // a String object, the [[StringData]] slot
+  String(StringValue),
+  // a Symbol object, the [[SymbolData]] slot
+  Symbol(SymbolValue),
+  // a BigInt object, the [[BigIntData]] slot
+  BigInt(BigIntValue),
+  // a Map, the [[MapData]] slot
+  Map(MapData),
+  // a Set, the [[SetData]] slot
+  Set(MapData),
+  ArrayIterator(ArrayIterator),
+  StringIterator(StringIterator),
+  MapIterator(CollectionIterator),
+  SetIterator(CollectionIterator),
+  ForInIterator(ForInIterator),
+  // not a language value, the iterator a pattern or a spread is reading from
+  IteratorRecord(IteratorRecord),
+}
+
+struct Object {
+  kind: ObjectKind,
+  properties: RefCell<PropertyMap>,
+  prototype: RefCell<Option<ObjectValue>>,
+  extensible: Cell<bool>,
+  // [[PrivateElements]], the private fields, methods and accessors classes added to the object
+  private_elements: RefCell<Vec<PrivateElement>>,
+}
+
+// properties by key, remembering the order they were created in.
+#[derive(Default)]
+struct PropertyMap {
+  keys: Vec<PropertyKey>,
+  values: HashMap<PropertyKey, Property>,
+}
+
+impl PropertyMap {
+  fn insert(&mut self, key: PropertyKey, property: Property) {
+    if self.values.insert(key.clone(), property).is_none() {
+      self.keys.push(key);
+    }
+  }
+
+  fn remove(&mut self, key: &PropertyKey) {
+    if self.values.remove(key).is_some() {
+      self.keys.retain(|current| current != key);
+    }
+  }
+}
 
 impl ObjectValue {
   pub fn new() -> Self {
-    ObjectValue {}
+    ObjectValue::new_with_kind(ObjectKind::Ordinary, None)
+  }
+
+  pub fn new_function(function: FunctionValue) -> Self {
+    ObjectValue::new_with_kind(ObjectKind::Function(function), None)
+  }
+
+  pub fn new_with_kind(kind: ObjectKind, prototype: Option<ObjectValue>) -> Self {
+    let object = Object {
+      kind,
+      properties: RefCell::new(PropertyMap::default()),
+      prototype: RefCell::new(prototype),
+      extensible: Cell::new(true),
+      private_elements: RefCell::new(Vec::new()),
+    };
+    ObjectValue { object: Rc::new(object) }
+  }
+
+  pub fn get_kind(&self) -> &ObjectKind {
+    &self.object.kind
+  }
+
+  pub fn get_function(&self) -> Option<&FunctionValue> {
+    match &self.object.kind {
+      ObjectKind::Function(function) => Some(function),
+      _ => None,
+    }
+  }
+
+  pub fn get_native_function(&self) -> Option<&NativeFunction> {
+    match &self.object.kind {
+      ObjectKind::NativeFunction(function) => Some(function),
+      _ => None,
+    }
+  }
+
+  pub fn get_bound_function(&self) -> Option<&BoundFunction> {
+    match &self.object.kind {
+      ObjectKind::BoundFunction(function) => Some(function),
+      _ => None,
+    }
+  }
+
+  pub fn is_function(&self) -> bool {
+    matches!(
+      self.object.kind,
+      ObjectKind::Function(_) | ObjectKind::NativeFunction(_) | ObjectKind::BoundFunction(_)
+    )
+  }
+
+  // 7.2.4 IsConstructor ( argument ), the function objects having a [[Construct]] internal method.
+  pub fn is_constructor(&self) -> bool {
+    match &self.object.kind {
+      ObjectKind::Function(function) => function.get_kind().is_constructor(),
+      ObjectKind::NativeFunction(function) => function.is_constructor(),
+      // 10.4.1.3 BoundFunctionCreate, it has [[Construct]] when its target has one
+      ObjectKind::BoundFunction(function) => function.get_target().is_constructor(),
+      _ => false,
+    }
+  }
+
+  pub fn is_error(&self) -> bool {
+    matches!(self.object.kind, ObjectKind::Error)
+  }
+
+  pub fn is_array(&self) -> bool {
+    matches!(self.object.kind, ObjectKind::Array)
+  }
+
+  // the [[BooleanData]] slot of a Boolean object.
+  pub fn get_boolean_data(&self) -> Option<&BooleanValue> {
+    match &self.object.kind {
+      ObjectKind::Boolean(boolean) => Some(boolean),
+      _ => None,
+    }
+  }
+
+  // the [[NumberData]] slot of a Number object.
+  pub fn get_number_data(&self) -> Option<&NumberValue> {
+    match &self.object.kind {
+      ObjectKind::Number(number) => Some(number),
+      _ => None,
+    }
+  }
+
+  // the [[SymbolData]] slot of a Symbol object.
+  pub fn get_symbol_data(&self) -> Option<&SymbolValue> {
+    match &self.object.kind {
+      ObjectKind::Symbol(symbol) => Some(symbol),
+      _ => None,
+    }
+  }
+
+  // the [[BigIntData]] slot of a BigInt object.
+  pub fn get_bigint_data(&self) -> Option<&BigIntValue> {
+    match &self.object.kind {
+      ObjectKind::BigInt(bigint) => Some(bigint),
+      _ => None,
+    }
+  }
+
+  // 10.1.1 [[GetPrototypeOf]] ( )
+  pub fn get_prototype(&self) -> Option<ObjectValue> {
+    self.object.prototype.borrow().clone()
+  }
+
+  // sets the prototype of an object being created, no invariant can be broken yet.
+  pub fn set_prototype(&self, prototype: Option<ObjectValue>) {
+    *self.object.prototype.borrow_mut() = prototype;
+  }
+
+  /*
+  10.1.2.1 OrdinarySetPrototypeOf ( O, V )
+    4. If current is V, return true.
+    5. If extensible is false, return false.
+    8. Repeat, while done is false,
+      a. If p is null, then set done to true.
+      b. Else if SameValue(p, O) is true, then return false.
+  */
+  pub fn set_prototype_of(&self, prototype: Option<ObjectValue>) -> bool {
+    let current = self.get_prototype();
+    let unchanged = match (&current, &prototype) {
+      (Some(current), Some(prototype)) => current.is_same(prototype),
+      (None, None) => true,
+      _ => false,
+    };
+    if unchanged {
+      return true;
+    }
+    if !self.is_extensible() {
+      return false;
+    }
+    let mut next = prototype.clone();
+    while let Some(object) = next {
+      if object.is_same(self) {
+        return false;
+      }
+      next = object.get_prototype();
+    }
+    self.set_prototype(prototype);
+    true
+  }
+
+  // 10.1.3 [[IsExtensible]] ( )
+  pub fn is_extensible(&self) -> bool {
+    self.object.extensible.get()
+  }
+
+  // 10.1.4 [[PreventExtensions]] ( )
+  pub fn prevent_extensions(&self) -> bool {
+    self.object.extensible.set(false);
+    true
+  }
+
+  // 10.1.5 [[GetOwnProperty]] ( P ), the code units of String objects are properties as well.
+  pub fn get_own_property(&self, key: &PropertyKey) -> Option<Property> {
+    let property = self.object.properties.borrow().values.get(key).cloned();
+    match self.object.kind {
+      ObjectKind::String(_) if property.is_none() => self.string_get_own_property(key),
+      _ => property,
+    }
+  }
+
+  // the property found first along the prototype chain, what [[Get]] and [[Set]] act on.
+  pub fn find_property(&self, key: &PropertyKey) -> Option<Property> {
+    if let Some(property) = self.get_own_property(key) {
+      return Some(property);
+    }
+    self.get_prototype()?.find_property(key)
+  }
+
+  // 10.1.6 [[DefineOwnProperty]] ( P, Desc ), arrays keep their `length` in sync.
+  pub fn define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+    match self.object.kind {
+      ObjectKind::Array => self.array_define_own_property(key, descriptor),
+      ObjectKind::String(_) => self.string_define_own_property(key, descriptor),
+      _ => self.ordinary_define_own_property(key, descriptor),
+    }
+  }
+
+  /*
+  10.1.6.3 ValidateAndApplyPropertyDescriptor ( O, P, extensible, Desc, current )
+    2. If current is undefined, then
+      a. If extensible is false, return false.
+    4. If current.[[Configurable]] is false, then
+      a. If Desc has a [[Configurable]] field and Desc.[[Configurable]] is true, return false.
+      b. If Desc has an [[Enumerable]] field and Desc.[[Enumerable]] is not current.[[Enumerable]], return false.
+      c. If IsGenericDescriptor(Desc) is false and IsAccessorDescriptor(Desc) is not IsAccessorDescriptor(current),
+        return false.
+      d. If IsAccessorDescriptor(current) is true, then
+        i. If Desc has a [[Get]] field and SameValue(Desc.[[Get]], current.[[Get]]) is false, return false.
+        ii. If Desc has a [[Set]] field and SameValue(Desc.[[Set]], current.[[Set]]) is false, return false.
+      e. Else if current.[[Writable]] is false, then
+        i. If Desc has a [[Writable]] field and Desc.[[Writable]] is true, return false.
+        ii. If Desc has a [[Value]] field and SameValue(Desc.[[Value]], current.[[Value]]) is false, return false.
+  */
+  pub fn ordinary_define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+    let Some(current) = self.get_own_property(&key) else {
+      if !self.is_extensible() {
+        return false;
+      }
+      self
+        .object
+        .properties
+        .borrow_mut()
+        .insert(key, descriptor.to_property());
+      return true;
+    };
+    if !ObjectValue::is_compatible_property_descriptor(&descriptor, &current) {
+      return false;
+    }
+    let property = apply_descriptor(current, descriptor);
+    self.object.properties.borrow_mut().insert(key, property);
+    true
+  }
+
+  // 10.1.6.2 IsCompatiblePropertyDescriptor ( Extensible, Desc, Current ), for a property that exists.
+  pub(crate) fn is_compatible_property_descriptor(descriptor: &PropertyDescriptor, current: &Property) -> bool {
+    if !current.configurable {
+      if descriptor.configurable == Some(true) {
+        return false;
+      }
+      if descriptor
+        .enumerable
+        .is_some_and(|enumerable| enumerable != current.enumerable)
+      {
+        return false;
+      }
+      if !descriptor.is_generic_descriptor() && descriptor.is_accessor_descriptor() != current.is_accessor() {
+        return false;
+      }
+      let same = |new: &Option<Value>, old: &Value| new.as_ref().is_none_or(|new| same_value(new, old));
+      match &current.slot {
+        PropertySlot::Accessor { get, set } => {
+          if !same(&descriptor.get, get) || !same(&descriptor.set, set) {
+            return false;
+          }
+        }
+        PropertySlot::Data { value, writable: false } => {
+          if descriptor.writable == Some(true) || !same(&descriptor.value, value) {
+            return false;
+          }
+        }
+        PropertySlot::Data { .. } => {}
+      }
+    }
+    true
+  }
+
+  /*
+  7.3.15 SetIntegrityLevel ( O, frozen ), for an object no script code has seen yet.
+    3. Let status be ? O.[[PreventExtensions]]().
+    7. Else,
+      b. For each element k of keys, do
+        i. Let currentDesc be ? O.[[GetOwnProperty]](k).
+        ii. If currentDesc is not undefined, then
+          1. If IsAccessorDescriptor(currentDesc) is true, then
+            a. Let desc be the PropertyDescriptor { [[Configurable]]: false }.
+          2. Else,
+            a. Let desc be the PropertyDescriptor { [[Configurable]]: false, [[Writable]]: false }.
+          3. Perform ? DefinePropertyOrThrow(O, k, desc).
+  */
+  pub fn freeze(&self) {
+    self.prevent_extensions();
+    for key in self.own_property_keys() {
+      let Some(property) = self.get_own_property(&key) else {
+        continue;
+      };
+      let writable = if property.is_accessor() { None } else { Some(false) };
+      let descriptor = PropertyDescriptor { configurable: Some(false), writable, ..PropertyDescriptor::default() };
+      self.define_own_property(key, descriptor);
+    }
+  }
+
+  // 10.1.7 [[HasProperty]] ( P )
+  pub fn has_property(&self, key: &PropertyKey) -> bool {
+    self.find_property(key).is_some()
+  }
+
+  // 10.1.10 [[Delete]] ( P ), only configurable properties can be deleted.
+  pub fn delete(&self, key: &PropertyKey) -> bool {
+    match self.get_own_property(key) {
+      Some(property) if !property.configurable => false,
+      Some(_) => {
+        self.object.properties.borrow_mut().remove(key);
+        true
+      }
+      None => true,
+    }
+  }
+
+  /*
+  10.1.11.1 OrdinaryOwnPropertyKeys ( O )
+    2. For each own property key P of O such that P is an array index, in ascending numeric index order, do
+    3. For each own property key P of O such that P is a String and P is not an array index, in ascending
+      chronological order of property creation, do
+    4. For each own property key P of O such that P is a Symbol, in ascending chronological order of property
+      creation, do
+  */
+  pub fn own_property_keys(&self) -> Vec<PropertyKey> {
+    let mut keys = self.string_own_property_keys();
+    let properties = self.object.properties.borrow();
+    let mut indices: Vec<(u32, &PropertyKey)> = properties
+      .keys
+      .iter()
+      .filter_map(|key| key.get_array_index().map(|index| (index, key)))
+      .collect();
+    indices.sort_by_key(|(index, _)| *index);
+    keys.extend(indices.into_iter().map(|(_, key)| key.clone()));
+    let strings = properties
+      .keys
+      .iter()
+      .filter(|key| !key.is_symbol() && key.get_array_index().is_none());
+    keys.extend(strings.cloned());
+    keys.extend(properties.keys.iter().filter(|key| key.is_symbol()).cloned());
+    keys
+  }
+
+  // the value of a data property found along the prototype chain, accessors are not run.
+  pub fn get_property(&self, key: &str) -> Option<Value> {
+    match self.find_property(&PropertyKey::from(key))?.slot {
+      PropertySlot::Data { value, .. } => Some(value),
+      PropertySlot::Accessor { .. } => None,
+    }
+  }
+
+  // 7.3.5 CreateDataProperty ( O, P, V )
+  pub fn set_property(&self, key: &str, value: Value) -> bool {
+    self.define_own_property(PropertyKey::from(key), PropertyDescriptor::new_data(value))
+  }
+
+  // the attributes of the properties of built-in objects: writable, configurable and not enumerable.
+  pub fn define_builtin_property(&self, key: &str, value: Value) {
+    let descriptor = PropertyDescriptor::new_data_with(value, true, false, true);
+    self.define_own_property(PropertyKey::from(key), descriptor);
+  }
+
+  // 7.3.27 PrivateElementFind ( O, P )
+  pub fn find_private_element(&self, key: &SymbolValue) -> Option<PrivateElement> {
+    let elements = self.object.private_elements.borrow();
+    elements.iter().find(|element| element.key.is_same(key)).cloned()
+  }
+
+  // the caller checked the object does not have an element with the same key yet.
+  pub fn add_private_element(&self, element: PrivateElement) {
+    self.object.private_elements.borrow_mut().push(element);
+  }
+
+  // 7.3.32 PrivateSet ( O, P, value ), step 3: the new value of a private field found on the object.
+  pub fn set_private_field(&self, key: &SymbolValue, value: Value) {
+    let mut elements = self.object.private_elements.borrow_mut();
+    let field = elements.iter_mut().find(|element| element.key.is_same(key));
+    if let Some(PrivateElement { kind: PrivateElementKind::Field(current), .. }) = field {
+      *current = value;
+    }
+  }
+
+  // objects compare by identity.
+  pub fn is_same(&self, other: &ObjectValue) -> bool {
+    Rc::ptr_eq(&self.object, &other.object)
+  }
+
+  // the identity of the object, for hashing it as the key of a Map.
+  pub fn get_id(&self) -> usize {
+    Rc::as_ptr(&self.object) as usize
+  }
+}
+
+// the fields present in the descriptor replace the attributes of the property, turning it from
+// a data property into an accessor property or the other way around when they ask to.
+fn apply_descriptor(current: Property, descriptor: PropertyDescriptor) -> Property {
+  let enumerable = descriptor.enumerable.unwrap_or(current.enumerable);
+  let configurable = descriptor.configurable.unwrap_or(current.configurable);
+  let undefined = Value::create_undefined_value;
+  let slot = match current.slot {
+    PropertySlot::Data { .. } if descriptor.is_accessor_descriptor() => PropertySlot::Accessor {
+      get: descriptor.get.unwrap_or_else(undefined),
+      set: descriptor.set.unwrap_or_else(undefined),
+    },
+    PropertySlot::Accessor { .. } if descriptor.is_data_descriptor() => PropertySlot::Data {
+      value: descriptor.value.unwrap_or_else(undefined),
+      writable: descriptor.writable.unwrap_or(false),
+    },
+    PropertySlot::Data { value, writable } => {
+      PropertySlot::Data { value: descriptor.value.unwrap_or(value), writable: descriptor.writable.unwrap_or(writable) }
+    }
+    PropertySlot::Accessor { get, set } => {
+      PropertySlot::Accessor { get: descriptor.get.unwrap_or(get), set: descriptor.set.unwrap_or(set) }
+    }
+  };
+  Property { slot, enumerable, configurable }
+}
+
+impl Default for ObjectValue {
+  fn default() -> Self {
+    ObjectValue::new()
+  }
+}
+
+// properties and prototypes often point back at the object, so only the kind is shown.
+impl fmt::Debug for ObjectValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "ObjectValue({:?})", self.object.kind)
   }
 }
@@ -0,0 +1,236 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+6.1.7 The Object Type
+Properties are identified using key values. A property key value is either an ECMAScript String value or a Symbol
+  value.
+A data property associates a key value with an ECMAScript language value and a set of Boolean attributes.
+An accessor property associates a key value with one or two accessor functions, and a set of Boolean attributes.
+
+6.2.6 The Property Descriptor Specification Type
+Values of the Property Descriptor type are Records. Each field's name is an attribute name and its value is a
+  corresponding attribute value. Any field may be present or absent.
+
+@links:
+- https://tc39.es/ecma262/#sec-object-type
+- https://tc39.es/ecma262/#sec-property-descriptor-specification-type
+*/
+use crate::values::{StringValue, SymbolValue, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone)]
+pub enum PropertyKey {
+  // the code units of the key, lone surrogates included
+  String(StringValue),
+  Symbol(SymbolValue),
+}
+
+impl PropertyKey {
+  pub fn is_symbol(&self) -> bool {
+    matches!(self, PropertyKey::Symbol(_))
+  }
+
+  /*
+  6.1.7 The Object Type
+  An array index is an integer index n such that +0𝔽 ≤ 𝔽(n) < 𝔽(2^32 - 1).
+  */
+  pub fn get_array_index(&self) -> Option<u32> {
+    let PropertyKey::String(key) = self else {
+      return None;
+    };
+    let key = key.get();
+    let index = key.parse::<u32>().ok().filter(|index| *index != u32::MAX)?;
+    // only the canonical form is an index, "01" is an ordinary key
+    (index.to_string() == key).then_some(index)
+  }
+
+  // the key as the string or symbol value it was made from.
+  pub fn to_value(&self) -> Value {
+    match self {
+      PropertyKey::String(key) => Value::String(key.clone()),
+      PropertyKey::Symbol(symbol) => Value::Symbol(symbol.clone()),
+    }
+  }
+}
+
+impl From<&str> for PropertyKey {
+  fn from(key: &str) -> Self {
+    PropertyKey::String(StringValue::from(key))
+  }
+}
+
+impl From<String> for PropertyKey {
+  fn from(key: String) -> Self {
+    PropertyKey::String(StringValue::new(key))
+  }
+}
+
+impl From<StringValue> for PropertyKey {
+  fn from(key: StringValue) -> Self {
+    PropertyKey::String(key)
+  }
+}
+
+impl PartialEq for PropertyKey {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (PropertyKey::String(left), PropertyKey::String(right)) => left == right,
+      (PropertyKey::Symbol(left), PropertyKey::Symbol(right)) => left.is_same(right),
+      _ => false,
+    }
+  }
+}
+
+impl Eq for PropertyKey {}
+
+impl Hash for PropertyKey {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    match self {
+      PropertyKey::String(key) => key.hash(state),
+      PropertyKey::Symbol(symbol) => symbol.get_id().hash(state),
+    }
+  }
+}
+
+impl fmt::Display for PropertyKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PropertyKey::String(key) => write!(f, "{}", key.get()),
+      PropertyKey::Symbol(symbol) => write!(f, "{}", symbol.get_descriptive_string()),
+    }
+  }
+}
+
+// a property as the object stores it, every attribute present.
+#[derive(Debug, Clone)]
+pub struct Property {
+  pub slot: PropertySlot,
+  pub enumerable: bool,
+  pub configurable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum PropertySlot {
+  Data { value: Value, writable: bool },
+  // `undefined` when the function is missing
+  Accessor { get: Value, set: Value },
+}
+
+impl Property {
+  pub fn is_accessor(&self) -> bool {
+    matches!(self.slot, PropertySlot::Accessor { .. })
+  }
+
+  pub fn is_writable(&self) -> bool {
+    matches!(self.slot, PropertySlot::Data { writable: true, .. })
+  }
+
+  /*
+  6.2.6.4 FromPropertyDescriptor ( Desc ), the complete descriptor of a stored property.
+  */
+  pub fn to_descriptor(&self) -> PropertyDescriptor {
+    let mut descriptor = PropertyDescriptor {
+      enumerable: Some(self.enumerable),
+      configurable: Some(self.configurable),
+      ..PropertyDescriptor::default()
+    };
+    match &self.slot {
+      PropertySlot::Data { value, writable } => {
+        descriptor.value = Some(value.clone());
+        descriptor.writable = Some(*writable);
+      }
+      PropertySlot::Accessor { get, set } => {
+        descriptor.get = Some(get.clone());
+        descriptor.set = Some(set.clone());
+      }
+    }
+    descriptor
+  }
+}
+
+// a property descriptor, absent fields are left alone when it is applied to an existing property.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyDescriptor {
+  pub value: Option<Value>,
+  pub writable: Option<bool>,
+  pub get: Option<Value>,
+  pub set: Option<Value>,
+  pub enumerable: Option<bool>,
+  pub configurable: Option<bool>,
+}
+
+impl PropertyDescriptor {
+  // the attributes of properties created by assignment and by object literals.
+  pub fn new_data(value: Value) -> Self {
+    PropertyDescriptor::new_data_with(value, true, true, true)
+  }
+
+  pub fn new_data_with(value: Value, writable: bool, enumerable: bool, configurable: bool) -> Self {
+    PropertyDescriptor {
+      value: Some(value),
+      writable: Some(writable),
+      enumerable: Some(enumerable),
+      configurable: Some(configurable),
+      ..PropertyDescriptor::default()
+    }
+  }
+
+  // a getter or a setter of an object literal or a class, the other half is left untouched.
+  pub fn new_accessor(get: Option<Value>, set: Option<Value>, enumerable: bool) -> Self {
+    PropertyDescriptor { get, set, enumerable: Some(enumerable), configurable: Some(true), ..Default::default() }
+  }
+
+  // 6.2.6.1 IsAccessorDescriptor ( Desc )
+  pub fn is_accessor_descriptor(&self) -> bool {
+    self.get.is_some() || self.set.is_some()
+  }
+
+  // 6.2.6.2 IsDataDescriptor ( Desc )
+  pub fn is_data_descriptor(&self) -> bool {
+    self.value.is_some() || self.writable.is_some()
+  }
+
+  // 6.2.6.3 IsGenericDescriptor ( Desc )
+  pub fn is_generic_descriptor(&self) -> bool {
+    !self.is_accessor_descriptor() && !self.is_data_descriptor()
+  }
+
+  // the property a descriptor creates on its own, missing attributes default to false and undefined.
+  pub fn to_property(&self) -> Property {
+    let slot = if self.is_accessor_descriptor() {
+      let get = self.get.clone().unwrap_or_else(Value::create_undefined_value);
+      let set = self.set.clone().unwrap_or_else(Value::create_undefined_value);
+      PropertySlot::Accessor { get, set }
+    } else {
+      let value = self.value.clone().unwrap_or_else(Value::create_undefined_value);
+      PropertySlot::Data { value, writable: self.writable.unwrap_or(false) }
+    };
+    Property { slot, enumerable: self.enumerable.unwrap_or(false), configurable: self.configurable.unwrap_or(false) }
+  }
+}
+
+/*
+6.2.9 The PrivateElement Specification Type
+  [[Key]]: a Private Name, the name of the field, method, or accessor.
+  [[Kind]]: field, method, or accessor.
+  [[Value]]: the value of the field or the function of the method, only present for fields and methods.
+  [[Get]] and [[Set]]: the getter and the setter of an accessor, each may be undefined.
+Private Names are symbols no script code can reach, described by the `#name` they were declared with.
+*/
+#[derive(Debug, Clone)]
+pub struct PrivateElement {
+  pub key: SymbolValue,
+  pub kind: PrivateElementKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum PrivateElementKind {
+  Field(Value),
+  Method(Value),
+  Accessor { get: Option<Value>, set: Option<Value> },
+}
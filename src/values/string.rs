@@ -5,14 +5,271 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 /*
+6.1.4 The String Type
+The String type is the set of all ordered sequences of zero or more 16-bit unsigned integer values ("elements") up
+  to a maximum length of 2^53 - 1 elements. Each element is regarded as a UTF-16 code unit value, the operations
+  that do not interpret the contents treat them as plain 16-bit integers.
+
+10.4.3 String Exotic Objects
+A String object is an exotic object that encapsulates a String value and exposes virtual integer-indexed data
+  properties corresponding to the individual code unit elements of the String value. String exotic objects
+  always have a data property named "length" whose value is the length of the encapsulated String value.
+
+The code units are shared, so copying a string value never copies its contents.
+
+@links:
+- https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type
+- https://tc39.es/ecma262/#sec-string-exotic-objects
 */
-#[derive(Debug)]
+use crate::values::object::{ObjectKind, ObjectValue};
+use crate::values::property::{Property, PropertyDescriptor, PropertyKey, PropertySlot};
+use crate::values::Value;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Clone)]
 pub struct StringValue {
-  value: String,
+  units: Rc<[u16]>,
+}
+
+// 11.1.4 the Record CodePointAt returns.
+#[derive(Debug, Clone, Copy)]
+pub struct CodePoint {
+  pub code_point: u32,
+  pub code_unit_count: usize,
+  pub is_unpaired_surrogate: bool,
 }
 
 impl StringValue {
   pub fn new(value: String) -> Self {
-    StringValue { value }
+    StringValue { units: value.encode_utf16().collect() }
+  }
+
+  pub fn from_utf16(units: Vec<u16>) -> Self {
+    StringValue { units: units.into() }
+  }
+
+  pub fn get_units(&self) -> &[u16] {
+    &self.units
+  }
+
+  // the contents as a rust string, a lone surrogate becomes U+FFFD.
+  pub fn get(&self) -> String {
+    String::from_utf16_lossy(&self.units)
+  }
+
+  pub fn len(&self) -> usize {
+    self.units.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.units.is_empty()
+  }
+
+  // the string-concatenation of two strings.
+  pub fn concat(&self, other: &StringValue) -> StringValue {
+    if other.is_empty() {
+      return self.clone();
+    }
+    if self.is_empty() {
+      return other.clone();
+    }
+    StringValue::from_utf16([self.get_units(), other.get_units()].concat())
+  }
+
+  // the substring of the code units from `from` up to `to`, exclusive.
+  pub fn substring(&self, from: usize, to: usize) -> StringValue {
+    if from == 0 && to == self.len() {
+      return self.clone();
+    }
+    StringValue::from_utf16(self.units[from..to].to_vec())
+  }
+
+  /*
+  11.1.4 Static Semantics: CodePointAt ( string, position )
+    1. Let size be the length of string.
+    2. Assert: position ≥ 0 and position < size.
+    3. Let first be the code unit at index position within string.
+    4. Let cp be the code point whose numeric value is the numeric value of first.
+    5. If first is neither a leading surrogate nor a trailing surrogate, then
+      a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1, [[IsUnpairedSurrogate]]: false }.
+    6. If first is a trailing surrogate or position + 1 = size, then
+      a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1, [[IsUnpairedSurrogate]]: true }.
+    7. Let second be the code unit at index position + 1 within string.
+    8. If second is not a trailing surrogate, then
+      a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1, [[IsUnpairedSurrogate]]: true }.
+    9. Set cp to UTF16SurrogatePairToCodePoint(first, second).
+    10. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 2, [[IsUnpairedSurrogate]]: false }.
+  */
+  pub fn code_point_at(&self, position: usize) -> CodePoint {
+    let first = self.units[position];
+    let single =
+      |is_unpaired_surrogate| CodePoint { code_point: first as u32, code_unit_count: 1, is_unpaired_surrogate };
+    if !is_surrogate(first) {
+      return single(false);
+    }
+    if is_trailing_surrogate(first) || position + 1 == self.len() {
+      return single(true);
+    }
+    let second = self.units[position + 1];
+    if !is_trailing_surrogate(second) {
+      return single(true);
+    }
+    let code_point = (first as u32 - 0xD800) * 0x400 + (second as u32 - 0xDC00) + 0x10000;
+    CodePoint { code_point, code_unit_count: 2, is_unpaired_surrogate: false }
+  }
+
+  /*
+  6.1.4.1 StringIndexOf ( string, searchValue, fromIndex )
+    1. Let len be the length of string.
+    2. If searchValue is the empty String and fromIndex ≤ len, return fromIndex.
+    3. Let searchLen be the length of searchValue.
+    4. For each integer i such that fromIndex ≤ i ≤ len - searchLen, in ascending order, do
+      a. Let candidate be the substring of string from i to i + searchLen.
+      b. If candidate is searchValue, return i.
+    5. Return not-found.
+  */
+  pub fn index_of(&self, search: &StringValue, from: usize) -> Option<usize> {
+    if from > self.len() {
+      return None;
+    }
+    if search.is_empty() {
+      return Some(from);
+    }
+    self.units[from..]
+      .windows(search.len())
+      .position(|candidate| candidate == search.get_units())
+      .map(|index| index + from)
+  }
+
+  // the last index at or before `from` where `search` starts.
+  pub fn last_index_of(&self, search: &StringValue, from: usize) -> Option<usize> {
+    let last = self.len().checked_sub(search.len())?;
+    (0..=from.min(last))
+      .rev()
+      .find(|index| self.units[*index..*index + search.len()] == *search.get_units())
+  }
+}
+
+// two strings are the same when they have the same code units in the same positions.
+impl PartialEq for StringValue {
+  fn eq(&self, other: &Self) -> bool {
+    self.units == other.units
+  }
+}
+
+impl Eq for StringValue {}
+
+// the code units are shown as the text they encode.
+impl fmt::Debug for StringValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("StringValue").field("value", &self.get()).finish()
+  }
+}
+
+impl Hash for StringValue {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.units.hash(state);
+  }
+}
+
+impl From<&str> for StringValue {
+  fn from(value: &str) -> Self {
+    StringValue { units: value.encode_utf16().collect() }
+  }
+}
+
+fn is_trailing_surrogate(unit: u16) -> bool {
+  (0xDC00..=0xDFFF).contains(&unit)
+}
+
+fn is_surrogate(unit: u16) -> bool {
+  (0xD800..=0xDFFF).contains(&unit)
+}
+
+// 11.1.1 Static Semantics: UTF16EncodeCodePoint ( cp )
+pub fn encode_code_point(code_point: u32, units: &mut Vec<u16>) {
+  if code_point <= 0xFFFF {
+    units.push(code_point as u16);
+    return;
+  }
+  let code_point = code_point - 0x10000;
+  units.push((code_point / 0x400 + 0xD800) as u16);
+  units.push((code_point % 0x400 + 0xDC00) as u16);
+}
+
+impl ObjectValue {
+  /*
+  10.4.3.4 StringCreate ( value, prototype )
+    3. Set S.[[StringData]] to value.
+    8. Let length be the length of value.
+    9. Perform ! DefinePropertyOrThrow(S, "length", PropertyDescriptor { [[Value]]: 𝔽(length), [[Writable]]: false,
+      [[Enumerable]]: false, [[Configurable]]: false }).
+  */
+  pub fn new_string(value: StringValue, prototype: Option<ObjectValue>) -> Self {
+    let length = Value::create_number_value(value.len() as i64);
+    let string = ObjectValue::new_with_kind(ObjectKind::String(value), prototype);
+    let descriptor = PropertyDescriptor::new_data_with(length, false, false, false);
+    string.ordinary_define_own_property(PropertyKey::from("length"), descriptor);
+    string
+  }
+
+  // the [[StringData]] slot of a String object.
+  pub fn get_string_data(&self) -> Option<&StringValue> {
+    match self.get_kind() {
+      ObjectKind::String(value) => Some(value),
+      _ => None,
+    }
+  }
+
+  /*
+  10.4.3.5 StringGetOwnProperty ( S, P )
+    1. If P is not a String, return undefined.
+    2. Let index be CanonicalNumericIndexString(P).
+    3. If index is undefined, return undefined.
+    4. If IsIntegralNumber(index) is false, return undefined.
+    5. If index is -0𝔽 or index < -0𝔽, return undefined.
+    6. Let str be S.[[StringData]].
+    8. Let len be the length of str.
+    9. If ℝ(index) ≥ len, return undefined.
+    10. Let resultStr be the substring of str from ℝ(index) to ℝ(index) + 1.
+    11. Return the PropertyDescriptor { [[Value]]: resultStr, [[Writable]]: false, [[Enumerable]]: true,
+      [[Configurable]]: false }.
+  */
+  pub(crate) fn string_get_own_property(&self, key: &PropertyKey) -> Option<Property> {
+    let value = self.get_string_data()?;
+    let index = key.get_array_index()? as usize;
+    if index >= value.len() {
+      return None;
+    }
+    let slot = PropertySlot::Data { value: Value::String(value.substring(index, index + 1)), writable: false };
+    Some(Property { slot, enumerable: true, configurable: false })
+  }
+
+  /*
+  10.4.3.2 [[DefineOwnProperty]] ( P, Desc )
+    1. Let stringDesc be StringGetOwnProperty(S, P).
+    2. If stringDesc is not undefined, then
+      a. Let extensible be S.[[Extensible]].
+      b. Return IsCompatiblePropertyDescriptor(extensible, Desc, stringDesc).
+    3. Return ! OrdinaryDefineOwnProperty(S, P, Desc).
+  */
+  pub(crate) fn string_define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+    match self.string_get_own_property(&key) {
+      Some(current) => ObjectValue::is_compatible_property_descriptor(&descriptor, &current),
+      None => self.ordinary_define_own_property(key, descriptor),
+    }
+  }
+
+  /*
+  10.4.3.3 [[OwnPropertyKeys]] ( )
+    5. For each integer i such that 0 ≤ i < len, in ascending order, do
+      a. Append ! ToString(𝔽(i)) to keys.
+  The keys of the ordinary properties follow.
+  */
+  pub(crate) fn string_own_property_keys(&self) -> Vec<PropertyKey> {
+    let length = self.get_string_data().map_or(0, StringValue::len);
+    (0..length).map(|index| PropertyKey::from(index.to_string())).collect()
   }
 }
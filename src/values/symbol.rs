@@ -5,18 +5,49 @@ Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
 */
 
 /*
+6.1.5 The Symbol Type
+The Symbol type is the set of all non-String values that may be used as the key of an Object property.
+Each possible Symbol value is unique and immutable.
+Each Symbol value immutably holds an associated value called [[Description]] that is either undefined or a String
+  value.
 
 @links:
--
+- https://tc39.es/ecma262/#sec-ecmascript-language-types-symbol-type
 */
-#[derive(Debug)]
+use std::rc::Rc;
+
+// every symbol is unique, two symbols are the same only when they share the allocation.
+#[derive(Debug, Clone)]
 pub struct SymbolValue {
-  // todo: consider using a better type for Symbol (tips. check in t39 spec or jscore ...)
-  value: String,
+  description: Rc<Option<String>>,
 }
 
 impl SymbolValue {
-  pub fn new(value: String) -> Self {
-    SymbolValue { value }
+  pub fn new(description: Option<String>) -> Self {
+    SymbolValue { description: Rc::new(description) }
+  }
+
+  // the [[Description]], None stands for undefined.
+  pub fn get_description(&self) -> Option<&String> {
+    self.description.as_ref().as_ref()
+  }
+
+  /*
+  20.4.3.3.1 SymbolDescriptiveString ( sym )
+    1. Let desc be sym's [[Description]] value.
+    2. If desc is undefined, set desc to the empty String.
+    4. Return the string-concatenation of "Symbol(", desc, and ")".
+  */
+  pub fn get_descriptive_string(&self) -> String {
+    format!("Symbol({})", self.get_description().map_or("", String::as_str))
+  }
+
+  pub fn is_same(&self, other: &SymbolValue) -> bool {
+    Rc::ptr_eq(&self.description, &other.description)
+  }
+
+  // the identity of the symbol, for hashing it as a property key.
+  pub fn get_id(&self) -> usize {
+    Rc::as_ptr(&self.description) as usize
   }
 }
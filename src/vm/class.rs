@@ -0,0 +1,424 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+15.7 Class Definitions
+A class is a constructor with a prototype object, its methods are defined on both while the class definition is
+  evaluated. The fields and the private methods of the instances are kept by the constructor, every object it
+  constructs gets them before the constructor body runs, or once super() returns in a derived class.
+Private elements live in a list of their own on the object, they are never properties and only the code of the
+  class declaring their Private Name can reach them.
+
+@links:
+- https://tc39.es/ecma262/#sec-class-definitions
+- https://tc39.es/ecma262/#sec-privateelementfind
+- https://tc39.es/ecma262/#sec-initializeinstanceelements
+*/
+#![allow(dead_code)]
+use crate::bytecode::opcode::{
+  CLASS_ELEMENT_GETTER, CLASS_ELEMENT_PRIVATE, CLASS_ELEMENT_SETTER, CLASS_ELEMENT_STATIC,
+};
+use crate::errors::{EngineError, Result};
+use crate::values::function::{
+  ClassDefinition, ClassElementName, ClassFieldDefinition, ClassStaticElement, FunctionValue,
+};
+use crate::values::property::{PrivateElement, PrivateElementKind, PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectKind, ObjectValue, SymbolValue, Value};
+use crate::vm::coercion::to_property_key;
+use crate::vm::core::Engine;
+use std::rc::Rc;
+
+impl<'ctx> Engine<'ctx> {
+  /*
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+    8. If ClassHeritage is not present, then
+      a. Let protoParent be %Object.prototype%.
+      b. Let constructorParent be %Function.prototype%.
+    9. Else,
+      e. If superclass is null, then
+        i. Let protoParent be null.
+        ii. Let constructorParent be %Function.prototype%.
+      f. Else if IsConstructor(superclass) is false, then
+        i. Throw a TypeError exception.
+      g. Else,
+        i. Let protoParent be ? Get(superclass, "prototype").
+        ii. If protoParent is not an Object and protoParent is not null, throw a TypeError exception.
+        iii. Let constructorParent be superclass.
+    10. Let proto be OrdinaryObjectCreate(protoParent).
+    14. Let F be constructorInfo.[[Closure]].
+    15. Perform MakeClassConstructor(F).
+    16. Perform SetFunctionName(F, className).
+    17. Perform MakeConstructor(F, false, proto).
+    19. Perform CreateMethodProperty(proto, "constructor", F).
+  */
+  pub fn create_class(
+    &mut self,
+    function: FunctionValue,
+    heritage: Option<Value>,
+  ) -> Result<(ObjectValue, ObjectValue)> {
+    let function_prototype = self.get_intrinsic("%Function.prototype%");
+    let (proto_parent, constructor_parent) = match heritage {
+      None => (self.get_intrinsic("%Object.prototype%"), function_prototype),
+      Some(Value::Null(_)) => (None, function_prototype),
+      Some(Value::Object(superclass)) if superclass.is_constructor() => {
+        let receiver = Value::Object(superclass.clone());
+        match self.get(&superclass, &PropertyKey::from("prototype"), &receiver)? {
+          Value::Object(prototype) => (Some(prototype), Some(superclass)),
+          Value::Null(_) => (None, Some(superclass)),
+          value => {
+            return Err(EngineError::TypeError(format!(
+              "Class extends value does not have valid prototype property {}",
+              value
+            )))
+          }
+        }
+      }
+      Some(value) => {
+        return Err(EngineError::TypeError(format!(
+          "Class extends value {} is not a constructor or null",
+          value
+        )))
+      }
+    };
+    let prototype = ObjectValue::new_with_kind(ObjectKind::Ordinary, proto_parent);
+    let function = function
+      .with_home_object(prototype.clone())
+      .with_class(Rc::new(ClassDefinition::default()));
+    let constructor = self.create_function_object(function);
+    constructor.set_prototype(constructor_parent);
+    let descriptor = PropertyDescriptor::new_data_with(Value::Object(prototype.clone()), false, false, false);
+    constructor.define_own_property(PropertyKey::from("prototype"), descriptor);
+    let descriptor = PropertyDescriptor::new_data_with(Value::Object(constructor.clone()), true, false, true);
+    prototype.define_own_property(PropertyKey::from("constructor"), descriptor);
+    Ok((constructor, prototype))
+  }
+
+  /*
+  15.4.4 Runtime Semantics: MethodDefinitionEvaluation
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+    25. For each ClassElement e of elements, do
+      e. If element is a PrivateElement, then
+        ii. If container contains a PrivateElement pe such that pe.[[Key]] is element.[[Key]], then
+          1. Assert: element.[[Kind]] and pe.[[Kind]] are both accessor.
+          2. If element.[[Get]] is undefined, then
+            a. Let combined be PrivateElement { [[Key]]: element.[[Key]], [[Kind]]: accessor, [[Get]]: pe.[[Get]],
+              [[Set]]: element.[[Set]] }.
+          3. Else,
+            a. Let combined be PrivateElement { [[Key]]: element.[[Key]], [[Kind]]: accessor,
+              [[Get]]: element.[[Get]], [[Set]]: pe.[[Set]] }.
+          4. Replace pe in container with combined.
+        iii. Else,
+          1. Append element to container.
+  Public methods are non-enumerable properties of the prototype or of the constructor, private ones wait on the
+    constructor until its instances are created or the class is done.
+  */
+  pub fn define_class_method(
+    &mut self,
+    constructor: &ObjectValue,
+    prototype: &ObjectValue,
+    key: Value,
+    function: Value,
+    flags: usize,
+  ) -> Result<()> {
+    let accessor = flags & (CLASS_ELEMENT_GETTER | CLASS_ELEMENT_SETTER);
+    if flags & CLASS_ELEMENT_PRIVATE == 0 {
+      let target = if flags & CLASS_ELEMENT_STATIC != 0 {
+        constructor
+      } else {
+        prototype
+      };
+      let key = to_property_key(self, &key)?;
+      let descriptor = match accessor {
+        CLASS_ELEMENT_GETTER => PropertyDescriptor::new_accessor(Some(function), None, false),
+        CLASS_ELEMENT_SETTER => PropertyDescriptor::new_accessor(None, Some(function), false),
+        _ => PropertyDescriptor::new_data_with(function, true, false, true),
+      };
+      return self.define_property_or_throw(target, key, descriptor);
+    }
+    let Value::Symbol(key) = key else {
+      unreachable!("private methods to be named by a Private Name");
+    };
+    let kind = match accessor {
+      CLASS_ELEMENT_GETTER => PrivateElementKind::Accessor { get: Some(function), set: None },
+      CLASS_ELEMENT_SETTER => PrivateElementKind::Accessor { get: None, set: Some(function) },
+      _ => PrivateElementKind::Method(function),
+    };
+    let class = get_class_definition(constructor);
+    let mut container = match flags & CLASS_ELEMENT_STATIC {
+      0 => class.private_methods.borrow_mut(),
+      _ => class.static_private_methods.borrow_mut(),
+    };
+    let existing = container.iter_mut().find(|element| element.key.is_same(&key));
+    match (existing, kind) {
+      (
+        Some(PrivateElement { kind: PrivateElementKind::Accessor { get, set }, .. }),
+        PrivateElementKind::Accessor { get: new_get, set: new_set },
+      ) => {
+        *get = new_get.or(get.take());
+        *set = new_set.or(set.take());
+      }
+      (_, kind) => container.push(PrivateElement { key, kind }),
+    }
+    Ok(())
+  }
+
+  /*
+  15.7.10 Runtime Semantics: ClassFieldDefinitionEvaluation
+    1. Let name be ? Evaluation of ClassElementName.
+    2. If Initializer is present, then
+      e. Let initializer be OrdinaryFunctionCreate(%Function.prototype%, sourceText, formalParameterList,
+        Initializer, non-lexical-this, env, privateEnv).
+      f. Perform MakeMethod(initializer, homeObject).
+    4. Return the ClassFieldDefinition Record { [[Name]]: name, [[Initializer]]: initializer }.
+  */
+  pub fn define_class_field(
+    &mut self,
+    constructor: &ObjectValue,
+    key: Value,
+    initializer: Value,
+    flags: usize,
+  ) -> Result<()> {
+    let name = match key {
+      Value::Symbol(key) if flags & CLASS_ELEMENT_PRIVATE != 0 => ClassElementName::Private(key),
+      key => ClassElementName::Property(to_property_key(self, &key)?),
+    };
+    let initializer = Some(initializer).filter(|initializer| !initializer.is_undefined());
+    let field = ClassFieldDefinition { name, initializer };
+    let class = get_class_definition(constructor);
+    match flags & CLASS_ELEMENT_STATIC {
+      0 => class.fields.borrow_mut().push(field),
+      _ => class
+        .static_elements
+        .borrow_mut()
+        .push(ClassStaticElement::Field(field)),
+    }
+    Ok(())
+  }
+
+  // 15.7.11 Runtime Semantics: ClassStaticBlockDefinitionEvaluation, the block runs once the class is done.
+  pub fn define_class_static_block(&mut self, constructor: &ObjectValue, function: Value) {
+    let class = get_class_definition(constructor);
+    class
+      .static_elements
+      .borrow_mut()
+      .push(ClassStaticElement::Block(function));
+  }
+
+  /*
+  15.7.14 Runtime Semantics: ClassDefinitionEvaluation
+    31. For each PrivateElement method of staticPrivateMethods, do
+      a. Perform ! PrivateMethodOrAccessorAdd(F, method).
+    32. For each element elementRecord of staticElements, do
+      a. If elementRecord is a ClassFieldDefinition Record, then
+        i. Let result be Completion(DefineField(F, elementRecord)).
+      b. Else,
+        i. Assert: elementRecord is a ClassStaticBlockDefinition Record.
+        ii. Let result be Completion(Call(elementRecord.[[BodyFunction]], F)).
+  */
+  pub fn initialize_class(&mut self, constructor: &ObjectValue) -> Result<()> {
+    let class = get_class_definition(constructor);
+    let methods = class.static_private_methods.take();
+    let elements = class.static_elements.take();
+    for method in methods {
+      self.private_method_or_accessor_add(constructor, method)?;
+    }
+    let receiver = Value::Object(constructor.clone());
+    for element in elements {
+      match element {
+        ClassStaticElement::Field(field) => self.define_field(constructor, &field)?,
+        ClassStaticElement::Block(function) => {
+          self.call_function(&function, &receiver, &[])?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /*
+  7.3.28 PrivateFieldAdd ( O, P, value )
+    2. Let entry be PrivateElementFind(O, P).
+    3. If entry is not empty, throw a TypeError exception.
+    4. Append PrivateElement { [[Key]]: P, [[Kind]]: field, [[Value]]: value } to O.[[PrivateElements]].
+  */
+  pub fn private_field_add(&mut self, object: &ObjectValue, key: &SymbolValue, value: Value) -> Result<()> {
+    if object.find_private_element(key).is_some() {
+      return Err(EngineError::TypeError(format!(
+        "Cannot initialize {} twice on the same object",
+        get_private_name(key)
+      )));
+    }
+    let kind = PrivateElementKind::Field(value);
+    object.add_private_element(PrivateElement { key: key.clone(), kind });
+    Ok(())
+  }
+
+  /*
+  7.3.29 PrivateMethodOrAccessorAdd ( O, method )
+    3. Let entry be PrivateElementFind(O, method.[[Key]]).
+    4. If entry is not empty, throw a TypeError exception.
+    5. Append method to O.[[PrivateElements]].
+  */
+  pub fn private_method_or_accessor_add(&mut self, object: &ObjectValue, method: PrivateElement) -> Result<()> {
+    if object.find_private_element(&method.key).is_some() {
+      return Err(EngineError::TypeError(format!(
+        "Cannot initialize {} twice on the same object",
+        get_private_name(&method.key)
+      )));
+    }
+    object.add_private_element(method);
+    Ok(())
+  }
+
+  /*
+  7.3.31 PrivateGet ( O, P )
+    1. Let entry be PrivateElementFind(O, P).
+    2. If entry is empty, throw a TypeError exception.
+    3. If entry.[[Kind]] is either field or method, then
+      a. Return entry.[[Value]].
+    4. Assert: entry.[[Kind]] is accessor.
+    5. If entry.[[Get]] is undefined, throw a TypeError exception.
+    6. Let getter be entry.[[Get]].
+    7. Return ? Call(getter, O).
+  Primitives can not have private elements, so they are treated like any object that does not have it.
+  */
+  pub fn private_get(&mut self, object: &Value, key: &SymbolValue) -> Result<Value> {
+    let entry = object.get_object().and_then(|object| object.find_private_element(key));
+    let Some(entry) = entry else {
+      return Err(EngineError::TypeError(format!(
+        "Cannot read private member {} from an object whose class did not declare it",
+        get_private_name(key)
+      )));
+    };
+    match entry.kind {
+      PrivateElementKind::Field(value) | PrivateElementKind::Method(value) => Ok(value),
+      PrivateElementKind::Accessor { get: Some(getter), .. } => self.call_function(&getter, object, &[]),
+      PrivateElementKind::Accessor { get: None, .. } => Err(EngineError::TypeError(format!(
+        "'{}' was defined without a getter",
+        get_private_name(key)
+      ))),
+    }
+  }
+
+  /*
+  7.3.32 PrivateSet ( O, P, value )
+    1. Let entry be PrivateElementFind(O, P).
+    2. If entry is empty, throw a TypeError exception.
+    3. If entry.[[Kind]] is field, then
+      a. Set entry.[[Value]] to value.
+    4. Else if entry.[[Kind]] is method, then
+      a. Throw a TypeError exception.
+    5. Else,
+      b. If entry.[[Set]] is undefined, throw a TypeError exception.
+      c. Let setter be entry.[[Set]].
+      d. Perform ? Call(setter, O, « value »).
+  */
+  pub fn private_set(&mut self, object: &Value, key: &SymbolValue, value: Value) -> Result<()> {
+    let entry = object
+      .get_object()
+      .and_then(|target| Some((target, target.find_private_element(key)?)));
+    let Some((target, entry)) = entry else {
+      return Err(EngineError::TypeError(format!(
+        "Cannot write private member {} to an object whose class did not declare it",
+        get_private_name(key)
+      )));
+    };
+    match entry.kind {
+      PrivateElementKind::Field(_) => target.set_private_field(key, value),
+      PrivateElementKind::Method(_) => {
+        return Err(EngineError::TypeError(format!(
+          "Private method '{}' is not writable",
+          get_private_name(key)
+        )))
+      }
+      PrivateElementKind::Accessor { set: Some(setter), .. } => {
+        self.call_function(&setter, object, &[value])?;
+      }
+      PrivateElementKind::Accessor { set: None, .. } => {
+        return Err(EngineError::TypeError(format!(
+          "'{}' was defined without a setter",
+          get_private_name(key)
+        )))
+      }
+    }
+    Ok(())
+  }
+
+  /*
+  13.10.1 Runtime Semantics: Evaluation, RelationalExpression : PrivateIdentifier in ShiftExpression
+    4. If rval is not an Object, throw a TypeError exception.
+    7. If PrivateElementFind(rval, privateName) is not empty, return true.
+    8. Return false.
+  */
+  pub fn has_private_element(&self, object: &Value, key: &SymbolValue) -> Result<bool> {
+    let Some(object) = object.get_object() else {
+      return Err(EngineError::TypeError(format!(
+        "Cannot use 'in' operator to search for '{}' in {}",
+        get_private_name(key),
+        object
+      )));
+    };
+    Ok(object.find_private_element(key).is_some())
+  }
+
+  /*
+  7.3.33 DefineField ( receiver, fieldRecord )
+    1. Let fieldName be fieldRecord.[[Name]].
+    2. Let initializer be fieldRecord.[[Initializer]].
+    3. If initializer is not empty, then
+      a. Let initValue be ? Call(initializer, receiver).
+    4. Else,
+      a. Let initValue be undefined.
+    5. If fieldName is a Private Name, then
+      a. Perform ? PrivateFieldAdd(receiver, fieldName, initValue).
+    6. Else,
+      b. Perform ? CreateDataPropertyOrThrow(receiver, fieldName, initValue).
+  */
+  pub fn define_field(&mut self, receiver: &ObjectValue, field: &ClassFieldDefinition) -> Result<()> {
+    let value = match &field.initializer {
+      Some(initializer) => self.call_function(initializer, &Value::Object(receiver.clone()), &[])?,
+      None => Value::create_undefined_value(),
+    };
+    match &field.name {
+      ClassElementName::Private(key) => self.private_field_add(receiver, key, value),
+      ClassElementName::Property(key) => self.create_data_property_or_throw(receiver, key.clone(), value),
+    }
+  }
+
+  /*
+  7.3.34 InitializeInstanceElements ( O, constructor )
+    1. Let methods be the value of constructor.[[PrivateMethods]].
+    2. For each PrivateElement method of methods, do
+      a. Perform ? PrivateMethodOrAccessorAdd(O, method).
+    3. Let fields be the value of constructor.[[Fields]].
+    4. For each element fieldRecord of fields, do
+      a. Perform ? DefineField(O, fieldRecord).
+  */
+  pub fn initialize_instance_elements(&mut self, object: &ObjectValue, constructor: &ObjectValue) -> Result<()> {
+    let Some(class) = constructor.get_function().and_then(FunctionValue::get_class).cloned() else {
+      return Ok(());
+    };
+    let methods = class.private_methods.borrow().clone();
+    for method in methods {
+      self.private_method_or_accessor_add(object, method)?;
+    }
+    let fields = class.fields.borrow().clone();
+    for field in &fields {
+      self.define_field(object, field)?;
+    }
+    Ok(())
+  }
+}
+
+// the elements of the class being defined, kept by its constructor.
+fn get_class_definition(constructor: &ObjectValue) -> Rc<ClassDefinition> {
+  let class = constructor.get_function().and_then(FunctionValue::get_class);
+  class.cloned().expect("class constructor to be on the stack")
+}
+
+// the `#name` a Private Name was declared with, for the messages of errors.
+fn get_private_name(key: &SymbolValue) -> String {
+  key.get_description().cloned().unwrap_or_default()
+}
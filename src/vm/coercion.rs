@@ -0,0 +1,551 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+7.1 Type Conversion
+The ECMAScript language implicitly performs automatic type conversion as needed. To clarify the semantics of
+  certain constructs it is useful to define a set of conversion abstract operations.
+The conversion abstract operations are polymorphic; they can accept a value of any ECMAScript language type.
+
+@links:
+- https://tc39.es/ecma262/#sec-type-conversion
+*/
+#![allow(dead_code)]
+use crate::errors::{EngineError, Result};
+use crate::values::property::PropertyKey;
+use crate::values::{BigIntValue, ObjectValue, StringValue, Value};
+use crate::vm::core::Engine;
+
+// the hint ToPrimitive is given, Default when the caller did not give one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreferredType {
+  Default,
+  String,
+  Number,
+}
+
+impl PreferredType {
+  fn as_str(&self) -> &'static str {
+    match self {
+      PreferredType::Default => "default",
+      PreferredType::String => "string",
+      PreferredType::Number => "number",
+    }
+  }
+}
+
+/*
+7.1.1 ToPrimitive ( input [ , preferredType ] )
+  1. If input is an Object, then
+    a. Let exoticToPrim be ? GetMethod(input, @@toPrimitive).
+    b. If exoticToPrim is not undefined, then
+      i. If preferredType is not present, then
+        1. Let hint be "default".
+      iv. Let result be ? Call(exoticToPrim, input, « hint »).
+      v. If result is not an Object, return result.
+      vi. Throw a TypeError exception.
+    c. If preferredType is not present, let preferredType be number.
+    d. Return ? OrdinaryToPrimitive(input, preferredType).
+  2. Return input.
+*/
+pub fn to_primitive(engine: &mut Engine, value: &Value, hint: PreferredType) -> Result<Value> {
+  let Some(object) = value.get_object() else {
+    return Ok(value.clone());
+  };
+  let key = engine.get_well_known_symbol("Symbol.toPrimitive");
+  if let Some(method) = engine.get_method(value, &key)? {
+    let hint = Value::create_string_value(hint.as_str().to_owned());
+    let result = engine.call_function(&method, value, &[hint])?;
+    if result.is_object() {
+      return Err(EngineError::TypeError(
+        "Cannot convert object to primitive value".to_owned(),
+      ));
+    }
+    return Ok(result);
+  }
+  ordinary_to_primitive(engine, object, value, hint)
+}
+
+/*
+7.1.1.1 OrdinaryToPrimitive ( O, hint )
+  1. If hint is string, then
+    a. Let methodNames be « "toString", "valueOf" ».
+  2. Else,
+    a. Let methodNames be « "valueOf", "toString" ».
+  3. For each element name of methodNames, do
+    a. Let method be ? Get(O, name).
+    b. If IsCallable(method) is true, then
+      i. Let result be ? Call(method, O).
+      ii. If result is not an Object, return result.
+  4. Throw a TypeError exception.
+*/
+fn ordinary_to_primitive(
+  engine: &mut Engine,
+  object: &ObjectValue,
+  value: &Value,
+  hint: PreferredType,
+) -> Result<Value> {
+  let names = match hint {
+    PreferredType::String => ["toString", "valueOf"],
+    PreferredType::Number | PreferredType::Default => ["valueOf", "toString"],
+  };
+  for name in names {
+    let method = engine.get(object, &PropertyKey::from(name), value)?;
+    if method.is_function() {
+      let result = engine.call_function(&method, value, &[])?;
+      if !result.is_object() {
+        return Ok(result);
+      }
+    }
+  }
+  Err(EngineError::TypeError(
+    "Cannot convert object to primitive value".to_owned(),
+  ))
+}
+
+/*
+7.1.2 ToBoolean ( argument )
+Only undefined, null, false, the zeros, NaN, the empty string and 0n are false.
+*/
+pub fn to_boolean(value: &Value) -> bool {
+  value.is_truthy()
+}
+
+// 7.1.4 ToNumber ( argument )
+pub fn to_number(engine: &mut Engine, value: &Value) -> Result<f64> {
+  match value {
+    Value::Number(number) => Ok(number.as_f64()),
+    Value::Undefined(_) | Value::Uninitialized => Ok(f64::NAN),
+    Value::Null(_) => Ok(0.0),
+    Value::Boolean(value) => Ok(if value.get() { 1.0 } else { 0.0 }),
+    Value::String(value) => Ok(string_to_number(&value.get())),
+    Value::Symbol(_) => Err(EngineError::TypeError(
+      "Cannot convert a Symbol value to a number".to_owned(),
+    )),
+    Value::BigInt(_) => Err(EngineError::TypeError(
+      "Cannot convert a BigInt value to a number".to_owned(),
+    )),
+    Value::Object(_) => {
+      let primitive = to_primitive(engine, value, PreferredType::Number)?;
+      to_number(engine, &primitive)
+    }
+  }
+}
+
+/*
+7.1.3 ToNumeric ( value )
+  1. Let primValue be ? ToPrimitive(value, number).
+  2. If primValue is a BigInt, return primValue.
+  3. Return ? ToNumber(primValue).
+The result is either a Number or a BigInt value.
+*/
+pub fn to_numeric(engine: &mut Engine, value: &Value) -> Result<Value> {
+  match to_primitive(engine, value, PreferredType::Number)? {
+    value @ (Value::Number(_) | Value::BigInt(_)) => Ok(value),
+    value => Ok(Value::create_float_value(to_number(engine, &value)?)),
+  }
+}
+
+/*
+7.1.4.1.1 StringToNumber ( str )
+  1. Let text be StringToCodePoints(str).
+  2. Let literal be ParseText(text, StringNumericLiteral).
+  3. If literal is a List of errors, return NaN.
+  4. Return StringNumericValue of literal.
+A StringNumericLiteral is white space around a decimal literal with an optional sign, `Infinity` included, or
+  around an unsigned hex, octal or binary integer. Numeric separators are not allowed.
+*/
+pub fn string_to_number(value: &str) -> f64 {
+  let value = value.trim_matches(is_white_space_or_line_terminator);
+  if value.is_empty() {
+    return 0.0;
+  }
+  let radix = match value.get(..2) {
+    Some("0x" | "0X") => 16,
+    Some("0o" | "0O") => 8,
+    Some("0b" | "0B") => 2,
+    _ => return decimal_to_number(value),
+  };
+  non_decimal_to_number(&value[2..], radix)
+}
+
+// 12.2 White Space and 12.3 Line Terminators, the space separators of Unicode and the BOM are white space too.
+pub fn is_white_space_or_line_terminator(char: char) -> bool {
+  // NEL is white space for rust but not for ECMAScript
+  char == '\u{FEFF}' || (char.is_whitespace() && char != '\u{85}')
+}
+
+// StrDecimalLiteral, rust parses the same digits once the spellings it accepts beyond them are ruled out.
+fn decimal_to_number(value: &str) -> f64 {
+  let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+  if unsigned == "Infinity" {
+    return if value.starts_with('-') {
+      f64::NEG_INFINITY
+    } else {
+      f64::INFINITY
+    };
+  }
+  let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+    Some(index) => (&unsigned[..index], Some(&unsigned[index + 1..])),
+    None => (unsigned, None),
+  };
+  let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+  let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+  let has_digits = !integer.is_empty() || !fraction.is_empty();
+  let exponent_digits = exponent.map(|exponent| exponent.strip_prefix(['+', '-']).unwrap_or(exponent));
+  let valid_exponent = exponent_digits.is_none_or(|digits| !digits.is_empty() && is_digits(digits));
+  if !has_digits || !is_digits(integer) || !is_digits(fraction) || !valid_exponent {
+    return f64::NAN;
+  }
+  value.parse::<f64>().unwrap_or(f64::NAN)
+}
+
+// NonDecimalIntegerLiteral, the digits are exact until they need more than 128 bits.
+fn non_decimal_to_number(digits: &str, radix: u32) -> f64 {
+  if digits.is_empty() || !digits.chars().all(|char| char.is_digit(radix)) {
+    return f64::NAN;
+  }
+  match u128::from_str_radix(digits, radix) {
+    Ok(value) => value as f64,
+    Err(_) => digits
+      .chars()
+      .filter_map(|char| char.to_digit(radix))
+      .fold(0.0, |value, digit| value * radix as f64 + digit as f64),
+  }
+}
+
+/*
+7.1.13 ToBigInt ( argument )
+  1. Let prim be ? ToPrimitive(argument, number).
+  2. Return the value that prim corresponds to in Table 12.
+Undefined, null, numbers and symbols throw a TypeError, booleans are 1n and 0n and strings are parsed with
+  StringToBigInt, a SyntaxError when they do not hold an integer.
+*/
+pub fn to_bigint(engine: &mut Engine, value: &Value) -> Result<BigIntValue> {
+  match to_primitive(engine, value, PreferredType::Number)? {
+    Value::BigInt(value) => Ok(value),
+    Value::Boolean(value) => Ok(BigIntValue::from_i64(value.get() as i64)),
+    Value::String(value) => string_to_bigint(&value.get())
+      .ok_or_else(|| EngineError::SyntaxError(format!("Cannot convert {} to a BigInt", value))),
+    Value::Number(number) => Err(EngineError::TypeError(format!(
+      "Cannot convert {} to a BigInt",
+      number_to_string(number.as_f64())
+    ))),
+    value => Err(EngineError::TypeError(format!("Cannot convert {} to a BigInt", value))),
+  }
+}
+
+/*
+7.1.14 StringToBigInt ( str )
+  1. Let text be StringToCodePoints(str).
+  2. Let literal be ParseText(text, StringIntegerLiteral).
+  3. If literal is a List of errors, return undefined.
+  4. Let mv be the MV of literal.
+  6. Return ℤ(mv).
+A StringIntegerLiteral is white space around a decimal integer with an optional sign, or around an unsigned hex,
+  octal or binary integer. None stands for undefined.
+*/
+pub fn string_to_bigint(value: &str) -> Option<BigIntValue> {
+  let value = value.trim_matches(is_white_space_or_line_terminator);
+  if value.is_empty() {
+    return Some(BigIntValue::zero());
+  }
+  let radix = match value.get(..2) {
+    Some("0x" | "0X") => 16,
+    Some("0o" | "0O") => 8,
+    Some("0b" | "0B") => 2,
+    _ => {
+      let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+      let integer = BigIntValue::from_str_radix(digits, 10)?;
+      return Some(if value.starts_with('-') {
+        integer.unary_minus()
+      } else {
+        integer
+      });
+    }
+  };
+  BigIntValue::from_str_radix(&value[2..], radix)
+}
+
+// 7.1.5 ToIntegerOrInfinity ( argument )
+pub fn to_integer_or_infinity(engine: &mut Engine, value: &Value) -> Result<f64> {
+  let number = to_number(engine, value)?;
+  if number.is_nan() {
+    return Ok(0.0);
+  }
+  Ok(number.trunc())
+}
+
+/*
+7.1.6 ToInt32 ( argument ), for a value already converted to a number.
+  2. If number is not finite or number is either +0𝔽 or -0𝔽, return +0𝔽.
+  3. Let int be truncate(ℝ(number)).
+  4. Let int32bit be int modulo 2^32.
+  5. If int32bit ≥ 2^31, return 𝔽(int32bit - 2^32); otherwise return 𝔽(int32bit).
+*/
+pub fn to_int32(number: f64) -> i32 {
+  to_uint32(number) as i32
+}
+
+// 7.1.7 ToUint32 ( argument ), for a value already converted to a number.
+pub fn to_uint32(number: f64) -> u32 {
+  if !number.is_finite() {
+    return 0;
+  }
+  number.trunc().rem_euclid(4294967296.0) as u32
+}
+
+/*
+7.1.17 ToString ( argument )
+Strings are returned as they are, the other primitives are formatted and objects are converted to a primitive
+  with the string hint first.
+*/
+pub fn to_string(engine: &mut Engine, value: &Value) -> Result<StringValue> {
+  match value {
+    Value::String(value) => Ok(value.clone()),
+    Value::Boolean(value) => Ok(StringValue::new(value.get().to_string())),
+    Value::Number(number) => Ok(StringValue::new(number_to_string(number.as_f64()))),
+    Value::BigInt(value) => Ok(StringValue::new(value.to_string_radix(10))),
+    Value::Symbol(_) => Err(EngineError::TypeError(
+      "Cannot convert a Symbol value to a string".to_owned(),
+    )),
+    Value::Object(_) => {
+      let primitive = to_primitive(engine, value, PreferredType::String)?;
+      to_string(engine, &primitive)
+    }
+    value => Ok(StringValue::new(value.to_string())),
+  }
+}
+
+/*
+6.1.6.1.20 Number::toString ( x, radix ), with radix 10
+  2. If x is NaN, return "NaN".
+  3. If x is either +0𝔽 or -0𝔽, return "0".
+  4. If x < -0𝔽, return the string-concatenation of "-" and Number::toString(-x, radix).
+  5. If x is +∞𝔽, return "Infinity".
+  6. Let n, k, and s be integers such that k ≥ 1, radix^(k - 1) ≤ s < radix^k, 𝔽(s × radix^(n - k)) is x, and k
+    is as small as possible.
+  7. If radix ≠ 10 or n is in the inclusive interval from -5 to 21, then
+    a. If n ≥ k, return s followed by n - k zeros.
+    b. If n > 0, return the first n digits of s, ".", and the remaining k - n digits.
+    c. Return "0.", -n zeros, and the digits of s.
+  8. Otherwise the exponential form d.ddde±n-1 is used.
+The shortest digits that round-trip are the ones rust prints in its exponential notation.
+*/
+pub fn number_to_string(number: f64) -> String {
+  if number.is_nan() {
+    return "NaN".to_owned();
+  }
+  if number == 0.0 {
+    return "0".to_owned();
+  }
+  if number < 0.0 {
+    return format!("-{}", number_to_string(-number));
+  }
+  if number.is_infinite() {
+    return "Infinity".to_owned();
+  }
+  let scientific = format!("{:e}", number);
+  let (mantissa, exponent) = scientific.split_once('e').expect("exponential notation");
+  let digits = mantissa.replace('.', "");
+  let (k, n) = (digits.len() as i32, exponent.parse::<i32>().expect("exponent") + 1);
+  if k <= n && n <= 21 {
+    return format!("{}{}", digits, "0".repeat((n - k) as usize));
+  }
+  if 0 < n && n <= 21 {
+    return format!("{}.{}", &digits[..n as usize], &digits[n as usize..]);
+  }
+  if -6 < n && n <= 0 {
+    return format!("0.{}{}", "0".repeat(-n as usize), digits);
+  }
+  let sign = if n - 1 < 0 { '-' } else { '+' };
+  match k {
+    1 => format!("{}e{}{}", digits, sign, (n - 1).abs()),
+    _ => format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, (n - 1).abs()),
+  }
+}
+
+/*
+6.1.6.1.20 Number::toString ( x, radix ), with a radix other than 10
+  6. Let n, k, and s be integers such that k ≥ 1, radix^(k - 1) ≤ s < radix^k, 𝔽(s × radix^(n - k)) is x, and k
+    is as small as possible.
+  7. If radix ≠ 10 or n is in the inclusive interval from -5 to 21, then
+    a. If n ≥ k, return s followed by n - k zeros.
+    b. If n > 0, return the first n digits of s, ".", and the remaining k - n digits.
+    c. Return "0.", -n zeros, and the digits of s.
+The fraction gets digits until the ones left are below the precision of the number, rounding the last one like
+  the engines of the browsers do. The integer digits past the precision of a double are zeros.
+*/
+pub fn number_to_radix_string(number: f64, radix: u32) -> String {
+  if radix == 10 || !number.is_finite() || number == 0.0 {
+    return number_to_string(number);
+  }
+  if number < 0.0 {
+    return format!("-{}", number_to_radix_string(-number, radix));
+  }
+  let radix_f64 = radix as f64;
+  let mut integer = number.trunc();
+  let mut fraction = number - integer;
+  // half the distance to the next double, the smallest difference the digits can still show
+  let mut delta = (0.5 * (number.next_up() - number)).max(0.0_f64.next_up());
+  let mut fraction_digits = Vec::new();
+  if fraction >= delta {
+    loop {
+      fraction *= radix_f64;
+      delta *= radix_f64;
+      let digit = fraction.trunc() as u32;
+      fraction_digits.push(digit);
+      fraction -= digit as f64;
+      if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+        // rounds up, carrying into the digits before and then into the integer part
+        loop {
+          match fraction_digits.pop() {
+            Some(digit) if digit + 1 < radix => {
+              fraction_digits.push(digit + 1);
+              break;
+            }
+            Some(_) => continue,
+            None => {
+              integer += 1.0;
+              break;
+            }
+          }
+        }
+        break;
+      }
+      if fraction < delta {
+        break;
+      }
+    }
+  }
+  let mut integer_digits = Vec::new();
+  while (integer / radix_f64) >= 2f64.powi(53) {
+    integer /= radix_f64;
+    integer_digits.push(0);
+  }
+  loop {
+    let remainder = integer % radix_f64;
+    integer_digits.push(remainder as u32);
+    integer = (integer - remainder) / radix_f64;
+    if integer <= 0.0 {
+      break;
+    }
+  }
+  let to_char = |digit: &u32| char::from_digit(*digit, radix).expect("a digit of the radix");
+  let mut result: String = integer_digits.iter().rev().map(to_char).collect();
+  if !fraction_digits.is_empty() {
+    result.push('.');
+    result.extend(fraction_digits.iter().map(to_char));
+  }
+  result
+}
+
+/*
+7.1.19 ToPropertyKey ( argument )
+  1. Let key be ? ToPrimitive(argument, string).
+  2. If key is a Symbol, then
+    a. Return key.
+  3. Return ! ToString(key).
+*/
+pub fn to_property_key(engine: &mut Engine, value: &Value) -> Result<PropertyKey> {
+  match to_primitive(engine, value, PreferredType::String)? {
+    Value::Symbol(symbol) => Ok(PropertyKey::Symbol(symbol)),
+    key => Ok(PropertyKey::from(to_string(engine, &key)?)),
+  }
+}
+
+// 7.1.20 ToLength ( argument )
+pub fn to_length(engine: &mut Engine, value: &Value) -> Result<u64> {
+  let length = to_integer_or_infinity(engine, value)?;
+  Ok(length.clamp(0.0, 9007199254740991.0) as u64)
+}
+
+/*
+7.1.22 ToIndex ( value )
+  1. Let integer be ? ToIntegerOrInfinity(value).
+  2. If integer is not in the inclusive interval from 0 to 2^53 - 1, throw a RangeError exception.
+  3. Return integer.
+*/
+pub fn to_index(engine: &mut Engine, value: &Value) -> Result<u64> {
+  let integer = to_integer_or_infinity(engine, value)?;
+  if !(0.0..=9007199254740991.0).contains(&integer) {
+    return Err(EngineError::RangeError("Invalid index".to_owned()));
+  }
+  Ok(integer as u64)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn number_to_string_uses_the_shortest_digits() {
+    assert_eq!(number_to_string(0.1 + 0.2), "0.30000000000000004");
+    assert_eq!(number_to_string(123.0), "123");
+    assert_eq!(number_to_string(-1.5), "-1.5");
+    assert_eq!(number_to_string(-0.0), "0");
+    assert_eq!(number_to_string(f64::NAN), "NaN");
+    assert_eq!(number_to_string(f64::NEG_INFINITY), "-Infinity");
+    assert_eq!(number_to_string(f64::MAX), "1.7976931348623157e+308");
+    assert_eq!(number_to_string(5e-324), "5e-324");
+  }
+
+  #[test]
+  fn number_to_string_switches_to_exponents_past_21_digits() {
+    assert_eq!(number_to_string(1e20), "100000000000000000000");
+    assert_eq!(number_to_string(1e21), "1e+21");
+    assert_eq!(number_to_string(1.5e21), "1.5e+21");
+    assert_eq!(number_to_string(0.000001), "0.000001");
+    assert_eq!(number_to_string(0.0000001), "1e-7");
+    assert_eq!(number_to_string(1.25e-7), "1.25e-7");
+  }
+
+  #[test]
+  fn number_to_radix_string_stops_at_the_precision_of_the_number() {
+    assert_eq!(number_to_radix_string(255.0, 16), "ff");
+    assert_eq!(number_to_radix_string(-255.5, 16), "-ff.8");
+    assert_eq!(number_to_radix_string(0.5, 2), "0.1");
+    assert_eq!(number_to_radix_string(3.75, 8), "3.6");
+    assert_eq!(
+      number_to_radix_string(0.1, 2),
+      "0.0001100110011001100110011001100110011001100110011001101"
+    );
+    assert_eq!(number_to_radix_string(2f64.powi(60), 2), format!("1{}", "0".repeat(60)));
+    assert_eq!(number_to_radix_string(f64::NAN, 2), "NaN");
+    assert_eq!(number_to_radix_string(1e21, 10), "1e+21");
+  }
+
+  #[test]
+  fn string_to_number_reads_decimal_literals() {
+    assert_eq!(string_to_number(""), 0.0);
+    assert_eq!(string_to_number(" \n\t "), 0.0);
+    assert_eq!(string_to_number("  42  "), 42.0);
+    assert_eq!(string_to_number("-.5"), -0.5);
+    assert_eq!(string_to_number("5."), 5.0);
+    assert_eq!(string_to_number("+1e3"), 1000.0);
+    assert_eq!(string_to_number("1E-2"), 0.01);
+    assert_eq!(string_to_number("-Infinity"), f64::NEG_INFINITY);
+    assert_eq!(string_to_number("\u{FEFF}7\u{2028}"), 7.0);
+    assert!(string_to_number("-0").is_sign_negative());
+  }
+
+  #[test]
+  fn string_to_number_reads_non_decimal_integers() {
+    assert_eq!(string_to_number("0x1F"), 31.0);
+    assert_eq!(string_to_number("0o17"), 15.0);
+    assert_eq!(string_to_number("0B101"), 5.0);
+    assert_eq!(string_to_number("0x10000000000000001"), 18446744073709552000.0);
+  }
+
+  #[test]
+  fn string_to_number_rejects_other_spellings() {
+    for text in [
+      "abc", "1_000", "-0x10", "0x", "1e", ".", "inf", "infinity", "NaN", "1 2", "\u{85}1",
+    ] {
+      assert!(string_to_number(text).is_nan(), "{:?} is not a number", text);
+    }
+  }
+}
@@ -0,0 +1,307 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+7.2 Testing and Comparison Operations
+The relational and equality operators compare their operands after converting them, objects are converted to
+  primitives and the primitives to numbers unless both are strings.
+The strict comparisons never convert, they only differ in how they treat NaN and the zeros.
+
+@links:
+- https://tc39.es/ecma262/#sec-testing-and-comparison-operations
+- https://tc39.es/ecma262/#sec-islessthan
+- https://tc39.es/ecma262/#sec-islooselyequal
+- https://tc39.es/ecma262/#sec-isstrictlyequal
+- https://tc39.es/ecma262/#sec-samevalue
+- https://tc39.es/ecma262/#sec-samevaluezero
+*/
+#![allow(dead_code)]
+use crate::errors::Result;
+use crate::values::Value;
+use crate::vm::coercion::{string_to_bigint, to_number, to_numeric, to_primitive, PreferredType};
+use crate::vm::core::Engine;
+
+/*
+7.2.13 IsLessThan ( x, y, LeftFirst )
+  1. If LeftFirst is true, then
+    a. Let px be ? ToPrimitive(x, number).
+    b. Let py be ? ToPrimitive(y, number).
+  2. Else,
+    a. NOTE: The order of evaluation needs to be reversed to preserve left to right evaluation.
+    b. Let py be ? ToPrimitive(y, number).
+    c. Let px be ? ToPrimitive(x, number).
+  3. If px is a String and py is a String, then
+    a. Let lx be the length of px.
+    ...
+    e. Return false when px is a prefix of py or the first differing code unit of px is not smaller.
+  4. Else,
+    a. If px is a BigInt and py is a String, then
+      i. Let ny be StringToBigInt(py).
+      ii. If ny is undefined, return undefined.
+      iii. Return BigInt::lessThan(px, ny).
+    b. If px is a String and py is a BigInt, then
+      i. Let nx be StringToBigInt(px).
+      ii. If nx is undefined, return undefined.
+      iii. Return BigInt::lessThan(nx, py).
+    c. Let nx be ? ToNumeric(px).
+    d. Let ny be ? ToNumeric(py).
+    e. If Type(nx) is Type(ny), then
+      i. If nx is a Number, return Number::lessThan(nx, ny).
+      ii. Else, return BigInt::lessThan(nx, ny).
+    h. If nx or ny is NaN, return undefined.
+    i. If nx is -∞𝔽 or ny is +∞𝔽, return true.
+    j. If nx is +∞𝔽 or ny is -∞𝔽, return false.
+    k. If ℝ(nx) < ℝ(ny), return true; otherwise return false.
+None stands for undefined.
+*/
+pub fn is_less_than(engine: &mut Engine, x: &Value, y: &Value, left_first: bool) -> Result<Option<bool>> {
+  let (px, py) = if left_first {
+    let px = to_primitive(engine, x, PreferredType::Number)?;
+    (px, to_primitive(engine, y, PreferredType::Number)?)
+  } else {
+    let py = to_primitive(engine, y, PreferredType::Number)?;
+    (to_primitive(engine, x, PreferredType::Number)?, py)
+  };
+  match (&px, &py) {
+    (Value::String(px), Value::String(py)) => return Ok(Some(px.get_units() < py.get_units())),
+    (Value::BigInt(px), Value::String(py)) => return Ok(string_to_bigint(&py.get()).map(|py| px.less_than(&py))),
+    (Value::String(px), Value::BigInt(py)) => return Ok(string_to_bigint(&px.get()).map(|px| px.less_than(py))),
+    _ => {}
+  }
+  match (to_numeric(engine, &px)?, to_numeric(engine, &py)?) {
+    (Value::Number(nx), Value::Number(ny)) => Ok(nx.less_than(&ny)),
+    (Value::BigInt(nx), Value::BigInt(ny)) => Ok(Some(nx.less_than(&ny))),
+    (Value::BigInt(nx), Value::Number(ny)) => Ok(nx.compare_number(ny.as_f64()).map(|order| order.is_lt())),
+    (Value::Number(nx), Value::BigInt(ny)) => Ok(ny.compare_number(nx.as_f64()).map(|order| order.is_gt())),
+    (nx, ny) => unreachable!("ToNumeric to give numbers or bigints, got {} and {}", nx, ny),
+  }
+}
+
+/*
+7.2.14 IsLooselyEqual ( x, y )
+  1. If Type(x) is Type(y), then
+    a. Return IsStrictlyEqual(x, y).
+  2. If x is null and y is undefined, return true.
+  3. If x is undefined and y is null, return true.
+  5. If x is a Number and y is a String, return ! IsLooselyEqual(x, ! ToNumber(y)).
+  6. If x is a String and y is a Number, return ! IsLooselyEqual(! ToNumber(x), y).
+  7. If x is a BigInt and y is a String, then
+    a. Let n be StringToBigInt(y).
+    b. If n is undefined, return false.
+    c. Return ! IsLooselyEqual(x, n).
+  8. If x is a String and y is a BigInt, return ! IsLooselyEqual(y, x).
+  9. If x is a Boolean, return ! IsLooselyEqual(! ToNumber(x), y).
+  10. If y is a Boolean, return ! IsLooselyEqual(x, ! ToNumber(y)).
+  11. If x is either a String, a Number, a BigInt, or a Symbol and y is an Object, then
+    a. Return ! IsLooselyEqual(x, ? ToPrimitive(y)).
+  12. If x is an Object and y is either a String, a Number, a BigInt, or a Symbol, then
+    a. Return ! IsLooselyEqual(? ToPrimitive(x), y).
+  13. If x is a BigInt and y is a Number, or if x is a Number and y is a BigInt, then
+    a. If x is not finite or y is not finite, return false.
+    b. If ℝ(x) = ℝ(y), return true; otherwise return false.
+  14. Return false.
+*/
+pub fn is_loosely_equal(engine: &mut Engine, x: &Value, y: &Value) -> Result<bool> {
+  match (x, y) {
+    _ if std::mem::discriminant(x) == std::mem::discriminant(y) => Ok(is_strictly_equal(x, y)),
+    (Value::Null(_), Value::Undefined(_)) | (Value::Undefined(_), Value::Null(_)) => Ok(true),
+    (Value::Number(_), Value::String(_)) | (_, Value::Boolean(_)) => {
+      let y = Value::create_float_value(to_number(engine, y)?);
+      is_loosely_equal(engine, x, &y)
+    }
+    (Value::String(_), Value::Number(_)) | (Value::Boolean(_), _) => {
+      let x = Value::create_float_value(to_number(engine, x)?);
+      is_loosely_equal(engine, &x, y)
+    }
+    (Value::BigInt(x), Value::String(y)) | (Value::String(y), Value::BigInt(x)) => {
+      Ok(string_to_bigint(&y.get()).is_some_and(|y| *x == y))
+    }
+    (Value::BigInt(x), Value::Number(y)) | (Value::Number(y), Value::BigInt(x)) => {
+      Ok(x.compare_number(y.as_f64()).is_some_and(|order| order.is_eq()))
+    }
+    (Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_), Value::Object(_)) => {
+      let y = to_primitive(engine, y, PreferredType::Default)?;
+      is_loosely_equal(engine, x, &y)
+    }
+    (Value::Object(_), Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_)) => {
+      let x = to_primitive(engine, x, PreferredType::Default)?;
+      is_loosely_equal(engine, &x, y)
+    }
+    _ => Ok(false),
+  }
+}
+
+/*
+7.2.15 IsStrictlyEqual ( x, y )
+  1. If Type(x) is not Type(y), return false.
+  2. If x is a Number, then
+    a. Return Number::equal(x, y).
+  3. Return SameValueNonNumber(x, y).
+NaN is not equal to itself and the zeros are equal.
+*/
+pub fn is_strictly_equal(x: &Value, y: &Value) -> bool {
+  match (x, y) {
+    (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+    _ => same_value_non_number(x, y),
+  }
+}
+
+/*
+7.2.10 SameValue ( x, y )
+  1. If Type(x) is not Type(y), return false.
+  2. If x is a Number, then
+    a. Return Number::sameValue(x, y).
+  3. Return SameValueNonNumber(x, y).
+*/
+pub fn same_value(x: &Value, y: &Value) -> bool {
+  match (x, y) {
+    (Value::Number(x), Value::Number(y)) => x.same_value(y),
+    _ => same_value_non_number(x, y),
+  }
+}
+
+/*
+7.2.11 SameValueZero ( x, y )
+  1. If Type(x) is not Type(y), return false.
+  2. If x is a Number, then
+    a. Return Number::sameValueZero(x, y).
+  3. Return SameValueNonNumber(x, y).
+NaN is the same as itself and the zeros are the same.
+*/
+pub fn same_value_zero(x: &Value, y: &Value) -> bool {
+  match (x, y) {
+    (Value::Number(x), Value::Number(y)) => x.is_nan() && y.is_nan() || x.as_f64() == y.as_f64(),
+    _ => same_value_non_number(x, y),
+  }
+}
+
+/*
+7.2.12 SameValueNonNumber ( x, y )
+  2. If x is either null or undefined, return true.
+  3. If x is a BigInt, then
+    a. Return BigInt::equal(x, y).
+  4. If x is a String, then
+    a. If x and y have the same length and the same code units in the same positions, return true; otherwise, return false.
+  5. If x is a Boolean, then
+    a. If x and y are both true or both false, return true; otherwise, return false.
+  6. NOTE: All other ECMAScript language values are compared by identity.
+  7. If x is y, return true; otherwise, return false.
+*/
+fn same_value_non_number(x: &Value, y: &Value) -> bool {
+  match (x, y) {
+    (Value::Undefined(_), Value::Undefined(_)) | (Value::Null(_), Value::Null(_)) => true,
+    (Value::BigInt(x), Value::BigInt(y)) => x == y,
+    (Value::String(x), Value::String(y)) => x == y,
+    (Value::Boolean(x), Value::Boolean(y)) => x.get() == y.get(),
+    (Value::Symbol(x), Value::Symbol(y)) => x.is_same(y),
+    (Value::Object(x), Value::Object(y)) => x.is_same(y),
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::compiler::compile;
+  use crate::context::Context;
+  use crate::stack::Stack;
+  use crate::utils::STACK_LIMIT;
+  use crate::values::{BigIntValue, ObjectKind, ObjectValue};
+  use oxc_allocator::Allocator;
+  use oxc_span::SourceType;
+  use std::rc::Rc;
+
+  fn number(value: f64) -> Value {
+    Value::create_float_value(value)
+  }
+
+  fn string(value: &str) -> Value {
+    Value::create_string_value(value.to_owned())
+  }
+
+  fn bigint(value: i64) -> Value {
+    Value::BigInt(BigIntValue::from_i64(value))
+  }
+
+  // IsLooselyEqual may call into the objects it converts, it runs on an engine with the builtins installed.
+  // the test gets an ordinary object, which converts to "[object Object]".
+  fn with_engine(test: impl FnOnce(&mut Engine, Value)) {
+    let mut ctx = Context::new();
+    let allocator = Allocator::default();
+    let compiler = compile(&allocator, "", SourceType::default(), &mut ctx).unwrap();
+    let prototype = ctx.get_intrinsic("%Object.prototype%").cloned();
+    let mut stack = Stack::new(STACK_LIMIT);
+    let mut engine = Engine::new(&mut ctx, &mut stack, Rc::new(compiler));
+    test(
+      &mut engine,
+      Value::Object(ObjectValue::new_with_kind(ObjectKind::Ordinary, prototype)),
+    );
+  }
+
+  #[test]
+  fn same_value_tells_the_zeros_apart_and_nan_alike() {
+    assert!(same_value(&number(f64::NAN), &number(f64::NAN)));
+    assert!(!same_value(&number(0.0), &number(-0.0)));
+    assert!(same_value_zero(&number(0.0), &number(-0.0)));
+    assert!(same_value_zero(&number(f64::NAN), &number(-f64::NAN)));
+    assert!(!is_strictly_equal(&number(f64::NAN), &number(f64::NAN)));
+    assert!(is_strictly_equal(&number(0.0), &number(-0.0)));
+  }
+
+  #[test]
+  fn same_value_compares_by_contents_or_identity() {
+    assert!(same_value(&string("a"), &string("a")));
+    assert!(!same_value(&string("1"), &number(1.0)));
+    assert!(same_value(&bigint(-3), &bigint(-3)));
+    assert!(!same_value(&bigint(1), &number(1.0)));
+    assert!(same_value(
+      &Value::create_undefined_value(),
+      &Value::create_undefined_value()
+    ));
+    assert!(!same_value(
+      &Value::create_undefined_value(),
+      &Value::create_null_value()
+    ));
+    let symbol = Value::create_symbol_value(None);
+    assert!(same_value(&symbol, &symbol.clone()));
+    assert!(!same_value(&symbol, &Value::create_symbol_value(None)));
+    let object = Value::create_object_value();
+    assert!(same_value(&object, &object.clone()));
+    assert!(!same_value(&object, &Value::create_object_value()));
+  }
+
+  #[test]
+  fn loose_equality_converts_primitives() {
+    with_engine(|engine, _| {
+      let undefined = Value::create_undefined_value();
+      let null = Value::create_null_value();
+      assert!(is_loosely_equal(engine, &null, &undefined).unwrap());
+      assert!(!is_loosely_equal(engine, &null, &number(0.0)).unwrap());
+      assert!(is_loosely_equal(engine, &string(" 1 "), &number(1.0)).unwrap());
+      assert!(is_loosely_equal(engine, &string(""), &number(0.0)).unwrap());
+      assert!(!is_loosely_equal(engine, &string("NaN"), &number(f64::NAN)).unwrap());
+      assert!(is_loosely_equal(engine, &Value::create_boolean_value(true), &string("1")).unwrap());
+      assert!(is_loosely_equal(engine, &number(-0.0), &Value::create_boolean_value(false)).unwrap());
+      assert!(is_loosely_equal(engine, &bigint(10), &string("0xA")).unwrap());
+      assert!(!is_loosely_equal(engine, &bigint(1), &string("1.5")).unwrap());
+      assert!(is_loosely_equal(engine, &number(2.0), &bigint(2)).unwrap());
+      assert!(!is_loosely_equal(engine, &number(2.5), &bigint(2)).unwrap());
+      assert!(!is_loosely_equal(engine, &number(f64::INFINITY), &bigint(2)).unwrap());
+    });
+  }
+
+  #[test]
+  fn loose_equality_converts_objects_to_primitives() {
+    with_engine(|engine, object| {
+      assert!(is_loosely_equal(engine, &object, &object.clone()).unwrap());
+      assert!(is_loosely_equal(engine, &object, &string("[object Object]")).unwrap());
+      assert!(is_loosely_equal(engine, &string("[object Object]"), &object).unwrap());
+      assert!(!is_loosely_equal(engine, &object, &number(0.0)).unwrap());
+      assert!(!is_loosely_equal(engine, &object, &Value::create_null_value()).unwrap());
+      assert!(!is_loosely_equal(engine, &object, &Value::create_undefined_value()).unwrap());
+    });
+  }
+}
@@ -1,88 +1,1349 @@
 use crate::{
+  builtins::error::create_error,
   bytecode::opcode,
-  compiler::{compile, compiler::CompilerReturn},
+  compiler::{compile, compiler::CompilerReturn, get_source_type},
   context::Context,
   disassembler::Disassembler,
+  errors::{EngineError, Result},
+  gc::StackFrame,
   stack::Stack,
-  utils::STACK_LIMIT,
-  values::Value,
+  utils::{get_line_column, NATIVE_CALL_LIMIT, STACK_LIMIT, STACK_TRACE_LIMIT},
+  values::{
+    function::{BoundFunction, FunctionKind, ThisEnvironment, Upvalue},
+    property::{PropertyDescriptor, PropertyKey},
+    BigIntValue, FunctionValue, NumberValue, ObjectValue, SymbolValue, Value,
+  },
+  vm::coercion::{to_boolean, to_number, to_numeric, to_primitive, to_property_key, to_string, PreferredType},
+  vm::comparison::{is_less_than, is_loosely_equal, is_strictly_equal},
 };
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const SUPER_NOT_CALLED: &str =
+  "Must call super constructor in derived class before accessing 'this' or returning from derived constructor";
+
 #[allow(dead_code)]
 pub struct Engine<'ctx> {
   ctx: &'ctx mut Context,
-  compiler: &'ctx CompilerReturn,
+  compiler: Rc<CompilerReturn>,
   stack: &'ctx mut Stack,
   frame_pointer: usize,
   instruction_pointer: usize,
+  // variables captured by the running function
+  upvalues: Vec<Rc<RefCell<Upvalue>>>,
+  // captured variables that still live on the stack, shared by every closure capturing them
+  open_upvalues: Vec<Rc<RefCell<Upvalue>>>,
+  // the this binding of the running function, with the function and the NewTarget it was entered with
+  environment: Rc<RefCell<ThisEnvironment>>,
+  // the constructor `new` was applied to, only a built-in constructor being called can see it
+  new_target: Option<ObjectValue>,
+  // calls made from native code still running, each of them holds a part of the Rust stack
+  native_depth: usize,
+  // the script being run, for the positions of stack traces
+  file: String,
+  source: String,
 }
 #[allow(dead_code)]
 impl<'ctx> Engine<'ctx> {
-  pub fn new(ctx: &'ctx mut Context, stack: &'ctx mut Stack, compiler: &'ctx CompilerReturn) -> Self {
+  pub fn new(ctx: &'ctx mut Context, stack: &'ctx mut Stack, compiler: Rc<CompilerReturn>) -> Self {
     //  return VM with 'ctx
-    Self { ctx, compiler, stack, instruction_pointer: 0, frame_pointer: 0 }
+    Self {
+      ctx,
+      compiler,
+      stack,
+      instruction_pointer: 0,
+      frame_pointer: 0,
+      upvalues: Vec::new(),
+      open_upvalues: Vec::new(),
+      environment: Rc::new(RefCell::new(ThisEnvironment::new(
+        Value::create_undefined_value(),
+        None,
+        None,
+      ))),
+      new_target: None,
+      native_depth: 0,
+      file: String::new(),
+      source: String::new(),
+    }
   }
-  pub fn bootstrap(ctx: &'ctx mut Context, source: &String, _debug: bool) -> Value {
+  pub fn bootstrap(ctx: &'ctx mut Context, file: &str, source: &str, _debug: bool) -> Result<Value> {
     let arena_allocator = oxc_allocator::Allocator::default();
-    let compiler = compile(&arena_allocator, source, ctx);
+    let source_type = get_source_type(file);
+    let compiler = Rc::new(compile(&arena_allocator, source, source_type, ctx)?);
     let mut stack = Stack::new(STACK_LIMIT);
-    let vm = Engine::new(ctx, &mut stack, &compiler);
+    let mut vm = Engine::new(ctx, &mut stack, compiler.clone());
+    vm.file = file.to_owned();
+    vm.source = source.to_owned();
+    // 9.1.1.4 Global Environment Records, the this value of a script is the global object, a module has none
+    if !source_type.is_module() {
+      let global = vm
+        .get_intrinsic("%GlobalThis%")
+        .map_or_else(Value::create_undefined_value, Value::Object);
+      vm.environment.borrow_mut().this = global;
+    }
     // debug
     if _debug {
-      let mut disassembler = Disassembler::new(&compiler.code, "main.ts", &compiler.constants, vm.ctx);
-      disassembler.disassemble();
+      Disassembler::new(&compiler, file, vm.ctx).disassemble();
     }
     vm.run()
   }
 
-  fn run(mut self) -> Value {
+  fn run(mut self) -> Result<Value> {
+    self.execute()
+  }
+
+  // runs until the script ends or until the frame entered from native code returns.
+  fn execute(&mut self) -> Result<Value> {
+    loop {
+      let address = self.instruction_pointer;
+      match self.step() {
+        Ok(Some(value)) => return Ok(value),
+        Ok(None) => {}
+        Err(error) => self.throw(error, address)?,
+      }
+    }
+  }
+
+  // runs one instruction, the value is the completion of the script once it is done.
+  fn step(&mut self) -> Result<Option<Value>> {
+    let instruction = self.read();
+    match instruction {
+      opcode::OPCODE_CONST => {
+        let index = self.get_constant();
+        self.stack.push(index);
+      }
+      opcode::OPCODE_ADD => self._addition_operation()?,
+      opcode::OPCODE_SUB => self._subtraction_operation()?,
+      opcode::OPCODE_MUL => self._multplication_operation()?,
+      opcode::OPCODE_DIV => self._division_operation()?,
+      opcode::OPCODE_MOD => self.numeric_operation(NumberValue::remainder, BigIntValue::remainder)?,
+      opcode::OPCODE_EXP => self.numeric_operation(NumberValue::exponentiate, BigIntValue::exponentiate)?,
+      opcode::OPCODE_AND => self.numeric_operation(NumberValue::bitwise_and, |x, y| Ok(x.bitwise_and(y)))?,
+      opcode::OPCODE_OR => self.numeric_operation(NumberValue::bitwise_or, |x, y| Ok(x.bitwise_or(y)))?,
+      opcode::OPCODE_XOR => self.numeric_operation(NumberValue::bitwise_xor, |x, y| Ok(x.bitwise_xor(y)))?,
+      opcode::OPCODE_SHL => self.numeric_operation(NumberValue::left_shift, BigIntValue::left_shift)?,
+      opcode::OPCODE_SHR => self.numeric_operation(NumberValue::signed_right_shift, BigIntValue::signed_right_shift)?,
+      opcode::OPCODE_USHR => {
+        self.numeric_operation(NumberValue::unsigned_right_shift, BigIntValue::unsigned_right_shift)?
+      }
+      opcode::OPCODE_NEG => self.unary_numeric_operation(NumberValue::unary_minus, BigIntValue::unary_minus)?,
+      opcode::OPCODE_BIT_NOT => self.unary_numeric_operation(NumberValue::bitwise_not, BigIntValue::bitwise_not)?,
+      opcode::OPCODE_INC => self.unary_numeric_operation(
+        |number| number.add(NumberValue::new(1)),
+        |bigint| bigint.add(&BigIntValue::from_i64(1)),
+      )?,
+      opcode::OPCODE_DEC => self.unary_numeric_operation(
+        |number| number.subtract(NumberValue::new(1)),
+        |bigint| bigint.subtract(&BigIntValue::from_i64(1)),
+      )?,
+      opcode::OPCODE_TO_NUMBER => {
+        let value = self.stack.pop()?;
+        let number = to_number(self, &value)?;
+        self.stack.push(Value::create_float_value(number));
+      }
+      opcode::OPCODE_TO_NUMERIC => {
+        let value = self.stack.pop()?;
+        let numeric = to_numeric(self, &value)?;
+        self.stack.push(numeric);
+      }
+      opcode::OPCODE_NOT => {
+        let value = self.stack.pop()?;
+        self.stack.push(Value::create_boolean_value(!to_boolean(&value)));
+      }
+      opcode::OPCODE_TO_STRING => {
+        let value = self.stack.pop()?;
+        let string = to_string(self, &value)?;
+        self.stack.push(Value::String(string));
+      }
+      opcode::OPCODE_TYPEOF => {
+        let value = self.stack.pop()?;
+        self.stack.push(Value::create_string_value(value.type_of().to_owned()));
+      }
+      opcode::OPCODE_LT => self.relational_operation(false, false)?,
+      opcode::OPCODE_GT => self.relational_operation(true, false)?,
+      opcode::OPCODE_LE => self.relational_operation(true, true)?,
+      opcode::OPCODE_GE => self.relational_operation(false, true)?,
+      opcode::OPCODE_IN => {
+        let (target, key) = (self.stack.pop()?, self.stack.pop()?);
+        let result = self.has_property_operation(&key, &target)?;
+        self.stack.push(Value::create_boolean_value(result));
+      }
+      opcode::OPCODE_INSTANCEOF => {
+        let (target, value) = (self.stack.pop()?, self.stack.pop()?);
+        let result = self.instance_of(&value, &target)?;
+        self.stack.push(Value::create_boolean_value(result));
+      }
+      opcode::OPCODE_EQ => self._eq_operation(),
+      opcode::OPCODE_WEAK_EQ => self.loose_equality_operation(false)?,
+      opcode::OPCODE_NE => self.loose_equality_operation(true)?,
+      opcode::OPCODE_JUMP => self._jump_operation(),
+      opcode::OPCODE_JUMP_IF_FALSE => self._jump_if_false_operation(),
+      opcode::OPCODE_LOAD_GLOBAL_SCOPE => self.load_global_scope_operation()?,
+      opcode::OPCODE_SET_GLOBAL_SCOPE => self.set_global_scope_operation()?,
+      opcode::OPCODE_LOAD_GLOBAL_NAME => {
+        let key = self.get_constant_key();
+        let value = self.get_global_binding(&key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_GLOBAL_NAME => {
+        let key = self.get_constant_key();
+        let value = self.stack.peek(0)?.clone();
+        self.set_global_binding(key, value)?;
+      }
+      opcode::OPCODE_TYPEOF_GLOBAL_NAME => {
+        let key = self.get_constant_key();
+        let type_of = self.type_of_global_binding(&key)?;
+        self.stack.push(type_of);
+      }
+      opcode::OPCODE_DELETE_GLOBAL_NAME => {
+        let key = self.get_constant_key();
+        let deleted = self.delete_global_binding(&key);
+        self.stack.push(Value::create_boolean_value(deleted));
+      }
+      opcode::OPCODE_POP => {
+        self.stack.pop()?;
+      }
+      opcode::OPCODE_SET_LOCAL_SCOPE => self.set_local_scope_operation(),
+      opcode::OPCODE_LOAD_LOCAL_SCOPE => self.load_local_scope_operation(),
+      opcode::OPCODE_SCOPE_ENTER => self.scope_enter_operation(),
+      opcode::OPCODE_SCOPE_EXIT => self.scope_exit_operation(),
+      opcode::OPCODE_CHECK_INITIALIZED => self.check_initialized_operation()?,
+      opcode::OPCODE_CALL => {
+        let argc = self.read();
+        self.call_operation(argc)?;
+      }
+      opcode::OPCODE_CALL_METHOD => {
+        let argc = self.read();
+        self.call_method_operation(argc)?;
+      }
+      opcode::OPCODE_CALL_SPREAD => {
+        let argc = self.spread_arguments()?;
+        self.call_operation(argc)?;
+      }
+      opcode::OPCODE_CALL_METHOD_SPREAD => {
+        let argc = self.spread_arguments()?;
+        self.call_method_operation(argc)?;
+      }
+      opcode::OPCODE_DUP => {
+        let value = self.stack.peek(0)?.clone();
+        self.stack.push(value);
+      }
+      opcode::OPCODE_PICK => {
+        let depth = self.read();
+        let value = self.stack.peek(depth)?.clone();
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SWAP => {
+        let (top, below) = (self.stack.pop()?, self.stack.pop()?);
+        self.stack.push(top);
+        self.stack.push(below);
+      }
+      opcode::OPCODE_TO_PROPERTY_KEY => {
+        let value = self.stack.pop()?;
+        let key = to_property_key(self, &value)?;
+        self.stack.push(key.to_value());
+      }
+      opcode::OPCODE_GET_PROP => {
+        let key = self.get_constant_key();
+        let base = self.stack.pop()?;
+        let value = self.get_value(&base, &key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_GET_COMPUTED_PROP => {
+        let key = self.stack.pop()?;
+        let base = self.stack.pop()?;
+        let key = to_property_key(self, &key)?;
+        let value = self.get_value(&base, &key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_PROP => {
+        let key = self.get_constant_key();
+        let value = self.stack.pop()?;
+        let base = self.stack.pop()?;
+        self.put_value(&base, key, value.clone())?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_COMPUTED_PROP => {
+        let value = self.stack.pop()?;
+        let key = self.stack.pop()?;
+        let base = self.stack.pop()?;
+        let key = to_property_key(self, &key)?;
+        self.put_value(&base, key, value.clone())?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_DELETE_PROP => {
+        let key = self.stack.pop()?;
+        let base = self.stack.pop()?;
+        let key = to_property_key(self, &key)?;
+        let deleted = self.delete_property(&base, &key)?;
+        self.stack.push(Value::create_boolean_value(deleted));
+      }
+      opcode::OPCODE_NEW => {
+        let argc = self.read();
+        self.new_operation(argc)?;
+      }
+      opcode::OPCODE_NEW_SPREAD => {
+        let argc = self.spread_arguments()?;
+        self.new_operation(argc)?;
+      }
+      opcode::OPCODE_CLOSURE => self.closure_operation(),
+      opcode::OPCODE_THIS => self.this_operation()?,
+      opcode::OPCODE_NEW_TARGET => {
+        let new_target = self.environment.borrow().new_target.clone();
+        let value = new_target.map_or_else(Value::create_undefined_value, Value::Object);
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SUPER_CALL => self.super_call_operation()?,
+      opcode::OPCODE_GET_SUPER_PROP => {
+        let key = self.get_constant_key();
+        let this = self.stack.pop()?;
+        let value = self.super_get(&this, &key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_GET_SUPER_COMPUTED_PROP => {
+        let key = self.stack.pop()?;
+        let this = self.stack.pop()?;
+        let key = to_property_key(self, &key)?;
+        let value = self.super_get(&this, &key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_SUPER_PROP => {
+        let key = self.get_constant_key();
+        let value = self.stack.pop()?;
+        let this = self.stack.pop()?;
+        self.super_set(&this, key, value.clone())?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_SUPER_COMPUTED_PROP => {
+        let value = self.stack.pop()?;
+        let key = self.stack.pop()?;
+        let this = self.stack.pop()?;
+        let key = to_property_key(self, &key)?;
+        self.super_set(&this, key, value.clone())?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_CLASS => self.class_operation()?,
+      opcode::OPCODE_METHOD => self.method_operation()?,
+      opcode::OPCODE_DEFINE_METHOD => self.define_method_operation()?,
+      opcode::OPCODE_DEFINE_FIELD => self.define_field_operation()?,
+      opcode::OPCODE_DEFINE_STATIC_BLOCK => {
+        let function = self.stack.pop()?;
+        let (constructor, _) = self.get_class_objects(0)?;
+        self.define_class_static_block(&constructor, function);
+      }
+      opcode::OPCODE_INITIALIZE_CLASS => {
+        let constructor = self.stack.peek(0)?.get_object().cloned();
+        self.initialize_class(&constructor.expect("class constructor to be on the stack"))?;
+      }
+      opcode::OPCODE_PRIVATE_NAME => {
+        let description = self.get_constant().get_string();
+        self.stack.push(Value::Symbol(SymbolValue::new(Some(description))));
+      }
+      opcode::OPCODE_GET_PRIVATE => {
+        let key = self.pop_private_name()?;
+        let object = self.stack.pop()?;
+        let value = self.private_get(&object, &key)?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_SET_PRIVATE => {
+        let value = self.stack.pop()?;
+        let key = self.pop_private_name()?;
+        let object = self.stack.pop()?;
+        self.private_set(&object, &key, value.clone())?;
+        self.stack.push(value);
+      }
+      opcode::OPCODE_HAS_PRIVATE => {
+        let key = self.pop_private_name()?;
+        let object = self.stack.pop()?;
+        let result = self.has_private_element(&object, &key)?;
+        self.stack.push(Value::create_boolean_value(result));
+      }
+      opcode::OPCODE_OBJECT => {
+        let object = self.create_object();
+        self.stack.push(Value::Object(object));
+      }
+      opcode::OPCODE_DEFINE_PROPERTY => self.define_property_operation()?,
+      opcode::OPCODE_DEFINE_COMPUTED_PROPERTY => self.define_computed_property_operation()?,
+      opcode::OPCODE_DEFINE_GETTER => self.define_accessor_operation(true)?,
+      opcode::OPCODE_DEFINE_SETTER => self.define_accessor_operation(false)?,
+      opcode::OPCODE_COPY_DATA_PROPERTIES => self.copy_data_properties_operation()?,
+      opcode::OPCODE_SET_PROTOTYPE => self.set_prototype_operation()?,
+      opcode::OPCODE_ARRAY => {
+        let array = self.create_array();
+        self.stack.push(Value::Object(array));
+      }
+      opcode::OPCODE_ARRAY_PUSH => self.array_push_operation()?,
+      opcode::OPCODE_ARRAY_HOLE => {
+        let array = self.get_literal_object()?;
+        array.set_array_length(array.get_array_length() + 1);
+      }
+      opcode::OPCODE_ARRAY_SPREAD => self.array_spread_operation()?,
+      opcode::OPCODE_REQUIRE_OBJECT_COERCIBLE => {
+        let value = self.stack.peek(0)?;
+        if value.is_undefined() || value.is_null() {
+          return Err(EngineError::TypeError(format!(
+            "Cannot destructure '{}' as it is {}.",
+            value, value
+          )));
+        }
+      }
+      opcode::OPCODE_GET_ITERATOR => {
+        let iterable = self.stack.pop()?;
+        let record = self.get_iterator(&iterable)?;
+        self.stack.push(Value::Object(ObjectValue::new_iterator_record(record)));
+      }
+      opcode::OPCODE_ITERATOR_VALUE => {
+        let object = self.pop_iterator_record()?;
+        let record = object
+          .get_iterator_record()
+          .expect("iterator record to be on the stack");
+        let value = self.iterator_step_value(record)?;
+        self.stack.push(value.unwrap_or_else(Value::create_undefined_value));
+      }
+      opcode::OPCODE_ITERATOR_REST => {
+        let object = self.pop_iterator_record()?;
+        let record = object
+          .get_iterator_record()
+          .expect("iterator record to be on the stack");
+        let values = self.iterator_to_list(record)?;
+        self.stack.push(Value::Object(self.create_array_from_list(values)));
+      }
+      opcode::OPCODE_ITERATOR_CLOSE => {
+        let object = self.pop_iterator_record()?;
+        let record = object
+          .get_iterator_record()
+          .expect("iterator record to be on the stack");
+        self.iterator_close(record)?;
+      }
+      opcode::OPCODE_ITERATOR_CLOSE_THROW => {
+        let object = self.pop_iterator_record()?;
+        let record = object
+          .get_iterator_record()
+          .expect("iterator record to be on the stack");
+        // the throw being handled is thrown again, whatever closing the iterator does
+        let _ = self.iterator_close(record);
+      }
+      opcode::OPCODE_ITERATOR_STEP => {
+        let target = self.read();
+        let object = self.pop_iterator_record()?;
+        let record = object
+          .get_iterator_record()
+          .expect("iterator record to be on the stack");
+        match self.iterator_step_value(record)? {
+          Some(value) => self.stack.push(value),
+          None => self.instruction_pointer = target,
+        }
+      }
+      opcode::OPCODE_FOR_IN_ITERATOR => {
+        let value = self.stack.pop()?;
+        let record = self.get_for_in_iterator(&value)?;
+        self.stack.push(Value::Object(ObjectValue::new_iterator_record(record)));
+      }
+      opcode::OPCODE_OBJECT_REST => self.object_rest_operation()?,
+      opcode::OPCODE_LOAD_UPVALUE => self.load_upvalue_operation(),
+      opcode::OPCODE_SET_UPVALUE => self.set_upvalue_operation(),
+      opcode::OPCODE_CLOSE_UPVALUES => {
+        let slot = self.read();
+        self.close_upvalues(self.frame_pointer + slot);
+      }
+      opcode::OPCODE_THROW => return Err(EngineError::Exception(self.stack.pop()?)),
+      opcode::OPCODE_THROW_TYPE_ERROR => return Err(EngineError::TypeError(self.get_constant().to_string())),
+      opcode::OPCODE_THROW_REFERENCE_ERROR => {
+        return Err(EngineError::ReferenceError(self.get_constant().to_string()));
+      }
+      opcode::OPCODE_RETURN => return self.return_operation(),
+      opcode::OPCODE_HALF => {
+        if !self.stack.is_empty() {
+          let value = self.stack.pop()?;
+          return Ok(Some(value));
+        } else {
+          return Ok(Some(Value::create_undefined_value()));
+        }
+      }
+      _ => todo!("opcode not implemented"),
+    }
+    Ok(None)
+  }
+
+  // unwinds to the closest handler protecting the instruction at `address`, dropping the frames
+  // that have none. errors of the engine itself can not be caught by the script.
+  fn throw(&mut self, error: EngineError, address: usize) -> Result<()> {
+    let value = match error {
+      EngineError::Exception(value) => value,
+      EngineError::StackUnderflow | EngineError::Other(_) => return Err(error),
+      EngineError::TypeError(message) => Value::Object(create_error(self, "TypeError", Some(message))),
+      EngineError::RangeError(message) => Value::Object(create_error(self, "RangeError", Some(message))),
+      EngineError::ReferenceError(message) => Value::Object(create_error(self, "ReferenceError", Some(message))),
+      EngineError::SyntaxError(message) => Value::Object(create_error(self, "SyntaxError", Some(message))),
+      EngineError::StackOverflow => {
+        let message = "Maximum call stack size exceeded".to_owned();
+        Value::Object(create_error(self, "RangeError", Some(message)))
+      }
+    };
+    let mut address = address;
     loop {
-      let instruction = self.read();
-      match instruction {
-        opcode::OPCODE_CONST => {
-          let index = self.get_constant();
-          self.stack.push(index);
+      let handler = self
+        .compiler
+        .handlers
+        .iter()
+        .find(|handler| handler.start <= address && address < handler.end)
+        .cloned();
+      if let Some(handler) = handler {
+        let depth = self.frame_pointer + handler.depth;
+        self.close_upvalues(depth);
+        self.stack.truncate(depth);
+        self.stack.push(value);
+        self.instruction_pointer = handler.target;
+        return Ok(());
+      }
+      let Some(frame) = self.stack.pop_frame() else {
+        return Err(EngineError::Exception(value));
+      };
+      self.close_upvalues(self.frame_pointer);
+      self.stack.truncate(self.frame_pointer);
+      self.compiler = frame.code;
+      self.upvalues = frame.upvalues;
+      self.environment = frame.environment;
+      self.frame_pointer = frame.frame_pointer;
+      self.instruction_pointer = frame.instruction_pointer;
+      // the native code that entered the frame goes on unwinding from its own call
+      if frame.boundary {
+        return Err(EngineError::Exception(value));
+      }
+      // the caller stopped right after its call instruction
+      address = frame.instruction_pointer - 1;
+    }
+  }
+
+  // the array of arguments a spread argument list was collected into is replaced by its elements,
+  // returns how many there are.
+  fn spread_arguments(&mut self) -> Result<usize> {
+    let arguments = self.stack.pop()?;
+    let arguments = self.create_list_from_array_like(&arguments)?;
+    let argc = arguments.len();
+    for argument in arguments {
+      self.stack.push(argument);
+    }
+    Ok(argc)
+  }
+
+  fn call_operation(&mut self, argc: usize) -> Result<()> {
+    let callee_index = self.stack.len() - argc - 1;
+    self.call_value(callee_index, Value::create_undefined_value())
+  }
+
+  // the receiver of a method call sits below the callee, it is taken off the stack so the
+  // frame has the same layout as any other call.
+  fn call_method_operation(&mut self, argc: usize) -> Result<()> {
+    let this_index = self.stack.len() - argc - 2;
+    let this = self.stack.remove(this_index)?;
+    self.call_value(this_index, this)
+  }
+
+  // the callee sits in the first slot of the new frame, followed by its arguments.
+  // built-ins run right away, with the arguments still on the stack.
+  fn call_value(&mut self, callee_index: usize, this: Value) -> Result<()> {
+    let callee = self.stack.get(callee_index)?;
+    // 10.4.1.1 [[Call]] ( thisArgument, argumentsList ) of a bound function, its target is called instead
+    if let Some(bound) = callee.get_object().and_then(ObjectValue::get_bound_function).cloned() {
+      self.push_bound_arguments(callee_index, &bound);
+      return self.call_value(callee_index, bound.get_this().clone());
+    }
+    if let Some(native) = callee.get_object().and_then(ObjectValue::get_native_function).cloned() {
+      let arguments = self.stack.get_values(callee_index + 1);
+      self.new_target = None;
+      let value = native.call(self, &this, &arguments)?;
+      self.stack.truncate(callee_index);
+      self.stack.push(value);
+      return Ok(());
+    }
+    let Some(function) = callee
+      .get_object()
+      .filter(|object| object.get_function().is_some())
+      .cloned()
+    else {
+      return Err(EngineError::TypeError(format!("{} is not a function", callee)));
+    };
+    self.enter_function(&function, callee_index, this, None, false)
+  }
+
+  // runs a function to completion from native code, like an accessor or a callback.
+  pub fn call_function(&mut self, callee: &Value, this: &Value, arguments: &[Value]) -> Result<Value> {
+    self.enter_native_call()?;
+    let value = self.call_function_from_native(callee, this, arguments);
+    self.native_depth -= 1;
+    value
+  }
+
+  // the engine re-enters itself on the Rust stack for every call made from native code, so those calls are
+  // counted apart from the frames of the script, which live on the `Stack`.
+  fn enter_native_call(&mut self) -> Result<()> {
+    if self.native_depth >= NATIVE_CALL_LIMIT {
+      return Err(EngineError::StackOverflow);
+    }
+    self.native_depth += 1;
+    Ok(())
+  }
+
+  fn call_function_from_native(&mut self, callee: &Value, this: &Value, arguments: &[Value]) -> Result<Value> {
+    if let Some(bound) = callee.get_object().and_then(ObjectValue::get_bound_function) {
+      let arguments = [bound.get_arguments(), arguments].concat();
+      let target = Value::Object(bound.get_target().clone());
+      return self.call_function_from_native(&target, &bound.get_this().clone(), &arguments);
+    }
+    if let Some(native) = callee.get_object().and_then(ObjectValue::get_native_function).cloned() {
+      self.new_target = None;
+      return native.call(self, this, arguments);
+    }
+    let Some(function) = callee
+      .get_object()
+      .filter(|object| object.get_function().is_some())
+      .cloned()
+    else {
+      return Err(EngineError::TypeError(format!("{} is not a function", callee)));
+    };
+    let callee_index = self.stack.len();
+    self.stack.push(callee.clone());
+    for argument in arguments {
+      self.stack.push(argument.clone());
+    }
+    if let Err(error) = self.enter_function(&function, callee_index, this.clone(), None, true) {
+      self.stack.truncate(callee_index);
+      return Err(error);
+    }
+    self.execute()
+  }
+
+  /*
+  10.4.1.1 [[Call]] ( thisArgument, argumentsList )
+    1. Let target be F.[[BoundTargetFunction]].
+    3. Let boundArgs be F.[[BoundArguments]].
+    4. Let args be the list-concatenation of boundArgs and argumentsList.
+  The target takes the place of the bound function in the frame, followed by the bound arguments.
+  */
+  fn push_bound_arguments(&mut self, callee_index: usize, bound: &BoundFunction) {
+    let arguments = self.stack.get_values(callee_index + 1);
+    self.stack.truncate(callee_index);
+    self.stack.push(Value::Object(bound.get_target().clone()));
+    for argument in bound.get_arguments().iter().chain(&arguments) {
+      self.stack.push(argument.clone());
+    }
+  }
+
+  /*
+  10.2.1 [[Call]] ( thisArgument, argumentsList )
+    2. If F.[[IsClassConstructor]] is true, then
+      a. Let error be a newly created TypeError object.
+      c. Return ThrowCompletion(error).
+  10.2.1.1 PrepareForOrdinaryCall ( F, newTarget )
+  10.2.1.2 OrdinaryCallBindThis ( F, calleeContext, thisArgument )
+    2. If thisMode is lexical, return unused.
+    5. If thisMode is strict, then
+      a. Let thisValue be thisArgument.
+    6. Else,
+      a. If thisArgument is either undefined or null, then
+        iii. Let thisValue be globalEnvRec.[[GlobalThisValue]].
+      b. Else,
+        i. Let thisValue be ! ToObject(thisArgument).
+  The arguments missing are undefined and the extra ones are dropped, so the frame always has the layout the
+    function was compiled for. new_target is only given by [[Construct]].
+  */
+  fn enter_function(
+    &mut self,
+    callee: &ObjectValue,
+    callee_index: usize,
+    this: Value,
+    new_target: Option<ObjectValue>,
+    boundary: bool,
+  ) -> Result<()> {
+    let function = callee.get_function().expect("callee to be a function");
+    if function.get_kind().is_class_constructor() && new_target.is_none() {
+      return Err(EngineError::TypeError(format!(
+        "Class constructor {} cannot be invoked without 'new'",
+        function.get_name()
+      )));
+    }
+    let this = match this {
+      this if function.is_strict() || this.is_uninitialized() => this,
+      Value::Undefined(_) | Value::Null(_) => self
+        .get_intrinsic("%GlobalThis%")
+        .map_or_else(Value::create_undefined_value, Value::Object),
+      this => Value::Object(self.to_object(&this)?),
+    };
+    let environment = match function.get_environment() {
+      Some(environment) => environment.clone(),
+      None => Rc::new(RefCell::new(ThisEnvironment::new(
+        this,
+        Some(callee.clone()),
+        new_target,
+      ))),
+    };
+    let arity = function.get_arity();
+    let argc = self.stack.len() - callee_index - 1;
+    for _ in argc..arity {
+      self.stack.push(Value::create_undefined_value());
+    }
+    let end = callee_index + 1 + arity;
+    if function.has_rest_parameter() {
+      let rest = self.create_array_from_list(self.stack.get_values(end));
+      self.stack.truncate(end);
+      self.stack.push(Value::Object(rest));
+    } else {
+      self.stack.truncate(end);
+    }
+    let frame = StackFrame::new(
+      self.compiler.clone(),
+      self.instruction_pointer,
+      self.frame_pointer,
+      self.upvalues.clone(),
+      self.environment.clone(),
+      boundary,
+    );
+    self.stack.push_frame(frame)?;
+    self.environment = environment;
+    self.upvalues = function.get_upvalues().to_vec();
+    self.compiler = function.get_code().clone();
+    self.frame_pointer = callee_index;
+    self.instruction_pointer = 0;
+    Ok(())
+  }
+
+  /*
+  10.2.2 [[Construct]] ( argumentsList, newTarget )
+    2. Let kind be F.[[ConstructorKind]].
+    3. If kind is base, then
+      a. Let thisArgument be ? OrdinaryCreateFromConstructor(newTarget, "%Object.prototype%").
+    6. If kind is base, then
+      a. Perform OrdinaryCallBindThis(F, calleeContext, thisArgument).
+      b. Let initializeResult be Completion(InitializeInstanceElements(thisArgument, F)).
+  The this value of a derived constructor is bound by super(), what it returns is settled by its return.
+  */
+  fn enter_constructor(
+    &mut self,
+    callee: &ObjectValue,
+    callee_index: usize,
+    new_target: ObjectValue,
+    boundary: bool,
+  ) -> Result<()> {
+    let this = match callee.get_function().map(FunctionValue::get_kind) {
+      Some(FunctionKind::DerivedConstructor) => Value::create_uninitialized_value(),
+      _ => {
+        let this = self.ordinary_create_from_constructor(&new_target, "%Object.prototype%")?;
+        self.initialize_instance_elements(&this, callee)?;
+        Value::Object(this)
+      }
+    };
+    self.enter_function(callee, callee_index, this, Some(new_target), boundary)
+  }
+
+  // built-in constructors run right away and see the constructor through get_new_target.
+  fn new_operation(&mut self, argc: usize) -> Result<()> {
+    let callee_index = self.stack.len() - argc - 1;
+    let callee = self.stack.get(callee_index)?;
+    let Some(constructor) = callee.get_object().filter(|object| object.is_constructor()).cloned() else {
+      return Err(EngineError::TypeError(format!("{} is not a constructor", callee)));
+    };
+    self.construct_value(callee_index, &constructor, constructor.clone())
+  }
+
+  /*
+  10.4.1.2 [[Construct]] ( argumentsList, newTarget )
+    1. Let target be F.[[BoundTargetFunction]].
+    5. If SameValue(F, newTarget) is true, set newTarget to target.
+    6. Return ? Construct(target, args, newTarget).
+  */
+  fn construct_value(&mut self, callee_index: usize, constructor: &ObjectValue, new_target: ObjectValue) -> Result<()> {
+    if let Some(bound) = constructor.get_bound_function().cloned() {
+      self.push_bound_arguments(callee_index, &bound);
+      let target = bound.get_target();
+      let new_target = if new_target.is_same(constructor) {
+        target.clone()
+      } else {
+        new_target
+      };
+      return self.construct_value(callee_index, target, new_target);
+    }
+    let Some(native) = constructor.get_native_function().cloned() else {
+      return self.enter_constructor(constructor, callee_index, new_target, false);
+    };
+    let arguments = self.stack.get_values(callee_index + 1);
+    self.new_target = Some(new_target);
+    let value = native.call(self, &Value::create_undefined_value(), &arguments);
+    self.new_target = None;
+    let value = value?;
+    self.stack.truncate(callee_index);
+    self.stack.push(value);
+    Ok(())
+  }
+
+  // 7.3.14 Construct ( F [ , argumentsList [ , newTarget ] ] ), runs the constructor to completion from native code.
+  pub fn construct(
+    &mut self,
+    constructor: &ObjectValue,
+    arguments: &[Value],
+    new_target: &ObjectValue,
+  ) -> Result<Value> {
+    self.enter_native_call()?;
+    let value = self.construct_from_native(constructor, arguments, new_target);
+    self.native_depth -= 1;
+    value
+  }
+
+  fn construct_from_native(
+    &mut self,
+    constructor: &ObjectValue,
+    arguments: &[Value],
+    new_target: &ObjectValue,
+  ) -> Result<Value> {
+    if let Some(bound) = constructor.get_bound_function() {
+      let arguments = [bound.get_arguments(), arguments].concat();
+      let target = bound.get_target().clone();
+      let new_target = if new_target.is_same(constructor) {
+        &target
+      } else {
+        new_target
+      };
+      return self.construct_from_native(&target, &arguments, new_target);
+    }
+    if let Some(native) = constructor.get_native_function().cloned() {
+      self.new_target = Some(new_target.clone());
+      let value = native.call(self, &Value::create_undefined_value(), arguments);
+      self.new_target = None;
+      return value;
+    }
+    let callee_index = self.stack.len();
+    self.stack.push(Value::Object(constructor.clone()));
+    for argument in arguments {
+      self.stack.push(argument.clone());
+    }
+    if let Err(error) = self.enter_constructor(constructor, callee_index, new_target.clone(), true) {
+      self.stack.truncate(callee_index);
+      return Err(error);
+    }
+    self.execute()
+  }
+
+  // whether the code running is strict mode code, its references are strict too.
+  pub fn is_strict_code(&self) -> bool {
+    self.compiler.is_strict
+  }
+
+  // NewTarget of the running built-in, read before it calls anything else.
+  pub fn get_new_target(&self) -> Option<ObjectValue> {
+    self.new_target.clone()
+  }
+
+  pub fn get_intrinsic(&self, name: &str) -> Option<ObjectValue> {
+    self.ctx.get_intrinsic(name).cloned()
+  }
+
+  // the property key of a well-known symbol, like `Symbol.toPrimitive`.
+  pub fn get_well_known_symbol(&self, description: &str) -> PropertyKey {
+    let symbol = self
+      .ctx
+      .get_well_known_symbol(description)
+      .expect("well-known symbol to exist");
+    PropertyKey::Symbol(symbol.clone())
+  }
+
+  // 20.4.2.2 Symbol.for ( key ), the symbol of the GlobalSymbolRegistry for `key`.
+  pub fn get_registered_symbol(&mut self, key: &str) -> SymbolValue {
+    self.ctx.get_registered_symbol(key)
+  }
+
+  // 20.4.5.1 KeyForSymbol ( sym )
+  pub fn get_symbol_registry_key(&self, symbol: &SymbolValue) -> Option<String> {
+    self.ctx.get_symbol_registry_key(symbol).cloned()
+  }
+
+  // one `at` line for each frame of the running code, innermost first.
+  pub fn get_stack_trace(&self) -> String {
+    let mut frames = vec![(&self.compiler, self.instruction_pointer)];
+    let callers = self.stack.frames().iter().rev();
+    frames.extend(callers.map(|frame| (&frame.code, frame.instruction_pointer)));
+    let outermost = frames.len() - 1;
+    let lines = frames
+      .iter()
+      .enumerate()
+      .take(STACK_TRACE_LIMIT)
+      .map(|(index, (code, address))| {
+        // the instruction pointer already moved past the instruction running
+        let (line, column) = self.get_source_position(code, address.saturating_sub(1));
+        if index == outermost || code.name.is_empty() {
+          return format!("    at {}:{}:{}", self.file, line, column);
         }
-        opcode::OPCODE_ADD => self._addition_operation(),
-        opcode::OPCODE_SUB => self._subtraction_operation(),
-        opcode::OPCODE_MUL => self._multplication_operation(),
-        opcode::OPCODE_DIV => self._division_operation(),
-        opcode::OPCODE_EQ => self._eq_operation(),
-        opcode::OPCODE_JUMP => self._jump_operation(),
-        opcode::OPCODE_JUMP_IF_FALSE => self._jump_if_false_operation(),
-        opcode::OPCODE_LOAD_GLOBAL_SCOPE => self.load_global_scope_operation(),
-        opcode::OPCODE_SET_GLOBAL_SCOPE => self.set_global_scope_operation(),
-        opcode::OPCODE_POP => return self.stack.peek(0).unwrap().to_owned(),
-        opcode::OPCODE_SET_LOCAL_SCOPE => self.set_local_scope_operation(),
-        opcode::OPCODE_LOAD_LOCAL_SCOPE => self.load_local_scope_operation(),
-        opcode::OPCODE_HALF => {
-          if !self.stack.is_empty() {
-            let value = self.stack.pop().unwrap();
-            return value;
-          } else {
-            return Value::Undefined;
-          }
+        format!("    at {} ({}:{}:{})", code.name, self.file, line, column)
+      });
+    lines.collect::<Vec<_>>().join("\n")
+  }
+
+  fn get_source_position(&self, code: &CompilerReturn, address: usize) -> (usize, usize) {
+    let index = code.positions.partition_point(|(offset, _)| *offset <= address);
+    let offset = index.checked_sub(1).map_or(0, |index| code.positions[index].1);
+    get_line_column(&self.source, offset as usize)
+  }
+
+  // returns the value when there is no caller left, so a `return` outside a function ends the script.
+  fn return_operation(&mut self) -> Result<Option<Value>> {
+    let value = self.stack.pop()?;
+    let Some(frame) = self.stack.pop_frame() else {
+      return Ok(Some(value));
+    };
+    let value = self.get_construct_result(value);
+    self.close_upvalues(self.frame_pointer);
+    self.stack.truncate(self.frame_pointer);
+    self.compiler = frame.code;
+    self.upvalues = frame.upvalues;
+    self.environment = frame.environment;
+    self.instruction_pointer = frame.instruction_pointer;
+    self.frame_pointer = frame.frame_pointer;
+    // native code takes the result itself
+    if frame.boundary {
+      return value.map(Some);
+    }
+    // the error of a constructor is thrown at the `new` of the caller, not inside the constructor
+    match value {
+      Ok(value) => self.stack.push(value),
+      Err(error) => self.throw(error, frame.instruction_pointer - 1)?,
+    }
+    Ok(None)
+  }
+
+  /*
+  10.2.2 [[Construct]] ( argumentsList, newTarget )
+    10. If result is a return completion, then
+      a. If result.[[Value]] is an Object, return result.[[Value]].
+      b. If kind is base, return thisArgument.
+      c. If result.[[Value]] is not undefined, throw a TypeError exception.
+    12. Let thisBinding be ? constructorEnv.GetThisBinding().
+    14. Return thisBinding.
+  Arrow functions share the environment of the constructor they were created in, their result is their own.
+  */
+  fn get_construct_result(&self, value: Value) -> Result<Value> {
+    let environment = self.environment.borrow();
+    let kind = self.compiler.kind;
+    if kind == FunctionKind::Arrow || environment.new_target.is_none() || value.is_object() {
+      return Ok(value);
+    }
+    if kind != FunctionKind::DerivedConstructor {
+      return Ok(environment.this.clone());
+    }
+    if !value.is_undefined() {
+      return Err(EngineError::TypeError(
+        "Derived constructors may only return object or undefined".to_owned(),
+      ));
+    }
+    if environment.this.is_uninitialized() {
+      return Err(EngineError::ReferenceError(SUPER_NOT_CALLED.to_owned()));
+    }
+    Ok(environment.this.clone())
+  }
+
+  // the object being built by a literal stays on the stack below the values of its properties.
+  fn get_literal_object(&self) -> Result<ObjectValue> {
+    let value = self.stack.peek(0)?;
+    Ok(value.get_object().cloned().expect("object literal to be on the stack"))
+  }
+
+  fn define_property_operation(&mut self) -> Result<()> {
+    let key = self.get_constant_key();
+    let value = self.stack.pop()?;
+    let object = self.get_literal_object()?;
+    self.create_data_property_or_throw(&object, key, value)
+  }
+
+  fn define_computed_property_operation(&mut self) -> Result<()> {
+    let value = self.stack.pop()?;
+    let key = self.stack.pop()?;
+    let key = to_property_key(self, &key)?;
+    let object = self.get_literal_object()?;
+    self.create_data_property_or_throw(&object, key, value)
+  }
+
+  // getters and setters of a literal are enumerable, the other half of the pair is kept.
+  fn define_accessor_operation(&mut self, getter: bool) -> Result<()> {
+    let function = self.stack.pop()?;
+    let key = self.stack.pop()?;
+    let key = to_property_key(self, &key)?;
+    let object = self.get_literal_object()?;
+    let descriptor = match getter {
+      true => PropertyDescriptor::new_accessor(Some(function), None, true),
+      false => PropertyDescriptor::new_accessor(None, Some(function), true),
+    };
+    self.define_property_or_throw(&object, key, descriptor)
+  }
+
+  fn copy_data_properties_operation(&mut self) -> Result<()> {
+    let source = self.stack.pop()?;
+    let object = self.get_literal_object()?;
+    self.copy_data_properties(&object, &source, &[])
+  }
+
+  // `__proto__: value` in a literal, values that are neither objects nor null are ignored.
+  fn set_prototype_operation(&mut self) -> Result<()> {
+    let value = self.stack.pop()?;
+    let object = self.get_literal_object()?;
+    match value {
+      Value::Object(prototype) => object.set_prototype(Some(prototype)),
+      Value::Null(_) => object.set_prototype(None),
+      _ => {}
+    }
+    Ok(())
+  }
+
+  /*
+  13.2.4.1 Runtime Semantics: ArrayAccumulation
+  Every element is defined at the current length of the array, which also counts the holes left by elisions.
+  */
+  fn array_push_operation(&mut self) -> Result<()> {
+    let value = self.stack.pop()?;
+    let array = self.get_literal_object()?;
+    let key = PropertyKey::from(array.get_array_length().to_string());
+    self.create_data_property_or_throw(&array, key, value)
+  }
+
+  fn array_spread_operation(&mut self) -> Result<()> {
+    let iterable = self.stack.pop()?;
+    let array = self.get_literal_object()?;
+    for value in self.iterate_to_list(&iterable)? {
+      let key = PropertyKey::from(array.get_array_length().to_string());
+      self.create_data_property_or_throw(&array, key, value)?;
+    }
+    Ok(())
+  }
+
+  // the object holding the record, the copies PICK left below it share it and see it finish.
+  fn pop_iterator_record(&mut self) -> Result<ObjectValue> {
+    let value = self.stack.pop()?;
+    let object = value
+      .get_object()
+      .filter(|object| object.get_iterator_record().is_some());
+    Ok(object.cloned().expect("iterator record to be on the stack"))
+  }
+
+  /*
+  14.3.3.2 Runtime Semantics: RestBindingInitialization
+    1. Let lhs be ? ResolveBinding(StringValue of BindingIdentifier, environment).
+    2. Let restObj be OrdinaryObjectCreate(%Object.prototype%).
+    3. Perform ? CopyDataProperties(restObj, value, excludedNames).
+  The keys the other properties of the pattern read are below the value.
+  */
+  fn object_rest_operation(&mut self) -> Result<()> {
+    let count = self.read();
+    let value = self.stack.pop()?;
+    let keys = self.stack.get_values(self.stack.len() - count);
+    self.stack.truncate(self.stack.len() - count);
+    let mut excluded = Vec::with_capacity(count);
+    for key in &keys {
+      excluded.push(to_property_key(self, key)?);
+    }
+    let object = self.create_object();
+    self.copy_data_properties(&object, &value, &excluded)?;
+    self.stack.push(Value::Object(object));
+    Ok(())
+  }
+
+  // function declarations and expressions are constructors, arrow functions are not.
+  fn closure_operation(&mut self) {
+    let code = self.get_function_code();
+    let function = self.create_closure(code);
+    let is_constructor = function.get_kind().is_constructor();
+    let object = self.create_function_object(function);
+    if is_constructor {
+      self.make_constructor(&object);
+    }
+    self.stack.push(Value::Object(object));
+  }
+
+  // the function code a closure is created from is a constant.
+  fn get_function_code(&mut self) -> Rc<CompilerReturn> {
+    let function = self.get_constant();
+    function
+      .get_function()
+      .expect("function code to be a constant")
+      .get_code()
+      .clone()
+  }
+
+  // the upvalues come from the running frame, arrow functions also keep its this binding.
+  fn create_closure(&mut self, code: Rc<CompilerReturn>) -> FunctionValue {
+    let upvalues = code
+      .upvalues
+      .iter()
+      .map(|capture| match capture.is_local {
+        true => self.capture_upvalue(self.frame_pointer + capture.index),
+        false => self.upvalues[capture.index].clone(),
+      })
+      .collect();
+    let function = FunctionValue::new_closure(code.clone(), upvalues);
+    match code.kind {
+      FunctionKind::Arrow => function.with_environment(self.environment.clone()),
+      _ => function,
+    }
+  }
+
+  // 15.4.4 Runtime Semantics: DefineMethod, MakeMethod ( F, homeObject ) with the object on top of the stack.
+  fn method_operation(&mut self) -> Result<()> {
+    let code = self.get_function_code();
+    let home_object = self.stack.pop()?;
+    let home_object = home_object
+      .get_object()
+      .cloned()
+      .expect("home object to be on the stack");
+    let function = self.create_closure(code).with_home_object(home_object);
+    self.stack.push(Value::Object(self.create_function_object(function)));
+    Ok(())
+  }
+
+  // the superclass is on top of the stack for a derived class, the constructor and the prototype replace it.
+  fn class_operation(&mut self) -> Result<()> {
+    let code = self.get_function_code();
+    let heritage = match code.kind {
+      FunctionKind::DerivedConstructor => Some(self.stack.pop()?),
+      _ => None,
+    };
+    let function = self.create_closure(code);
+    let (constructor, prototype) = self.create_class(function, heritage)?;
+    self.stack.push(Value::Object(constructor));
+    self.stack.push(Value::Object(prototype));
+    Ok(())
+  }
+
+  // the constructor and the prototype of the class being defined, below the operands of its elements.
+  fn get_class_objects(&self, depth: usize) -> Result<(ObjectValue, ObjectValue)> {
+    let constructor = self.stack.peek(depth + 1)?.get_object().cloned();
+    let prototype = self.stack.peek(depth)?.get_object().cloned();
+    Ok((
+      constructor.expect("class constructor to be on the stack"),
+      prototype.expect("class prototype to be on the stack"),
+    ))
+  }
+
+  fn define_method_operation(&mut self) -> Result<()> {
+    let flags = self.read();
+    let (function, key) = (self.stack.pop()?, self.stack.pop()?);
+    let (constructor, prototype) = self.get_class_objects(0)?;
+    self.define_class_method(&constructor, &prototype, key, function, flags)
+  }
+
+  fn define_field_operation(&mut self) -> Result<()> {
+    let flags = self.read();
+    let (initializer, key) = (self.stack.pop()?, self.stack.pop()?);
+    let (constructor, _) = self.get_class_objects(0)?;
+    self.define_class_field(&constructor, key, initializer, flags)
+  }
+
+  /*
+  9.1.1.3.1 BindThisValue ( envRec, V )
+    2. If envRec.[[ThisBindingStatus]] is initialized, throw a ReferenceError exception.
+    3. Set envRec.[[ThisValue]] to V.
+  9.1.1.3.4 GetThisBinding ( )
+    2. If envRec.[[ThisBindingStatus]] is uninitialized, throw a ReferenceError exception.
+    3. Return envRec.[[ThisValue]].
+  */
+  fn this_operation(&mut self) -> Result<()> {
+    let this = self.environment.borrow().this.clone();
+    if this.is_uninitialized() {
+      return Err(EngineError::ReferenceError(SUPER_NOT_CALLED.to_owned()));
+    }
+    self.stack.push(this);
+    Ok(())
+  }
+
+  /*
+  13.3.7.1 Runtime Semantics: Evaluation, SuperCall : super Arguments
+    1. Let newTarget be GetNewTarget().
+    3. Let func be GetSuperConstructor().
+    4. Let argList be ? ArgumentListEvaluation of Arguments.
+    5. If IsConstructor(func) is false, throw a TypeError exception.
+    6. Let result be ? Construct(func, argList, newTarget).
+    7. Let thisER be GetThisEnvironment().
+    8. Perform ? thisER.BindThisValue(result).
+    9. Let F be thisER.[[FunctionObject]].
+    11. Perform ? InitializeInstanceElements(result, F).
+    12. Return result.
+  */
+  fn super_call_operation(&mut self) -> Result<()> {
+    let arguments = self.stack.pop()?;
+    let arguments = self.create_list_from_array_like(&arguments)?;
+    let environment = self.environment.clone();
+    let (function, new_target) = {
+      let environment = environment.borrow();
+      (environment.function.clone(), environment.new_target.clone())
+    };
+    let (Some(function), Some(new_target)) = (function, new_target) else {
+      return Err(EngineError::SyntaxError("'super' keyword unexpected here".to_owned()));
+    };
+    // 13.3.7.2 GetSuperConstructor ( ), the prototype of the active function
+    let parent = function.get_prototype();
+    let Some(parent) = parent.clone().filter(ObjectValue::is_constructor) else {
+      let parent = parent.map_or_else(Value::create_null_value, Value::Object);
+      return Err(EngineError::TypeError(format!(
+        "Super constructor {} of anonymous class is not a constructor",
+        parent
+      )));
+    };
+    let result = self.construct(&parent, &arguments, &new_target)?;
+    if !environment.borrow().this.is_uninitialized() {
+      return Err(EngineError::ReferenceError(
+        "Super constructor may only be called once".to_owned(),
+      ));
+    }
+    environment.borrow_mut().this = result.clone();
+    if let Value::Object(object) = &result {
+      self.initialize_instance_elements(object, &function)?;
+    }
+    self.stack.push(result);
+    Ok(())
+  }
+
+  /*
+  9.1.1.3.5 GetSuperBase ( )
+    1. Let home be envRec.[[FunctionObject]].[[HomeObject]].
+    2. If home is undefined, return undefined.
+    4. Return ? home.[[GetPrototypeOf]]().
+  */
+  fn get_super_base(&self) -> Result<Option<ObjectValue>> {
+    let environment = self.environment.borrow();
+    let function = environment.function.as_ref().and_then(ObjectValue::get_function);
+    match function.and_then(FunctionValue::get_home_object) {
+      Some(home_object) => Ok(home_object.get_prototype()),
+      None => Err(EngineError::SyntaxError("'super' keyword unexpected here".to_owned())),
+    }
+  }
+
+  // 13.3.7.3 MakeSuperPropertyReference ( actualThis, propertyKey, strict ), read with the this value as receiver.
+  fn super_get(&mut self, this: &Value, key: &PropertyKey) -> Result<Value> {
+    match self.get_super_base()? {
+      Some(base) => self.get(&base, key, this),
+      None => Err(EngineError::TypeError(format!(
+        "Cannot read properties of null (reading '{}')",
+        key
+      ))),
+    }
+  }
+
+  fn super_set(&mut self, this: &Value, key: PropertyKey, value: Value) -> Result<()> {
+    match self.get_super_base()? {
+      Some(base) => self.put_super_value(&base, key, value, this),
+      None => Err(EngineError::TypeError(format!(
+        "Cannot set properties of null (setting '{}')",
+        key
+      ))),
+    }
+  }
+
+  // the Private Names of a class are symbols in the locals of the code defining it.
+  fn pop_private_name(&mut self) -> Result<SymbolValue> {
+    match self.stack.pop()? {
+      Value::Symbol(symbol) => Ok(symbol),
+      _ => unreachable!("private name to be on the stack"),
+    }
+  }
+
+  // closures capturing the same slot share one upvalue, so they see each other's writes.
+  fn capture_upvalue(&mut self, slot: usize) -> Rc<RefCell<Upvalue>> {
+    let open = self
+      .open_upvalues
+      .iter()
+      .find(|upvalue| matches!(*upvalue.borrow(), Upvalue::Open(s) if s == slot));
+    if let Some(upvalue) = open {
+      return upvalue.clone();
+    }
+    let upvalue = Rc::new(RefCell::new(Upvalue::Open(slot)));
+    self.open_upvalues.push(upvalue.clone());
+    upvalue
+  }
+
+  // moves the values of the slots about to be released into their upvalues.
+  fn close_upvalues(&mut self, from: usize) {
+    let stack = &self.stack;
+    self.open_upvalues.retain(|upvalue| {
+      let mut upvalue = upvalue.borrow_mut();
+      match *upvalue {
+        Upvalue::Open(slot) if slot >= from => {
+          *upvalue = Upvalue::Closed(stack.get(slot).unwrap().clone());
+          false
         }
-        _ => todo!("opcode not implemented"),
+        _ => true,
       }
+    });
+  }
+
+  fn load_upvalue_operation(&mut self) {
+    let index = self.read();
+    let value = match &*self.upvalues[index].borrow() {
+      Upvalue::Open(slot) => self.stack.get(*slot).unwrap().clone(),
+      Upvalue::Closed(value) => value.clone(),
+    };
+    self.stack.push(value);
+  }
+
+  fn set_upvalue_operation(&mut self) {
+    let index = self.read();
+    let value = self.stack.peek(0).unwrap().to_owned();
+    let mut upvalue = self.upvalues[index].borrow_mut();
+    match &mut *upvalue {
+      Upvalue::Open(slot) => self.stack.set(*slot, value).unwrap(),
+      Upvalue::Closed(closed) => *closed = value,
     }
   }
 
-  fn set_local_scope_operation(&mut self) {}
+  fn set_local_scope_operation(&mut self) {
+    let index = self.read();
+    let value = self.stack.peek(0).unwrap().to_owned();
+    self.stack.set(self.frame_pointer + index, value).unwrap();
+  }
+
   fn load_local_scope_operation(&mut self) {
-    // let index = self.read();
+    let index = self.read();
+    let value = self.stack.get(self.frame_pointer + index).unwrap();
+    self.stack.push(value.to_owned());
   }
 
-  fn set_global_scope_operation(&mut self) {
+  fn scope_enter_operation(&mut self) {
+    let count = self.read();
+    for _ in 0..count {
+      self.stack.push(Value::create_uninitialized_value());
+    }
+  }
+
+  fn check_initialized_operation(&mut self) -> Result<()> {
+    let name = self.get_constant();
+    if self.stack.peek(0)?.is_uninitialized() {
+      let message = format!("Cannot access '{}' before initialization", name);
+      return Err(EngineError::ReferenceError(message));
+    }
+    Ok(())
+  }
+
+  // locals of the scope being left are the values on top of the stack.
+  fn scope_exit_operation(&mut self) {
+    let count = self.read();
+    self.close_upvalues(self.stack.len() - count);
+    self.stack.pop_values(count);
+  }
+
+  // a named `var` of the global scope is a property of the global object, the other bindings live in the context.
+  fn set_global_scope_operation(&mut self) -> Result<()> {
     let index = self.read();
     //  get last value from stack
-    let value = self.stack.peek(0).unwrap();
-    self.ctx.set_variable(index, value.to_owned());
+    let value = self.stack.peek(0)?.to_owned();
+    match self.ctx.get_global_object_key(index) {
+      Some(key) => self.set_global_binding(key, value)?,
+      None => self.ctx.set_global_variable(index, value),
+    }
+    Ok(())
   }
 
-  fn load_global_scope_operation(&mut self) {
+  fn load_global_scope_operation(&mut self) -> Result<()> {
     let index = self.read();
-    let value = self.ctx.get_variable_value(index);
-    self.stack.push(value.to_owned());
+    let value = match self.ctx.get_global_object_key(index) {
+      Some(key) => self.get_global_binding(&key)?,
+      None => self.ctx.get_global_variable(index).unwrap().value.to_owned(),
+    };
+    self.stack.push(value);
+    Ok(())
   }
   fn _jump_operation(&mut self) {
     let index = self.read();
@@ -91,7 +1352,7 @@ impl<'ctx> Engine<'ctx> {
   fn _jump_if_false_operation(&mut self) {
     let index = self.read();
     let condition = self.stack.pop().unwrap();
-    if !condition.is_truthy() {
+    if !to_boolean(&condition) {
       self.instruction_pointer = index;
     }
   }
@@ -105,110 +1366,120 @@ impl<'ctx> Engine<'ctx> {
     let index = self.read();
     self.compiler.constants[index].clone()
   }
+
+  // the name of a property given by a string constant.
+  fn get_constant_key(&mut self) -> PropertyKey {
+    match self.get_constant() {
+      Value::String(key) => PropertyKey::from(key),
+      _ => panic!("Value is not a string"),
+    }
+  }
   fn _eq_operation(&mut self) {
     let (right, left) = (self.stack.pop().unwrap(), self.stack.pop().unwrap());
-    let value = Value::new_boolean(right.is_equal(&left));
+    let value = Value::create_boolean_value(is_strictly_equal(&left, &right));
     self.stack.push(value);
   }
 
-  fn _multplication_operation(&mut self) {
-    let (right, left) = (self.stack.pop().unwrap(), self.stack.pop().unwrap());
-    if left.is_float() && right.is_float() {
-      let result = Value::new_float(left.get_float() - right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_integer() {
-      let result = Value::new_integer(left.get_integer() - right.get_integer());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_float() {
-      let result = Value::new_float(left.get_integer() as f64 - right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_float() && right.is_integer() {
-      let result = Value::new_float(left.get_float() - right.get_integer() as f64);
-      self.stack.push(result);
-      return;
-    }
-    panic!("Unsupported operation, left: {:?} - right: {:?}", left, right);
+  fn _multplication_operation(&mut self) -> Result<()> {
+    self.numeric_operation(NumberValue::multiply, |x, y| Ok(x.multiply(y)))
   }
-  pub fn _addition_operation(&mut self) {
-    let (right, left) = (self.stack.pop().unwrap(), self.stack.pop().unwrap());
-    if left.is_number() && right.is_float() {
-      let result = Value::new_float(left.get_integer() as f64 + right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_float() && right.is_integer() {
-      let result = Value::new_float(left.get_float() + right.get_integer() as f64);
-      self.stack.push(result);
-      return;
+
+  /*
+  13.15.3 ApplyStringOrNumericBinaryOperator ( lval, opText, rval )
+    1. If opText is +, then
+      a. Let lprim be ? ToPrimitive(lval).
+      b. Let rprim be ? ToPrimitive(rval).
+      c. If lprim is a String or rprim is a String, then
+        i. Let lstr be ? ToString(lprim).
+        ii. Let rstr be ? ToString(rprim).
+        iii. Return the string-concatenation of lstr and rstr.
+      d. Set lval to lprim.
+      e. Set rval to rprim.
+  */
+  pub fn _addition_operation(&mut self) -> Result<()> {
+    let (right, left) = (self.stack.pop()?, self.stack.pop()?);
+    if let (Value::Number(left), Value::Number(right)) = (&left, &right) {
+      self.stack.push(Value::Number(left.add(*right)));
+      return Ok(());
     }
-    if left.is_integer() && right.is_integer() {
-      let result = Value::new_integer(left.get_integer() + right.get_integer());
-      self.stack.push(result);
-      return;
+    let left = to_primitive(self, &left, PreferredType::Default)?;
+    let right = to_primitive(self, &right, PreferredType::Default)?;
+    if left.is_string() || right.is_string() {
+      let result = to_string(self, &left)?.concat(&to_string(self, &right)?);
+      self.stack.push(Value::String(result));
+      return Ok(());
     }
+    let left = to_numeric(self, &left)?;
+    let right = to_numeric(self, &right)?;
+    let result = apply_numeric_operation(&left, &right, NumberValue::add, |x, y| Ok(x.add(y)))?;
+    self.stack.push(result);
+    Ok(())
+  }
 
-    if left.is_string() && right.is_string() {
-      let result = Value::new_string(left.get_string() + &right.get_string());
-      self.stack.push(result);
-      return;
-    }
-    panic!("Unsupported operation, left: {:?} + right: {:?}", left, right);
+  pub fn _subtraction_operation(&mut self) -> Result<()> {
+    self.numeric_operation(NumberValue::subtract, |x, y| Ok(x.subtract(y)))
   }
 
-  pub fn _subtraction_operation(&mut self) {
-    let (right, left) = (self.stack.pop().unwrap(), self.stack.pop().unwrap());
-    if left.is_float() && right.is_float() {
-      let result = Value::new_float(left.get_float() - right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_integer() {
-      let result = Value::new_integer(left.get_integer() - right.get_integer());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_float() {
-      let result = Value::new_float(left.get_integer() as f64 - right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_float() && right.is_integer() {
-      let result = Value::new_float(left.get_float() - right.get_integer() as f64);
-      self.stack.push(result);
-      return;
-    }
-    panic!("Unsupported operation, left: {:?} - right: {:?}", left, right);
+  fn _division_operation(&mut self) -> Result<()> {
+    self.numeric_operation(NumberValue::divide, BigIntValue::divide)
   }
 
-  fn _division_operation(&mut self) {
-    let (right, left) = (self.stack.pop().unwrap(), self.stack.pop().unwrap());
-    if left.is_float() && right.is_float() {
-      let result = Value::new_float(left.get_float() / right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_integer() {
-      let result = Value::new_integer(left.get_integer() / right.get_integer());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_integer() && right.is_float() {
-      let result = Value::new_float(left.get_integer() as f64 / right.get_float());
-      self.stack.push(result);
-      return;
-    }
-    if left.is_float() && right.is_integer() {
-      let result = Value::new_float(left.get_float() / right.get_integer() as f64);
-      self.stack.push(result);
-      return;
-    }
-    panic!("Unsupported operation, left: {:?} / right: {:?}", left, right);
+  /*
+  13.15.3 ApplyStringOrNumericBinaryOperator ( lval, opText, rval )
+    3. Let lnum be ? ToNumeric(lval).
+    4. Let rnum be ? ToNumeric(rval).
+  */
+  fn numeric_operation(
+    &mut self,
+    number_operation: fn(NumberValue, NumberValue) -> NumberValue,
+    bigint_operation: fn(&BigIntValue, &BigIntValue) -> Result<BigIntValue>,
+  ) -> Result<()> {
+    let (right, left) = (self.stack.pop()?, self.stack.pop()?);
+    let left = to_numeric(self, &left)?;
+    let right = to_numeric(self, &right)?;
+    let result = apply_numeric_operation(&left, &right, number_operation, bigint_operation)?;
+    self.stack.push(result);
+    Ok(())
+  }
+
+  // the unary operators that convert their operand with ToNumeric.
+  fn unary_numeric_operation(
+    &mut self,
+    number_operation: fn(NumberValue) -> NumberValue,
+    bigint_operation: fn(&BigIntValue) -> BigIntValue,
+  ) -> Result<()> {
+    let value = self.stack.pop()?;
+    let result = match to_numeric(self, &value)? {
+      Value::BigInt(bigint) => Value::BigInt(bigint_operation(&bigint)),
+      Value::Number(number) => Value::Number(number_operation(number)),
+      numeric => unreachable!("ToNumeric to give a number or a bigint, got {}", numeric),
+    };
+    self.stack.push(result);
+    Ok(())
+  }
+
+  /*
+  13.10.1 Runtime Semantics: Evaluation, RelationalExpression
+  `a > b` is `b < a` and `a <= b` is `!(b < a)`, with the operands still converted from left to right. An
+    undefined result of IsLessThan makes every comparison false.
+  */
+  fn relational_operation(&mut self, swap: bool, negate: bool) -> Result<()> {
+    let (right, left) = (self.stack.pop()?, self.stack.pop()?);
+    let result = match swap {
+      false => is_less_than(self, &left, &right, true)?,
+      true => is_less_than(self, &right, &left, false)?,
+    };
+    let result = result.is_some_and(|result| result != negate);
+    self.stack.push(Value::create_boolean_value(result));
+    Ok(())
+  }
+
+  // 13.11.1 Runtime Semantics: Evaluation, EqualityExpression `==` and `!=`.
+  fn loose_equality_operation(&mut self, negate: bool) -> Result<()> {
+    let (right, left) = (self.stack.pop()?, self.stack.pop()?);
+    let result = is_loosely_equal(self, &left, &right)?;
+    self.stack.push(Value::create_boolean_value(result != negate));
+    Ok(())
   }
 
   fn binary_operation(&mut self, op: fn(Value, Value) -> Value) {
@@ -217,3 +1488,29 @@ impl<'ctx> Engine<'ctx> {
     self.stack.push(result);
   }
 }
+
+/*
+13.15.3 ApplyStringOrNumericBinaryOperator ( lval, opText, rval )
+  5. If Type(lnum) is not Type(rnum), throw a TypeError exception.
+  6. If lnum is a BigInt, then
+    a. If opText is **, return ? BigInt::exponentiate(lnum, rnum).
+    b. If opText is /, return ? BigInt::divide(lnum, rnum).
+    c. If opText is %, return ? BigInt::remainder(lnum, rnum).
+    d. If opText is >>>, return ? BigInt::unsignedRightShift(lnum, rnum).
+  7. Let operation be the abstract operation associated with opText and Type(lnum) in Table 43.
+  8. Return operation(lnum, rnum).
+*/
+fn apply_numeric_operation(
+  left: &Value,
+  right: &Value,
+  number_operation: fn(NumberValue, NumberValue) -> NumberValue,
+  bigint_operation: fn(&BigIntValue, &BigIntValue) -> Result<BigIntValue>,
+) -> Result<Value> {
+  match (left, right) {
+    (Value::Number(left), Value::Number(right)) => Ok(Value::Number(number_operation(*left, *right))),
+    (Value::BigInt(left), Value::BigInt(right)) => Ok(Value::BigInt(bigint_operation(left, right)?)),
+    _ => Err(EngineError::TypeError(
+      "Cannot mix BigInt and other types, use explicit conversions".to_owned(),
+    )),
+  }
+}
@@ -0,0 +1,184 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+7.4 Operations on Iterator Objects
+An iterator is read by calling its `next` method until the result object it returns is done, a consumer that
+  stops early closes it by calling its `return` method.
+
+@links:
+- https://tc39.es/ecma262/#sec-operations-on-iterator-objects
+*/
+#![allow(dead_code)]
+use crate::errors::{EngineError, Result};
+use crate::values::iterator::IteratorRecord;
+use crate::values::property::{PropertyDescriptor, PropertyKey};
+use crate::values::{ObjectValue, Value};
+use crate::vm::core::Engine;
+
+impl<'ctx> Engine<'ctx> {
+  /*
+  7.4.3 GetIterator ( obj, kind )
+    3. Else,
+      a. Let method be ? GetMethod(obj, @@iterator).
+    4. If method is undefined, throw a TypeError exception.
+    5. Return ? GetIteratorFromMethod(obj, method).
+  */
+  pub fn get_iterator(&mut self, value: &Value) -> Result<IteratorRecord> {
+    let key = self.get_well_known_symbol("Symbol.iterator");
+    let Some(method) = self.get_method(value, &key)? else {
+      return Err(EngineError::TypeError(format!("{} is not iterable", value)));
+    };
+    self.get_iterator_from_method(value, &method)
+  }
+
+  /*
+  7.4.2 GetIteratorFromMethod ( obj, method )
+    1. Let iterator be ? Call(method, obj).
+    2. If iterator is not an Object, throw a TypeError exception.
+    3. Let nextMethod be ? Get(iterator, "next").
+    4. Let iteratorRecord be the Iterator Record { [[Iterator]]: iterator, [[NextMethod]]: nextMethod, [[Done]]: false }.
+  */
+  pub fn get_iterator_from_method(&mut self, value: &Value, method: &Value) -> Result<IteratorRecord> {
+    let Value::Object(iterator) = self.call_function(method, value, &[])? else {
+      return Err(EngineError::TypeError(
+        "Result of the Symbol.iterator method is not an object".to_owned(),
+      ));
+    };
+    let next_method = self.get(&iterator, &PropertyKey::from("next"), &Value::Object(iterator.clone()))?;
+    Ok(IteratorRecord::new(iterator, next_method))
+  }
+
+  /*
+  14.7.5.6 ForIn/OfHeadEvaluation ( uninitializedBoundNames, expr, iterationKind )
+    6. If iterationKind is enumerate, then
+      a. If exprValue is either undefined or null, then
+        i. Return Completion Record { [[Type]]: break, [[Value]]: empty, [[Target]]: empty }.
+      b. Let obj be ! ToObject(exprValue).
+      c. Let iterator be EnumerateObjectProperties(obj).
+      d. Let nextMethod be ! GetV(iterator, "next").
+  Undefined and null get an iterator with nothing to visit, the loop ends before its first iteration.
+  */
+  pub fn get_for_in_iterator(&mut self, value: &Value) -> Result<IteratorRecord> {
+    let object = match value {
+      value if value.is_undefined() || value.is_null() => None,
+      value => Some(self.to_object(value)?),
+    };
+    let prototype = self.get_intrinsic("%ForInIteratorPrototype%");
+    let iterator = ObjectValue::new_for_in_iterator(object, prototype);
+    let next_method = self.get(&iterator, &PropertyKey::from("next"), &Value::Object(iterator.clone()))?;
+    Ok(IteratorRecord::new(iterator, next_method))
+  }
+
+  /*
+  7.4.8 IteratorStepValue ( iteratorRecord )
+  The record is done once `next` throws, returns something that is not an object or returns a done result, so
+    the iterator is not closed after it failed.
+  */
+  pub fn iterator_step_value(&mut self, record: &IteratorRecord) -> Result<Option<Value>> {
+    if record.is_done() {
+      return Ok(None);
+    }
+    let result = self.iterator_next(record);
+    let result = match result {
+      Ok(result) => result,
+      Err(error) => {
+        record.set_done();
+        return Err(error);
+      }
+    };
+    let receiver = Value::Object(result.clone());
+    let done = self.get(&result, &PropertyKey::from("done"), &receiver);
+    match done {
+      Ok(done) if done.is_truthy() => {
+        record.set_done();
+        return Ok(None);
+      }
+      Ok(_) => {}
+      Err(error) => {
+        record.set_done();
+        return Err(error);
+      }
+    }
+    let value = self.get(&result, &PropertyKey::from("value"), &receiver);
+    if value.is_err() {
+      record.set_done();
+    }
+    value.map(Some)
+  }
+
+  // 7.4.4 IteratorNext ( iteratorRecord [ , value ] )
+  fn iterator_next(&mut self, record: &IteratorRecord) -> Result<ObjectValue> {
+    let iterator = Value::Object(record.iterator.clone());
+    let result = self.call_function(&record.next_method, &iterator, &[])?;
+    match result {
+      Value::Object(result) => Ok(result),
+      result => Err(EngineError::TypeError(format!(
+        "Iterator result {} is not an object",
+        result
+      ))),
+    }
+  }
+
+  /*
+  7.4.11 IteratorClose ( iteratorRecord, completion )
+    3. Let innerResult be Completion(GetMethod(iterator, "return")).
+    4. If innerResult is a normal completion, then
+      a. Let return be innerResult.[[Value]].
+      b. If return is undefined, return ? completion.
+      c. Set innerResult to Completion(Call(return, iterator)).
+    7. If innerResult.[[Value]] is not an Object, throw a TypeError exception.
+  A record that is already done has nothing left to close.
+  */
+  pub fn iterator_close(&mut self, record: &IteratorRecord) -> Result<()> {
+    if record.is_done() {
+      return Ok(());
+    }
+    record.set_done();
+    let iterator = Value::Object(record.iterator.clone());
+    let Some(method) = self.get_method(&iterator, &PropertyKey::from("return"))? else {
+      return Ok(());
+    };
+    let result = self.call_function(&method, &iterator, &[])?;
+    if !result.is_object() {
+      return Err(EngineError::TypeError(format!(
+        "Iterator result {} is not an object",
+        result
+      )));
+    }
+    Ok(())
+  }
+
+  // 7.4.11 IteratorClose with a throw completion, the error wins over whatever closing the iterator does.
+  pub fn iterator_close_with_error(&mut self, record: &IteratorRecord, error: EngineError) -> EngineError {
+    let _ = self.iterator_close(record);
+    error
+  }
+
+  // 7.4.12 IteratorToList ( iteratorRecord )
+  pub fn iterator_to_list(&mut self, record: &IteratorRecord) -> Result<Vec<Value>> {
+    let mut values = Vec::new();
+    while let Some(value) = self.iterator_step_value(record)? {
+      values.push(value);
+    }
+    Ok(values)
+  }
+
+  // the values of an iterable, through its iterator.
+  pub fn iterate_to_list(&mut self, value: &Value) -> Result<Vec<Value>> {
+    let record = self.get_iterator(value)?;
+    self.iterator_to_list(&record)
+  }
+
+  // 7.4.14 CreateIteratorResultObject ( value, done )
+  pub fn create_iter_result_object(&self, value: Value, done: bool) -> Value {
+    let result = self.create_object();
+    result.define_own_property(PropertyKey::from("value"), PropertyDescriptor::new_data(value));
+    let done = Value::create_boolean_value(done);
+    result.define_own_property(PropertyKey::from("done"), PropertyDescriptor::new_data(done));
+    Value::Object(result)
+  }
+}
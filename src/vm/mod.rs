@@ -1 +1,6 @@
+pub mod class;
+pub mod coercion;
+pub mod comparison;
 pub mod core;
+pub mod iterator;
+pub mod operations;
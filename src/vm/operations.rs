@@ -0,0 +1,618 @@
+/*
+Copyright 2024 Yazalde Filimone <yazaldefilimon@gmail.com>
+
+
+*/
+
+/*
+7.3 Operations on Objects
+The abstract operations that may call back into script code, through accessors and the functions they are given,
+  so they run on the engine instead of on the object.
+
+@links:
+- https://tc39.es/ecma262/#sec-operations-on-objects
+- https://tc39.es/ecma262/#sec-ordinaryget
+- https://tc39.es/ecma262/#sec-ordinarysetwithowndescriptor
+*/
+#![allow(dead_code)]
+use crate::builtins::define_readonly_property;
+use crate::errors::{EngineError, Result};
+use crate::values::property::{PropertyDescriptor, PropertyKey, PropertySlot};
+use crate::values::{FunctionValue, ObjectKind, ObjectValue, Value};
+use crate::vm::coercion::{to_boolean, to_length, to_number, to_property_key, to_uint32};
+use crate::vm::core::Engine;
+
+impl<'ctx> Engine<'ctx> {
+  /*
+  10.1.8.1 OrdinaryGet ( O, P, Receiver )
+    1. Let desc be ? O.[[GetOwnProperty]](P).
+    2. If desc is undefined, then
+      a. Let parent be ? O.[[GetPrototypeOf]]().
+      b. If parent is null, return undefined.
+      c. Return ? parent.[[Get]](P, Receiver).
+    3. If IsDataDescriptor(desc) is true, return desc.[[Value]].
+    5. Let getter be desc.[[Get]].
+    6. If getter is undefined, return undefined.
+    7. Return ? Call(getter, Receiver).
+  */
+  pub fn get(&mut self, object: &ObjectValue, key: &PropertyKey, receiver: &Value) -> Result<Value> {
+    match object.find_property(key).map(|property| property.slot) {
+      None => Ok(Value::create_undefined_value()),
+      Some(PropertySlot::Data { value, .. }) => Ok(value),
+      Some(PropertySlot::Accessor { get, .. }) if get.is_undefined() => Ok(Value::create_undefined_value()),
+      Some(PropertySlot::Accessor { get, .. }) => self.call_function(&get, receiver, &[]),
+    }
+  }
+
+  /*
+  10.1.9.2 OrdinarySetWithOwnDescriptor ( O, P, V, Receiver, ownDesc )
+    2. If IsDataDescriptor(ownDesc) is true, then
+      a. If ownDesc.[[Writable]] is false, return false.
+      b. If Receiver is not an Object, return false.
+      c. Let existingDescriptor be ? Receiver.[[GetOwnProperty]](P).
+      d. If existingDescriptor is not undefined, then
+        i. If IsAccessorDescriptor(existingDescriptor) is true, return false.
+        ii. If existingDescriptor.[[Writable]] is false, return false.
+        iii. Let valueDesc be the PropertyDescriptor { [[Value]]: V }.
+        iv. Return ? Receiver.[[DefineOwnProperty]](P, valueDesc).
+      e. Else,
+        i. Return ? CreateDataProperty(Receiver, P, V).
+    4. Let setter be ownDesc.[[Set]].
+    5. If setter is undefined, return false.
+    6. Perform ? Call(setter, Receiver, « V »).
+    7. Return true.
+  */
+  pub fn set(&mut self, object: &ObjectValue, key: PropertyKey, value: Value, receiver: &Value) -> Result<bool> {
+    let value = self.validate_array_length(object, &key, value)?;
+    // a missing property behaves as a writable data property
+    let writable = match object.find_property(&key).map(|property| property.slot) {
+      None => true,
+      Some(PropertySlot::Data { writable, .. }) => writable,
+      Some(PropertySlot::Accessor { set, .. }) if set.is_undefined() => return Ok(false),
+      Some(PropertySlot::Accessor { set, .. }) => {
+        self.call_function(&set, receiver, &[value])?;
+        return Ok(true);
+      }
+    };
+    let Some(receiver) = receiver.get_object().filter(|_| writable) else {
+      return Ok(false);
+    };
+    match receiver.get_own_property(&key) {
+      Some(existing) if !existing.is_writable() => Ok(false),
+      Some(_) => {
+        let descriptor = PropertyDescriptor { value: Some(value), ..PropertyDescriptor::default() };
+        Ok(receiver.define_own_property(key, descriptor))
+      }
+      None => Ok(receiver.define_own_property(key, PropertyDescriptor::new_data(value))),
+    }
+  }
+
+  /*
+  6.2.5.5 GetValue ( V ), for a property reference
+    3. If IsPropertyReference(V) is true, then
+      a. Let baseObj be ? ToObject(V.[[Base]]).
+      c. Return ? baseObj.[[Get]](V.[[ReferencedName]], GetThisValue(V)).
+  The `length` and the code units of a string are read without wrapping it, they are own properties of the
+    wrapper no prototype can shadow.
+  */
+  pub fn get_value(&mut self, base: &Value, key: &PropertyKey) -> Result<Value> {
+    if base.is_undefined() || base.is_null() {
+      return Err(EngineError::TypeError(format!(
+        "Cannot read properties of {} (reading '{}')",
+        base, key
+      )));
+    }
+    if let Value::String(string) = base {
+      if *key == PropertyKey::from("length") {
+        return Ok(Value::create_number_value(string.len() as i64));
+      }
+      if let Some(index) = key.get_array_index().filter(|index| (*index as usize) < string.len()) {
+        let index = index as usize;
+        return Ok(Value::String(string.substring(index, index + 1)));
+      }
+    }
+    let object = self.to_object(base)?;
+    self.get(&object, key, base)
+  }
+
+  /*
+  6.2.5.6 PutValue ( V, W ), for a property reference
+    3. If IsPropertyReference(V) is true, then
+      a. Let baseObj be ? ToObject(V.[[Base]]).
+      c. Let succeeded be ? baseObj.[[Set]](V.[[ReferencedName]], W, GetThisValue(V)).
+      d. If succeeded is false and V.[[Strict]] is true, throw a TypeError exception.
+  A reference is strict when the code making it is strict mode code.
+  */
+  pub fn put_value(&mut self, base: &Value, key: PropertyKey, value: Value) -> Result<()> {
+    if base.is_undefined() || base.is_null() {
+      return Err(EngineError::TypeError(format!(
+        "Cannot set properties of {} (setting '{}')",
+        base, key
+      )));
+    }
+    let object = self.to_object(base)?;
+    if !self.set(&object, key.clone(), value, base)? && self.is_strict_code() {
+      return Err(EngineError::TypeError(get_set_error(&object, base, &key)));
+    }
+    Ok(())
+  }
+
+  /*
+  6.2.5.6 PutValue ( V, W ), for a super reference
+    3. If IsPropertyReference(V) is true, then
+      c. Let succeeded be ? baseObj.[[Set]](V.[[ReferencedName]], W, GetThisValue(V)).
+  The base is the prototype of the home object and the this value is the receiver.
+  */
+  pub fn put_super_value(&mut self, base: &ObjectValue, key: PropertyKey, value: Value, this: &Value) -> Result<()> {
+    if !self.set(base, key.clone(), value, this)? && self.is_strict_code() {
+      return Err(EngineError::TypeError(get_set_error(base, this, &key)));
+    }
+    Ok(())
+  }
+
+  /*
+  13.5.1.2 Runtime Semantics: Evaluation, delete UnaryExpression
+    5. If IsPropertyReference(ref) is true, then
+      b. Let baseObj be ? ToObject(ref.[[Base]]).
+      d. Let deleteStatus be ? baseObj.[[Delete]](ref.[[ReferencedName]]).
+      e. If deleteStatus is false and ref.[[Strict]] is true, throw a TypeError exception.
+      f. Return deleteStatus.
+  */
+  pub fn delete_property(&mut self, base: &Value, key: &PropertyKey) -> Result<bool> {
+    let object = self.to_object(base)?;
+    let deleted = object.delete(key);
+    if !deleted && self.is_strict_code() {
+      return Err(EngineError::TypeError(format!(
+        "Cannot delete property '{}' of {}",
+        key,
+        describe(base)
+      )));
+    }
+    Ok(deleted)
+  }
+
+  /*
+  9.1.1.4 Global Environment Records
+  The [[ObjectRecord]] of the global environment is backed by the global object, the `var` and function
+    declarations of scripts and the names no declaration resolved to are its properties.
+  */
+  fn get_global_object(&self) -> ObjectValue {
+    self.get_intrinsic("%GlobalThis%").expect("global object to exist")
+  }
+
+  /*
+  9.1.1.2.6 GetBindingValue ( N, S ), of the object record of the global environment
+    2. Let value be ? HasProperty(bindingObject, N).
+    3. If value is false, then
+      a. If S is false, return undefined; otherwise throw a ReferenceError exception.
+    4. Return ? Get(bindingObject, N).
+  6.2.5.5 GetValue ( V )
+    3. If IsUnresolvableReference(V) is true, throw a ReferenceError exception.
+  A name the global object doesn't have is unresolvable, reading it throws in sloppy mode code too.
+  */
+  pub fn get_global_binding(&mut self, key: &PropertyKey) -> Result<Value> {
+    let global = self.get_global_object();
+    if !global.has_property(key) {
+      return Err(EngineError::ReferenceError(format!("{} is not defined", key)));
+    }
+    self.get(&global, key, &Value::Object(global.clone()))
+  }
+
+  /*
+  9.1.1.2.5 SetMutableBinding ( N, V, S ), of the object record of the global environment
+    2. Let stillExists be ? HasProperty(bindingObject, N).
+    3. If stillExists is false and S is true, throw a ReferenceError exception.
+    4. Perform ? Set(bindingObject, N, V, S).
+  6.2.5.6 PutValue ( V, W )
+    3. If IsUnresolvableReference(V) is true, then
+      a. If V.[[Strict]] is true, throw a ReferenceError exception.
+      b. Let globalObj be GetGlobalObject().
+      c. Perform ? Set(globalObj, V.[[ReferencedName]], W, false).
+  */
+  pub fn set_global_binding(&mut self, key: PropertyKey, value: Value) -> Result<()> {
+    let global = self.get_global_object();
+    if self.is_strict_code() && !global.has_property(&key) {
+      return Err(EngineError::ReferenceError(format!("{} is not defined", key)));
+    }
+    self.put_value(&Value::Object(global), key, value)
+  }
+
+  /*
+  13.5.3.1 Runtime Semantics: Evaluation, typeof UnaryExpression
+    2. If val is a Reference Record, then
+      a. If IsUnresolvableReference(val) is true, return "undefined".
+  */
+  pub fn type_of_global_binding(&mut self, key: &PropertyKey) -> Result<Value> {
+    let global = self.get_global_object();
+    if !global.has_property(key) {
+      return Ok(Value::create_string_value("undefined".to_owned()));
+    }
+    let value = self.get(&global, key, &Value::Object(global.clone()))?;
+    Ok(Value::create_string_value(value.type_of().to_owned()))
+  }
+
+  /*
+  9.1.1.4.7 DeleteBinding ( N )
+    4. Let globalObject be ObjRec.[[BindingObject]].
+    5. Let existingProp be ? HasOwnProperty(globalObject, N).
+    6. If existingProp is true, then
+      a. Return ? ObjRec.DeleteBinding(N).
+    7. Return true.
+  */
+  pub fn delete_global_binding(&mut self, key: &PropertyKey) -> bool {
+    self.get_global_object().delete(key)
+  }
+
+  // 7.3.4 Set ( O, P, V, Throw ) with Throw set to true, what strict mode code does.
+  pub fn set_or_throw(&mut self, object: &ObjectValue, key: PropertyKey, value: Value) -> Result<()> {
+    let receiver = Value::Object(object.clone());
+    if !self.set(object, key.clone(), value, &receiver)? {
+      return Err(EngineError::TypeError(get_set_error(object, &receiver, &key)));
+    }
+    Ok(())
+  }
+
+  // 7.3.7 CreateDataPropertyOrThrow ( O, P, V )
+  pub fn create_data_property_or_throw(&mut self, object: &ObjectValue, key: PropertyKey, value: Value) -> Result<()> {
+    if !object.define_own_property(key.clone(), PropertyDescriptor::new_data(value)) {
+      return Err(EngineError::TypeError(format!(
+        "Cannot define property {}, object is not extensible",
+        key
+      )));
+    }
+    Ok(())
+  }
+
+  // 7.3.9 DefinePropertyOrThrow ( O, P, desc )
+  pub fn define_property_or_throw(
+    &mut self,
+    object: &ObjectValue,
+    key: PropertyKey,
+    descriptor: PropertyDescriptor,
+  ) -> Result<()> {
+    let mut descriptor = descriptor;
+    if let Some(value) = descriptor.value.take() {
+      descriptor.value = Some(self.validate_array_length(object, &key, value)?);
+    }
+    if !object.define_own_property(key.clone(), descriptor) {
+      return Err(EngineError::TypeError(format!("Cannot redefine property: {}", key)));
+    }
+    Ok(())
+  }
+
+  /*
+  7.3.25 CopyDataProperties ( target, source, excludedItems )
+    1. If source is either undefined or null, return unused.
+    2. Let from be ! ToObject(source).
+    3. Let keys be ? from.[[OwnPropertyKeys]]().
+    4. For each element nextKey of keys, do
+      b. If excluded is false, then
+        i. Let desc be ? from.[[GetOwnProperty]](nextKey).
+        ii. If desc is not undefined and desc.[[Enumerable]] is true, then
+          1. Let propValue be ? Get(from, nextKey).
+          2. Perform ! CreateDataPropertyOrThrow(target, nextKey, propValue).
+  */
+  pub fn copy_data_properties(&mut self, target: &ObjectValue, source: &Value, excluded: &[PropertyKey]) -> Result<()> {
+    if source.is_undefined() || source.is_null() {
+      return Ok(());
+    }
+    let from = self.to_object(source)?;
+    let receiver = Value::Object(from.clone());
+    for key in from.own_property_keys() {
+      if excluded.contains(&key) {
+        continue;
+      }
+      if from.get_own_property(&key).is_some_and(|property| property.enumerable) {
+        let value = self.get(&from, &key, &receiver)?;
+        self.create_data_property_or_throw(target, key, value)?;
+      }
+    }
+    Ok(())
+  }
+
+  /*
+  7.1.18 ToObject ( argument )
+  Undefined and Null throw a TypeError, objects are returned as they are and the other primitives are
+    wrapped in an object inheriting from the prototype of their type.
+  */
+  pub fn to_object(&self, value: &Value) -> Result<ObjectValue> {
+    let (kind, prototype) = match value {
+      Value::Object(object) => return Ok(object.clone()),
+      Value::String(string) => {
+        let prototype = self.get_intrinsic("%String.prototype%");
+        return Ok(ObjectValue::new_string(string.clone(), prototype));
+      }
+      Value::Boolean(boolean) => (ObjectKind::Boolean(boolean.clone()), "%Boolean.prototype%"),
+      Value::Number(number) => (ObjectKind::Number(*number), "%Number.prototype%"),
+      Value::Symbol(symbol) => (ObjectKind::Symbol(symbol.clone()), "%Symbol.prototype%"),
+      Value::BigInt(bigint) => (ObjectKind::BigInt(bigint.clone()), "%BigInt.prototype%"),
+      Value::Undefined(_) | Value::Null(_) | Value::Uninitialized => {
+        return Err(EngineError::TypeError(
+          "Cannot convert undefined or null to object".to_owned(),
+        ));
+      }
+    };
+    Ok(ObjectValue::new_with_kind(kind, self.get_intrinsic(prototype)))
+  }
+
+  /*
+  10.4.2.4 ArraySetLength ( A, Desc )
+    3. Let newLen be ? ToUint32(Desc.[[Value]]).
+    4. Let numberLen be ? ToNumber(Desc.[[Value]]).
+    5. If SameValueZero(newLen, numberLen) is false, throw a RangeError exception.
+  */
+  fn validate_array_length(&mut self, object: &ObjectValue, key: &PropertyKey, value: Value) -> Result<Value> {
+    if !object.is_array() || *key != PropertyKey::from("length") {
+      return Ok(value);
+    }
+    let number = to_number(self, &value)?;
+    let length = to_uint32(number);
+    if length as f64 != number {
+      return Err(EngineError::RangeError("Invalid array length".to_owned()));
+    }
+    Ok(Value::create_number_value(length as i64))
+  }
+
+  // 7.3.10 DeletePropertyOrThrow ( O, P )
+  pub fn delete_property_or_throw(&mut self, object: &ObjectValue, key: &PropertyKey) -> Result<()> {
+    if !object.delete(key) {
+      let message = format!(
+        "Cannot delete property '{}' of {}",
+        key,
+        describe(&Value::Object(object.clone()))
+      );
+      return Err(EngineError::TypeError(message));
+    }
+    Ok(())
+  }
+
+  /*
+  7.3.11 GetMethod ( V, P )
+    1. Let func be ? GetV(V, P).
+    2. If func is either undefined or null, return undefined.
+    3. If IsCallable(func) is false, throw a TypeError exception.
+    4. Return func.
+  */
+  pub fn get_method(&mut self, value: &Value, key: &PropertyKey) -> Result<Option<Value>> {
+    let method = self.get_value(value, key)?;
+    if method.is_undefined() || method.is_null() {
+      return Ok(None);
+    }
+    if !method.is_function() {
+      return Err(EngineError::TypeError(format!("{} is not a function", method)));
+    }
+    Ok(Some(method))
+  }
+
+  /*
+  13.10.2 InstanceofOperator ( V, target )
+    1. If target is not an Object, throw a TypeError exception.
+    2. Let instOfHandler be ? GetMethod(target, @@hasInstance).
+    3. If instOfHandler is not undefined, then
+      a. Return ToBoolean(? Call(instOfHandler, target, « V »)).
+    4. If IsCallable(target) is false, throw a TypeError exception.
+    5. Return ? OrdinaryHasInstance(target, V).
+  */
+  pub fn instance_of(&mut self, value: &Value, target: &Value) -> Result<bool> {
+    let Some(object) = target.get_object() else {
+      return Err(EngineError::TypeError(
+        "Right-hand side of 'instanceof' is not an object".to_owned(),
+      ));
+    };
+    let key = self.get_well_known_symbol("Symbol.hasInstance");
+    if let Some(handler) = self.get_method(target, &key)? {
+      let result = self.call_function(&handler, target, std::slice::from_ref(value))?;
+      return Ok(to_boolean(&result));
+    }
+    if !object.is_function() {
+      return Err(EngineError::TypeError(
+        "Right-hand side of 'instanceof' is not callable".to_owned(),
+      ));
+    }
+    self.ordinary_has_instance(object, value)
+  }
+
+  /*
+  7.3.21 OrdinaryHasInstance ( C, O )
+    2. If C has a [[BoundTargetFunction]] internal slot, then
+      b. Return ? InstanceofOperator(O, BC).
+    3. If O is not an Object, return false.
+    4. Let P be ? Get(C, "prototype").
+    5. If P is not an Object, throw a TypeError exception.
+    6. Repeat,
+      a. Set O to ? O.[[GetPrototypeOf]]().
+      b. If O is null, return false.
+      c. If SameValue(P, O) is true, return true.
+  */
+  pub fn ordinary_has_instance(&mut self, constructor: &ObjectValue, value: &Value) -> Result<bool> {
+    if let Some(bound) = constructor.get_bound_function() {
+      return self.instance_of(value, &Value::Object(bound.get_target().clone()));
+    }
+    let Some(object) = value.get_object() else {
+      return Ok(false);
+    };
+    let receiver = Value::Object(constructor.clone());
+    let prototype = self.get(constructor, &PropertyKey::from("prototype"), &receiver)?;
+    let Some(prototype) = prototype.get_object() else {
+      return Err(EngineError::TypeError(format!(
+        "Function has non-object prototype '{}' in instanceof check",
+        prototype
+      )));
+    };
+    let mut current = object.get_prototype();
+    while let Some(object) = current {
+      if object.is_same(prototype) {
+        return Ok(true);
+      }
+      current = object.get_prototype();
+    }
+    Ok(false)
+  }
+
+  /*
+  13.10.1 Runtime Semantics: Evaluation, RelationalExpression in ShiftExpression
+    5. If rval is not an Object, throw a TypeError exception.
+    6. Return ? HasProperty(rval, ? ToPropertyKey(lval)).
+  */
+  pub fn has_property_operation(&mut self, key: &Value, target: &Value) -> Result<bool> {
+    let Some(object) = target.get_object() else {
+      return Err(EngineError::TypeError(format!(
+        "Cannot use 'in' operator to search for '{}' in {}",
+        key, target
+      )));
+    };
+    let key = to_property_key(self, key)?;
+    Ok(object.has_property(&key))
+  }
+
+  // 7.3.18 LengthOfArrayLike ( obj )
+  pub fn length_of_array_like(&mut self, object: &ObjectValue) -> Result<u64> {
+    let length = self.get(object, &PropertyKey::from("length"), &Value::Object(object.clone()))?;
+    to_length(self, &length)
+  }
+
+  /*
+  7.3.19 CreateListFromArrayLike ( obj )
+    2. If obj is not an Object, throw a TypeError exception.
+    3. Let len be ? LengthOfArrayLike(obj).
+    4. Let list be a new empty List.
+    5. Let index be 0.
+    6. Repeat, while index < len,
+      a. Let indexName be ! ToString(𝔽(index)).
+      b. Let next be ? Get(obj, indexName).
+      d. Append next to list.
+      e. Set index to index + 1.
+    7. Return list.
+  */
+  pub fn create_list_from_array_like(&mut self, value: &Value) -> Result<Vec<Value>> {
+    let Some(object) = value.get_object() else {
+      return Err(EngineError::TypeError(
+        "CreateListFromArrayLike called on non-object".to_owned(),
+      ));
+    };
+    let length = self.length_of_array_like(object)?;
+    let mut list = Vec::new();
+    for index in 0..length {
+      list.push(self.get(object, &PropertyKey::from(index.to_string()), value)?);
+    }
+    Ok(list)
+  }
+
+  /*
+  10.1.14 GetPrototypeFromConstructor ( constructor, intrinsicDefaultProto )
+    2. Let proto be ? Get(constructor, "prototype").
+    3. If proto is not an Object, then
+      a. Let realm be ? GetFunctionRealm(constructor).
+      b. Set proto to realm's intrinsic object named intrinsicDefaultProto.
+    4. Return proto.
+  */
+  pub fn get_prototype_from_constructor(
+    &mut self,
+    constructor: &ObjectValue,
+    default_prototype: &str,
+  ) -> Result<Option<ObjectValue>> {
+    let receiver = Value::Object(constructor.clone());
+    match self.get(constructor, &PropertyKey::from("prototype"), &receiver)? {
+      Value::Object(prototype) => Ok(Some(prototype)),
+      _ => Ok(self.get_intrinsic(default_prototype)),
+    }
+  }
+
+  // 10.1.13 OrdinaryCreateFromConstructor ( constructor, intrinsicDefaultProto ), for an ordinary object.
+  pub fn ordinary_create_from_constructor(
+    &mut self,
+    constructor: &ObjectValue,
+    default_prototype: &str,
+  ) -> Result<ObjectValue> {
+    let prototype = self.get_prototype_from_constructor(constructor, default_prototype)?;
+    Ok(ObjectValue::new_with_kind(ObjectKind::Ordinary, prototype))
+  }
+
+  // 10.4.2.2 ArrayCreate ( length [ , proto ] ), an empty array inheriting from %Array.prototype%.
+  pub fn create_array(&self) -> ObjectValue {
+    ObjectValue::new_array(self.get_intrinsic("%Array.prototype%"))
+  }
+
+  // 7.3.17 CreateArrayFromList ( elements )
+  pub fn create_array_from_list(&self, elements: Vec<Value>) -> ObjectValue {
+    let array = self.create_array();
+    for (index, element) in elements.into_iter().enumerate() {
+      array.define_own_property(
+        PropertyKey::from(index.to_string()),
+        PropertyDescriptor::new_data(element),
+      );
+    }
+    array
+  }
+
+  // an ordinary object inheriting from %Object.prototype%, like the ones object literals create.
+  pub fn create_object(&self) -> ObjectValue {
+    ObjectValue::new_with_kind(ObjectKind::Ordinary, self.get_intrinsic("%Object.prototype%"))
+  }
+
+  /*
+  10.2.3 OrdinaryFunctionCreate ( functionPrototype, sourceText, ParameterList, Body, thisMode, env, privateEnv )
+  10.2.9 SetFunctionName ( F, name [ , prefix ] )
+  10.2.10 SetFunctionLength ( F, length )
+  The function object of a closure, with its `length` and `name` as non-writable, non-enumerable properties.
+  */
+  /*
+  10.2.5 MakeConstructor ( F [ , writablePrototype [ , prototype ] ] )
+    5. If prototype is not present, then
+      a. Set prototype to OrdinaryObjectCreate(%Object.prototype%).
+      b. Perform ! DefinePropertyOrThrow(prototype, "constructor", PropertyDescriptor { [[Value]]: F,
+        [[Writable]]: writablePrototype, [[Enumerable]]: false, [[Configurable]]: true }).
+    6. Perform ! DefinePropertyOrThrow(F, "prototype", PropertyDescriptor { [[Value]]: prototype,
+      [[Writable]]: writablePrototype, [[Enumerable]]: false, [[Configurable]]: false }).
+  */
+  pub fn make_constructor(&self, function: &ObjectValue) {
+    let prototype = self.create_object();
+    prototype.define_builtin_property("constructor", Value::Object(function.clone()));
+    let descriptor = PropertyDescriptor::new_data_with(Value::Object(prototype), true, false, false);
+    function.define_own_property(PropertyKey::from("prototype"), descriptor);
+  }
+
+  pub fn create_function_object(&self, function: FunctionValue) -> ObjectValue {
+    let length = Value::create_number_value(function.get_length() as i64);
+    let name = Value::create_string_value(function.get_name().to_owned());
+    let prototype = self.get_intrinsic("%Function.prototype%");
+    let object = ObjectValue::new_with_kind(ObjectKind::Function(function), prototype);
+    define_readonly_property(&object, "length", length, true);
+    define_readonly_property(&object, "name", name, true);
+    object
+  }
+}
+
+// how a value is named in the messages of errors about its properties.
+fn describe(value: &Value) -> String {
+  match value {
+    Value::Object(object) if object.is_function() => format!("function {}", value),
+    Value::Object(_) => "#<Object>".to_owned(),
+    Value::String(_) => format!("string '{}'", value),
+    value => value.to_string(),
+  }
+}
+
+// the reason [[Set]] refused the assignment, found by looking at what stands in its way.
+fn get_set_error(object: &ObjectValue, receiver: &Value, key: &PropertyKey) -> String {
+  if !receiver.is_object() {
+    return format!("Cannot create property '{}' on {}", key, describe(receiver));
+  }
+  match object.find_property(key).map(|property| property.slot) {
+    Some(PropertySlot::Accessor { .. }) => {
+      format!(
+        "Cannot set property {} of {} which has only a getter",
+        key,
+        describe(receiver)
+      )
+    }
+    Some(PropertySlot::Data { writable: false, .. }) => {
+      format!(
+        "Cannot assign to read only property '{}' of object '{}'",
+        key,
+        describe(receiver)
+      )
+    }
+    _ => format!("Cannot add property {}, object is not extensible", key),
+  }
+}
@@ -0,0 +1,49 @@
+// runs every script of tests/scripts and checks it against the comments at its top: `// expect: ` lines are what
+// the engine prints for the completion value and `// throws: ` lines the start of the error it ends with.
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn scripts() {
+  let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+  let mut scripts: Vec<_> = fs::read_dir(&directory)
+    .expect("tests/scripts to exist")
+    .map(|entry| entry.expect("a script").file_name())
+    .collect();
+  scripts.sort();
+  let mut failures = Vec::new();
+  for script in scripts {
+    let source = fs::read_to_string(directory.join(&script)).expect("the script to be readable");
+    let name = script.to_string_lossy();
+    let output = Command::new(env!("CARGO_BIN_EXE_core-engine"))
+      .arg("run")
+      .arg(&script)
+      .current_dir(&directory)
+      .output()
+      .expect("the engine to run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (expected, printed) = if let Some(expected) = get_expected(&source, "// expect: ") {
+      (expected, stdout.trim_end().to_owned())
+    } else if let Some(expected) = get_expected(&source, "// throws: ") {
+      let lines = stderr.lines().take(expected.lines().count());
+      (expected, lines.collect::<Vec<_>>().join("\n"))
+    } else {
+      panic!("{} has no `// expect: ` or `// throws: ` line", name);
+    };
+    if printed != expected {
+      failures.push(format!(
+        "{}\n  expected: {}\n  printed:  {}\n{}",
+        name, expected, printed, stderr
+      ));
+    }
+  }
+  assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// the lines of the leading comments with the prefix, one after the other.
+fn get_expected(source: &str, prefix: &str) -> Option<String> {
+  let lines: Vec<_> = source.lines().map_while(|line| line.strip_prefix(prefix)).collect();
+  (!lines.is_empty()).then(|| lines.join("\n"))
+}
@@ -0,0 +1,16 @@
+// expect: String(StringValue { value: "10 1-2- 1,3,5 60 1 2,3 true,false,false,true 2139" })
+let list = [1, 2, , 4];
+list.push(5, 6);
+list[9] = 10;
+let length = list.length;
+list.length = 3;
+let mapped = [1, 2, 3].map(function (value, index) {
+  return value + index;
+});
+let total = 0;
+[10, 20, 30].forEach(function (value) {
+  total = total + value;
+});
+let sliced = [1, 2, 3, 4].slice(1, 3);
+let flags = [Array.isArray(sliced), Array.isArray({ length: 0 }), list.hasOwnProperty("2"), list.hasOwnProperty("1")];
+[length, list.join("-"), mapped.join(), total, [5, 6, 7].indexOf(6), sliced, flags, [3, 1, 2].reverse().concat([9]).join("")].join(" ")
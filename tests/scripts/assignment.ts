@@ -0,0 +1,24 @@
+// expect: String(StringValue { value: "8,22,1,3,3,4,a1,0,1,0,0" })
+let x = 10;
+x += 5;
+x -= 3;
+x *= 2;
+x /= 4;
+x %= 4;
+x **= 3;
+let bits = 6;
+bits &= 3;
+bits |= 8;
+bits ^= 1;
+bits <<= 2;
+bits >>= 1;
+let counter = { count: 1 };
+let before = counter.count++;
+let after = ++counter.count;
+let list = [5];
+list[0]--;
+let text = "a";
+text += 1;
+let i = 0;
+let r = [x, bits, before, after, counter.count, list[0], text, i++, i, --i, i];
+r.join()
@@ -0,0 +1,5 @@
+// throws: Uncaught SyntaxError: Async functions are not supported
+async function load() {
+  return 1;
+}
+load()
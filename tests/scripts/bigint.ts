@@ -0,0 +1,17 @@
+// expect: String(StringValue { value: "1267650600228229401496703217721,-3,-1,-5,-1,251,ff,true,true,RangeError,TypeError" })
+let r = [];
+r.push(String(2n ** 100n + 12345n));
+r.push(String(-7n / 2n), String(-7n % 2n), String(-9n >> 1n));
+r.push(String(BigInt.asIntN(8, 255n)), String(BigInt.asUintN(8, -5n)));
+r.push((255n).toString(16), String(10n == 10), String(1n < 1.5));
+try {
+  1n / 0n;
+} catch (error) {
+  r.push(error.name);
+}
+try {
+  1n + 1;
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
@@ -0,0 +1,31 @@
+// expect: String(StringValue { value: "5,true,[box],(box),<box>,bound describe,1,2,true,7,9,function,TypeError" })
+function Point(x, y) {
+  this.x = x;
+  this.y = y;
+}
+Point.prototype.sum = function () {
+  return this.x + this.y;
+};
+function describe(prefix, suffix) {
+  return prefix + this.name + suffix;
+}
+let named = { name: "box" };
+let point = new Point(2, 3);
+let bound = describe.bind(named, "<");
+let r = [point.sum(), point instanceof Point, describe.call(named, "[", "]"), describe.apply(named, ["(", ")"]), bound(">")];
+r.push(bound.name, bound.length, describe.length, new Point(1, 1).constructor === Point);
+function Counter() {
+  this.value = 7;
+  return 5;
+}
+function Replaced() {
+  return { value: 9 };
+}
+r.push(new Counter().value, new Replaced().value, typeof Function.prototype.call);
+let arrow = () => 1;
+try {
+  new arrow();
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
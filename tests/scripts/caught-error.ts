@@ -0,0 +1,13 @@
+// expect: Number(Integer(11))
+let caught = 0;
+try {
+  throw Error("plain");
+} catch (error) {
+  caught = caught + 1;
+}
+try {
+  throw new SyntaxError();
+} catch (error) {
+  caught = caught + 10;
+}
+caught
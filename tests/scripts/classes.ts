@@ -0,0 +1,37 @@
+// expect: String(StringValue { value: "robin makes a sound: tweet,robin,2,4,2,true,false,true,function,true,TypeError" })
+class Animal {
+  static count = 0;
+  legs = 4;
+  #name;
+  constructor(name) {
+    this.#name = name;
+    Animal.count++;
+  }
+  get name() {
+    return this.#name;
+  }
+  speak() {
+    return this.#name + " makes a sound";
+  }
+  static isAnimal(value) {
+    return #name in value;
+  }
+}
+class Bird extends Animal {
+  legs = 2;
+  #secret() {
+    return "tweet";
+  }
+  speak() {
+    return super.speak() + ": " + this.#secret();
+  }
+}
+let bird = new Bird("robin");
+let r = [bird.speak(), bird.name, bird.legs, new Animal("cat").legs, Animal.count, Animal.isAnimal(bird), Animal.isAnimal({})];
+r.push(bird instanceof Animal, typeof Animal, Object.getPrototypeOf(Bird) === Animal);
+try {
+  Animal("dog");
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
@@ -0,0 +1,29 @@
+// expect: Number(Integer(1341))
+function counter(start) {
+  let count = start;
+  function next() {
+    count = count + 1;
+    return count;
+  }
+  return next;
+}
+function adder(a) {
+  return function (b) {
+    return function (c) {
+      return a + b + c;
+    };
+  };
+}
+let first = counter(0);
+let second = counter(100);
+first();
+first();
+second();
+let sum = 0;
+for (let i = 1; i - 4; i = i + 1) {
+  let read = function () {
+    return i;
+  };
+  sum = sum + read();
+}
+first() + second() + adder(1000)(200)(30) + sum
@@ -0,0 +1,24 @@
+// expect: String(StringValue { value: "nan,zero,string,3,3,true,12,0,95,1/1/0/2" })
+let m = new Map([[NaN, "nan"], [0, "zero"], ["0", "string"]]);
+let s = new Set([1, 1, "1", -0]);
+let r = [m.get(NaN), m.get(-0), m.get("0"), m.size, s.size, s.has(0)];
+for (let i = 0; i < 100; i++) {
+  m.set(i, i);
+}
+for (let i = 0; i < 90; i++) {
+  m.delete(i);
+}
+let keys = [];
+m.forEach(function (value, key) {
+  keys.push(key);
+});
+r.push(keys.length, keys[1], m.get(95));
+let seen = [];
+for (const value of s) {
+  seen.push(value);
+  if (value === 1) {
+    s.add(2);
+  }
+}
+r.push(seen.join("/"));
+r.join()
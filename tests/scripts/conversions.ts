@@ -0,0 +1,7 @@
+// expect: String(StringValue { value: "0.30000000000000004,1e+21,1e-7,0,123.456,31,0,NaN,-Infinity,3,1000,5,8,0,7,1,2,null,1,NaN" })
+let r = [];
+r.push("" + (0.1 + 0.2), "" + 1e21, "" + 1e-7, "" + 0, "" + 123.456);
+r.push(+"  0x1F ", +"", +"1_0", +"-Infinity", +"0b11", +"1e3");
+let primitive = { valueOf: function () { return 4; }, toString: function () { return "text"; } };
+r.push(primitive + 1, primitive * 2, +[], +[7], "" + [1, 2], "" + null, +true, +undefined);
+r.join()
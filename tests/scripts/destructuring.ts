@@ -0,0 +1,13 @@
+// expect: String(StringValue { value: "1 20 4,5 1 30 2,3, 2 1 8 9 1/6/7/0 1/2/3/2" })
+let [a, , b = 20, ...rest] = [1, 2, undefined, 4, 5];
+let { x, y: { z = 30 } = {}, ...others } = { x: 1, w: 2, v: 3 };
+let swapped = [1, 2];
+let first = 0;
+let second = 0;
+[second, first] = swapped;
+let target = {};
+({ p: target.q, r: target["s"] = 9 } = { p: 8 });
+function params(m, { n = 6 } = {}, [o] = [7], ...more) {
+  return [m, n, o, more.length].join("/");
+}
+[a, b, rest, x, z, [others.w, others.v, others.x], first, second, target.q, target.s, params(1), params(1, { n: 2 }, [3], 4, 5)].join(" ")
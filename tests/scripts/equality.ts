@@ -0,0 +1,5 @@
+// expect: String(StringValue { value: "false,true,false,true,true,true,true,true,false,true,-1,0" })
+let object = { valueOf: function () { return 1; } };
+let values = [NaN == NaN, null == undefined, null == 0, "1" == 1, true == "1", 0 === -0, object == 1, object == object];
+values.push(Object.is(0, -0), Object.is(NaN, NaN), [NaN].indexOf(NaN), [0].indexOf(-0));
+values.join()
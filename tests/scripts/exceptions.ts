@@ -0,0 +1,45 @@
+// expect: Number(Integer(112012))
+let log = 0;
+function thrower(value) {
+  throw value;
+}
+function nested() {
+  try {
+    thrower(1);
+  } finally {
+    log = log + 10;
+  }
+  return 1000;
+}
+function finallyReturns() {
+  try {
+    return 1;
+  } finally {
+    log = log + 100;
+  }
+}
+try {
+  nested();
+} catch (error) {
+  log = log + error;
+}
+for (let i = 3; i; i = i - 1) {
+  try {
+    if (i === 2) {
+      break;
+    }
+  } finally {
+    log = log + 1000;
+  }
+}
+try {
+  missing;
+} catch {
+  log = log + 10000;
+}
+try {
+  Math;
+} catch {
+  log = log + 100000;
+}
+log + finallyReturns()
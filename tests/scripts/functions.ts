@@ -0,0 +1,26 @@
+// expect: Number(Integer(1061))
+function add(a, b) {
+  return a + b;
+}
+function countdown(n) {
+  if (n === 0) {
+    return 0;
+  }
+  return n + countdown(n - 1);
+}
+function early(n) {
+  while (n) {
+    return 1;
+  }
+  return 2;
+}
+function second(a, b) {
+  return b;
+}
+let total = add(1, 2) + countdown(10) + early(5) + early(0);
+if (second(1)) {
+  total = 0;
+} else {
+  total = total + 1000;
+}
+total
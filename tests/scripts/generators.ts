@@ -0,0 +1,5 @@
+// throws: Uncaught SyntaxError: Generator functions are not supported
+function* count() {
+  yield 1;
+}
+count()
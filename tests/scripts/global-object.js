@@ -0,0 +1,27 @@
+// expect: String(StringValue { value: "5,1,3,true,undefined,false,function,true,ReferenceError,2,ReferenceError,false" })
+globalThis.foo = 5;
+var v = 1;
+function f() {
+  x = 3;
+}
+f();
+var r = [foo, globalThis.v, x, "x" in globalThis, typeof missing, delete v, typeof globalThis.f];
+r.push(delete x);
+try {
+  x;
+} catch (error) {
+  r.push(error.name);
+}
+globalThis.v = 2;
+r.push(v);
+undefined = 1;
+try {
+  (function () {
+    "use strict";
+    y = 1;
+  })();
+} catch (error) {
+  r.push(error.name);
+}
+r.push("y" in globalThis);
+r.join()
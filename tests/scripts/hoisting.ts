@@ -0,0 +1,20 @@
+// expect: Number(Integer(1111))
+var later = 10;
+let total = early(1);
+function early(n) {
+  return n + later;
+}
+function varInBlocks() {
+  {
+    var hoisted = 100;
+  }
+  return hoisted;
+}
+function readBeforeVar() {
+  if (seen) {
+    return 0;
+  }
+  var seen = 1;
+  return 1000;
+}
+total + varInBlocks() + readBeforeVar()
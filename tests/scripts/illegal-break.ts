@@ -0,0 +1,4 @@
+// throws: Uncaught SyntaxError: Illegal break statement
+let r = 1;
+break;
+r
@@ -0,0 +1,40 @@
+// expect: String(StringValue { value: "1,3,a1,b2,h,i,own,inherited,1,3,true,3" })
+let r = [];
+for (const value of [1, 2, 3]) {
+  if (value === 2) continue;
+  r.push(value);
+}
+for (const [key, value] of new Map([["a", 1], ["b", 2]])) {
+  r.push(key + value);
+}
+for (const letter of "hi") {
+  r.push(letter);
+}
+let parent = { inherited: 1 };
+let child = Object.create(parent);
+child.own = 2;
+for (const key in child) {
+  r.push(key);
+}
+let closed = [];
+let iterable = {
+  [Symbol.iterator]() {
+    let count = 0;
+    return {
+      next() {
+        count++;
+        return { value: count, done: count > 5 };
+      },
+      return() {
+        closed.push("closed");
+        return {};
+      },
+    };
+  },
+};
+for (const value of iterable) {
+  if (value === 2) break;
+}
+let set = new Set([1, 2, 2, 3]);
+r.push(closed.length, set.size, set.has(2), [...set].length);
+r.join()
@@ -0,0 +1,33 @@
+// expect: Number(Integer(321))
+let total = 0;
+let i = 3;
+outer: while (i) {
+  i = i - 1;
+  let j = 3;
+  while (j) {
+    j = j - 1;
+    if (j === 1) {
+      continue outer;
+    }
+    if (i === 0) {
+      break outer;
+    }
+    total = total + 10;
+  }
+  total = total + 1000;
+}
+for (let k = 5; k; k = k - 1) {
+  if (k === 3) {
+    continue;
+  }
+  if (k === 1) {
+    break;
+  }
+  total = total + 100;
+}
+block: {
+  total = total + 1;
+  break block;
+  total = total + 5000;
+}
+total
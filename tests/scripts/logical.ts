@@ -0,0 +1,17 @@
+// expect: String(StringValue { value: "a,2,4,0,false,true,0,yes,3,,,,10,true,set,0" })
+let calls = 0;
+function touch() {
+  calls = calls + 1;
+  return calls;
+}
+let object = { inner: { value: 3 }, nothing: null };
+let r = [0 || "a", 1 && 2, null ?? 4, 0 ?? 5, false && touch(), true || touch(), calls];
+r.push(1 < 2 ? "yes" : "no", object?.inner?.value, object.nothing?.value, object.missing?.deep.deeper, object.nothing?.call());
+let settings = { size: 0, name: "", flag: null };
+settings.size ||= 10;
+settings.name &&= "never";
+settings.flag ??= "set";
+let unused = 1;
+unused ||= touch();
+r.push(settings.size, settings.name === "", settings.flag, calls);
+r.join()
@@ -0,0 +1,18 @@
+// expect: Number(Integer(2233))
+let total = 0;
+let i = 3;
+while (i) {
+  total = total + i;
+  i = i - 1;
+}
+do {
+  total = total + total;
+} while (i);
+for (let j = 2; j; j = j - 1) {
+  total = total + 100;
+}
+let k = 0;
+for (k = 2, total = total + 1; k; k = k - 1) {
+  total = total + 10, total = total + 1000;
+}
+total
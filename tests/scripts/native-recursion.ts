@@ -0,0 +1,23 @@
+// expect: String(StringValue { value: "RangeError,Maximum call stack size exceeded" })
+let r = [];
+let getter = {
+  get deep() {
+    return this.deep;
+  },
+};
+try {
+  getter.deep;
+} catch (error) {
+  r.push(error.name);
+}
+let primitive = {
+  valueOf() {
+    return +this;
+  },
+};
+try {
+  +primitive;
+} catch (error) {
+  r.push(error.message);
+}
+r.join()
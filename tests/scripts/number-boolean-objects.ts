@@ -0,0 +1,12 @@
+// expect: String(StringValue { value: "6,object,true,11001,5,true,false,ff,-ff.8,0.1,5v1j4f4ds7c000,61,[object Number],[object Boolean],true,false,true,RangeError" })
+let five = Object(5);
+let yes = Object(true);
+let r = [five + 1, typeof five, five instanceof Object, (25).toString(2), (5).toString(), true.toString(), false.valueOf()];
+r.push((255).toString(16), (-255.5).toString(16), (0.5).toString(2), (1e21).toString(36), (2 ** 60).toString(2).length);
+r.push(Object.prototype.toString.call(five), Object.prototype.toString.call(yes), yes.valueOf(), !yes, five.valueOf() === 5);
+try {
+  (5).toString(1);
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
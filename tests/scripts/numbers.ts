@@ -0,0 +1,5 @@
+// expect: String(StringValue { value: "0.30000000000000004 Infinity NaN 6 3.5 9007199254740992 Infinity -Infinity true false -2.5 0.30000000000000004" })
+let big = 9007199254740992;
+let negativeZero = 0 / (0 - 1);
+let nan = 0 / 0;
+[0.1 + 0.2, 1 / 0, nan, 2 * 3, 7 / 2, big + 1, 1e308 * 10, 1 / negativeZero, negativeZero === 0, nan === nan, 5 - 7.5, 0.1 * 3].join(" ")
@@ -0,0 +1,3 @@
+// expect: String(StringValue { value: "1 -1 1024 1 7 6 -6 -2147483648 -4 15 true true true false true true false true -5 5 number undefined object function  true true" })
+let x = 5;
+[7 % 3, -7 % 3, 2 ** 10, 5 & 3, 5 | 3, 5 ^ 3, ~5, 1 << 31, -16 >> 2, -16 >>> 28, 3 < 4, "b" > "a", 4 <= 4, 3 >= 4, 1 == 1, 1 != 2, 1 !== 1, !0, -x, +x, typeof x, typeof missing, typeof null, typeof function () {}, void 0, "x" in { x: 1 }, [] instanceof Array].join(" ")
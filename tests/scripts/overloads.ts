@@ -0,0 +1,8 @@
+// expect: String(StringValue { value: "3,ab,undefined" })
+function add(a: number, b: number): number;
+function add(a: string, b: string): string;
+function add(a: any, b: any) {
+  return a + b;
+}
+declare function missing(): void;
+[add(1, 2), add("a", "b"), typeof missing].join()
@@ -0,0 +1,15 @@
+// expect: String(StringValue { value: "ReferenceError,5,1/2/4,1/5/10" })
+function early(a = b, b = 1) {
+  return a;
+}
+function later(a, b = a + 1, [c] = [b * 2]) {
+  return [a, b, c].join("/");
+}
+let r = [];
+try {
+  early();
+} catch (error) {
+  r.push(error.name);
+}
+r.push(early(5), later(1), later(1, 5));
+r.join()
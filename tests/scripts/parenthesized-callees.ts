@@ -0,0 +1,21 @@
+// expect: String(StringValue { value: "object,object,nested,object,object,,TypeError" })
+let object = {
+  name: "object",
+  get() {
+    return this.name;
+  },
+  nested: {
+    name: "nested",
+    get() {
+      return this.name;
+    },
+  },
+};
+let missing = null;
+let r = [(object.get)(), (object?.get)(), (object.nested?.get)(), ((object.get))(), (object["get"])(), (missing?.get)?.()];
+try {
+  (missing?.get)();
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
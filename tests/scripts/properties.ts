@@ -0,0 +1,38 @@
+// expect: Number(Integer(1011324))
+let point = {
+  x: 1,
+  "y": 2,
+  get sum() {
+    return point.x + point.y;
+  },
+  set both(value) {
+    point.x = value;
+    point.y = value;
+  },
+};
+point.z = 3;
+point["w"] = 4;
+let key = "x";
+point[key] = point[key] + 10;
+let total = point.sum;
+point.both = 100;
+if (delete point.z) {
+  total = total + point.sum + point.w;
+}
+if (point.z === undefined) {
+  total = total + 1000;
+}
+Object.defineProperty(point, "fixed", { value: 7, writable: false, enumerable: false });
+let descriptor = Object.getOwnPropertyDescriptor(point, "fixed");
+if (descriptor.writable === false) {
+  total = total + descriptor.value + 10000;
+}
+let inherited = Object.create(point);
+inherited.own = 1;
+if (inherited.hasOwnProperty("x")) {
+  total = 0;
+}
+if (Object.isFrozen(Object.freeze({ a: 1 }))) {
+  total = total + inherited.x + 1000000;
+}
+total
@@ -0,0 +1,10 @@
+// expect: String(StringValue { value: "3,1,true,1,3,2" })
+let high = "\uD800";
+let low = "\uDC00";
+let object = {};
+object[high] = 1;
+object[low] = 2;
+object[high + low] = 3;
+let names = Object.getOwnPropertyNames(object);
+let r = [names.length, names[0].length, names[0] === high, object["\uD800"], object["\u{10000}"], names[2].length];
+r.join()
@@ -0,0 +1,5 @@
+// throws: Uncaught RangeError: Maximum call stack size exceeded
+function forever(n) {
+  return forever(n + 1);
+}
+forever(0)
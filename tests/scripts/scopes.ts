@@ -0,0 +1,27 @@
+// expect: Number(Integer(211112))
+let value = 1;
+let total = 0;
+{
+  let value = 10;
+  {
+    let value = 100;
+    total = total + value;
+  }
+  total = total + value;
+}
+{
+  let other = 1000;
+  total = total + other;
+}
+{
+  let again = 10000;
+  total = total + again;
+}
+function shadow(value) {
+  let inner = value + 1;
+  function read() {
+    return inner + value;
+  }
+  return read();
+}
+total + value + shadow(100000)
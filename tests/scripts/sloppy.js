@@ -0,0 +1,11 @@
+// expect: String(StringValue { value: "1,,false,true,undefined,ReferenceError" })
+var frozen = Object.freeze({ x: 1 });
+frozen.x = 2;
+frozen.y = 3;
+var r = [frozen.x, frozen.y, delete frozen.x, delete notDeclared, typeof notDeclared];
+try {
+  notDeclared;
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
@@ -0,0 +1,17 @@
+// expect: String(StringValue { value: "1/2,0/5,13,2,4,y/z" })
+function pair(a, b) {
+  return [a, b].join("/");
+}
+function Point(x, y) {
+  this.x = x;
+  this.y = y;
+}
+let point = {
+  x: 10,
+  plus: function (a, b) {
+    return this.x + a + b;
+  },
+};
+let xs = [1, 2];
+let r = [pair(...xs), pair(0, ...[5]), point.plus(...xs), new Point(...xs).y, [..."ab", ...xs].length, pair(..."yz")];
+r.join()
@@ -0,0 +1,24 @@
+// expect: String(StringValue { value: "TypeError,TypeError,ReferenceError,TypeError" })
+const frozen = Object.freeze({ x: 1 });
+const r: string[] = [];
+try {
+  frozen.x = 2;
+} catch (error) {
+  r.push(error.name);
+}
+try {
+  delete frozen.x;
+} catch (error) {
+  r.push(error.name);
+}
+try {
+  notDeclared = 1;
+} catch (error) {
+  r.push(error.name);
+}
+try {
+  undefined = 1;
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
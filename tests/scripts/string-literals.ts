@@ -0,0 +1,6 @@
+// expect: String(StringValue { value: "1,2,56832,128512,2,ab,AB,1,false" })
+let lone = "\uD800";
+let pair = "😀";
+let r = [lone.length, pair.length, pair.charCodeAt(1), pair.codePointAt(0), `\u{1F600}`.length, "a\
+b", "\x41B", `\uD800`.length, lone.isWellFormed()];
+r.join()
@@ -0,0 +1,8 @@
+// expect: String(StringValue { value: "12|o|72|4|8|World|, |HELLO, WORLD|pad|3|ababab|005|true|true|d|object|2|b|42|Hi|x12|true|2|128512|H" })
+let text = "Hello, World";
+let wrapped = new String("ab");
+let r = [text.length, text.charAt(4), text.charCodeAt(0), text.indexOf("o"), text.lastIndexOf("o"), text.slice(-5), text.substring(7, 5)];
+r.push(text.toUpperCase(), "  pad ".trim(), "a-b-c".split("-").length, "ab".repeat(3), "5".padStart(3, "0"), text.includes("World"));
+r.push(text.startsWith("Hell"), text.at(-1), typeof wrapped, wrapped.length, wrapped[1], String(42), String.fromCharCode(72, 105));
+r.push("x".concat(1, 2), "abc" < "abd", "😀".length, "😀".codePointAt(0), text[0]);
+r.join("|")
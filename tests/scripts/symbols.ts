@@ -0,0 +1,15 @@
+// expect: String(StringValue { value: "true,false,app,,symbol,app,Symbol(app),1,1,symbol,,TypeError" })
+let shared = Symbol.for("app");
+let local = Symbol("app");
+let key = Symbol("key");
+let object = {};
+object[key] = 1;
+object.plain = 2;
+let r = [shared === Symbol.for("app"), local === shared, Symbol.keyFor(shared), Symbol.keyFor(local), typeof local, local.description];
+r.push(local.toString(), Object.getOwnPropertyNames(object).length, object[key], typeof Symbol.iterator, Symbol.keyFor(Symbol.iterator));
+try {
+  local + "";
+} catch (error) {
+  r.push(error.name);
+}
+r.join()
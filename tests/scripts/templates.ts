@@ -0,0 +1,15 @@
+// expect: String(StringValue { value: "hello world!|2x|5|x\\ty/z/:3+4|true|false|3|true|b|\\u0" })
+function tag(strings, ...values) {
+  return strings;
+}
+function get() {
+  return tag`a${1}b${2}`;
+}
+function parts(strings, ...values) {
+  return strings.raw.join("/") + ":" + values.join("+");
+}
+let name = "world";
+let first = get();
+let r = [`hello ${name}!`, `${1 + 1}${"x"}`, `line\n`.length, parts`x\ty${3}z${4}`, first === get(), first === tag`a${1}b${2}`];
+r.push(first.length, Object.isFrozen(first), first.raw[1], String.raw`\u${0}`);
+r.join("|")
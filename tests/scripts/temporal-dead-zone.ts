@@ -0,0 +1,6 @@
+// throws: Uncaught ReferenceError: Cannot access 'value' before initialization
+function read() {
+  return value;
+}
+read();
+let value = 1;
@@ -0,0 +1,11 @@
+// throws: Uncaught RangeError: out of range
+// throws:     at inner (uncaught-error.ts:6:9)
+// throws:     at outer (uncaught-error.ts:9:3)
+// throws:     at uncaught-error.ts:11:1
+function inner() {
+  throw new RangeError("out of range");
+}
+function outer() {
+  inner();
+}
+outer();
@@ -0,0 +1,4 @@
+// throws: Uncaught SyntaxError: Illegal continue statement: 'bar' does not denote an iteration statement
+foo: for (const x of [1]) {
+  continue bar;
+}